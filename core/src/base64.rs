@@ -0,0 +1,221 @@
+use core::marker::PhantomData;
+
+use crate::Read;
+
+/// The alphabet used by a base64 encoding.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Base64Alphabet {
+  /// The standard alphabet, using `+` and `/`.
+  Standard,
+  /// The URL-safe alphabet, using `-` and `_`.
+  UrlSafe,
+}
+
+impl Base64Alphabet {
+  /// Decode a symbol into its six-bit value, if it's within this alphabet.
+  #[inline(always)]
+  fn decode(self, symbol: u8) -> Option<u8> {
+    Some(match (symbol, self) {
+      (b'A' ..= b'Z', _) => symbol - b'A',
+      (b'a' ..= b'z', _) => symbol - b'a' + 26,
+      (b'0' ..= b'9', _) => symbol - b'0' + 52,
+      (b'+', Base64Alphabet::Standard) | (b'-', Base64Alphabet::UrlSafe) => 62,
+      (b'/', Base64Alphabet::Standard) | (b'_', Base64Alphabet::UrlSafe) => 63,
+      _ => None?,
+    })
+  }
+}
+
+/// An error when reading from a `Base64Read`.
+#[derive(Clone, Copy, Debug)]
+pub enum Base64Error<E: Copy + core::fmt::Debug> {
+  /// An error from the underlying reader.
+  ReadError(E),
+  /// The base64 was invalid.
+  InvalidBase64,
+  /// The base64 ended, yet more bytes were requested.
+  Ended,
+}
+
+/// A reader which decodes base64 from an underlying reader.
+///
+/// The bytes are decoded on the fly, without an intermediate buffer for the encoded data. The
+/// base64 ends with its padding (which is optional), a byte outside of the alphabet (such as the
+/// `.` separating the segments of a JWT), or an error from the underlying reader. The byte ending
+/// the base64 is consumed from the underlying reader. As `Read` has no notion of the end of the
+/// underlying reader, an error from the underlying reader within a group of symbols is only
+/// returned after the bytes decoded from the partial group are read.
+#[derive(Debug)]
+pub struct Base64Read<'read, R: Read<'read>> {
+  reader: R,
+  alphabet: Base64Alphabet,
+  /// The bytes decoded from the current group of symbols.
+  decoded: [u8; 3],
+  /// The position within the bytes decoded.
+  position: usize,
+  /// The amount of bytes decoded from the current group of symbols.
+  len: usize,
+  /// If the base64 has ended.
+  ended: bool,
+  /// The error from the underlying reader which ended the base64, if one did.
+  error: Option<R::Error>,
+  _read: PhantomData<&'read ()>,
+}
+
+impl<'read, R: Read<'read>> Base64Read<'read, R> {
+  /// Create a new reader, decoding base64 encoded with the specified alphabet.
+  pub fn new(reader: R, alphabet: Base64Alphabet) -> Self {
+    Base64Read {
+      reader,
+      alphabet,
+      decoded: [0; 3],
+      position: 0,
+      len: 0,
+      ended: false,
+      error: None,
+      _read: PhantomData,
+    }
+  }
+
+  /// Consume this, returning the underlying reader.
+  pub fn into_inner(self) -> R {
+    self.reader
+  }
+
+  /// Decode the next group of symbols.
+  fn decode_group(&mut self) -> Result<(), Base64Error<R::Error>> {
+    if self.ended {
+      Err(self.error.take().map(Base64Error::ReadError).unwrap_or(Base64Error::Ended))?;
+    }
+
+    let mut group = 0u32;
+    let mut symbols = 0;
+    while symbols < 4 {
+      let symbol = match self.reader.read_byte() {
+        Ok(symbol) => symbol,
+        Err(e) => {
+          if symbols == 0 {
+            Err(Base64Error::ReadError(e))?;
+          }
+          self.ended = true;
+          self.error = Some(e);
+          break;
+        }
+      };
+      let Some(value) = self.alphabet.decode(symbol) else {
+        self.ended = true;
+        // Read past the rest of the padding
+        if (symbol == b'=') &&
+          (symbols == 2) &&
+          (self.reader.read_byte().map_err(Base64Error::ReadError)? != b'=')
+        {
+          Err(Base64Error::InvalidBase64)?;
+        }
+        break;
+      };
+      group = (group << 6) | u32::from(value);
+      symbols += 1;
+    }
+
+    // A single symbol is insufficient to encode a byte
+    let len = match symbols {
+      0 => Err(Base64Error::Ended)?,
+      1 => Err(Base64Error::InvalidBase64)?,
+      _ => symbols - 1,
+    };
+    group <<= 6 * (4 - symbols);
+    let [_, a, b, c] = group.to_be_bytes();
+    self.decoded = [a, b, c];
+    self.position = 0;
+    self.len = len;
+    Ok(())
+  }
+}
+
+impl<'read, R: Read<'read>> Read<'read> for Base64Read<'read, R> {
+  type Error = Base64Error<R::Error>;
+
+  #[inline(always)]
+  fn read_byte(&mut self) -> Result<u8, Self::Error> {
+    if self.position == self.len {
+      self.decode_group()?;
+    }
+    let res = self.decoded[self.position];
+    self.position += 1;
+    Ok(res)
+  }
+
+  #[inline(always)]
+  fn read_exact(&mut self, slice: &mut [u8]) -> Result<(), Self::Error> {
+    for byte in slice {
+      *byte = self.read_byte()?;
+    }
+    Ok(())
+  }
+
+  #[inline(always)]
+  fn read_into_buffer(&mut self, buffer: &mut [u8]) -> Result<usize, Self::Error> {
+    if self.position == self.len {
+      self.decode_group()?;
+    }
+    let decoded = &self.decoded[self.position .. self.len];
+    let len = decoded.len().min(buffer.len());
+    buffer[.. len].copy_from_slice(&decoded[.. len]);
+    self.position += len;
+    Ok(len)
+  }
+}
+
+#[test]
+fn base64() {
+  use crate::{ConstStack, Deserializer};
+
+  fn check(encoded: &[u8], alphabet: Base64Alphabet) {
+    let mut deserializer =
+      Deserializer::<_, ConstStack<32>>::new(Base64Read::new(encoded, alphabet)).unwrap();
+    let mut fields = deserializer.value().unwrap().fields().unwrap();
+    let mut field = fields.next().unwrap().unwrap();
+    assert_eq!(field.key().unwrap().collect::<Result<String, _>>().unwrap(), "key");
+    let mut values = field.value().unwrap().iterate().unwrap();
+    assert_eq!(
+      values.next().unwrap().unwrap().to_str().unwrap().collect::<Result<String, _>>().unwrap(),
+      "~~~???"
+    );
+    assert!(values.next().unwrap().unwrap().to_bool().unwrap());
+    assert!(values.next().is_none());
+    drop(values);
+    let mut field = fields.next().unwrap().unwrap();
+    assert_eq!(field.key().unwrap().collect::<Result<String, _>>().unwrap(), "n");
+    assert_eq!(field.value().unwrap().to_number().unwrap().i64(), Some(1));
+    assert!(fields.next().is_none());
+  }
+  check(b"eyJrZXkiOiBbIn5+fj8/PyIsIHRydWVdLCAibiI6IDF9", Base64Alphabet::Standard);
+  check(b"eyJrZXkiOiBbIn5-fj8_PyIsIHRydWVdLCAibiI6IDF9", Base64Alphabet::UrlSafe);
+
+  // A partial group at the end of the underlying reader, as within a JWT, followed by its
+  // signature
+  let mut reader = Base64Read::new(&b"eyJhIjoxfQ.signature"[..], Base64Alphabet::UrlSafe);
+  let mut decoded = [0; 7];
+  reader.read_exact(&mut decoded).unwrap();
+  assert_eq!(&decoded, br#"{"a":1}"#);
+  assert!(matches!(reader.read_byte(), Err(Base64Error::Ended)));
+  assert_eq!(reader.into_inner(), b"signature");
+
+  // A partial group at the end of the underlying reader, and padding
+  for encoded in [&b"eyJhIjoxfQ"[..], b"eyJhIjoxfQ=="] {
+    let mut reader = Base64Read::new(encoded, Base64Alphabet::Standard);
+    let mut decoded = [0; 7];
+    reader.read_exact(&mut decoded).unwrap();
+    assert_eq!(&decoded, br#"{"a":1}"#);
+    assert!(reader.read_byte().is_err());
+  }
+
+  assert!(matches!(
+    Base64Read::new(&b"e.yJh"[..], Base64Alphabet::Standard).read_byte(),
+    Err(Base64Error::InvalidBase64)
+  ));
+  assert!(matches!(
+    Base64Read::new(&b"e-yJh"[..], Base64Alphabet::Standard).read_byte(),
+    Err(Base64Error::InvalidBase64)
+  ));
+}
@@ -0,0 +1,223 @@
+use core::marker::PhantomData;
+
+use crate::Read;
+
+/// An error when reading from a `ChunkedRead`.
+#[derive(Clone, Copy, Debug)]
+pub enum ChunkedError<E: Copy + core::fmt::Debug> {
+  /// An error from the underlying reader.
+  ReadError(E),
+  /// The chunked encoding was invalid.
+  InvalidChunk,
+  /// The body ended, yet more bytes were requested.
+  Ended,
+}
+
+/// A reader which decodes an HTTP/1.1 body with the chunked transfer coding.
+///
+/// This reads the payload of a body encoded per
+/// [RFC 9112](https://datatracker.ietf.org/doc/html/rfc9112#section-7.1), as it's read from the
+/// underlying reader (such as a socket), without buffering the body. This allows deserializing the
+/// body of a response as it's received. Chunk extensions are ignored. Once the last chunk is read,
+/// the trailer section is read past, leaving the underlying reader positioned after the message.
+#[derive(Debug)]
+pub struct ChunkedRead<'read, R: Read<'read>> {
+  reader: R,
+  /// The amount of bytes remaining within the current chunk.
+  remaining: u64,
+  /// If a chunk has been read, which will be followed by a CRLF.
+  chunk_read: bool,
+  /// If the last chunk has been read.
+  ended: bool,
+  _read: PhantomData<&'read ()>,
+}
+
+impl<'read, R: Read<'read>> ChunkedRead<'read, R> {
+  /// Create a new reader, decoding the chunked body read from the underlying reader.
+  pub fn new(reader: R) -> Self {
+    ChunkedRead { reader, remaining: 0, chunk_read: false, ended: false, _read: PhantomData }
+  }
+
+  /// Consume this, returning the underlying reader.
+  pub fn into_inner(self) -> R {
+    self.reader
+  }
+
+  /// Read past the rest of the body, returning the underlying reader.
+  ///
+  /// The deserializer won't read past the end of a document, so the last chunk (and the trailer
+  /// section) may remain unread after the document has been deserialized. This reads past them
+  /// (discarding any of the payload yet to be read), leaving the underlying reader positioned
+  /// after the message, as necessary to read another message from it.
+  pub fn finish(mut self) -> Result<R, ChunkedError<R::Error>> {
+    loop {
+      match self.next_chunk() {
+        Ok(()) => {}
+        Err(ChunkedError::Ended) => break Ok(self.reader),
+        Err(e) => Err(e)?,
+      }
+      while self.remaining != 0 {
+        self.read_byte()?;
+      }
+    }
+  }
+
+  /// Read a CRLF from the underlying reader.
+  fn read_crlf(&mut self) -> Result<(), ChunkedError<R::Error>> {
+    let mut crlf = [0; 2];
+    self.reader.read_exact(&mut crlf).map_err(ChunkedError::ReadError)?;
+    if &crlf != b"\r\n" {
+      Err(ChunkedError::InvalidChunk)?;
+    }
+    Ok(())
+  }
+
+  /// Read past the rest of a line, through its CRLF.
+  fn read_line(&mut self, mut byte: u8) -> Result<(), ChunkedError<R::Error>> {
+    while byte != b'\r' {
+      byte = self.reader.read_byte().map_err(ChunkedError::ReadError)?;
+    }
+    if self.reader.read_byte().map_err(ChunkedError::ReadError)? != b'\n' {
+      Err(ChunkedError::InvalidChunk)?;
+    }
+    Ok(())
+  }
+
+  /// Read the header of the next chunk with data, if the body has yet to end.
+  fn next_chunk(&mut self) -> Result<(), ChunkedError<R::Error>> {
+    while self.remaining == 0 {
+      if self.ended {
+        Err(ChunkedError::Ended)?;
+      }
+      if self.chunk_read {
+        self.read_crlf()?;
+      }
+
+      let mut size = 0u64;
+      let mut digits = 0;
+      let byte = loop {
+        let byte = self.reader.read_byte().map_err(ChunkedError::ReadError)?;
+        let digit = match byte {
+          b'0' ..= b'9' => byte - b'0',
+          b'a' ..= b'f' => byte - b'a' + 10,
+          b'A' ..= b'F' => byte - b'A' + 10,
+          _ => break byte,
+        };
+        size = size
+          .checked_mul(16)
+          .map(|size| size + u64::from(digit))
+          .ok_or(ChunkedError::InvalidChunk)?;
+        digits += 1;
+      };
+      // The size may be followed by chunk extensions, which we ignore
+      if (digits == 0) || (!matches!(byte, b'\r' | b';' | b' ' | b'\t')) {
+        Err(ChunkedError::InvalidChunk)?;
+      }
+      self.read_line(byte)?;
+
+      self.remaining = size;
+      self.chunk_read = true;
+      if size == 0 {
+        self.ended = true;
+        // Read past the trailer section, which ends with an empty line
+        loop {
+          let byte = self.reader.read_byte().map_err(ChunkedError::ReadError)?;
+          if byte == b'\r' {
+            self.read_line(byte)?;
+            break;
+          }
+          self.read_line(byte)?;
+        }
+      }
+    }
+    Ok(())
+  }
+
+  /// The length of the next read, for a buffer of the specified length.
+  fn next_len(&self, len: usize) -> usize {
+    usize::try_from(self.remaining).map(|remaining| remaining.min(len)).unwrap_or(len)
+  }
+}
+
+impl<'read, R: Read<'read>> Read<'read> for ChunkedRead<'read, R> {
+  type Error = ChunkedError<R::Error>;
+
+  #[inline(always)]
+  fn read_byte(&mut self) -> Result<u8, Self::Error> {
+    self.next_chunk()?;
+    let byte = self.reader.read_byte().map_err(ChunkedError::ReadError)?;
+    self.remaining -= 1;
+    Ok(byte)
+  }
+
+  #[inline(always)]
+  fn read_exact(&mut self, mut slice: &mut [u8]) -> Result<(), Self::Error> {
+    while !slice.is_empty() {
+      self.next_chunk()?;
+      let len = self.next_len(slice.len());
+      let (read, rest) = slice.split_at_mut(len);
+      self.reader.read_exact(read).map_err(ChunkedError::ReadError)?;
+      // `len` is bounded by `remaining`, so this won't truncate
+      self.remaining -= read.len() as u64;
+      slice = rest;
+    }
+    Ok(())
+  }
+
+  #[inline(always)]
+  fn read_into_buffer(&mut self, buffer: &mut [u8]) -> Result<usize, Self::Error> {
+    self.next_chunk()?;
+    let len = self.next_len(buffer.len());
+    let len = self.reader.read_into_buffer(&mut buffer[.. len]).map_err(ChunkedError::ReadError)?;
+    self.remaining -= len as u64;
+    Ok(len)
+  }
+}
+
+#[test]
+fn chunked() {
+  use crate::{ConstStack, Deserializer};
+
+  // The chunks split the key, a number, and an escape sequence
+  let body =
+    b"4\r\n{\"ke\r\n6;name=value\r\ny\": [1\r\n9\r\n2, \"\\n\"]}\r\n0\r\nTrailer: a\r\n\r\nnext";
+  let mut deserializer =
+    Deserializer::<_, ConstStack<32>>::new(ChunkedRead::new(&body[..])).unwrap();
+  let mut fields = deserializer.value().unwrap().fields().unwrap();
+  let mut field = fields.next().unwrap().unwrap();
+  assert!(field.key_matches("key").unwrap());
+  let mut values = field.value().unwrap().iterate().unwrap();
+  assert_eq!(values.next().unwrap().unwrap().to_number().unwrap().i64(), Some(12));
+  assert_eq!(
+    values.next().unwrap().unwrap().to_str().unwrap().collect::<Result<String, _>>().unwrap(),
+    "\n"
+  );
+  assert!(values.next().is_none());
+  drop(values);
+  assert!(fields.next().is_none());
+  drop(fields);
+  assert!(deserializer.buffered().unwrap().is_empty());
+  // The last chunk and the trailer section are read past
+  assert_eq!(deserializer.into_reader().finish().unwrap(), b"next");
+  assert_eq!(ChunkedRead::new(&b"1\r\na\r\n0\r\n\r\n"[..]).finish().unwrap(), b"");
+
+  let mut reader = ChunkedRead::new(&b"3\r\nabc\r\n2\r\nde\r\n0\r\n\r\n"[..]);
+  let mut read = [0; 4];
+  reader.read_exact(&mut read).unwrap();
+  assert_eq!(&read, b"abcd");
+  assert_eq!(reader.read_into_buffer(&mut read).unwrap(), 1);
+  assert_eq!(read[0], b'e');
+  assert!(matches!(reader.read_byte(), Err(ChunkedError::Ended)));
+
+  for invalid in [&b"\r\n"[..], b"g\r\n", b"3\nabc\r\n", b"3\r\nabcd\r\n", b"11111111111111111\r\n"]
+  {
+    let mut reader = ChunkedRead::new(invalid);
+    let mut read = [0; 4];
+    assert!(matches!(reader.read_exact(&mut read), Err(ChunkedError::InvalidChunk)));
+  }
+  // The underlying reader ended before the body did
+  assert!(matches!(
+    ChunkedRead::new(&b"5\r\nabc"[..]).read_exact(&mut [0; 5]),
+    Err(ChunkedError::ReadError(_))
+  ));
+}
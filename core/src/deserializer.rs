@@ -1,8 +1,8 @@
-use crate::*;
+use crate::{*, path::Path};
 
 /// Advance the reader until there's a non-whitespace character.
 #[inline(always)]
-fn advance_whitespace<'read, R: Read<'read>, S: Stack>(
+pub(super) fn advance_whitespace<'read, R: Read<'read>, S: Stack>(
   reader: &mut PeekableRead<'read, R>,
 ) -> Result<(), JsonError<'read, R, S>> {
   let mut next;
@@ -11,7 +11,7 @@ fn advance_whitespace<'read, R: Read<'read>, S: Stack>(
     // https://datatracker.ietf.org/doc/html/rfc8259#section-2 defines whitespace as follows
     matches!(next, b'\x20' | b'\x09' | b'\x0A' | b'\x0D')
   } {
-    reader.read_byte().map_err(JsonError::ReadError)?;
+    reader.read_byte().map_err(IoError)?;
   }
   Ok(())
 }
@@ -22,9 +22,9 @@ pub(super) fn advance_past_colon<'read, R: Read<'read>, S: Stack>(
   reader: &mut PeekableRead<'read, R>,
 ) -> Result<(), JsonError<'read, R, S>> {
   advance_whitespace(reader)?;
-  match reader.read_byte().map_err(JsonError::ReadError)? {
+  match reader.read_byte().map_err(IoError)? {
     b':' => advance_whitespace(reader)?,
-    _ => Err(JsonError::InvalidKeyValueDelimiter)?,
+    _ => Err(SyntaxError::InvalidKeyValueDelimiter)?,
   }
   Ok(())
 }
@@ -37,14 +37,31 @@ pub(super) fn advance_past_comma_or_to_close<'read, R: Read<'read>, S: Stack>(
   advance_whitespace(reader)?;
   match reader.peek() {
     b',' => {
-      reader.read_byte().map_err(JsonError::ReadError)?;
+      reader.read_byte().map_err(IoError)?;
       advance_whitespace(reader)?;
-      if matches!(reader.peek(), b']' | b'}') {
-        Err(JsonError::TrailingComma)?;
+      match reader.peek() {
+        b']' | b'}' => Err(SyntaxError::TrailingComma)?,
+        b'\0' if reader.ended() => Err(SyntaxError::TrailingComma)?,
+        _ => {}
       }
     }
     b']' | b'}' => {}
-    _ => Err(JsonError::InvalidValue)?,
+    // The end of the input, after a scalar root value
+    b'\0' if reader.ended() => {}
+    _ => Err(SyntaxError::InvalidValue)?,
+  }
+  Ok(())
+}
+
+/// Check the end of the input was reached, with solely whitespace before it.
+///
+/// This is for a scalar root value, and must be called once the value has been read.
+pub(super) fn check_end<'read, R: Read<'read>, S: Stack>(
+  reader: &mut PeekableRead<'read, R>,
+) -> Result<(), JsonError<'read, R, S>> {
+  advance_whitespace(reader)?;
+  if !reader.ended() {
+    Err(SyntaxError::InvalidValue)?;
   }
   Ok(())
 }
@@ -91,6 +108,70 @@ pub(super) enum SingleStepResult {
   Unknown(SingleStepUnknownResult),
 }
 
+/// A limit on the depth of the structures which may be opened, as set by
+/// `Value::with_depth_limit`.
+#[derive(Clone, Copy)]
+struct DepthLimit {
+  /// The maximum depth of the stack after opening a structure.
+  limit: usize,
+  /// The depth of the stack which, once the stack is shallower, lifts this limit.
+  scope: usize,
+}
+
+/// The amount of depth limits which may be nested within each other, with each restored once the
+/// limit nested within it is lifted.
+const NESTED_DEPTH_LIMITS: usize = 4;
+
+/// The depth limits in effect, from the outermost to the innermost.
+///
+/// Each limit nested within another is tighter than it, so the innermost limit is the one
+/// enforced. Once it's lifted, the limit it was nested within is enforced again.
+#[derive(Clone, Copy)]
+struct DepthLimits {
+  limits: [DepthLimit; NESTED_DEPTH_LIMITS],
+  len: usize,
+}
+
+impl DepthLimits {
+  const NONE: Self =
+    Self { limits: [DepthLimit { limit: usize::MAX, scope: 0 }; NESTED_DEPTH_LIMITS], len: 0 };
+
+  /// The maximum depth of the stack after opening a structure.
+  #[inline(always)]
+  fn limit(&self) -> usize {
+    self.len.checked_sub(1).map_or(usize::MAX, |i| self.limits[i].limit)
+  }
+
+  /// Lift the limits for the values which have been advanced past.
+  #[inline(always)]
+  fn lift(&mut self, depth: usize) {
+    while (self.len != 0) && (depth < self.limits[self.len - 1].scope) {
+      self.len -= 1;
+    }
+  }
+
+  /// Limit the depth of the stack, until it's shallower than `scope`.
+  ///
+  /// Limits which are no tighter than the limit already in effect are ignored. If the maximum
+  /// amount of nested limits are already in effect, the innermost limit is tightened instead,
+  /// remaining in effect until its own value is advanced past.
+  #[inline(always)]
+  fn push(&mut self, limit: usize, scope: usize) {
+    if limit >= self.limit() {
+      return;
+    }
+    match self.len.checked_sub(1) {
+      Some(i) if (self.limits[i].scope == scope) || (self.len == NESTED_DEPTH_LIMITS) => {
+        self.limits[i].limit = limit;
+      }
+      _ => {
+        self.limits[self.len] = DepthLimit { limit, scope };
+        self.len += 1;
+      }
+    }
+  }
+}
+
 /// Step the deserializer forwards.
 ///
 /// This assumes there is no leading whitespace present in `reader` and will advance past any
@@ -98,7 +179,13 @@ pub(super) enum SingleStepResult {
 fn single_step<'read, 'parent, R: Read<'read>, S: Stack>(
   reader: &'parent mut PeekableRead<'read, R>,
   stack: &'parent mut S,
+  path: &'parent mut Path,
+  dialect: Dialect,
+  depth_limits: &'parent mut DepthLimits,
 ) -> Result<SingleStepResult, JsonError<'read, R, S>> {
+  // If we've advanced past values whose depth was limited, lift their limits
+  depth_limits.lift(stack.depth());
+
   match stack.peek().ok_or(JsonError::InternalError)? {
     State::Object => {
       let next = reader.peek();
@@ -106,6 +193,7 @@ fn single_step<'read, 'parent, R: Read<'read>, S: Stack>(
       // Check if the object terminates
       if next == b'}' {
         stack.pop().ok_or(JsonError::InternalError)?;
+        path.close();
 
         // If this isn't the outer object, advance past the comma after
         if stack.depth() != 0 {
@@ -115,7 +203,7 @@ fn single_step<'read, 'parent, R: Read<'read>, S: Stack>(
             the object, as the '}' was already internally read (consumed from the underlying
             reader) by `PeekableRead`.
           */
-          reader.read_byte().map_err(JsonError::ReadError)?;
+          reader.read_byte().map_err(IoError)?;
           advance_past_comma_or_to_close(reader)?;
         }
 
@@ -124,23 +212,25 @@ fn single_step<'read, 'parent, R: Read<'read>, S: Stack>(
 
       // Read the name of this field
       if next != b'"' {
-        Err(JsonError::InvalidKey)?;
+        Err(SyntaxError::InvalidKey)?;
       }
       // Advance past the '"'
-      reader.read_byte().map_err(JsonError::ReadError)?;
+      reader.read_byte().map_err(IoError)?;
 
       // Push how we're reading a value of an unknown type onto the stack, for the value
       stack.push(State::Unknown).map_err(JsonError::StackError)?;
+      path.advance();
       Ok(SingleStepResult::Object(SingleStepObjectResult::Field))
     }
     State::Array => {
       // Check if the array terminates
       if reader.peek() == b']' {
         stack.pop().ok_or(JsonError::InternalError)?;
+        path.close();
 
         // If this isn't the outer object, advance past the comma after
         if stack.depth() != 0 {
-          reader.read_byte().map_err(JsonError::ReadError)?;
+          reader.read_byte().map_err(IoError)?;
           advance_past_comma_or_to_close(reader)?;
         }
 
@@ -149,6 +239,7 @@ fn single_step<'read, 'parent, R: Read<'read>, S: Stack>(
 
       // Since the array doesn't terminate, read the next value
       stack.push(State::Unknown).map_err(JsonError::StackError)?;
+      path.advance();
       Ok(SingleStepResult::Array(SingleStepArrayResult::Value))
     }
     State::Unknown => {
@@ -157,35 +248,45 @@ fn single_step<'read, 'parent, R: Read<'read>, S: Stack>(
       let result = match kind(reader) {
         // Handle if this opens an object
         Type::Object => {
-          reader.read_byte().map_err(JsonError::ReadError)?;
+          reader.read_byte().map_err(IoError)?;
           advance_whitespace(reader)?;
           stack.push(State::Object).map_err(JsonError::StackError)?;
+          if stack.depth() > depth_limits.limit() {
+            Err(JsonError::DepthLimitExceeded)?;
+          }
+          reader.counters.object_opened(stack.depth());
+          path.open_object();
           return Ok(SingleStepResult::Unknown(SingleStepUnknownResult::ObjectOpened));
         }
         // Handle if this opens an array
         Type::Array => {
-          reader.read_byte().map_err(JsonError::ReadError)?;
+          reader.read_byte().map_err(IoError)?;
           advance_whitespace(reader)?;
           stack.push(State::Array).map_err(JsonError::StackError)?;
+          if stack.depth() > depth_limits.limit() {
+            Err(JsonError::DepthLimitExceeded)?;
+          }
+          reader.counters.array_opened(stack.depth());
+          path.open_array();
           return Ok(SingleStepResult::Unknown(SingleStepUnknownResult::ArrayOpened));
         }
         // Handle if this opens an string
         Type::String => {
-          reader.read_byte().map_err(JsonError::ReadError)?;
+          reader.read_byte().map_err(IoError)?;
           return Ok(SingleStepResult::Unknown(SingleStepUnknownResult::String));
         }
-        Type::Number => {
-          SingleStepResult::Unknown(SingleStepUnknownResult::Number(number::to_number_str(reader)?))
-        }
+        Type::Number => SingleStepResult::Unknown(SingleStepUnknownResult::Number(
+          number::to_number_str(reader, dialect)?,
+        )),
         Type::Bool => {
           let mut bool_string = [0; 4];
-          reader.read_exact_into_non_empty_slice(&mut bool_string).map_err(JsonError::ReadError)?;
+          reader.read_exact_into_non_empty_slice(&mut bool_string).map_err(IoError)?;
           let bool = if &bool_string == b"true" {
             true
           } else {
-            let e = reader.read_byte().map_err(JsonError::ReadError)?;
+            let e = reader.read_byte().map_err(IoError)?;
             if !((bool_string == *b"fals") & (e == b'e')) {
-              Err(JsonError::TypeError)?;
+              Err(SyntaxError::InvalidValue)?;
             }
             false
           };
@@ -193,9 +294,9 @@ fn single_step<'read, 'parent, R: Read<'read>, S: Stack>(
         }
         Type::Null => {
           let mut null_string = [0; 4];
-          reader.read_exact_into_non_empty_slice(&mut null_string).map_err(JsonError::ReadError)?;
+          reader.read_exact_into_non_empty_slice(&mut null_string).map_err(IoError)?;
           if null_string != *b"null" {
-            Err(JsonError::InvalidValue)?;
+            Err(SyntaxError::InvalidValue)?;
           }
           SingleStepResult::Unknown(SingleStepUnknownResult::Null)
         }
@@ -209,6 +310,40 @@ fn single_step<'read, 'parent, R: Read<'read>, S: Stack>(
   }
 }
 
+/// Advance to the closing delimiter of the current structure, solely scanning its structure.
+///
+/// This tracks the nesting of structures and the bounds of strings, without validating anything
+/// else. The closing delimiter is left to be read. `depth` is the depth of the stack within the
+/// current structure, and `limit` the maximum depth of the stack after opening a structure, which
+/// the structures nested within the current structure are held to.
+fn advance_to_close_structurally<'read, R: Read<'read>, S: Stack>(
+  reader: &mut PeekableRead<'read, R>,
+  depth: usize,
+  limit: usize,
+) -> Result<(), JsonError<'read, R, S>> {
+  let nestable = u64::try_from(limit.saturating_sub(depth)).unwrap_or(u64::MAX);
+  let mut nested = 0u64;
+  loop {
+    match reader.peek() {
+      b'}' | b']' if nested == 0 => return Ok(()),
+      b'}' | b']' => nested -= 1,
+      b'{' | b'[' => {
+        nested += 1;
+        if nested > nestable {
+          Err(JsonError::DepthLimitExceeded)?;
+        }
+      }
+      b'"' => {
+        reader.read_byte().map_err(IoError)?;
+        skip_unvalidated(reader, None)?;
+        continue;
+      }
+      _ => {}
+    }
+    reader.read_byte().map_err(IoError)?;
+  }
+}
+
 enum ToDrop {
   None,
   StringKey(bool),
@@ -224,12 +359,47 @@ pub(crate) struct DelayedDrop<'read, R: Read<'read>, S: Stack> {
   /// An item to `Drop` whenever the deserializer regains the control flow.
   to_drop: ToDrop,
   /// The amount of structures to drop whenever the deserializer regains the control flow.
+  ///
+  /// Every structure queued to be dropped is open, with an entry on the stack, so this is capped
+  /// by the depth of the stack. If this invariant is violated, the deserializer is poisoned with
+  /// `JsonError::InternalError`.
   structures_to_drop: u64,
   /// If an unknown value should be dropped whenever the deserializer regains the control flow.
   drop_value: bool,
 }
 
 impl<'read, R: Read<'read>, S: Stack> DelayedDrop<'read, R, S> {
+  /// Increment the amount of structures to drop, upon a structure being opened.
+  #[inline(always)]
+  fn structure_opened(
+    deserializer: &mut Deserializer<'read, R, S>,
+  ) -> Result<(), JsonError<'read, R, S>> {
+    let structures_to_drop = deserializer
+      .delayed_drop
+      .structures_to_drop
+      .checked_add(1)
+      .filter(|structures| {
+        u64::try_from(deserializer.stack.depth()).is_ok_and(|depth| *structures <= depth)
+      })
+      .ok_or(JsonError::InternalError)?;
+    deserializer.delayed_drop.structures_to_drop = structures_to_drop;
+    deserializer.reader.counters.structure_skipped();
+    Ok(())
+  }
+
+  /// Decrement the amount of structures to drop, upon a structure being closed.
+  #[inline(always)]
+  fn structure_closed(
+    deserializer: &mut Deserializer<'read, R, S>,
+  ) -> Result<(), JsonError<'read, R, S>> {
+    deserializer.delayed_drop.structures_to_drop = deserializer
+      .delayed_drop
+      .structures_to_drop
+      .checked_sub(1)
+      .ok_or(JsonError::InternalError)?;
+    Ok(())
+  }
+
   pub(crate) fn drop(
     deserializer: &mut Deserializer<'read, R, S>,
   ) -> Result<(), JsonError<'read, R, S>> {
@@ -259,7 +429,13 @@ impl<'read, R: Read<'read>, S: Stack> DelayedDrop<'read, R, S> {
       if deserializer.delayed_drop.drop_value {
         deserializer.delayed_drop.drop_value = false;
 
-        let step = match single_step(&mut deserializer.reader, &mut deserializer.stack)? {
+        let step = match single_step(
+          &mut deserializer.reader,
+          &mut deserializer.stack,
+          &mut deserializer.path,
+          deserializer.dialect,
+          &mut deserializer.depth_limits,
+        )? {
           SingleStepResult::Unknown(step) => step,
           // If we had a `Value`, it's an invariant the top of the stack was `State::Unknown`
           _ => Err(JsonError::InternalError)?,
@@ -281,7 +457,21 @@ impl<'read, R: Read<'read>, S: Stack> DelayedDrop<'read, R, S> {
 
       // Handle dropping of any structures
       while deserializer.delayed_drop.structures_to_drop != 0 {
-        let step = single_step(&mut deserializer.reader, &mut deserializer.stack)?;
+        if deserializer.dialect.skipped_values == ValidationProfile::Trusting {
+          deserializer.depth_limits.lift(deserializer.stack.depth());
+          advance_to_close_structurally(
+            &mut deserializer.reader,
+            deserializer.stack.depth(),
+            deserializer.depth_limits.limit(),
+          )?;
+        }
+        let step = single_step(
+          &mut deserializer.reader,
+          &mut deserializer.stack,
+          &mut deserializer.path,
+          deserializer.dialect,
+          &mut deserializer.depth_limits,
+        )?;
         match step {
           SingleStepResult::Unknown(SingleStepUnknownResult::String) => {
             // Queue the drop for this string, then iteratively restart this function to actually
@@ -295,11 +485,11 @@ impl<'read, R: Read<'read>, S: Stack> DelayedDrop<'read, R, S> {
           }
           SingleStepResult::Object(SingleStepObjectResult::Closed) |
           SingleStepResult::Array(SingleStepArrayResult::Closed) => {
-            deserializer.delayed_drop.structures_to_drop -= 1
+            Self::structure_closed(deserializer)?
           }
           SingleStepResult::Unknown(
             SingleStepUnknownResult::ObjectOpened | SingleStepUnknownResult::ArrayOpened,
-          ) => deserializer.delayed_drop.structures_to_drop += 1,
+          ) => Self::structure_opened(deserializer)?,
           _ => {}
         }
       }
@@ -313,10 +503,24 @@ impl<'read, R: Read<'read>, S: Stack> DelayedDrop<'read, R, S> {
   }
 }
 
+/// A deserializer which reads from a type-erased reader, using the default stack.
+///
+/// All uses of this share a single instantiation of the deserializer.
+pub type ErasedDeserializer<'read, 'erased> =
+  Deserializer<'read, crate::ErasedRead<'read, 'erased>, crate::DefaultStack>;
+
 /// A deserializer for a JSON-encoded structure.
+///
+/// This is `Send` and `Sync` when the reader, the stack, and their errors are, as are the values
+/// and iterators borrowing it.
 pub struct Deserializer<'read, R: Read<'read>, S: Stack> {
   pub(crate) reader: PeekableRead<'read, R>,
   stack: S,
+  pub(crate) path: Path,
+  pub(crate) dialect: Dialect,
+  /// The position the key currently being read must end by, per `Dialect::key_length_limit`.
+  pub(crate) key_end: Option<usize>,
+  depth_limits: DepthLimits,
   delayed_drop: DelayedDrop<'read, R, S>,
 }
 
@@ -337,7 +541,10 @@ impl<'read, R: Read<'read>, S: Stack> Deserializer<'read, R, S> {
   #[inline(always)]
   pub(crate) fn drop_structure(&mut self) {
     self.delayed_drop.nothing_queued = false;
-    self.delayed_drop.structures_to_drop += 1;
+    // This may be called when dropping an iterator, so any error is cached for the next call
+    if let Err(e) = DelayedDrop::structure_opened(self) {
+      self.poison(e);
+    }
   }
   /// Queue the drop of a value of unknown type.
   #[inline(always)]
@@ -346,6 +553,23 @@ impl<'read, R: Read<'read>, S: Stack> Deserializer<'read, R, S> {
     self.delayed_drop.drop_value = true;
   }
 
+  /// The depth of the value the deserializer is currently at.
+  #[inline(always)]
+  pub(crate) fn depth(&self) -> usize {
+    // The stack has an entry for every structure the value is within, and the value itself
+    self.stack.depth().saturating_sub(1)
+  }
+
+  /// Limit the depth of the structures within the value the deserializer is currently at.
+  ///
+  /// If a limit is already in effect, the tighter of the two limits is kept until this value is
+  /// advanced past, after which the existing limit is enforced again.
+  #[inline(always)]
+  pub(crate) fn limit_depth(&mut self, depth: usize) {
+    let limit = self.depth().saturating_add(depth);
+    self.depth_limits.push(limit, self.stack.depth());
+  }
+
   /// Poison the deserializer such that all future calls return an error.
   #[inline(always)]
   pub(crate) fn poison(&mut self, error: JsonError<'read, R, S>) {
@@ -356,7 +580,15 @@ impl<'read, R: Read<'read>, S: Stack> Deserializer<'read, R, S> {
   #[inline(always)]
   pub(super) fn single_step(&mut self) -> Result<SingleStepResult, JsonError<'read, R, S>> {
     let res = DelayedDrop::drop(self);
-    let res = res.and_then(|()| single_step(&mut self.reader, &mut self.stack));
+    let res = res.and_then(|()| {
+      single_step(
+        &mut self.reader,
+        &mut self.stack,
+        &mut self.path,
+        self.dialect,
+        &mut self.depth_limits,
+      )
+    });
     if let Some(e) = res.as_ref().err() {
       self.delayed_drop.nothing_queued = false;
       self.delayed_drop.error = Some(*e);
@@ -369,6 +601,7 @@ impl<'read, R: Read<'read>, S: Stack> Deserializer<'read, R, S> {
 // Internally, we assume whenever this is held, the top item on the stack is `State::Unknown`
 pub struct Value<'read, 'parent, R: Read<'read>, S: Stack> {
   pub(crate) deserializer: Option<&'parent mut Deserializer<'read, R, S>>,
+  pub(crate) kind: Type,
 }
 
 impl<'read, 'parent, R: Read<'read>, S: Stack> Drop for Value<'read, 'parent, R, S> {
@@ -391,20 +624,57 @@ impl<'read, R: Read<'read>, S: Stack> Deserializer<'read, R, S> {
   /// This will advance past any whitespace present at the start of the reader, per RFC 8259's
   /// definition of whitespace.
   ///
-  /// If `reader` is aligned to valid JSON, this will read past the immediately present structure
-  /// yet no further. If `reader` is not aligned to valid JSON, the state of `reader` is undefined
-  /// after this.
+  /// If `reader` is aligned to valid JSON, this will read past the immediately present structure.
+  /// If `reader` lends its bytes or reads multiple bytes at once (via `Read::read_into_buffer`),
+  /// this may read further, into the deserializer's lookahead buffer, with the bytes read yet not
+  /// consumed available via `Deserializer::buffered`. If `reader` is not aligned to valid JSON, the
+  /// state of `reader` is undefined after this.
   #[inline(always)]
   pub fn new(reader: R) -> Result<Self, JsonError<'read, R, S>> {
-    let mut reader = PeekableRead::try_from(reader).map_err(JsonError::ReadError)?;
+    Self::with_dialect(reader, Dialect::STRICT)
+  }
+
+  /// Create a new deserializer for the specified dialect of JSON.
+  ///
+  /// This is otherwise identical to `Deserializer::new`.
+  #[inline(always)]
+  pub fn with_dialect(reader: R, dialect: Dialect) -> Result<Self, JsonError<'read, R, S>> {
+    Self::with_stack(reader, S::empty(), dialect)
+  }
+
+  /// Create a new deserializer, reusing an existing stack.
+  ///
+  /// This allows a single stack (such as one with a large capacity, or one which allocated) to be
+  /// reused across many deserializers, as returned by `Deserializer::into_parts`. Any items left
+  /// on the stack (such as from a deserializer which errored) will be removed.
+  ///
+  /// This is otherwise identical to `Deserializer::with_dialect`.
+  #[inline(always)]
+  pub fn with_stack(
+    reader: R,
+    mut stack: S,
+    dialect: Dialect,
+  ) -> Result<Self, JsonError<'read, R, S>> {
+    let mut reader = PeekableRead::try_from(reader).map_err(IoError)?;
+    if dialect.byte_order_mark && (reader.peek() == 0xef) {
+      let mut bom = [0; 3];
+      reader.read_exact_into_non_empty_slice(&mut bom).map_err(IoError)?;
+      if bom != *b"\xef\xbb\xbf" {
+        Err(SyntaxError::InvalidValue)?;
+      }
+    }
     advance_whitespace(&mut reader)?;
 
-    let mut stack = S::empty();
+    while stack.pop().is_some() {}
     stack.push(State::Unknown).map_err(JsonError::StackError)?;
 
     Ok(Deserializer {
       reader,
       stack,
+      path: Path::new(),
+      dialect,
+      key_end: None,
+      depth_limits: DepthLimits::NONE,
       delayed_drop: DelayedDrop {
         nothing_queued: true,
         error: None,
@@ -426,10 +696,324 @@ impl<'read, R: Read<'read>, S: Stack> Deserializer<'read, R, S> {
     if (self.stack.depth() != 1) || (!self.delayed_drop.nothing_queued) {
       Err(JsonError::ReusedDeserializer)?;
     }
-    let mut result = Value { deserializer: Some(self) };
-    if !matches!(result.kind()?, Type::Object | Type::Array) {
-      Err(JsonError::TypeError)?;
+    let result = Value::new(self)?;
+    if !matches!(result.kind(), Type::Object | Type::Array) {
+      Err(JsonError::TypeError(TypeError))?;
     }
     Ok(result)
   }
+  /// Step into the root value, which is expected to be a scalar followed solely by whitespace.
+  fn scalar(&mut self) -> Result<SingleStepUnknownResult, JsonError<'read, R, S>> {
+    if (self.stack.depth() != 1) || (!self.delayed_drop.nothing_queued) {
+      Err(JsonError::ReusedDeserializer)?;
+    }
+    self.reader.permit_end();
+    match self.single_step()? {
+      SingleStepResult::Unknown(result) => Ok(result),
+      _ => Err(JsonError::InternalError),
+    }
+  }
+
+  /// Deserialize the input as a single number.
+  ///
+  /// The input must solely be the number, optionally surrounded by whitespace. The end of the
+  /// input is considered to be reached when the reader returns an error, as `Read` has no notion of
+  /// the end of the underlying reader. This is not eligible to be called if `Deserializer::value`
+  /// was, nor more than once.
+  pub fn number(&mut self) -> Result<Number, JsonError<'read, R, S>> {
+    let SingleStepUnknownResult::Number(number) = self.scalar()? else {
+      Err(JsonError::TypeError(TypeError))?
+    };
+    check_end(&mut self.reader)?;
+    Ok(number)
+  }
+
+  /// Deserialize the input as a single string.
+  ///
+  /// This yields the characters of the string as `Value::to_str` does. The input following the
+  /// string is checked to solely be whitespace once the string has been entirely read, with an
+  /// error yielded otherwise. This is otherwise identical to `Deserializer::number`.
+  pub fn string(
+    &mut self,
+  ) -> Result<
+    impl use<'read, '_, R, S> + Iterator<Item = Result<char, JsonError<'read, R, S>>>,
+    JsonError<'read, R, S>,
+  > {
+    let SingleStepUnknownResult::String = self.scalar()? else {
+      Err(JsonError::TypeError(TypeError))?
+    };
+    let mut string = Some(handle_string_value(self));
+    Ok(core::iter::from_fn(move || {
+      let chars = string.as_mut()?;
+      let res = match chars.next() {
+        Some(Ok(char)) => return Some(Ok(char)),
+        Some(Err(e)) => Some(Err(e)),
+        None => chars.check_end().err().map(Err),
+      };
+      string = None;
+      res
+    }))
+  }
+
+  /// Deserialize the input as a single boolean.
+  ///
+  /// This is otherwise identical to `Deserializer::number`.
+  pub fn bool(&mut self) -> Result<bool, JsonError<'read, R, S>> {
+    let SingleStepUnknownResult::Bool(bool) = self.scalar()? else {
+      Err(JsonError::TypeError(TypeError))?
+    };
+    check_end(&mut self.reader)?;
+    Ok(bool)
+  }
+
+  /// Deserialize the input as a single `null`.
+  ///
+  /// This is otherwise identical to `Deserializer::number`.
+  pub fn null(&mut self) -> Result<(), JsonError<'read, R, S>> {
+    let SingleStepUnknownResult::Null = self.scalar()? else {
+      Err(JsonError::TypeError(TypeError))?
+    };
+    check_end(&mut self.reader)
+  }
+
+  /// The bytes read from the reader which have yet to be consumed by the deserializer.
+  ///
+  /// This first completes any pending work, such as advancing past dropped values. Once the
+  /// structure has been entirely deserialized (or its `Value` dropped), this will be the bytes
+  /// read from the reader after the structure. These, followed by the remaining bytes from the
+  /// reader (`Deserializer::into_reader`), are the remainder of the stream.
+  ///
+  /// For readers which don't lend their bytes, this will be at most `LOOKAHEAD_CAPACITY` bytes.
+  pub fn buffered(&mut self) -> Result<&[u8], JsonError<'read, R, S>> {
+    DelayedDrop::drop(self)?;
+    let buffered = self.reader.buffered();
+    // If the structure was closed, its closing delimiter was read yet not considered consumed
+    Ok(if self.stack.depth() == 0 { buffered.get(1 ..).unwrap_or(&[]) } else { buffered })
+  }
+
+  /// The amount of bytes consumed from the reader by the deserializer.
+  ///
+  /// This is the amount of bytes read from the reader, minus those not yet consumed (which would be
+  /// returned by `Deserializer::buffered`). Pending work (such as advancing past dropped values) is
+  /// not completed first, so this may be lower than the amount `Deserializer::buffered` would
+  /// imply. This allows enforcing quotas or reporting progress while deserializing.
+  #[inline(always)]
+  pub fn bytes_consumed(&self) -> usize {
+    // If the structure was closed, its closing delimiter was read yet not considered consumed
+    self.reader.consumed() + usize::from(self.stack.depth() == 0)
+  }
+
+  /// The path to the deserializer's current position within the structure.
+  ///
+  /// After an error is returned, this will be the path to where the error was encountered.
+  #[cfg(feature = "path")]
+  #[inline(always)]
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  /// Counters on the work performed by the deserializer.
+  #[cfg(feature = "instrumentation")]
+  #[inline(always)]
+  pub fn counters(&self) -> Counters {
+    Counters { bytes_read: self.reader.read(), ..self.reader.counters }
+  }
+
+  /// The maximum depth of nested structures the deserializer has reached.
+  ///
+  /// This is shorthand for `Deserializer::counters().max_depth`.
+  #[cfg(feature = "instrumentation")]
+  #[inline(always)]
+  pub fn max_depth_reached(&self) -> usize {
+    self.reader.counters.max_depth
+  }
+
+  /// Consume the deserializer, returning the reader.
+  ///
+  /// Any bytes read into the deserializer's lookahead buffer will be lost. They should be
+  /// reclaimed with `Deserializer::buffered` beforehand.
+  #[inline(always)]
+  pub fn into_reader(self) -> R {
+    self.reader.into_reader()
+  }
+
+  /// Consume the deserializer, returning the reader and the stack.
+  ///
+  /// The stack may be reused with `Deserializer::with_stack`. As with
+  /// `Deserializer::into_reader`, any bytes read into the deserializer's lookahead buffer will be
+  /// lost.
+  #[inline(always)]
+  pub fn into_parts(self) -> (R, S) {
+    (self.reader.into_reader(), self.stack)
+  }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn with_stack() {
+  use alloc::vec::Vec;
+
+  let mut stack = Vec::with_capacity(64);
+  for (serialization, valid) in
+    [(&b"[[1], {\"a\": [2]}]"[..], true), (b"[[[1, 2", false), (b"{\"a\": [[]]}", true)]
+  {
+    let mut deserializer =
+      Deserializer::<_, Vec<State>>::with_stack(serialization, stack, Dialect::STRICT).unwrap();
+    // Drop the value, then complete the pending work of advancing past it
+    drop(deserializer.value().unwrap());
+    assert_eq!(deserializer.buffered().is_ok(), valid);
+    stack = deserializer.into_parts().1;
+    assert!(stack.capacity() >= 64);
+  }
+}
+
+#[test]
+fn byte_order_mark() {
+  use crate::ConstStack;
+
+  let read = |json: &'static [u8], byte_order_mark| {
+    let dialect = Dialect { byte_order_mark, ..Dialect::STRICT };
+    let mut deserializer = Deserializer::<_, ConstStack<32>>::with_dialect(json, dialect)?;
+    drop(deserializer.value()?);
+    deserializer.buffered().map(<[u8]>::len)
+  };
+
+  assert!(read(b"\xef\xbb\xbf{}", false).is_err());
+  assert_eq!(read(b"\xef\xbb\xbf{}", true).unwrap(), 0);
+  assert_eq!(read(b"\xef\xbb\xbf \n[]", true).unwrap(), 0);
+  assert_eq!(read(b"{}", true).unwrap(), 0);
+  assert!(read(b"\xef\xbb{}", true).is_err());
+  assert!(read(b"\xef\xbb\xbf", true).is_err());
+}
+
+#[test]
+fn scalar() {
+  use crate::ConstStack;
+
+  type Deserializer<'read> = crate::Deserializer<'read, &'read [u8], ConstStack<32>>;
+  let number = |json: &'static str| Deserializer::new(json.as_bytes())?.number();
+
+  assert_eq!(number("42").unwrap().i64(), Some(42));
+  assert_eq!(number(" -1.5e3 \n").unwrap().f64(), Some(-1500.0));
+  for invalid in ["42 43", "42,", "42]", "4 2", "-", "01", "42\0"] {
+    assert!(matches!(number(invalid), Err(JsonError::SyntaxError(_))), "{invalid}");
+  }
+  assert!(matches!(number("true"), Err(JsonError::TypeError(TypeError))));
+  let mut deserializer = Deserializer::with_dialect(b"NaN".as_slice(), Dialect::RELAXED).unwrap();
+  assert!(deserializer.number().unwrap().f64_extended().unwrap().is_nan());
+  assert!(matches!(number("[42]"), Err(JsonError::TypeError(TypeError))));
+
+  let mut deserializer = Deserializer::new(br#" "a\u0062c" "#.as_slice()).unwrap();
+  assert_eq!(
+    deserializer.string().unwrap().collect::<Result<std::string::String, _>>().unwrap(),
+    "abc"
+  );
+  for invalid in [r#""a" "b""#, r#""a"]"#, r#""a","#, r#""a"#] {
+    let mut deserializer = Deserializer::new(invalid.as_bytes()).unwrap();
+    assert!(
+      deserializer.string().unwrap().collect::<Result<std::string::String, _>>().is_err(),
+      "{invalid}"
+    );
+  }
+
+  assert!(Deserializer::new(b"true".as_slice()).unwrap().bool().unwrap());
+  assert!(!Deserializer::new(b" false ".as_slice()).unwrap().bool().unwrap());
+  assert!(Deserializer::new(b"tru".as_slice()).unwrap().bool().is_err());
+  assert!(Deserializer::new(b"null".as_slice()).unwrap().null().is_ok());
+  assert!(Deserializer::new(b"null null".as_slice()).unwrap().null().is_err());
+
+  // The accessors may not be used after `Deserializer::value`, nor more than once
+  let mut deserializer = Deserializer::new(b"[]".as_slice()).unwrap();
+  drop(deserializer.value().unwrap());
+  assert!(matches!(deserializer.number(), Err(JsonError::ReusedDeserializer)));
+  let mut deserializer = Deserializer::new(b"1".as_slice()).unwrap();
+  deserializer.number().unwrap();
+  assert!(matches!(deserializer.number(), Err(JsonError::ReusedDeserializer)));
+}
+
+#[test]
+fn trusting() {
+  use crate::ConstStack;
+
+  let read_b = |json: &'static str, skipped_values| {
+    let dialect = Dialect { skipped_values, ..Dialect::STRICT };
+    let mut deserializer =
+      Deserializer::<_, ConstStack<32>>::with_dialect(json.as_bytes(), dialect).unwrap();
+    let mut fields = deserializer.value().unwrap().fields().unwrap();
+    let mut b = None;
+    while let Some(field) = fields.next() {
+      let mut field = field?;
+      if field.key()?.next().transpose()? == Some('b') {
+        b = field.value()?.to_number()?.i64();
+      }
+    }
+    Ok::<_, JsonError<'_, _, _>>(b)
+  };
+
+  let valid = r#"{"a":[1,{"x":"]}\"","y":[[],{}]},"c"],"b":1}"#;
+  for profile in [
+    ValidationProfile::FullValidation,
+    ValidationProfile::ValidateRead,
+    ValidationProfile::Trusting,
+  ] {
+    assert_eq!(read_b(valid, profile).unwrap(), Some(1));
+  }
+
+  // Skipped structures are solely scanned for their structure
+  for invalid in [r#"{"a":[tru,{"x" 1,},"\q"],"b":2}"#, r#"{"a":{"x":[1 2]},"b":2}"#] {
+    assert!(read_b(invalid, ValidationProfile::FullValidation).is_err());
+    assert!(read_b(invalid, ValidationProfile::ValidateRead).is_err());
+    assert_eq!(read_b(invalid, ValidationProfile::Trusting).unwrap(), Some(2));
+  }
+
+  // The structure itself is still required to be balanced
+  assert!(read_b(r#"{"a":[1,"b":2}"#, ValidationProfile::Trusting).is_err());
+  assert!(read_b(r#"{"a":[1]],"b":2}"#, ValidationProfile::Trusting).is_err());
+}
+
+#[test]
+fn auto_traits() {
+  use crate::{Value, Field, FieldIterator, ArrayIterator};
+
+  fn send_sync<T: Send + Sync>() {}
+  // The deserializer, and the views borrowing it, are `Send` and `Sync` when the reader, stack,
+  // and their errors are, allowing them to be held across `await` points on work-stealing
+  // executors
+  #[allow(dead_code)]
+  fn generic<'read, R: Read<'read> + Send + Sync, S: Stack + Send + Sync>()
+  where
+    R::Error: Send + Sync,
+    S::Error: Send + Sync,
+  {
+    send_sync::<Deserializer<'read, R, S>>();
+    send_sync::<Value<'read, '_, R, S>>();
+    send_sync::<Field<'read, '_, R, S>>();
+    send_sync::<FieldIterator<'read, '_, R, S>>();
+    send_sync::<ArrayIterator<'read, '_, R, S>>();
+    send_sync::<JsonError<'read, R, S>>();
+  }
+  generic::<&[u8], ConstStack<32>>();
+  send_sync::<crate::Number>();
+}
+
+#[test]
+fn structures_to_drop() {
+  use crate::ConstStack;
+
+  let mut deserializer = Deserializer::<_, ConstStack<4>>::new(&b"[[[1]], 2]"[..]).unwrap();
+  let mut values = deserializer.value().unwrap().iterate().unwrap();
+  drop(values.next().unwrap().unwrap().iterate().unwrap());
+  assert_eq!(values.next().unwrap().unwrap().to_number().unwrap().i64(), Some(2));
+  assert_eq!(values.deserializer.delayed_drop.structures_to_drop, 0);
+  // Dropping the unfinished array queues it to be dropped
+  drop(values);
+  assert_eq!(deserializer.delayed_drop.structures_to_drop, 1);
+
+  // More structures than are open being queued is an error, not an underflow when advancing
+  let mut deserializer = Deserializer::<_, ConstStack<4>>::new(&b"[[1], 2]"[..]).unwrap();
+  let mut values = deserializer.value().unwrap().iterate().unwrap();
+  for _ in 0 .. 4 {
+    values.deserializer.drop_structure();
+  }
+  assert!(matches!(values.next(), Some(Err(JsonError::InternalError))));
 }
@@ -0,0 +1,190 @@
+/// How thoroughly to validate values which are skipped.
+///
+/// Skipped values are those never read, such as the values of fields which are never accessed,
+/// and the remainders of values which were only partially read. Validating less of them allows
+/// skipping them faster, at the cost of accepting documents which are invalid solely within them.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum ValidationProfile {
+  /// Fully validate skipped values, as if they were read.
+  #[default]
+  FullValidation,
+  /// Validate skipped values, except for the contents of strings.
+  ///
+  /// The closing quote of a skipped string is found without validating its escape sequences, its
+  /// UTF-8, or that it lacks control characters.
+  ValidateRead,
+  /// Solely scan skipped objects and arrays for their structure.
+  ///
+  /// Skipped objects and arrays are scanned for their closing delimiter, solely tracking the
+  /// nesting of structures and the bounds of strings (as with `ValidationProfile::ValidateRead`).
+  /// Their keys, values, and delimiters between them are not validated. Skipped values which
+  /// aren't objects or arrays are handled as with `ValidationProfile::ValidateRead`. Limits set
+  /// by `Value::with_depth_limit` still apply to the structures nested within them.
+  Trusting,
+}
+
+/// The dialect of JSON to deserialize.
+///
+/// By default, this is strictly RFC 8259. Extensions to RFC 8259 may be individually enabled to
+/// deserialize JSON from producers which don't strictly follow it.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct Dialect {
+  /// Accept the tokens `NaN`, `Infinity`, and `-Infinity` as numbers.
+  ///
+  /// These are emitted by producers such as Python's `json` module. Their values are available via
+  /// `Number::f64_extended`.
+  pub non_finite_numbers: bool,
+  /// Accept hexadecimal (`0x`), octal (`0o`), and binary (`0b`) integer literals as numbers.
+  ///
+  /// The prefixes must be lowercase, as with Rust, while the digits themselves may be of either
+  /// case. The literals may be negated with a leading `-` and must fit within a `u64` (prior to
+  /// negation).
+  pub radix_integers: bool,
+  /// Accept (and ignore) a UTF-8 byte order mark preceding the serialization.
+  ///
+  /// These are emitted by producers such as Windows' text editors, despite RFC 8259 forbidding
+  /// them.
+  pub byte_order_mark: bool,
+  /// Decode unpaired UTF-16 surrogates within strings as `U+FFFD` (the replacement character).
+  ///
+  /// RFC 8259 permits unpaired surrogates (such as `"\ud800"`) yet leaves their semantics
+  /// undefined. By default, strings with them are considered valid yet not UTF-8, erroring with
+  /// `JsonError::NotUtf8` when read. Enabling this decodes them lossily instead.
+  pub lone_surrogates: bool,
+  /// Accept unescaped control characters (those below `U+0020`) within strings.
+  ///
+  /// These are emitted by producers such as loggers which write raw tabs and newlines into
+  /// strings, despite RFC 8259 requiring they be escaped. They're yielded verbatim.
+  pub control_characters: bool,
+  /// How thoroughly to validate values which are skipped.
+  ///
+  /// Values which are read are always fully validated.
+  pub skipped_values: ValidationProfile,
+  /// The maximum length of a number, in bytes, if limited.
+  ///
+  /// Numbers which exceed this error with `JsonError::LengthLimitExceeded` once the limit is
+  /// reached, bounding the time spent on any single number (such as a number with millions of
+  /// digits) independently of the length of the document.
+  pub number_length_limit: Option<usize>,
+  /// The maximum length of a key, in bytes of its serialization (excluding its quotes), if
+  /// limited.
+  ///
+  /// Keys which exceed this error with `JsonError::LengthLimitExceeded` once the limit is reached,
+  /// whether the key is read or skipped. Keys within structures skipped per
+  /// `ValidationProfile::Trusting` are solely scanned for their bounds and aren't limited.
+  pub key_length_limit: Option<usize>,
+}
+
+impl Dialect {
+  /// The dialect strictly defined by RFC 8259.
+  pub const STRICT: Dialect = Dialect {
+    non_finite_numbers: false,
+    radix_integers: false,
+    byte_order_mark: false,
+    lone_surrogates: false,
+    control_characters: false,
+    skipped_values: ValidationProfile::FullValidation,
+    number_length_limit: None,
+    key_length_limit: None,
+  };
+  /// The dialect strictly defined by RFC 8259, with limits on the lengths of numbers and keys.
+  ///
+  /// This is intended for documents from untrusted sources. The limits are generous for any
+  /// reasonable document, allowing numbers of up to 1 KiB and keys of up to 64 KiB.
+  pub const HARDENED: Dialect = Dialect {
+    number_length_limit: Some(1024),
+    key_length_limit: Some(64 * 1024),
+    ..Dialect::STRICT
+  };
+  /// The dialect with all extensions to RFC 8259 enabled.
+  ///
+  /// This validates skipped values per `ValidationProfile::ValidateRead`, as
+  /// `ValidationProfile::Trusting` is a trade-off of strictness for throughput, not an extension.
+  pub const RELAXED: Dialect = Dialect {
+    non_finite_numbers: true,
+    radix_integers: true,
+    byte_order_mark: true,
+    lone_surrogates: true,
+    control_characters: true,
+    skipped_values: ValidationProfile::ValidateRead,
+    number_length_limit: None,
+    key_length_limit: None,
+  };
+}
+
+#[test]
+fn length_limits() {
+  use crate::{ErrorKind, ConstStack, Deserializer};
+
+  let dialect = Dialect {
+    number_length_limit: Some(4),
+    key_length_limit: Some(4),
+    radix_integers: true,
+    ..Dialect::STRICT
+  };
+  // Read the object's keys and values, or solely its values, or skip it entirely
+  let read = |serialization: &str, dialect, read_keys, read_values| {
+    let mut deserializer =
+      Deserializer::<_, ConstStack<32>>::with_dialect(serialization.as_bytes(), dialect)?;
+    let mut fields = deserializer.value()?.fields()?;
+    let mut read = 0;
+    while let Some(field) = fields.next() {
+      let mut field = field?;
+      if read_keys {
+        read += field.key()?.try_fold(0, |len, char| char.map(|_| len + 1))?;
+      }
+      if read_values {
+        let value = field.value()?;
+        if value.kind() == crate::Type::Number {
+          value.to_number()?;
+          read += 1;
+        }
+      }
+    }
+    drop(fields);
+    Ok::<_, ErrorKind>(read)
+  };
+  let read_all = |serialization: &str, dialect| {
+    [(true, true), (false, true), (true, false), (false, false)]
+      .map(|(read_keys, read_values)| read(serialization, dialect, read_keys, read_values))
+  };
+
+  // Numbers and keys of exactly the limit are accepted, as are longer strings which aren't keys
+  assert!(
+    read_all(r#"{"abcd": 1234, "a": -123, "b": 1e10, "c": "abcdef"}"#, dialect)
+      .iter()
+      .all(Result::is_ok)
+  );
+  assert!(read_all(r#"{"\n\n": 0x00}"#, dialect).iter().all(Result::is_ok));
+
+  // Longer numbers and keys error, whether they're read or skipped
+  for serialization in [
+    r#"{"abcde": 1}"#,
+    r#"{"\n\n\n": 1}"#,
+    r#"{"a": 12345}"#,
+    r#"{"a": -1234}"#,
+    r#"{"a": 0x000}"#,
+    r#"{"a": [12345]}"#,
+  ] {
+    for result in read_all(serialization, dialect) {
+      assert_eq!(result, Err(ErrorKind::LengthLimitExceeded), "{serialization}");
+    }
+    let skipping = Dialect { skipped_values: crate::ValidationProfile::ValidateRead, ..dialect };
+    assert_eq!(read(serialization, skipping, false, false), Err(ErrorKind::LengthLimitExceeded));
+    let unlimited = Dialect { number_length_limit: None, key_length_limit: None, ..dialect };
+    assert!(read_all(serialization, unlimited).iter().all(Result::is_ok));
+  }
+  assert_eq!(ErrorKind::LengthLimitExceeded.category(), crate::ErrorCategory::Limit);
+
+  // The hardened dialect rejects pathological numbers and keys
+  let number = "1".repeat(1024 * 1024);
+  let serialization = format!(r#"{{"a": {number}}}"#);
+  assert!(read_all(&serialization, Dialect::STRICT).iter().all(Result::is_ok));
+  for result in read_all(&serialization, Dialect::HARDENED) {
+    assert_eq!(result, Err(ErrorKind::LengthLimitExceeded));
+  }
+  let serialization = format!(r#"{{"{number}": 1}}"#);
+  for result in read_all(&serialization, Dialect::HARDENED) {
+    assert_eq!(result, Err(ErrorKind::LengthLimitExceeded));
+  }
+}
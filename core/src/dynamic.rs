@@ -0,0 +1,293 @@
+use core::ops::ControlFlow;
+
+use crate::*;
+
+/// The operations on a `Value`, without its reader and stack within its type.
+///
+/// This is implemented for a slot holding the `Value`, which is taken upon being consumed.
+trait ErasedValue {
+  fn to_number(&mut self) -> Result<Number, ErrorKind>;
+  fn to_bool(&mut self) -> Result<bool, ErrorKind>;
+  fn to_null(&mut self) -> Result<(), ErrorKind>;
+  #[allow(clippy::type_complexity)]
+  fn to_str(
+    &mut self,
+    chars: &mut dyn FnMut(&mut dyn Iterator<Item = Result<char, ErrorKind>>),
+  ) -> Result<(), ErrorKind>;
+  fn iterate(
+    &mut self,
+    element: &mut dyn FnMut(DynValue<'_>) -> Result<ControlFlow<()>, ErrorKind>,
+  ) -> Result<(), ErrorKind>;
+  fn fields(
+    &mut self,
+    field: &mut dyn FnMut(DynField<'_>) -> Result<ControlFlow<()>, ErrorKind>,
+  ) -> Result<(), ErrorKind>;
+}
+
+/// The operations on a `Field`, without its reader and stack within its type.
+trait ErasedField {
+  fn next_key_char(&mut self) -> Option<Result<char, ErrorKind>>;
+  fn key_matches(&mut self, expected: &str) -> Result<bool, ErrorKind>;
+  fn value(&mut self, value: &mut dyn FnMut(DynValue<'_>)) -> Result<(), ErrorKind>;
+}
+
+impl<'read, R: Read<'read>, S: Stack> ErasedValue for Option<Value<'read, '_, R, S>> {
+  fn to_number(&mut self) -> Result<Number, ErrorKind> {
+    let value = self.take().ok_or(ErrorKind::InternalError)?;
+    value.to_number().map_err(|e| e.kind())
+  }
+  fn to_bool(&mut self) -> Result<bool, ErrorKind> {
+    let value = self.take().ok_or(ErrorKind::InternalError)?;
+    value.to_bool().map_err(|e| e.kind())
+  }
+  fn to_null(&mut self) -> Result<(), ErrorKind> {
+    let value = self.take().ok_or(ErrorKind::InternalError)?;
+    value.to_null().map_err(|e| e.kind())
+  }
+  fn to_str(
+    &mut self,
+    chars: &mut dyn FnMut(&mut dyn Iterator<Item = Result<char, ErrorKind>>),
+  ) -> Result<(), ErrorKind> {
+    let value = self.take().ok_or(ErrorKind::InternalError)?;
+    let string = value.to_str().map_err(|e| e.kind())?;
+    chars(&mut string.map(|char| char.map_err(|e| e.kind())));
+    Ok(())
+  }
+  fn iterate(
+    &mut self,
+    element: &mut dyn FnMut(DynValue<'_>) -> Result<ControlFlow<()>, ErrorKind>,
+  ) -> Result<(), ErrorKind> {
+    let value = self.take().ok_or(ErrorKind::InternalError)?;
+    let mut elements = value.iterate().map_err(|e| e.kind())?;
+    while let Some(value) = elements.next() {
+      let value = value.map_err(|e| e.kind())?;
+      let kind = value.kind();
+      if element(DynValue { value: &mut Some(value), kind })?.is_break() {
+        break;
+      }
+    }
+    Ok(())
+  }
+  fn fields(
+    &mut self,
+    field: &mut dyn FnMut(DynField<'_>) -> Result<ControlFlow<()>, ErrorKind>,
+  ) -> Result<(), ErrorKind> {
+    let value = self.take().ok_or(ErrorKind::InternalError)?;
+    let mut fields = value.fields().map_err(|e| e.kind())?;
+    while let Some(next) = fields.next() {
+      let next = next.map_err(|e| e.kind())?;
+      if field(DynField(&mut Some(next)))?.is_break() {
+        break;
+      }
+    }
+    Ok(())
+  }
+}
+
+impl<'read, R: Read<'read>, S: Stack> ErasedField for Option<Field<'read, '_, R, S>> {
+  fn next_key_char(&mut self) -> Option<Result<char, ErrorKind>> {
+    let Some(field) = self.as_mut() else { return Some(Err(ErrorKind::InternalError)) };
+    match field.key() {
+      Ok(key) => key.next().map(|char| char.map_err(|e| e.kind())),
+      Err(e) => Some(Err(e.kind())),
+    }
+  }
+  fn key_matches(&mut self, expected: &str) -> Result<bool, ErrorKind> {
+    let field = self.as_mut().ok_or(ErrorKind::InternalError)?;
+    field.key_matches(expected).map_err(|e| e.kind())
+  }
+  fn value(&mut self, value: &mut dyn FnMut(DynValue<'_>)) -> Result<(), ErrorKind> {
+    let field = self.take().ok_or(ErrorKind::InternalError)?;
+    let field_value = field.value().map_err(|e| e.kind())?;
+    let kind = field_value.kind();
+    value(DynValue { value: &mut Some(field_value), kind });
+    Ok(())
+  }
+}
+
+/// A value, without its reader and stack within its type.
+///
+/// This offers the operations of `Value` (with errors represented by their `ErrorKind`), yet
+/// dispatches them dynamically. This allows values to be passed across crate boundaries, such as
+/// to plug-ins which consume JSON values without being generic over the host's reader and stack.
+/// As nothing may be allocated, the elements of arrays and the fields of objects are passed to
+/// callbacks, instead of being yielded by iterators. `DynValue`s are obtained via
+/// `Value::with_dyn`.
+pub struct DynValue<'value> {
+  value: &'value mut dyn ErasedValue,
+  kind: Type,
+}
+
+impl DynValue<'_> {
+  /// The type of the current item.
+  ///
+  /// This has the same caveats as `Value::kind`.
+  #[inline(always)]
+  pub fn kind(&self) -> Type {
+    self.kind
+  }
+
+  /// Get the current item as a number.
+  pub fn to_number(self) -> Result<Number, ErrorKind> {
+    self.value.to_number()
+  }
+
+  /// Get the current item as a `bool`.
+  pub fn to_bool(self) -> Result<bool, ErrorKind> {
+    self.value.to_bool()
+  }
+
+  /// Get the current item as `null`.
+  pub fn to_null(self) -> Result<(), ErrorKind> {
+    self.value.to_null()
+  }
+
+  /// Get the current item as a string.
+  ///
+  /// The characters of the string (as yielded by `Value::to_str`) are passed to `chars`, whose
+  /// result is returned. The iterator doesn't have to be exhausted.
+  pub fn to_str<T>(
+    self,
+    chars: impl FnOnce(&mut dyn Iterator<Item = Result<char, ErrorKind>>) -> T,
+  ) -> Result<T, ErrorKind> {
+    let mut chars = Some(chars);
+    let mut result = None;
+    self.value.to_str(&mut |iter| result = chars.take().map(|chars| chars(iter)))?;
+    result.ok_or(ErrorKind::InternalError)
+  }
+
+  /// Iterate over all items within this array.
+  ///
+  /// Each item is passed to `element`, in order. If `element` returns `ControlFlow::Break`, the
+  /// rest of the array is dropped, as when dropping an `ArrayIterator`. If `element` returns an
+  /// error, it's returned.
+  pub fn iterate(
+    self,
+    mut element: impl FnMut(DynValue<'_>) -> Result<ControlFlow<()>, ErrorKind>,
+  ) -> Result<(), ErrorKind> {
+    self.value.iterate(&mut element)
+  }
+
+  /// Iterate over all fields within this object.
+  ///
+  /// Each field is passed to `field`, in order. If `field` returns `ControlFlow::Break`, the rest
+  /// of the object is dropped, as when dropping a `FieldIterator`. If `field` returns an error,
+  /// it's returned.
+  pub fn fields(
+    self,
+    mut field: impl FnMut(DynField<'_>) -> Result<ControlFlow<()>, ErrorKind>,
+  ) -> Result<(), ErrorKind> {
+    self.value.fields(&mut field)
+  }
+}
+
+/// A field, without its reader and stack within its type.
+///
+/// This is the `Field` equivalent for `DynValue`.
+pub struct DynField<'field>(&'field mut dyn ErasedField);
+
+/// An iterator over the characters of a field's key, as yielded by `DynField::key`.
+pub struct DynKey<'field, 'key>(&'key mut DynField<'field>);
+
+impl Iterator for DynKey<'_, '_> {
+  type Item = Result<char, ErrorKind>;
+  #[inline(always)]
+  fn next(&mut self) -> Option<Self::Item> {
+    self.0.0.next_key_char()
+  }
+}
+
+impl<'field> DynField<'field> {
+  /// Access the iterator for the string used as the field's key.
+  ///
+  /// This has the same caveats as `Field::key`.
+  #[inline(always)]
+  pub fn key(&mut self) -> DynKey<'field, '_> {
+    DynKey(self)
+  }
+
+  /// Check if the field's key matches the expected key.
+  ///
+  /// This has the same caveats as `Field::key_matches`.
+  pub fn key_matches(&mut self, expected: &str) -> Result<bool, ErrorKind> {
+    self.0.key_matches(expected)
+  }
+
+  /// Access the field's value.
+  ///
+  /// The value is passed to `value`, whose result is returned.
+  pub fn value<T>(self, value: impl FnOnce(DynValue<'_>) -> T) -> Result<T, ErrorKind> {
+    let mut value = Some(value);
+    let mut result = None;
+    self.0.value(&mut |dyn_value| result = value.take().map(|value| value(dyn_value)))?;
+    result.ok_or(ErrorKind::InternalError)
+  }
+}
+
+impl<'read, R: Read<'read>, S: Stack> Value<'read, '_, R, S> {
+  /// Access this value as a `DynValue`.
+  ///
+  /// The `DynValue` is passed to `value`, whose result is returned. This allows passing the value
+  /// to code which isn't generic over the reader and stack.
+  pub fn with_dyn<T>(self, value: impl FnOnce(DynValue<'_>) -> T) -> T {
+    let kind = self.kind();
+    value(DynValue { value: &mut Some(self), kind })
+  }
+}
+
+#[test]
+fn dyn_value() {
+  // A plug-in, summing the numbers within a value, which isn't generic over the reader or stack
+  fn sum(value: DynValue<'_>, skip: &str) -> Result<i64, ErrorKind> {
+    let mut total = 0;
+    match value.kind() {
+      Type::Number => total += value.to_number()?.i64().ok_or(ErrorKind::TypeError)?,
+      Type::String => {
+        let len = value.to_str(|chars| chars.filter(Result::is_ok).count())?;
+        total += i64::try_from(len).unwrap();
+      }
+      Type::Array => value.iterate(|value| {
+        total += sum(value, skip)?;
+        Ok(ControlFlow::Continue(()))
+      })?,
+      Type::Object => value.fields(|mut field| {
+        if field.key_matches(skip)? {
+          return Ok(ControlFlow::Continue(()));
+        }
+        // Stop at a field whose key starts with `!`
+        if field.key().next().transpose()? == Some('!') {
+          return Ok(ControlFlow::Break(()));
+        }
+        total += field.value(|value| sum(value, skip))??;
+        Ok(ControlFlow::Continue(()))
+      })?,
+      Type::Bool => total += i64::from(value.to_bool()?),
+      Type::Null => value.to_null()?,
+    }
+    Ok(total)
+  }
+
+  let json = r#"{"a": [1, 2, {"b": 3, "c": [4]}], "d": "str", "e": [true, null], "!f": 5, "g": 6}"#;
+  let run = |json: &str, skip: &str| {
+    let mut deserializer = Deserializer::<_, ConstStack<8>>::new(json.as_bytes()).unwrap();
+    let mut fields = deserializer.value().unwrap().fields().unwrap();
+    let mut field = fields.next().unwrap().unwrap();
+    assert!(field.key_matches("doc").unwrap());
+    let total = field.value().unwrap().with_dyn(|value| sum(value, skip));
+    // The deserializer remains usable afterwards, having advanced past the rest of the value
+    let mut field = fields.next().unwrap().unwrap();
+    assert!(field.key_matches("after").unwrap());
+    assert_eq!(field.value().unwrap().to_number().unwrap().i64(), Some(7));
+    total
+  };
+  let json = format!(r#"{{"doc": {json}, "after": 7}}"#);
+  assert_eq!(run(&json, ""), Ok(1 + 2 + 3 + 4 + 3 + 1));
+  assert_eq!(run(&json, "a"), Ok(3 + 1));
+  assert_eq!(run(&json.replace("\"str\"", "false"), "b"), Ok(1 + 2 + 4 + 1));
+  // Errors from the plug-in are returned
+  assert_eq!(run(r#"{"doc": [1.5], "after": 7}"#, ""), Err(ErrorKind::TypeError));
+
+  let mut deserializer = Deserializer::<_, ConstStack<8>>::new(&b"[1, [2, ]]"[..]).unwrap();
+  let total = deserializer.value().unwrap().with_dyn(|value| sum(value, ""));
+  assert!(matches!(total, Err(ErrorKind::SyntaxError(_))));
+}
@@ -0,0 +1,339 @@
+use core::{marker::PhantomData, fmt};
+
+use miniz_oxide::inflate::{
+  TINFLStatus,
+  core::{
+    DecompressorOxide, TINFL_LZ_DICT_SIZE, decompress,
+    inflate_flags::{TINFL_FLAG_HAS_MORE_INPUT, TINFL_FLAG_PARSE_ZLIB_HEADER},
+  },
+};
+
+use crate::Read;
+
+/// The capacity of the buffer for compressed bytes, used for readers which don't lend their bytes.
+const INPUT_CAPACITY: usize = 64;
+
+/// The format of compressed data.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CompressionFormat {
+  /// A raw DEFLATE stream, as specified by
+  /// [RFC 1951](https://datatracker.ietf.org/doc/html/rfc1951).
+  Deflate,
+  /// A zlib stream, as specified by [RFC 1950](https://datatracker.ietf.org/doc/html/rfc1950).
+  Zlib,
+  /// A gzip member, as specified by [RFC 1952](https://datatracker.ietf.org/doc/html/rfc1952).
+  Gzip,
+}
+
+/// The size of the window of decompressed bytes an `InflateRead` requires.
+pub const INFLATE_WINDOW: usize = TINFL_LZ_DICT_SIZE;
+
+/// An error when reading from an `InflateRead`.
+#[derive(Clone, Copy, Debug)]
+pub enum InflateError<E: Copy + fmt::Debug> {
+  /// An error from the underlying reader.
+  ReadError(E),
+  /// The compressed data was invalid.
+  InvalidCompression,
+  /// The compressed data ended, yet more bytes were requested.
+  Ended,
+}
+
+/// A reader which decompresses data from an underlying reader.
+///
+/// The bytes are decompressed on the fly without allocating. This requires the 32 KiB window
+/// DEFLATE requires, which is provided by the caller (allowing it to be placed wherever is
+/// appropriate), and the decompressor's state (approximately 11 KiB). As the deserializer doesn't
+/// read past the end of a structure, the trailer of a gzip member (its checksum) is not verified.
+pub struct InflateRead<'read, 'window, R: Read<'read>> {
+  reader: R,
+  format: CompressionFormat,
+  /// If the header of the gzip member has yet to be read.
+  header: bool,
+  decompressor: DecompressorOxide,
+  /// The bytes lent by the reader, if they're the current input.
+  lent: &'read [u8],
+  /// The buffer for the input of readers which don't lend their bytes.
+  input: [u8; INPUT_CAPACITY],
+  input_position: usize,
+  input_len: usize,
+  /// The window of decompressed bytes.
+  window: &'window mut [u8; INFLATE_WINDOW],
+  /// The position of the next decompressed byte to read within the window.
+  position: usize,
+  /// The position after the last decompressed byte within the window.
+  end: usize,
+  /// If the decompressor needs more compressed bytes to make progress.
+  needs_input: bool,
+  /// If the compressed data has ended.
+  ended: bool,
+  _read: PhantomData<&'read ()>,
+}
+
+impl<'read, R: Read<'read> + fmt::Debug> fmt::Debug for InflateRead<'read, '_, R> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("InflateRead")
+      .field("reader", &self.reader)
+      .field("format", &self.format)
+      .field("ended", &self.ended)
+      .finish_non_exhaustive()
+  }
+}
+
+impl<'read, 'window, R: Read<'read>> InflateRead<'read, 'window, R> {
+  /// Create a new reader, decompressing data of the specified format.
+  ///
+  /// The window's existing contents are irrelevant and will be overwritten.
+  pub fn new(
+    reader: R,
+    format: CompressionFormat,
+    window: &'window mut [u8; INFLATE_WINDOW],
+  ) -> Self {
+    InflateRead {
+      reader,
+      format,
+      header: format == CompressionFormat::Gzip,
+      decompressor: DecompressorOxide::new(),
+      lent: &[],
+      input: [0; INPUT_CAPACITY],
+      input_position: 0,
+      input_len: 0,
+      window,
+      position: 0,
+      end: 0,
+      needs_input: true,
+      ended: false,
+      _read: PhantomData,
+    }
+  }
+
+  /// Consume this, returning the underlying reader.
+  ///
+  /// Compressed bytes read from the underlying reader which have yet to be decompressed are lost.
+  pub fn into_inner(self) -> R {
+    self.reader
+  }
+
+  /// The compressed bytes read which have yet to be decompressed.
+  #[inline(always)]
+  fn input(&self) -> &[u8] {
+    if self.lent.is_empty() {
+      &self.input[self.input_position .. self.input_len]
+    } else {
+      &self.lent[self.input_position ..]
+    }
+  }
+
+  /// Read more compressed bytes from the underlying reader.
+  fn refill(&mut self) -> Result<(), InflateError<R::Error>> {
+    self.input_position = 0;
+    match self.reader.lend() {
+      Some(lent) if !lent.is_empty() => self.lent = lent,
+      _ => {
+        self.lent = &[];
+        self.input_len = self
+          .reader
+          .read_into_buffer(&mut self.input)
+          .map_err(InflateError::ReadError)?
+          .clamp(1, INPUT_CAPACITY);
+      }
+    }
+    Ok(())
+  }
+
+  /// Read a single compressed byte.
+  fn input_byte(&mut self) -> Result<u8, InflateError<R::Error>> {
+    if self.input().is_empty() {
+      self.refill()?;
+    }
+    let res = self.input()[0];
+    self.input_position += 1;
+    Ok(res)
+  }
+
+  /// Read past the header of a gzip member.
+  fn read_gzip_header(&mut self) -> Result<(), InflateError<R::Error>> {
+    const FHCRC: u8 = 1 << 1;
+    const FEXTRA: u8 = 1 << 2;
+    const FNAME: u8 = 1 << 3;
+    const FCOMMENT: u8 = 1 << 4;
+    const RESERVED: u8 = 0b1110_0000;
+
+    let mut header = [0; 10];
+    for byte in &mut header {
+      *byte = self.input_byte()?;
+    }
+    let [id1, id2, compression_method, flags, ..] = header;
+    if (id1 != 0x1f) || (id2 != 0x8b) || (compression_method != 8) || ((flags & RESERVED) != 0) {
+      Err(InflateError::InvalidCompression)?;
+    }
+
+    if (flags & FEXTRA) != 0 {
+      let len = u16::from_le_bytes([self.input_byte()?, self.input_byte()?]);
+      for _ in 0 .. len {
+        self.input_byte()?;
+      }
+    }
+    // The name and the comment are each terminated by a zero byte
+    for flag in [FNAME, FCOMMENT] {
+      if (flags & flag) != 0 {
+        while self.input_byte()? != 0 {}
+      }
+    }
+    if (flags & FHCRC) != 0 {
+      self.input_byte()?;
+      self.input_byte()?;
+    }
+    Ok(())
+  }
+
+  /// Decompress more bytes into the window.
+  fn decompress(&mut self) -> Result<(), InflateError<R::Error>> {
+    if self.header {
+      self.read_gzip_header()?;
+      self.header = false;
+    }
+
+    let flags = TINFL_FLAG_HAS_MORE_INPUT |
+      if self.format == CompressionFormat::Zlib { TINFL_FLAG_PARSE_ZLIB_HEADER } else { 0 };
+    // The window wraps around, with `decompress` writing from `end` until the end of the window
+    let out_pos = self.end % INFLATE_WINDOW;
+    loop {
+      if self.ended {
+        Err(InflateError::Ended)?;
+      }
+      // Only read more compressed bytes if the decompressor can not yield more output without them
+      if self.needs_input && self.input().is_empty() {
+        self.refill()?;
+      }
+
+      let input = if self.lent.is_empty() {
+        &self.input[self.input_position .. self.input_len]
+      } else {
+        &self.lent[self.input_position ..]
+      };
+      let (status, consumed, written) =
+        decompress(&mut self.decompressor, input, self.window, out_pos, flags);
+      self.input_position += consumed;
+      match status {
+        TINFLStatus::Done => self.ended = true,
+        TINFLStatus::NeedsMoreInput | TINFLStatus::HasMoreOutput => {}
+        _ => Err(InflateError::InvalidCompression)?,
+      }
+      self.needs_input = status == TINFLStatus::NeedsMoreInput;
+      if written != 0 {
+        self.position = out_pos;
+        self.end = out_pos + written;
+        return Ok(());
+      }
+    }
+  }
+}
+
+impl<'read, R: Read<'read>> Read<'read> for InflateRead<'read, '_, R> {
+  type Error = InflateError<R::Error>;
+
+  #[inline(always)]
+  fn read_byte(&mut self) -> Result<u8, Self::Error> {
+    if self.position == self.end {
+      self.decompress()?;
+    }
+    let res = self.window[self.position];
+    self.position += 1;
+    Ok(res)
+  }
+
+  #[inline(always)]
+  fn read_exact(&mut self, slice: &mut [u8]) -> Result<(), Self::Error> {
+    for byte in slice {
+      *byte = self.read_byte()?;
+    }
+    Ok(())
+  }
+
+  #[inline(always)]
+  fn read_into_buffer(&mut self, buffer: &mut [u8]) -> Result<usize, Self::Error> {
+    if self.position == self.end {
+      self.decompress()?;
+    }
+    let decompressed = &self.window[self.position .. self.end];
+    let len = decompressed.len().min(buffer.len());
+    buffer[.. len].copy_from_slice(&decompressed[.. len]);
+    self.position += len;
+    Ok(len)
+  }
+}
+
+#[test]
+fn inflate() {
+  use crate::{ConstStack, Deserializer};
+
+  const DEFLATE: &[u8] = &[
+    0xab, 0x56, 0xca, 0x4e, 0xad, 0x54, 0xb2, 0x52, 0x88, 0x56, 0x2a, 0x4a, 0x2d, 0x48, 0x4d, 0x2c,
+    0x49, 0x4d, 0x51, 0x18, 0x42, 0x0c, 0x25, 0x1d, 0x85, 0x92, 0xa2, 0xd2, 0x54, 0x1d, 0x85, 0xbc,
+    0xd2, 0x9c, 0x9c, 0x58, 0x1d, 0x05, 0xa5, 0x3c, 0xa0, 0x57, 0x0c, 0x6b, 0x01,
+  ];
+
+  fn check<'read, R: Read<'read> + fmt::Debug>(reader: R, format: CompressionFormat) {
+    let mut window = vec![0; INFLATE_WINDOW];
+    let window = <&mut [u8; INFLATE_WINDOW]>::try_from(window.as_mut_slice()).unwrap();
+    let mut deserializer =
+      Deserializer::<_, ConstStack<32>>::new(InflateRead::new(reader, format, window)).unwrap();
+    let mut fields = deserializer.value().unwrap().fields().unwrap();
+    let mut field = fields.next().unwrap().unwrap();
+    assert_eq!(field.key().unwrap().collect::<Result<String, _>>().unwrap(), "key");
+    let mut values = field.value().unwrap().iterate().unwrap();
+    assert_eq!(
+      values.next().unwrap().unwrap().to_str().unwrap().collect::<Result<String, _>>().unwrap(),
+      "repeated ".repeat(20)
+    );
+    assert!(values.next().unwrap().unwrap().to_bool().unwrap());
+    values.next().unwrap().unwrap().to_null().unwrap();
+    assert!(values.next().is_none());
+    drop(values);
+    let mut field = fields.next().unwrap().unwrap();
+    assert_eq!(field.key().unwrap().collect::<Result<String, _>>().unwrap(), "n");
+    assert_eq!(field.value().unwrap().to_number().unwrap().i64(), Some(1));
+    assert!(fields.next().is_none());
+  }
+
+  // A reader which doesn't lend its bytes
+  #[derive(Debug)]
+  struct Bytes<'read>(&'read [u8]);
+  impl<'read> Read<'read> for Bytes<'read> {
+    type Error = crate::io::SliceError;
+    fn read_exact(&mut self, slice: &mut [u8]) -> Result<(), Self::Error> {
+      self.0.read_exact(slice)
+    }
+  }
+
+  let zlib = [&[0x78, 0xda], DEFLATE, &[0x0c, 0x5d, 0x4d, 0xc0]].concat();
+  // A gzip member with a name
+  let gzip = [
+    &[0x1f, 0x8b, 0x08, 0x08, 0x00, 0x00, 0x00, 0x00, 0x02, 0xff],
+    b"a.json\0".as_slice(),
+    DEFLATE,
+    &[0x27, 0x95, 0x65, 0x18, 0xd5, 0x00, 0x00, 0x00],
+  ]
+  .concat();
+  for (compressed, format) in [
+    (DEFLATE, CompressionFormat::Deflate),
+    (&zlib, CompressionFormat::Zlib),
+    (&gzip, CompressionFormat::Gzip),
+  ] {
+    check(compressed, format);
+    check(Bytes(compressed), format);
+  }
+
+  let mut window = vec![0; INFLATE_WINDOW];
+  let window = <&mut [u8; INFLATE_WINDOW]>::try_from(window.as_mut_slice()).unwrap();
+  let mut reader = InflateRead::new(DEFLATE, CompressionFormat::Deflate, window);
+  let mut decompressed = [0; 213];
+  reader.read_exact(&mut decompressed).unwrap();
+  assert!(matches!(reader.read_byte(), Err(InflateError::Ended)));
+
+  assert!(matches!(
+    InflateRead::new(&DEFLATE[1 ..], CompressionFormat::Zlib, reader.window).read_byte(),
+    Err(InflateError::InvalidCompression)
+  ));
+}
@@ -0,0 +1,172 @@
+/// Counters on the work performed by a deserializer, as retrieved by `Deserializer::counters`.
+///
+/// These allow diagnosing performance quantitatively, such as determining if a deserialization is
+/// bound by the reader, unescaping strings, or skipping unread values.
+#[cfg(feature = "instrumentation")]
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct Counters {
+  /// The amount of bytes read from the reader.
+  ///
+  /// This includes bytes read into the lookahead buffer which have yet to be consumed.
+  pub bytes_read: usize,
+  /// The amount of windows read from the reader, each either lent by the reader or read into the
+  /// lookahead buffer.
+  pub windows: u64,
+  /// The amount of bytes advanced to within the current window, without reading a new window
+  /// from the reader.
+  pub peek_hits: usize,
+  /// The amount of escapes processed within strings (including keys and skipped strings).
+  pub escapes: u64,
+  /// The amount of numbers parsed.
+  pub numbers: u64,
+  /// The amount of objects and arrays advanced past without being entirely read, including those
+  /// nested within them.
+  pub structures_skipped: u64,
+  /// The maximum depth of nested structures reached, where a structure containing solely scalars
+  /// has depth `1`.
+  ///
+  /// A stack permitting a depth of `max_depth + 1` suffices to deserialize the document (as each
+  /// value within a structure occupies an additional entry), allowing stacks to be sized
+  /// empirically. Structures skipped under `ValidationProfile::Trusting` are scanned without
+  /// being opened, and aren't included within this, `objects`, or `arrays`.
+  pub max_depth: usize,
+  /// The amount of objects opened.
+  pub objects: u64,
+  /// The amount of arrays opened.
+  pub arrays: u64,
+}
+
+#[cfg(feature = "instrumentation")]
+impl Counters {
+  #[inline(always)]
+  pub(crate) fn new() -> Self {
+    Self::default()
+  }
+  #[inline(always)]
+  pub(crate) fn window(&mut self) {
+    self.windows += 1;
+  }
+  #[inline(always)]
+  pub(crate) fn peek_hits(&mut self, amount: usize) {
+    self.peek_hits += amount;
+  }
+  #[inline(always)]
+  pub(crate) fn escape(&mut self) {
+    self.escapes += 1;
+  }
+  #[inline(always)]
+  pub(crate) fn number(&mut self) {
+    self.numbers += 1;
+  }
+  #[inline(always)]
+  pub(crate) fn structure_skipped(&mut self) {
+    self.structures_skipped += 1;
+  }
+  #[inline(always)]
+  pub(crate) fn object_opened(&mut self, depth: usize) {
+    self.objects += 1;
+    self.max_depth = self.max_depth.max(depth);
+  }
+  #[inline(always)]
+  pub(crate) fn array_opened(&mut self, depth: usize) {
+    self.arrays += 1;
+    self.max_depth = self.max_depth.max(depth);
+  }
+}
+
+/// No-op counters, used when the `instrumentation` feature is disabled.
+#[cfg(not(feature = "instrumentation"))]
+pub(crate) struct Counters;
+
+#[cfg(not(feature = "instrumentation"))]
+#[allow(clippy::unused_self)]
+impl Counters {
+  #[inline(always)]
+  pub(crate) fn new() -> Self {
+    Counters
+  }
+  #[inline(always)]
+  pub(crate) fn window(&mut self) {}
+  #[inline(always)]
+  pub(crate) fn peek_hits(&mut self, _amount: usize) {}
+  #[inline(always)]
+  pub(crate) fn escape(&mut self) {}
+  #[inline(always)]
+  pub(crate) fn number(&mut self) {}
+  #[inline(always)]
+  pub(crate) fn structure_skipped(&mut self) {}
+  #[inline(always)]
+  pub(crate) fn object_opened(&mut self, _depth: usize) {}
+  #[inline(always)]
+  pub(crate) fn array_opened(&mut self, _depth: usize) {}
+}
+
+#[cfg(feature = "instrumentation")]
+#[test]
+fn counters() {
+  use crate::{ConstStack, Deserializer};
+
+  let serialization = br#"{ "a\n": [1, 2.5, {"b": [[]]}], "c": "d\"", "e": 3 }"#;
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+  let mut fields = deserializer.value().unwrap().fields().unwrap();
+  while let Some(field) = fields.next() {
+    let mut field = field.unwrap();
+    let key = field.key().unwrap().collect::<Result<std::string::String, _>>().unwrap();
+    match key.as_str() {
+      // Only partially read the array, skipping the rest of it
+      "a\n" => {
+        let mut elements = field.value().unwrap().iterate().unwrap();
+        elements.next().unwrap().unwrap().to_number().unwrap();
+      }
+      "e" => {
+        field.value().unwrap().to_number().unwrap();
+      }
+      // Skip the string
+      _ => {}
+    }
+  }
+  drop(fields);
+  assert!(deserializer.buffered().unwrap().is_empty());
+
+  let counters = deserializer.counters();
+  assert_eq!(counters.bytes_read, serialization.len());
+  // A slice is lent as a single window
+  assert_eq!(counters.windows, 1);
+  assert_eq!(counters.peek_hits, serialization.len() - 1);
+  assert_eq!(counters.escapes, 2);
+  assert_eq!(counters.numbers, 3);
+  // The array, the object within it, and the arrays within the object
+  assert_eq!(counters.structures_skipped, 4);
+  // The skipped structures are still counted as they're advanced past
+  assert_eq!((counters.objects, counters.arrays), (2, 3));
+  assert_eq!(counters.max_depth, 5);
+  assert_eq!(deserializer.max_depth_reached(), 5);
+
+  // Readers which don't lend their bytes are read in windows of up to `LOOKAHEAD_CAPACITY` bytes
+  #[derive(Debug)]
+  struct Unlent<'a>(&'a [u8]);
+  impl<'read> crate::Read<'read> for Unlent<'_> {
+    type Error = ();
+    fn read_exact(&mut self, slice: &mut [u8]) -> Result<(), ()> {
+      self.0.read_exact(slice).map_err(|_| ())
+    }
+    fn read_into_buffer(&mut self, buffer: &mut [u8]) -> Result<usize, ()> {
+      let len = buffer.len().min(self.0.len());
+      if len == 0 {
+        Err(())?;
+      }
+      self.read_exact(&mut buffer[.. len])?;
+      Ok(len)
+    }
+  }
+  let serialization = [b"[".as_slice(), &[b' '; 2 * crate::LOOKAHEAD_CAPACITY], b"1]"].concat();
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(Unlent(&serialization)).unwrap();
+  let mut elements = deserializer.value().unwrap().iterate().unwrap();
+  elements.next().unwrap().unwrap().to_number().unwrap();
+  assert!(elements.next().is_none());
+  drop(elements);
+  let counters = deserializer.counters();
+  assert_eq!(counters.bytes_read, serialization.len());
+  assert_eq!(counters.windows, 3);
+  assert_eq!(counters.peek_hits, serialization.len() - 3);
+}
@@ -1,4 +1,6 @@
-use core::{marker::PhantomData, fmt::Debug};
+use core::fmt::Debug;
+
+use crate::instrumentation::Counters;
 
 /// A no-`std` `io::Read` alternative.
 ///
@@ -19,34 +21,214 @@ pub trait Read<'read>: Sized {
 
   /// Read into a slice from the reader.
   fn read_exact(&mut self, slice: &mut [u8]) -> Result<(), Self::Error>;
+
+  /// Read into a buffer from the reader, returning the amount of bytes read.
+  ///
+  /// This is used to refill the deserializer's lookahead buffer. This MUST read at least one byte
+  /// into the (non-empty) buffer or return an error. This MAY read less bytes than the length of
+  /// the buffer, and SHOULD NOT wait for more bytes to become available once at least one byte has
+  /// been read. By default, this reads a single byte.
+  #[inline(always)]
+  fn read_into_buffer(&mut self, buffer: &mut [u8]) -> Result<usize, Self::Error> {
+    buffer[0] = self.read_byte()?;
+    Ok(1)
+  }
+
+  /// Lend the remaining bytes from the reader, if possible.
+  ///
+  /// If bytes are returned, they're considered consumed from the reader. This is used by readers
+  /// which already hold the serialization in memory, allowing the deserializer to read directly
+  /// from the reader's bytes instead of copying them into its own lookahead buffer. This MUST
+  /// return `None` if no bytes remain. By default, this returns `None`.
+  #[inline(always)]
+  fn lend(&mut self) -> Option<&'read [u8]> {
+    None
+  }
+}
+
+/// A reader which may be rewound to a position previously marked.
+///
+/// Some formats require trying one interpretation of a value and, if it doesn't apply,
+/// backtracking to try another (such as untagged unions). As the deserializer may read ahead of
+/// the value it yields, backtracking is performed by marking the reader prior to creating a
+/// deserializer, and rewinding the reader to the mark once the deserializer is dropped.
+///
+/// Implementors may solely support rewinding within a bounded window of bytes, erroring if more
+/// bytes than they retain were read since the mark. This is trivially implemented for `&[u8]`,
+/// which supports rewinding to any mark.
+pub trait MarkableRead<'read>: Read<'read> {
+  /// A marked position within the reader.
+  type Mark: Copy;
+
+  /// Mark the current position within the reader.
+  fn mark(&self) -> Self::Mark;
+
+  /// Rewind the reader to a marked position.
+  ///
+  /// The mark MUST have been returned by `MarkableRead::mark` for this reader. Marks from other
+  /// readers, or for positions the reader is no longer able to rewind to, MAY error.
+  fn rewind(&mut self, mark: Self::Mark) -> Result<(), Self::Error>;
 }
 
-/// A wrapper for an `impl Read` with a one-byte buffer, enabling peeking.
+/// The capacity of the lookahead buffer used for readers which don't lend their bytes.
+///
+/// A deserializer will hold at most this many bytes read from such a reader which have yet to be
+/// consumed. These bytes may be reclaimed with `Deserializer::buffered`.
+pub const LOOKAHEAD_CAPACITY: usize = 32;
+
+/// A wrapper for an `impl Read` with a lookahead buffer, enabling peeking.
 ///
-/// This will always read at least one byte from the underlying reader.
+/// This will always have at least one byte read from the underlying reader, which has yet to be
+/// consumed. When the reader lends its bytes, they're read directly. Otherwise, the reader's bytes
+/// are read into a fixed-size buffer of `LOOKAHEAD_CAPACITY` bytes.
 pub(crate) struct PeekableRead<'read, R: Read<'read>> {
-  buffer: u8,
+  /// The next byte, which has yet to be consumed.
+  peeked: u8,
+  /// The position of `peeked` within the current window.
+  position: usize,
+  /// The bytes lent by the reader, if they're the current window.
+  lent: &'read [u8],
+  /// The buffer for readers which don't lend their bytes.
+  buffer: [u8; LOOKAHEAD_CAPACITY],
+  /// The length of the buffer's contents.
+  buffered: usize,
+  /// The amount of bytes within the prior windows.
+  prior_windows: usize,
+  /// If the end of the reader is permitted.
+  end_permitted: bool,
+  /// If the end of the reader was reached, with a `NUL` byte in its place.
+  ended: bool,
+  pub(crate) counters: Counters,
   reader: R,
-  _read: PhantomData<&'read ()>,
 }
 
 impl<'read, R: Read<'read>> PeekableRead<'read, R> {
-  pub(crate) fn try_from(mut reader: R) -> Result<Self, R::Error> {
-    Ok(Self { buffer: reader.read_byte()?, reader, _read: PhantomData })
+  pub(crate) fn try_from(reader: R) -> Result<Self, R::Error> {
+    let mut res = Self {
+      peeked: 0,
+      position: 0,
+      lent: &[],
+      buffer: [0; LOOKAHEAD_CAPACITY],
+      buffered: 0,
+      prior_windows: 0,
+      end_permitted: false,
+      ended: false,
+      counters: Counters::new(),
+      reader,
+    };
+    res.refill()?;
+    Ok(res)
   }
 }
 
 impl<'read, R: Read<'read>> PeekableRead<'read, R> {
+  /// The current window of bytes read from the underlying reader.
+  ///
+  /// This will be non-empty and `position` will always be within bounds.
+  #[inline(always)]
+  fn window(&self) -> &[u8] {
+    if self.lent.is_empty() { &self.buffer[.. self.buffered] } else { self.lent }
+  }
+
+  /// Refill the current window with a new, non-empty window.
+  #[inline(never)]
+  fn refill(&mut self) -> Result<(), R::Error> {
+    self.prior_windows += self.window().len();
+    self.position = 0;
+    match self.reader.lend() {
+      Some(lent) if !lent.is_empty() => {
+        self.lent = lent;
+        self.counters.window();
+      }
+      _ => {
+        self.lent = &[];
+        // `read_into_buffer` is required to read at least one byte, and may not read more bytes
+        // than fit, yet we don't trust arbitrary implementations to uphold this
+        match self.reader.read_into_buffer(&mut self.buffer) {
+          Ok(read) => {
+            self.buffered = read.clamp(1, LOOKAHEAD_CAPACITY);
+            self.counters.window();
+          }
+          /*
+            As `Read` has no notion of the end of the underlying reader, the first error is
+            considered the end when permitted. This is represented by a `NUL` byte, which is never
+            valid outside of a string (and never valid unescaped within a string).
+          */
+          Err(_) if self.end_permitted && (!self.ended) => {
+            self.ended = true;
+            self.buffer[0] = 0;
+            self.buffered = 1;
+          }
+          Err(e) => Err(e)?,
+        }
+      }
+    }
+    self.peeked = self.window()[0];
+    Ok(())
+  }
+
+  /// Advance to the next byte, refilling the window if necessary.
+  #[inline(always)]
+  fn advance(&mut self) -> Result<(), R::Error> {
+    self.position += 1;
+    match self.window().get(self.position) {
+      Some(next) => {
+        self.peeked = *next;
+        self.counters.peek_hits(1);
+      }
+      None => self.refill()?,
+    }
+    Ok(())
+  }
+
+  /// The bytes read from the underlying reader which have yet to be consumed.
+  #[inline(always)]
+  pub(crate) fn buffered(&self) -> &[u8] {
+    &self.window()[self.position ..]
+  }
+
+  /// The amount of bytes consumed from the underlying reader.
+  #[inline(always)]
+  pub(crate) fn consumed(&self) -> usize {
+    self.prior_windows + self.position
+  }
+
+  /// The amount of bytes read from the underlying reader.
+  #[cfg(feature = "instrumentation")]
+  #[inline(always)]
+  pub(crate) fn read(&self) -> usize {
+    // The `NUL` byte substituted for the end of the reader wasn't read from it
+    self.prior_windows + self.window().len() - usize::from(self.ended)
+  }
+
+  /// Consume this, returning the underlying reader.
+  #[inline(always)]
+  pub(crate) fn into_reader(self) -> R {
+    self.reader
+  }
+
+  /// Permit the end of the reader, as for a scalar which is the root value.
+  #[inline(always)]
+  pub(crate) fn permit_end(&mut self) {
+    self.end_permitted = true;
+  }
+
+  /// If the end of the reader was reached, with a `NUL` byte in its place.
+  #[inline(always)]
+  pub(crate) fn ended(&self) -> bool {
+    self.ended
+  }
+
   #[must_use]
   #[inline(always)]
   pub(crate) fn peek(&self) -> u8 {
-    self.buffer
+    self.peeked
   }
 
   #[inline(always)]
   pub(crate) fn read_byte(&mut self) -> Result<u8, R::Error> {
-    let res = self.buffer;
-    self.buffer = self.reader.read_byte()?;
+    let res = self.peeked;
+    self.advance()?;
     Ok(res)
   }
 
@@ -59,11 +241,20 @@ impl<'read, R: Read<'read>> PeekableRead<'read, R> {
     &mut self,
     slice: &mut [u8],
   ) -> Result<(), R::Error> {
-    slice[0] = self.buffer;
-    self.reader.read_exact(&mut slice[1 ..])?;
+    slice[0] = self.peeked;
+    let mut slice = &mut slice[1 ..];
+    while !slice.is_empty() {
+      self.advance()?;
+      let available = &self.window()[self.position ..];
+      let len = available.len().min(slice.len());
+      slice[.. len].copy_from_slice(&available[.. len]);
+      slice = &mut slice[len ..];
+      // Position ourselves on the last byte copied, as `advance` will move past it
+      self.position += len - 1;
+      self.counters.peek_hits(len - 1);
+    }
     // Since we've consumed the buffer, update it with the byte after the read slice
-    self.buffer = self.reader.read_byte()?;
-    Ok(())
+    self.advance()
   }
 }
 
@@ -94,6 +285,26 @@ impl<'read> Read<'read> for &'read [u8] {
     *self = &self[slice.len() ..];
     Ok(())
   }
+
+  #[inline(always)]
+  fn lend(&mut self) -> Option<&'read [u8]> {
+    (!self.is_empty()).then(|| core::mem::take(self))
+  }
+}
+
+impl<'read> MarkableRead<'read> for &'read [u8] {
+  type Mark = &'read [u8];
+
+  #[inline(always)]
+  fn mark(&self) -> Self::Mark {
+    self
+  }
+
+  #[inline(always)]
+  fn rewind(&mut self, mark: Self::Mark) -> Result<(), Self::Error> {
+    *self = mark;
+    Ok(())
+  }
 }
 
 impl<'read, R: Read<'read>> Read<'read> for &mut R {
@@ -108,6 +319,30 @@ impl<'read, R: Read<'read>> Read<'read> for &mut R {
   fn read_exact(&mut self, slice: &mut [u8]) -> Result<(), Self::Error> {
     R::read_exact(self, slice)
   }
+
+  #[inline(always)]
+  fn read_into_buffer(&mut self, buffer: &mut [u8]) -> Result<usize, Self::Error> {
+    R::read_into_buffer(self, buffer)
+  }
+
+  #[inline(always)]
+  fn lend(&mut self) -> Option<&'read [u8]> {
+    R::lend(self)
+  }
+}
+
+impl<'read, R: MarkableRead<'read>> MarkableRead<'read> for &mut R {
+  type Mark = R::Mark;
+
+  #[inline(always)]
+  fn mark(&self) -> Self::Mark {
+    R::mark(self)
+  }
+
+  #[inline(always)]
+  fn rewind(&mut self, mark: Self::Mark) -> Result<(), Self::Error> {
+    R::rewind(self, mark)
+  }
 }
 
 /// An opaque error from a wrapped [`std::io::Read`] implementor.
@@ -118,6 +353,13 @@ pub struct ReadError;
 #[cfg(feature = "std")]
 pub struct ReadAdapter<R: std::io::Read>(R);
 #[cfg(feature = "std")]
+impl<R: std::io::Read> From<R> for ReadAdapter<R> {
+  #[inline(always)]
+  fn from(reader: R) -> Self {
+    Self(reader)
+  }
+}
+#[cfg(feature = "std")]
 impl<R: std::io::Read> Read<'_> for ReadAdapter<R> {
   type Error = ReadError;
 
@@ -125,4 +367,240 @@ impl<R: std::io::Read> Read<'_> for ReadAdapter<R> {
   fn read_exact(&mut self, slice: &mut [u8]) -> Result<(), Self::Error> {
     R::read_exact(&mut self.0, slice).map_err(|_| ReadError)
   }
+
+  #[inline(always)]
+  fn read_into_buffer(&mut self, buffer: &mut [u8]) -> Result<usize, Self::Error> {
+    loop {
+      match R::read(&mut self.0, buffer) {
+        Ok(0) => Err(ReadError)?,
+        Ok(len) => return Ok(len),
+        Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+        Err(_) => Err(ReadError)?,
+      }
+    }
+  }
+}
+
+/// An opaque error from a type-erased reader.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ErasedError;
+
+/// A dyn-compatible view of a `Read` implementor, with its error made opaque.
+trait Erase<'read> {
+  fn erased_read_byte(&mut self) -> Result<u8, ErasedError>;
+  fn erased_read_exact(&mut self, slice: &mut [u8]) -> Result<(), ErasedError>;
+  fn erased_read_into_buffer(&mut self, buffer: &mut [u8]) -> Result<usize, ErasedError>;
+  fn erased_lend(&mut self) -> Option<&'read [u8]>;
+}
+impl<'read, R: Read<'read>> Erase<'read> for R {
+  #[inline(always)]
+  fn erased_read_byte(&mut self) -> Result<u8, ErasedError> {
+    R::read_byte(self).map_err(|_| ErasedError)
+  }
+  #[inline(always)]
+  fn erased_read_exact(&mut self, slice: &mut [u8]) -> Result<(), ErasedError> {
+    R::read_exact(self, slice).map_err(|_| ErasedError)
+  }
+  #[inline(always)]
+  fn erased_read_into_buffer(&mut self, buffer: &mut [u8]) -> Result<usize, ErasedError> {
+    R::read_into_buffer(self, buffer).map_err(|_| ErasedError)
+  }
+  #[inline(always)]
+  fn erased_lend(&mut self) -> Option<&'read [u8]> {
+    R::lend(self)
+  }
+}
+
+/// A type-erased reader.
+///
+/// The deserializer is monomorphized for every combination of reader and stack it's used with.
+/// Projects which deserialize from several types of readers may wrap each with this (and use a
+/// single type of stack, such as `DefaultStack`), so the deserializer is solely instantiated once,
+/// reducing the size of the binary. This is at the cost of dynamically dispatching each read from
+/// the underlying reader and of the underlying reader's errors, which are made opaque.
+///
+/// Readers which lend their bytes continue to do so, making this solely a single dynamic call for
+/// readers of slices.
+pub struct ErasedRead<'read, 'erased>(&'erased mut (dyn 'erased + Erase<'read>));
+
+impl<'read, 'erased> ErasedRead<'read, 'erased> {
+  /// Erase the type of a reader.
+  #[inline(always)]
+  pub fn new<R: Read<'read>>(reader: &'erased mut R) -> Self {
+    ErasedRead(reader)
+  }
+}
+
+impl Debug for ErasedRead<'_, '_> {
+  fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fmt.debug_struct("ErasedRead").finish_non_exhaustive()
+  }
+}
+
+impl<'read> Read<'read> for ErasedRead<'read, '_> {
+  type Error = ErasedError;
+
+  #[inline(always)]
+  fn read_byte(&mut self) -> Result<u8, Self::Error> {
+    self.0.erased_read_byte()
+  }
+
+  #[inline(always)]
+  fn read_exact(&mut self, slice: &mut [u8]) -> Result<(), Self::Error> {
+    self.0.erased_read_exact(slice)
+  }
+
+  #[inline(always)]
+  fn read_into_buffer(&mut self, buffer: &mut [u8]) -> Result<usize, Self::Error> {
+    self.0.erased_read_into_buffer(buffer)
+  }
+
+  #[inline(always)]
+  fn lend(&mut self) -> Option<&'read [u8]> {
+    self.0.erased_lend()
+  }
+}
+
+#[test]
+fn lookahead() {
+  use crate::{ConstStack, Deserializer};
+
+  // A reader which doesn't lend its bytes and reads a varying amount of bytes at a time
+  #[derive(Debug)]
+  struct Stream<'read> {
+    bytes: &'read [u8],
+    reads: usize,
+  }
+  impl<'read> Read<'read> for Stream<'read> {
+    type Error = SliceError;
+    fn read_exact(&mut self, slice: &mut [u8]) -> Result<(), Self::Error> {
+      self.bytes.read_exact(slice)
+    }
+    fn read_into_buffer(&mut self, buffer: &mut [u8]) -> Result<usize, Self::Error> {
+      self.reads += 1;
+      let len = (self.reads % 5).clamp(1, buffer.len().min(self.bytes.len().max(1)));
+      self.bytes.read_exact(&mut buffer[.. len])?;
+      Ok(len)
+    }
+  }
+
+  fn check<'read, R: Read<'read> + Debug>(reader: R) {
+    let mut deserializer = Deserializer::<_, ConstStack<32>>::new(reader).unwrap();
+    let mut fields = deserializer.value().unwrap().fields().unwrap();
+    let mut field = fields.next().unwrap().unwrap();
+    assert_eq!(field.key().unwrap().collect::<Result<String, _>>().unwrap(), "key");
+    let mut values = field.value().unwrap().iterate().unwrap();
+    assert!(values.next().unwrap().unwrap().to_bool().unwrap());
+    assert!(!values.next().unwrap().unwrap().to_bool().unwrap());
+    values.next().unwrap().unwrap().to_null().unwrap();
+    assert_eq!(
+      values.next().unwrap().unwrap().to_str().unwrap().collect::<Result<String, _>>().unwrap(),
+      "a string which is longer than the lookahead buffer",
+    );
+    assert_eq!(values.next().unwrap().unwrap().to_number().unwrap().f64(), Some(1.5));
+    assert!(values.next().is_none());
+    drop(values);
+    assert!(fields.next().is_none());
+  }
+
+  let serialization =
+    br#"{ "key": [true, false, null, "a string which is longer than the lookahead buffer", 1.5] }"#;
+  check(serialization.as_slice());
+  check(Stream { bytes: serialization, reads: 0 });
+}
+
+#[test]
+fn rewind() {
+  use crate::{ConstStack, Deserializer, JsonError};
+
+  // Try to read the document as an object, then backtrack to read it as an array
+  let mut reader = &b"[1, 2] trailing"[..];
+  let mark = reader.mark();
+  {
+    let mut deserializer = Deserializer::<_, ConstStack<4>>::new(&mut reader).unwrap();
+    assert!(matches!(deserializer.value().unwrap().fields(), Err(JsonError::TypeError(_))));
+  }
+  // The deserializer read the entire slice, as it's lent
+  assert!(reader.is_empty());
+  reader.rewind(mark).unwrap();
+  {
+    let mut deserializer = Deserializer::<_, ConstStack<4>>::new(&mut reader).unwrap();
+    let mut elements = deserializer.value().unwrap().iterate().unwrap();
+    assert_eq!(elements.next().unwrap().unwrap().to_number().unwrap().i64(), Some(1));
+  }
+  reader.rewind(mark).unwrap();
+  assert_eq!(reader, b"[1, 2] trailing");
+}
+
+#[test]
+fn buffered() {
+  use crate::{ConstStack, Deserializer};
+
+  let serialization = br#" { "a": [1, 2] } [3]"#;
+
+  // Deserialize the entire structure
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+  {
+    let mut fields = deserializer.value().unwrap().fields().unwrap();
+    let mut values = fields.next().unwrap().unwrap().value().unwrap().iterate().unwrap();
+    while let Some(value) = values.next() {
+      value.unwrap().to_number().unwrap();
+    }
+    drop(values);
+    assert!(fields.next().is_none());
+  }
+  assert_eq!(deserializer.buffered().unwrap(), b" [3]");
+  assert_eq!(deserializer.bytes_consumed(), serialization.len() - 4);
+
+  // Drop the structure without deserializing it
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+  drop(deserializer.value().unwrap());
+  assert_eq!(deserializer.buffered().unwrap(), b" [3]");
+
+  // With a reader which reads one byte at a time, nothing is read past the structure
+  #[derive(Debug)]
+  struct Bytes<'read>(&'read [u8]);
+  impl<'read> Read<'read> for Bytes<'read> {
+    type Error = SliceError;
+    fn read_exact(&mut self, slice: &mut [u8]) -> Result<(), Self::Error> {
+      self.0.read_exact(slice)
+    }
+  }
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(Bytes(serialization)).unwrap();
+  // The leading whitespace has been consumed
+  assert_eq!(deserializer.bytes_consumed(), 1);
+  drop(deserializer.value().unwrap());
+  assert!(deserializer.buffered().unwrap().is_empty());
+  assert_eq!(deserializer.bytes_consumed(), serialization.len() - 4);
+  assert_eq!(deserializer.into_reader().0, b" [3]");
+}
+
+#[test]
+fn erased() {
+  use crate::{Base64Alphabet, Base64Read, DefaultStack, ErasedDeserializer, JsonError};
+
+  // A single instantiation of the deserializer, used with several types of readers
+  fn sum<'read, 'erased>(
+    mut deserializer: ErasedDeserializer<'read, 'erased>,
+  ) -> Result<i64, JsonError<'read, ErasedRead<'read, 'erased>, DefaultStack>> {
+    let mut sum = 0;
+    let mut elements = deserializer.value()?.iterate()?;
+    while let Some(element) = elements.next() {
+      sum += element?.to_number()?.i64().unwrap();
+    }
+    Ok(sum)
+  }
+
+  let mut slice = &b"[1, 2, 3]"[..];
+  assert_eq!(sum(ErasedDeserializer::new(ErasedRead::new(&mut slice)).unwrap()).unwrap(), 6);
+
+  let mut base64 = Base64Read::new(&b"WzQsIDVd"[..], Base64Alphabet::Standard);
+  assert_eq!(sum(ErasedDeserializer::new(ErasedRead::new(&mut base64)).unwrap()).unwrap(), 9);
+
+  // The underlying reader's errors are opaque
+  let mut truncated = &b"[1, 2"[..];
+  assert!(matches!(
+    sum(ErasedDeserializer::new(ErasedRead::new(&mut truncated)).unwrap()),
+    Err(JsonError::ReadError(ErasedError))
+  ));
 }
@@ -9,21 +9,78 @@ extern crate alloc;
 extern crate std;
 
 mod io;
+mod base64;
+mod chunked;
+#[cfg(feature = "std")]
+mod prefetch;
+#[cfg(feature = "miniz")]
+mod inflate;
 mod stack;
 mod string;
 mod number;
+mod dialect;
 mod deserializer;
+mod path;
+mod sequence;
+mod statistics;
+mod instrumentation;
+mod visit;
+mod tag;
+mod nested;
+mod dynamic;
 
-pub use io::Read;
+pub use io::{Read, MarkableRead, LOOKAHEAD_CAPACITY, ErasedError, ErasedRead};
+pub use base64::{Base64Alphabet, Base64Error, Base64Read};
+pub use chunked::{ChunkedError, ChunkedRead};
+#[cfg(feature = "miniz")]
+pub use inflate::{INFLATE_WINDOW, CompressionFormat, InflateError, InflateRead};
 #[cfg(feature = "std")]
 pub use io::ReadAdapter;
+#[cfg(feature = "std")]
+pub use prefetch::{PrefetchError, PrefetchRead};
 use io::PeekableRead;
 pub use stack::*;
 use string::*;
-pub use number::{NumberSink, Number};
-pub use deserializer::{Deserializer, Value};
+pub use number::{NumberSink, Number, NotRepresentable};
+pub use dialect::{ValidationProfile, Dialect};
+pub use deserializer::{Deserializer, ErasedDeserializer, Value};
+pub use sequence::{Framing, DocumentError, Documents, Document};
+pub use statistics::Statistics;
+pub use visit::{JsonVisitor, walk};
+pub use tag::{TagHandler, match_tag};
+pub use nested::{StringReadError, StringRead};
+pub use dynamic::{DynValue, DynField, DynKey};
+#[cfg(feature = "path")]
+pub use path::{Path, PathSegment};
+#[cfg(feature = "instrumentation")]
+pub use instrumentation::Counters;
 use deserializer::*;
 
+/// An error within the syntax of the JSON serialization.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SyntaxError {
+  /// The JSON had an invalid key.
+  InvalidKey,
+  /// The JSON had an invalid delimiter between the key and value (`:` expected).
+  InvalidKeyValueDelimiter,
+  /// The JSON had an invalid value.
+  InvalidValue,
+  /// The JSON had a trailing comma.
+  TrailingComma,
+  /// The JSON had mismatched delimiters between the open and close of the structure.
+  MismatchedDelimiter,
+}
+
+/// An operation could not be performed given the value's type.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TypeError;
+
+/// An error from the reader.
+///
+/// This is converted into `JsonError::ReadError`, allowing reader errors to be propagated with `?`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct IoError<E>(pub E);
+
 /// An error incurred when deserializing.
 #[derive(Debug)]
 pub enum JsonError<'read, R: Read<'read>, S: Stack> {
@@ -35,20 +92,18 @@ pub enum JsonError<'read, R: Read<'read>, S: Stack> {
   StackError(S::Error),
   /// The deserializer was reused.
   ReusedDeserializer,
-  /// The JSON had an invalid key.
-  InvalidKey,
-  /// The JSON had an invalid delimiter between the key and value (`:` expected).
-  InvalidKeyValueDelimiter,
-  /// The JSON had an invalid value.
-  InvalidValue,
+  /// The JSON had invalid syntax.
+  SyntaxError(SyntaxError),
   /// The string represented by the JSON serialization was valid yet not UTF-8.
   NotUtf8,
-  /// The JSON had a trailing comma.
-  TrailingComma,
-  /// The JSON had mismatched delimiters between the open and close of the structure.
-  MismatchedDelimiter,
   /// Operation could not be performed given the value's type.
-  TypeError,
+  TypeError(TypeError),
+  /// The number could not be represented by the type requested.
+  NumberNotRepresentable(NotRepresentable),
+  /// The value was nested deeper than the depth allowed.
+  DepthLimitExceeded,
+  /// A number or key was longer than the length allowed.
+  LengthLimitExceeded,
 }
 impl<'read, R: Read<'read>, S: Stack> Clone for JsonError<'read, R, S> {
   #[inline(always)]
@@ -57,10 +112,132 @@ impl<'read, R: Read<'read>, S: Stack> Clone for JsonError<'read, R, S> {
   }
 }
 impl<'read, R: Read<'read>, S: Stack> Copy for JsonError<'read, R, S> {}
+impl<'read, R: Read<'read>, S: Stack> From<SyntaxError> for JsonError<'read, R, S> {
+  #[inline(always)]
+  fn from(error: SyntaxError) -> Self {
+    JsonError::SyntaxError(error)
+  }
+}
+impl<'read, R: Read<'read>, S: Stack> From<TypeError> for JsonError<'read, R, S> {
+  #[inline(always)]
+  fn from(error: TypeError) -> Self {
+    JsonError::TypeError(error)
+  }
+}
+impl<'read, R: Read<'read>, S: Stack> From<IoError<R::Error>> for JsonError<'read, R, S> {
+  #[inline(always)]
+  fn from(IoError(error): IoError<R::Error>) -> Self {
+    JsonError::ReadError(error)
+  }
+}
+impl<'read, R: Read<'read>, S: Stack> From<NotRepresentable> for JsonError<'read, R, S> {
+  #[inline(always)]
+  fn from(reason: NotRepresentable) -> Self {
+    JsonError::NumberNotRepresentable(reason)
+  }
+}
+
+/// The kind of an error incurred when deserializing.
+///
+/// This is `JsonError` without the errors from the reader and stack, which are generic. This
+/// allows errors to be stored and matched without carrying the reader's and stack's types.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ErrorKind {
+  /// An unexpected state was reached during deserialization.
+  InternalError,
+  /// An error from the reader.
+  ReadError,
+  /// An error from the stack.
+  StackError,
+  /// The deserializer was reused.
+  ReusedDeserializer,
+  /// The JSON had invalid syntax.
+  SyntaxError(SyntaxError),
+  /// The string represented by the JSON serialization was valid yet not UTF-8.
+  NotUtf8,
+  /// Operation could not be performed given the value's type.
+  TypeError,
+  /// The number could not be represented by the type requested.
+  NumberNotRepresentable(NotRepresentable),
+  /// The value was nested deeper than the depth allowed.
+  DepthLimitExceeded,
+  /// A number or key was longer than the length allowed.
+  LengthLimitExceeded,
+}
+impl<'read, R: Read<'read>, S: Stack> JsonError<'read, R, S> {
+  /// The kind of this error.
+  #[inline(always)]
+  pub fn kind(&self) -> ErrorKind {
+    match self {
+      JsonError::InternalError => ErrorKind::InternalError,
+      JsonError::ReadError(_) => ErrorKind::ReadError,
+      JsonError::StackError(_) => ErrorKind::StackError,
+      JsonError::ReusedDeserializer => ErrorKind::ReusedDeserializer,
+      JsonError::SyntaxError(error) => ErrorKind::SyntaxError(*error),
+      JsonError::NotUtf8 => ErrorKind::NotUtf8,
+      JsonError::TypeError(TypeError) => ErrorKind::TypeError,
+      JsonError::NumberNotRepresentable(reason) => ErrorKind::NumberNotRepresentable(*reason),
+      JsonError::DepthLimitExceeded => ErrorKind::DepthLimitExceeded,
+      JsonError::LengthLimitExceeded => ErrorKind::LengthLimitExceeded,
+    }
+  }
+}
+
+/// The category of an error incurred when deserializing.
+///
+/// This allows branching on the class of an error, without matching on every variant of
+/// `JsonError` or `ErrorKind`. Categories may be added in the future, so matches on this must
+/// handle unknown categories.
+#[non_exhaustive]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ErrorCategory {
+  /// An error from the reader.
+  Io,
+  /// The JSON had invalid syntax.
+  Syntax,
+  /// The string represented by the JSON serialization was valid yet not UTF-8.
+  Utf8,
+  /// A limit was exceeded, such as the depth allowed by the stack.
+  Limit,
+  /// Operation could not be performed given the value's type.
+  Type,
+  /// An unexpected state was reached, or the deserializer was misused (such as by being reused).
+  Internal,
+}
+impl ErrorKind {
+  /// The category of this error.
+  #[inline(always)]
+  pub fn category(&self) -> ErrorCategory {
+    match self {
+      ErrorKind::InternalError | ErrorKind::ReusedDeserializer => ErrorCategory::Internal,
+      ErrorKind::ReadError => ErrorCategory::Io,
+      ErrorKind::StackError | ErrorKind::DepthLimitExceeded | ErrorKind::LengthLimitExceeded => {
+        ErrorCategory::Limit
+      }
+      ErrorKind::SyntaxError(_) => ErrorCategory::Syntax,
+      ErrorKind::NotUtf8 => ErrorCategory::Utf8,
+      ErrorKind::TypeError | ErrorKind::NumberNotRepresentable(_) => ErrorCategory::Type,
+    }
+  }
+}
+impl<'read, R: Read<'read>, S: Stack> JsonError<'read, R, S> {
+  /// The category of this error.
+  #[inline(always)]
+  pub fn category(&self) -> ErrorCategory {
+    self.kind().category()
+  }
+}
+impl<'read, R: Read<'read>, S: Stack> From<JsonError<'read, R, S>> for ErrorKind {
+  #[inline(always)]
+  fn from(error: JsonError<'read, R, S>) -> Self {
+    error.kind()
+  }
+}
 
 /// The type of the value.
 ///
 /// <https://datatracker.ietf.org/doc/html/rfc8259#section-3> defines all possible values.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum Type {
   /// An object.
   Object,
@@ -69,6 +246,9 @@ pub enum Type {
   /// A string.
   String,
   /// A RFC-8259 number.
+  ///
+  /// As the type is determined without reading the value, this doesn't distinguish integers from
+  /// floats. Once read, `Number::is_integer` may be used to do so.
   Number,
   /// A boolean.
   Bool,
@@ -108,7 +288,7 @@ pub struct Field<'read, 'parent, R: Read<'read>, S: Stack> {
 fn handle_field<'read, 'parent, R: Read<'read>, S: Stack>(
   deserializer: &'parent mut Deserializer<'read, R, S>,
 ) -> Field<'read, 'parent, R, S> {
-  Field { key: Some(StringKey(String::read(deserializer))) }
+  Field { key: Some(StringKey::read(deserializer)) }
 }
 
 /// Handle a string value.
@@ -148,10 +328,25 @@ impl<'read, 'parent, R: Read<'read>, S: Stack> Field<'read, 'parent, R, S> {
     self.key.as_mut().ok_or(JsonError::InternalError)
   }
 
+  /// Check if the field's key matches the expected key.
+  ///
+  /// This is equivalent to comparing the characters yielded by `Field::key` against the expected
+  /// key, yet compares the bytes of the key directly while they're unescaped ASCII, only decoding
+  /// the key once an escape sequence or non-ASCII byte is encountered. If only part of the key was
+  /// already read via `Field::key`, the rest of the key is checked against the expected key. Keys
+  /// which aren't valid UTF-8 never match.
+  ///
+  /// If the key doesn't match, the rest of the key may be left unread. It will still be advanced
+  /// past when `Field::value` is called.
+  #[inline(always)]
+  pub fn key_matches(&mut self, expected: &str) -> Result<bool, JsonError<'read, R, S>> {
+    self.key.as_mut().ok_or(JsonError::InternalError)?.matches(expected)
+  }
+
   /// Access the field's value.
   #[inline(always)]
   pub fn value(mut self) -> Result<Value<'read, 'parent, R, S>, JsonError<'read, R, S>> {
-    Ok(Value { deserializer: Some(self.key.take().ok_or(JsonError::InternalError)?.drop()) })
+    Value::new(self.key.take().ok_or(JsonError::InternalError)?.drop())
   }
 }
 
@@ -159,7 +354,7 @@ impl<'read, 'parent, R: Read<'read>, S: Stack> Drop for Field<'read, 'parent, R,
   #[inline(always)]
   fn drop(&mut self) {
     if let Some(key) = self.key.take() {
-      drop(Value { deserializer: Some(key.drop()) });
+      key.drop().drop_value();
     }
   }
 }
@@ -213,6 +408,51 @@ impl<'read, 'parent, R: Read<'read>, S: Stack> FieldIterator<'read, 'parent, R,
       }
     }
   }
+
+  /// The next field within the object, as its key and its value.
+  ///
+  /// This is `FieldIterator::next`, except the key is passed to `key` (as an iterator of its
+  /// characters, as yielded by `Field::key`) before the field's value is returned with the result.
+  /// This sequences reading the key before the value, without handling the `Field` itself. The
+  /// iterator doesn't have to be exhausted, with the rest of the key advanced past before the
+  /// value is returned.
+  #[allow(clippy::type_complexity)]
+  pub fn next_key_value<K>(
+    &mut self,
+    key: impl FnOnce(&mut dyn Iterator<Item = Result<char, JsonError<'read, R, S>>>) -> K,
+  ) -> Option<Result<(K, Value<'read, '_, R, S>), JsonError<'read, R, S>>> {
+    let mut field = match self.next()? {
+      Ok(field) => field,
+      Err(e) => return Some(Err(e)),
+    };
+    let key = match field.key() {
+      Ok(iter) => key(iter),
+      Err(e) => return Some(Err(e)),
+    };
+    Some(field.value().map(|value| (key, value)))
+  }
+
+  /// The amount of bytes consumed from the reader by the deserializer.
+  ///
+  /// This is `Deserializer::bytes_consumed`, allowing progress to be reported (or quotas enforced)
+  /// while iterating over the object.
+  #[inline(always)]
+  pub fn bytes_consumed(&self) -> usize {
+    self.deserializer.bytes_consumed()
+  }
+
+  /// Abandon the rest of the object.
+  ///
+  /// The fields which weren't read are not advanced past now. Instead, advancing past them is
+  /// queued, and solely performed when the deserializer is next used (such as by the parent's
+  /// iterator, or `Deserializer::buffered`). Any errors within them are returned then. If the
+  /// deserializer is never used again, they're never read.
+  ///
+  /// This is equivalent to dropping the iterator, yet makes the intent explicit.
+  #[inline(always)]
+  pub fn abandon(self) {
+    drop(self);
+  }
 }
 
 /// An iterator over an array.
@@ -257,7 +497,7 @@ impl<'read, 'parent, R: Read<'read>, S: Stack> ArrayIterator<'read, 'parent, R,
       };
       match result {
         SingleStepArrayResult::Value => {
-          break Some(Ok(Value { deserializer: Some(self.deserializer) }));
+          break Some(Value::new(self.deserializer));
         }
         SingleStepArrayResult::Closed => {
           self.done = true;
@@ -266,18 +506,166 @@ impl<'read, 'parent, R: Read<'read>, S: Stack> ArrayIterator<'read, 'parent, R,
       }
     }
   }
+
+  /// The amount of bytes consumed from the reader by the deserializer.
+  ///
+  /// This is `Deserializer::bytes_consumed`, allowing progress to be reported (or quotas enforced)
+  /// while iterating over the array.
+  #[inline(always)]
+  pub fn bytes_consumed(&self) -> usize {
+    self.deserializer.bytes_consumed()
+  }
+
+  /// Abandon the rest of the array.
+  ///
+  /// The elements which weren't read are not advanced past now. Instead, advancing past them is
+  /// queued, and solely performed when the deserializer is next used (such as by the parent's
+  /// iterator, or `Deserializer::buffered`). Any errors within them are returned then. If the
+  /// deserializer is never used again, they're never read.
+  ///
+  /// This is equivalent to dropping the iterator, yet makes the intent explicit.
+  #[inline(always)]
+  pub fn abandon(self) {
+    drop(self);
+  }
+}
+
+/// An iterator over up to a certain amount of the first items within an array.
+///
+/// Once the amount of items have been yielded, the rest of the array is abandoned (as with
+/// `ArrayIterator::abandon`), without being deserialized.
+pub struct SampledArrayIterator<'read, 'parent, R: Read<'read>, S: Stack> {
+  iterator: ArrayIterator<'read, 'parent, R, S>,
+  remaining: usize,
+}
+
+impl<'read, 'parent, R: Read<'read>, S: Stack> SampledArrayIterator<'read, 'parent, R, S> {
+  /// The next item within the array, if fewer than the amount of items to sample have been
+  /// yielded.
+  ///
+  /// This is `ArrayIterator::next`, with the same limitations.
+  #[allow(clippy::should_implement_trait)]
+  pub fn next(&mut self) -> Option<Result<Value<'read, '_, R, S>, JsonError<'read, R, S>>> {
+    if self.remaining == 0 {
+      // Abandon the rest of the array, allowing the parent to advance past it
+      if !self.iterator.done {
+        self.iterator.done = true;
+        self.iterator.deserializer.drop_structure();
+      }
+      None?;
+    }
+    self.remaining -= 1;
+    self.iterator.next()
+  }
+
+  /// The amount of bytes consumed from the reader by the deserializer.
+  ///
+  /// This is `Deserializer::bytes_consumed`.
+  #[inline(always)]
+  pub fn bytes_consumed(&self) -> usize {
+    self.iterator.bytes_consumed()
+  }
 }
 
 impl<'read, 'parent, R: Read<'read>, S: Stack> Value<'read, 'parent, R, S> {
+  /// Create a new `Value` for the item the deserializer is positioned at.
+  ///
+  /// This completes any pending work, such as advancing past dropped values, so the type of the
+  /// item may be determined.
+  #[inline(always)]
+  pub(crate) fn new(
+    deserializer: &'parent mut Deserializer<'read, R, S>,
+  ) -> Result<Self, JsonError<'read, R, S>> {
+    DelayedDrop::drop(deserializer)?;
+    let kind = kind(&deserializer.reader);
+    Ok(Value { deserializer: Some(deserializer), kind })
+  }
+
   /// Get the type of the current item.
   ///
   /// This does not assert it's a valid instance of this class of items. It solely asserts if this
-  /// is a valid item, it will be of this type.
+  /// is a valid item, it will be of this type. The type is determined when the `Value` is created,
+  /// making this free to call repeatedly.
   #[inline(always)]
-  pub fn kind(&mut self) -> Result<Type, JsonError<'read, R, S>> {
-    let deserializer = self.deserializer.as_mut().ok_or(JsonError::InternalError)?;
-    DelayedDrop::drop(deserializer)?;
-    Ok(kind(&deserializer.reader))
+  pub fn kind(&self) -> Type {
+    self.kind
+  }
+
+  /// If the current item is an object.
+  ///
+  /// This has the same caveats as `Value::kind`.
+  #[inline(always)]
+  pub fn is_object(&self) -> bool {
+    self.kind == Type::Object
+  }
+
+  /// If the current item is an array.
+  ///
+  /// This has the same caveats as `Value::kind`.
+  #[inline(always)]
+  pub fn is_array(&self) -> bool {
+    self.kind == Type::Array
+  }
+
+  /// If the current item is a string.
+  ///
+  /// This has the same caveats as `Value::kind`.
+  #[inline(always)]
+  pub fn is_string(&self) -> bool {
+    self.kind == Type::String
+  }
+
+  /// If the current item is a number.
+  ///
+  /// This has the same caveats as `Value::kind`.
+  #[inline(always)]
+  pub fn is_number(&self) -> bool {
+    self.kind == Type::Number
+  }
+
+  /// If the current item is a boolean.
+  ///
+  /// This has the same caveats as `Value::kind`.
+  #[inline(always)]
+  pub fn is_bool(&self) -> bool {
+    self.kind == Type::Bool
+  }
+
+  /// If the current item is `null`.
+  ///
+  /// This has the same caveats as `Value::kind`.
+  #[inline(always)]
+  pub fn is_null(&self) -> bool {
+    self.kind == Type::Null
+  }
+
+  /// The depth of the current item within the structure.
+  ///
+  /// The root value has depth `0`, and the values within a structure have a depth one greater
+  /// than the structure's.
+  #[inline(always)]
+  pub fn depth(&self) -> usize {
+    self.deserializer.as_ref().map_or(0, |deserializer| deserializer.depth())
+  }
+
+  /// Limit how deeply structures may be nested within this value.
+  ///
+  /// Once a structure is opened more than `depth` structures deep within this value (where a
+  /// structure containing solely scalars is one structure deep), the deserializer will error with
+  /// `JsonError::DepthLimitExceeded`. This applies to values skipped over as well, and is lifted
+  /// once this value is advanced past. This allows an untrusted value nested within a trusted
+  /// document to be constrained more tightly than the `Stack` constrains the document.
+  ///
+  /// If this value is within another value whose depth was limited, the tighter of the two limits
+  /// applies until this value is advanced past, after which the other value's limit applies
+  /// again. Up to four limits may be nested in this way, with further limits instead tightening
+  /// the innermost limit until its value is advanced past.
+  #[inline(always)]
+  pub fn with_depth_limit(mut self, depth: usize) -> Self {
+    if let Some(deserializer) = self.deserializer.as_mut() {
+      deserializer.limit_depth(depth);
+    }
+    self
   }
 
   /// Iterate over the fields within this object.
@@ -290,7 +678,7 @@ impl<'read, 'parent, R: Read<'read>, S: Stack> Value<'read, 'parent, R, S> {
       SingleStepResult::Unknown(SingleStepUnknownResult::ObjectOpened) => {
         Ok(FieldIterator { deserializer, done: false })
       }
-      SingleStepResult::Unknown(_) => Err(JsonError::TypeError)?,
+      SingleStepResult::Unknown(_) => Err(JsonError::TypeError(TypeError))?,
       _ => Err(JsonError::InternalError),
     }
   }
@@ -303,11 +691,25 @@ impl<'read, 'parent, R: Read<'read>, S: Stack> Value<'read, 'parent, R, S> {
       SingleStepResult::Unknown(SingleStepUnknownResult::ArrayOpened) => {
         Ok(ArrayIterator { deserializer, done: false })
       }
-      SingleStepResult::Unknown(_) => Err(JsonError::TypeError)?,
+      SingleStepResult::Unknown(_) => Err(JsonError::TypeError(TypeError))?,
       _ => Err(JsonError::InternalError),
     }
   }
 
+  /// Iterate over up to `max_items` of the first items within this container.
+  ///
+  /// The rest of the array is abandoned once `max_items` have been yielded, without being
+  /// deserialized, allowing exploratory tooling (such as schema inference) to sample huge arrays
+  /// without paying for their entirety. The rest of the array is still read by the deserializer
+  /// to advance past it, yet only as necessary to find where it ends.
+  #[inline(always)]
+  pub fn iterate_sampled(
+    self,
+    max_items: usize,
+  ) -> Result<SampledArrayIterator<'read, 'parent, R, S>, JsonError<'read, R, S>> {
+    Ok(SampledArrayIterator { iterator: self.iterate()?, remaining: max_items })
+  }
+
   /// Get the current item as a 'string'.
   ///
   /// As we cannot perform allocations, we do not yield a [`alloc::string::String`] but rather an
@@ -331,7 +733,7 @@ impl<'read, 'parent, R: Read<'read>, S: Stack> Value<'read, 'parent, R, S> {
       SingleStepResult::Unknown(SingleStepUnknownResult::String) => {
         Ok(handle_string_value(deserializer))
       }
-      SingleStepResult::Unknown(_) => Err(JsonError::TypeError)?,
+      SingleStepResult::Unknown(_) => Err(JsonError::TypeError(TypeError))?,
       _ => Err(JsonError::InternalError),
     }
   }
@@ -342,7 +744,7 @@ impl<'read, 'parent, R: Read<'read>, S: Stack> Value<'read, 'parent, R, S> {
     let deserializer = self.deserializer.take().ok_or(JsonError::InternalError)?;
     match deserializer.single_step()? {
       SingleStepResult::Unknown(SingleStepUnknownResult::Number(number)) => Ok(number),
-      SingleStepResult::Unknown(_) => Err(JsonError::TypeError)?,
+      SingleStepResult::Unknown(_) => Err(JsonError::TypeError(TypeError))?,
       _ => Err(JsonError::InternalError),
     }
   }
@@ -353,11 +755,62 @@ impl<'read, 'parent, R: Read<'read>, S: Stack> Value<'read, 'parent, R, S> {
     let deserializer = self.deserializer.take().ok_or(JsonError::InternalError)?;
     match deserializer.single_step()? {
       SingleStepResult::Unknown(SingleStepUnknownResult::Bool(bool)) => Ok(bool),
-      SingleStepResult::Unknown(_) => Err(JsonError::TypeError)?,
+      SingleStepResult::Unknown(_) => Err(JsonError::TypeError(TypeError))?,
       _ => Err(JsonError::InternalError),
     }
   }
 
+  /// Get the current item as a number, leniently.
+  ///
+  /// In addition to numbers, this accepts strings which contain a number (such as `"1.5"`), as
+  /// commonly produced by APIs which serialize numbers as strings.
+  #[inline(always)]
+  pub fn to_number_lenient(self) -> Result<Number, JsonError<'read, R, S>> {
+    if self.kind != Type::String {
+      return self.to_number();
+    }
+    number::to_number_chars(self.to_str()?)?.ok_or(JsonError::TypeError(TypeError))
+  }
+
+  /// Get the current item as an `i64`, leniently.
+  ///
+  /// This accepts numbers and strings which contain a number (per `Value::to_number_lenient`),
+  /// which must be integers representable within an `i64` (per `Number::i64_lenient`).
+  #[inline(always)]
+  pub fn to_i64_lenient(self) -> Result<i64, JsonError<'read, R, S>> {
+    self.to_number_lenient()?.i64_lenient().ok_or(JsonError::TypeError(TypeError))
+  }
+
+  /// Get the current item as an `f64`, leniently.
+  ///
+  /// This accepts numbers and strings which contain a number (per `Value::to_number_lenient`),
+  /// which must be within the range of an `f64` (per `Number::f64`).
+  #[inline(always)]
+  pub fn to_f64_lenient(self) -> Result<f64, JsonError<'read, R, S>> {
+    self.to_number_lenient()?.f64().ok_or(JsonError::TypeError(TypeError))
+  }
+
+  /// Get the current item as a `bool`, leniently.
+  ///
+  /// In addition to booleans, this accepts the strings `"true"` and `"false"`.
+  #[inline(always)]
+  pub fn to_bool_lenient(self) -> Result<bool, JsonError<'read, R, S>> {
+    if self.kind != Type::String {
+      return self.to_bool();
+    }
+    let mut chars = ['\0'; 5];
+    let mut len = 0;
+    for char in self.to_str()? {
+      *chars.get_mut(len).ok_or(JsonError::TypeError(TypeError))? = char?;
+      len += 1;
+    }
+    match &chars[.. len] {
+      ['t', 'r', 'u', 'e'] => Ok(true),
+      ['f', 'a', 'l', 's', 'e'] => Ok(false),
+      _ => Err(JsonError::TypeError(TypeError)),
+    }
+  }
+
   /// Get the current item as `null`.
   ///
   /// The point of this method is to assert the value is `null` _and valid_. `kind` only tells the
@@ -367,8 +820,323 @@ impl<'read, 'parent, R: Read<'read>, S: Stack> Value<'read, 'parent, R, S> {
     let deserializer = self.deserializer.take().ok_or(JsonError::InternalError)?;
     match deserializer.single_step()? {
       SingleStepResult::Unknown(SingleStepUnknownResult::Null) => Ok(()),
-      SingleStepResult::Unknown(_) => Err(JsonError::TypeError)?,
+      SingleStepResult::Unknown(_) => Err(JsonError::TypeError(TypeError))?,
       _ => Err(JsonError::InternalError),
     }
   }
 }
+
+#[test]
+fn error_kind() {
+  let kind = |serialization: &[u8]| {
+    let mut deserializer = Deserializer::<_, ConstStack<32>>::new(serialization).unwrap();
+    let mut elements = deserializer.value().unwrap().iterate().unwrap();
+    loop {
+      match elements.next() {
+        Some(Ok(element)) => {
+          if let Err(e) = element.to_bool() {
+            return e.kind();
+          }
+        }
+        Some(Err(e)) => return ErrorKind::from(e),
+        None => panic!("no error"),
+      }
+    }
+  };
+  assert_eq!(kind(b"[true,]"), ErrorKind::SyntaxError(SyntaxError::TrailingComma));
+  assert_eq!(kind(b"[true, fals, true]"), ErrorKind::SyntaxError(SyntaxError::InvalidValue));
+  assert_eq!(kind(b"[true, null]"), ErrorKind::TypeError);
+
+  assert_eq!(kind(b"[true,]").category(), ErrorCategory::Syntax);
+  assert_eq!(kind(b"[true, null]").category(), ErrorCategory::Type);
+  assert_eq!(ErrorKind::StackError.category(), ErrorCategory::Limit);
+  assert_eq!(ErrorKind::DepthLimitExceeded.category(), ErrorCategory::Limit);
+  assert_eq!(ErrorKind::ReadError.category(), ErrorCategory::Io);
+
+  // The layered errors convert into `JsonError`
+  type Error = JsonError<'static, &'static [u8], ConstStack<32>>;
+  assert_eq!(Error::from(TypeError).kind(), ErrorKind::TypeError);
+  assert!(matches!(
+    Error::from(IoError(io::SliceError::Short(1))),
+    JsonError::ReadError(io::SliceError::Short(1))
+  ));
+  assert_eq!(Error::from(SyntaxError::TrailingComma).kind().category(), ErrorCategory::Syntax);
+}
+
+#[test]
+fn lenient() {
+  let serialization =
+    br#"[1, 1.0, "-2", "1.5e1", " 1", "1a", 1.5, true, "true", "false", "truer"]"#;
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+  let mut elements = deserializer.value().unwrap().iterate().unwrap();
+  macro_rules! next {
+    () => {
+      elements.next().unwrap().unwrap()
+    };
+  }
+  assert_eq!(next!().to_i64_lenient().unwrap(), 1);
+  assert_eq!(next!().to_i64_lenient().unwrap(), 1);
+  assert_eq!(next!().to_i64_lenient().unwrap(), -2);
+  assert_eq!(next!().to_i64_lenient().unwrap(), 15);
+  assert!(matches!(next!().to_i64_lenient(), Err(JsonError::TypeError(TypeError))));
+  assert!(matches!(next!().to_f64_lenient(), Err(JsonError::TypeError(TypeError))));
+  assert!(matches!(next!().to_i64_lenient(), Err(JsonError::TypeError(TypeError))));
+  assert!(next!().to_bool_lenient().unwrap());
+  assert!(next!().to_bool_lenient().unwrap());
+  assert!(!next!().to_bool_lenient().unwrap());
+  assert!(matches!(next!().to_bool_lenient(), Err(JsonError::TypeError(TypeError))));
+  assert!(elements.next().is_none());
+
+  // Numbers which solely approximate integers aren't coerced
+  let mut deserializer =
+    Deserializer::<_, ConstStack<32>>::new(&b"[1.00000000000000000000000001, 1e-400, 100e-2]"[..])
+      .unwrap();
+  let mut elements = deserializer.value().unwrap().iterate().unwrap();
+  for expected in [None, None, Some(1)] {
+    let number = elements.next().unwrap().unwrap().to_number().unwrap();
+    assert_eq!(number.i64_lenient(), expected);
+  }
+}
+
+#[test]
+fn key_matches() {
+  let serialization =
+    br#"{"a": 1, "abc": 2, "\u0061bc": 3, "\u00e9": 4, "\uD800": 5, "\u00e9\u00e9": 6}"#;
+  for (expected, matches) in [
+    ("abc", [false, true, true, false, false, false]),
+    ("a", [true, false, false, false, false, false]),
+    ("\u{e9}", [false, false, false, true, false, false]),
+    ("", [false; 6]),
+  ] {
+    let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+    let mut fields = deserializer.value().unwrap().fields().unwrap();
+    let mut results = vec![];
+    while let Some(field) = fields.next() {
+      let mut field = field.unwrap();
+      results.push(field.key_matches(expected).unwrap());
+      field.value().unwrap().to_number().unwrap();
+    }
+    assert_eq!(results, matches);
+  }
+
+  // A key partially read is checked from where it was read to
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&br#"{"abc": 1}"#[..]).unwrap();
+  let mut fields = deserializer.value().unwrap().fields().unwrap();
+  let mut field = fields.next().unwrap().unwrap();
+  assert_eq!(field.key().unwrap().next().unwrap().unwrap(), 'a');
+  assert!(field.key_matches("bc").unwrap());
+}
+
+#[test]
+fn depth() {
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&br#"{"a": [[1]]}"#[..]).unwrap();
+  let value = deserializer.value().unwrap();
+  assert_eq!(value.depth(), 0);
+  let mut fields = value.fields().unwrap();
+  let field = fields.next().unwrap().unwrap();
+  let value = field.value().unwrap();
+  assert_eq!(value.depth(), 1);
+  let mut elements = value.iterate().unwrap();
+  let value = elements.next().unwrap().unwrap();
+  assert_eq!(value.depth(), 2);
+  let mut elements = value.iterate().unwrap();
+  assert_eq!(elements.next().unwrap().unwrap().depth(), 3);
+}
+
+#[test]
+fn with_depth_limit() {
+  let serialization = br#"{"blob": [[1], {"a": [2]}], "after": [[[[3]]]]}"#;
+
+  // Read the limited value entirely
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+  let mut fields = deserializer.value().unwrap().fields().unwrap();
+  let field = fields.next().unwrap().unwrap();
+  let mut elements = field.value().unwrap().with_depth_limit(3).iterate().unwrap();
+  while let Some(element) = elements.next() {
+    drop(element.unwrap());
+  }
+  drop(elements);
+  // The limit is lifted after the limited value
+  let field = fields.next().unwrap().unwrap();
+  let mut elements = field.value().unwrap().iterate().unwrap();
+  let mut elements = elements.next().unwrap().unwrap().iterate().unwrap();
+  let mut elements = elements.next().unwrap().unwrap().iterate().unwrap();
+  let mut elements = elements.next().unwrap().unwrap().iterate().unwrap();
+  assert_eq!(elements.next().unwrap().unwrap().to_number().unwrap().i64(), Some(3));
+  drop(elements);
+
+  // The limit is lifted even if the limited value is skipped
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+  let mut fields = deserializer.value().unwrap().fields().unwrap();
+  drop(fields.next().unwrap().unwrap().value().unwrap().with_depth_limit(3));
+  while let Some(field) = fields.next() {
+    drop(field.unwrap());
+  }
+  drop(fields);
+  assert!(deserializer.buffered().unwrap().is_empty());
+
+  // Exceeding the limit errors, whether the structure is read or skipped
+  for read in [true, false] {
+    let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+    let mut fields = deserializer.value().unwrap().fields().unwrap();
+    let field = fields.next().unwrap().unwrap();
+    let mut elements = field.value().unwrap().with_depth_limit(2).iterate().unwrap();
+    assert!(elements.next().unwrap().unwrap().iterate().is_ok());
+    let element = elements.next().unwrap().unwrap();
+    if read {
+      let mut fields = element.fields().unwrap();
+      let field = fields.next().unwrap().unwrap();
+      assert!(matches!(field.value().unwrap().iterate(), Err(JsonError::DepthLimitExceeded)));
+    } else {
+      drop(element);
+      assert!(matches!(elements.next(), Some(Err(JsonError::DepthLimitExceeded))));
+    }
+  }
+
+  // Structures skipped while solely scanning their structure are held to the limit as well
+  let dialect = Dialect { skipped_values: ValidationProfile::Trusting, ..Dialect::STRICT };
+  for (limit, exceeded) in [(2, true), (3, false)] {
+    let mut deserializer =
+      Deserializer::<_, ConstStack<32>>::with_dialect(&serialization[..], dialect).unwrap();
+    let mut fields = deserializer.value().unwrap().fields().unwrap();
+    {
+      let field = fields.next().unwrap().unwrap();
+      let mut elements = field.value().unwrap().with_depth_limit(limit).iterate().unwrap();
+      drop(elements.next().unwrap().unwrap());
+      drop(elements.next().unwrap().unwrap());
+      let next = elements.next();
+      assert_eq!(matches!(next, Some(Err(JsonError::DepthLimitExceeded))), exceeded);
+      if exceeded {
+        continue;
+      }
+      assert!(next.is_none());
+    }
+    let field = fields.next().unwrap().unwrap();
+    let mut elements = field.value().unwrap().iterate().unwrap();
+    let mut elements = elements.next().unwrap().unwrap().iterate().unwrap();
+    let mut elements = elements.next().unwrap().unwrap().iterate().unwrap();
+    let mut elements = elements.next().unwrap().unwrap().iterate().unwrap();
+    assert_eq!(elements.next().unwrap().unwrap().to_number().unwrap().i64(), Some(3));
+  }
+
+  // Scalars are permitted with a limit of zero
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&br#"[1, []]"#[..]).unwrap();
+  let mut elements = deserializer.value().unwrap().iterate().unwrap();
+  let element = elements.next().unwrap().unwrap().with_depth_limit(0);
+  assert_eq!(element.to_number().unwrap().i64(), Some(1));
+  let element = elements.next().unwrap().unwrap().with_depth_limit(0);
+  assert!(matches!(element.iterate(), Err(JsonError::DepthLimitExceeded)));
+
+  // A tighter limit nested within another is lifted once its value is advanced past, restoring
+  // the enclosing limit for the value's siblings
+  let serialization = br#"{"outer": [1, [[2]]], "after": [[[3]]]}"#;
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+  let mut fields = deserializer.value().unwrap().fields().unwrap();
+  {
+    let field = fields.next().unwrap().unwrap();
+    let mut elements = field.value().unwrap().with_depth_limit(10).iterate().unwrap();
+    let element = elements.next().unwrap().unwrap().with_depth_limit(0);
+    assert_eq!(element.to_number().unwrap().i64(), Some(1));
+    let mut elements = elements.next().unwrap().unwrap().iterate().unwrap();
+    let mut elements = elements.next().unwrap().unwrap().iterate().unwrap();
+    assert_eq!(elements.next().unwrap().unwrap().to_number().unwrap().i64(), Some(2));
+  }
+  // The enclosing limit is lifted after its value
+  let field = fields.next().unwrap().unwrap();
+  let mut elements = field.value().unwrap().iterate().unwrap();
+  let mut elements = elements.next().unwrap().unwrap().iterate().unwrap();
+  let mut elements = elements.next().unwrap().unwrap().iterate().unwrap();
+  assert_eq!(elements.next().unwrap().unwrap().to_number().unwrap().i64(), Some(3));
+  drop(elements);
+
+  // The enclosing limit is restored, not lifted, once the nested limit is
+  let serialization = br#"[[1, [[2]]]]"#;
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+  let mut elements = deserializer.value().unwrap().iterate().unwrap();
+  let mut elements = elements.next().unwrap().unwrap().with_depth_limit(1).iterate().unwrap();
+  drop(elements.next().unwrap().unwrap().with_depth_limit(0));
+  let element = elements.next().unwrap().unwrap();
+  assert!(matches!(element.iterate(), Err(JsonError::DepthLimitExceeded)));
+}
+
+#[test]
+fn next_key_value() {
+  let mut deserializer =
+    Deserializer::<_, ConstStack<32>>::new(&br#"{"ab": 1, "\ud800": 2, "cd": [3]}"#[..]).unwrap();
+  let mut fields = deserializer.value().unwrap().fields().unwrap();
+  let (key, value) =
+    fields.next_key_value(|key| key.collect::<Result<std::string::String, _>>()).unwrap().unwrap();
+  assert_eq!(key.unwrap(), "ab");
+  assert_eq!(value.to_number().unwrap().i64(), Some(1));
+  // Keys which aren't valid UTF-8 are reported to the closure, without failing the field
+  let (key, value) =
+    fields.next_key_value(|key| key.collect::<Result<std::string::String, _>>()).unwrap().unwrap();
+  assert!(key.is_err());
+  assert_eq!(value.to_number().unwrap().i64(), Some(2));
+  // The key doesn't have to be exhausted
+  let (first, value) = fields.next_key_value(|key| key.next()).unwrap().unwrap();
+  assert_eq!(first.unwrap().unwrap(), 'c');
+  assert!(value.is_array());
+  drop(value);
+  assert!(fields.next_key_value(|_| ()).is_none());
+}
+
+#[test]
+fn abandon() {
+  let serialization = br#"{"a": [1, 2, [3]], "b": {"c": "d"}, "e": 4} "#;
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+  let mut fields = deserializer.value().unwrap().fields().unwrap();
+  let mut field = fields.next().unwrap().unwrap();
+  assert!(field.key_matches("a").unwrap());
+  let mut elements = field.value().unwrap().iterate().unwrap();
+  assert_eq!(elements.next().unwrap().unwrap().to_number().unwrap().i64(), Some(1));
+  let consumed = elements.bytes_consumed();
+  assert!(consumed < br#"{"a": [1, 2"#.len());
+  // The rest of the array isn't read until the deserializer is next used
+  elements.abandon();
+  assert_eq!(fields.bytes_consumed(), consumed);
+  let mut field = fields.next().unwrap().unwrap();
+  assert!(field.key_matches("b").unwrap());
+  drop(field);
+  assert!(fields.bytes_consumed() > br#"{"a": [1, 2, [3]]"#.len());
+  fields.abandon();
+  assert_eq!(deserializer.buffered().unwrap(), b" ");
+
+  // Errors within the abandoned values are returned once the deserializer is next used
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&br#"[[1, 2x], 3]"#[..]).unwrap();
+  let mut elements = deserializer.value().unwrap().iterate().unwrap();
+  elements.next().unwrap().unwrap().iterate().unwrap().abandon();
+  assert!(elements.next().unwrap().is_err());
+}
+
+#[test]
+fn iterate_sampled() {
+  let serialization = br#"[[1, [2], {"a": 3}, 4, 5], [], [6], 7]"#;
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+  let mut arrays = deserializer.value().unwrap().iterate().unwrap();
+  let mut sampled = vec![];
+  while let Some(array) = arrays.next() {
+    let array = array.unwrap();
+    if !array.is_array() {
+      assert_eq!(array.to_number().unwrap().i64(), Some(7));
+      continue;
+    }
+    let mut elements = array.iterate_sampled(2).unwrap();
+    let mut items = 0;
+    while let Some(element) = elements.next() {
+      // The elements are dropped without being read
+      element.unwrap();
+      items += 1;
+    }
+    assert!(elements.next().is_none());
+    sampled.push(items);
+  }
+  assert_eq!(sampled, [2, 0, 1]);
+
+  // Nothing is yielded when sampling zero items
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&b"[[1], 2]"[..]).unwrap();
+  let mut elements = deserializer.value().unwrap().iterate_sampled(0).unwrap();
+  assert!(elements.next().is_none());
+  drop(elements);
+  assert_eq!(deserializer.buffered().unwrap(), b"");
+}
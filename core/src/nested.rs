@@ -0,0 +1,157 @@
+use crate::*;
+
+/// An error when reading from a `StringRead`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StringReadError {
+  /// An error when reading the string.
+  StringError(ErrorKind),
+  /// The string ended, yet more bytes were requested.
+  Ended,
+}
+
+/// A reader of the contents of a string.
+///
+/// This encodes the characters of a string (with its escape sequences handled) as UTF-8, as
+/// they're read. As APIs frequently embed JSON documents within strings, this allows deserializing
+/// such a nested document with a second `Deserializer`, without collecting the string.
+#[derive(Clone)]
+pub struct StringRead<I: Iterator<Item = Result<char, ErrorKind>>> {
+  chars: I,
+  // The UTF-8 encoding of the character currently being read
+  encoded: [u8; 4],
+  position: usize,
+  len: usize,
+}
+
+impl<I: Iterator<Item = Result<char, ErrorKind>>> StringRead<I> {
+  /// Read the contents of a string, from its characters.
+  #[inline(always)]
+  pub fn new(chars: I) -> Self {
+    StringRead { chars, encoded: [0; 4], position: 0, len: 0 }
+  }
+
+  /// Read into a buffer, returning the amount of bytes read.
+  ///
+  /// This solely returns `0` if the string has ended (or the buffer is empty). This is also used
+  /// by `core-json-traits`'s `IteratorRead`, which reads characters which are always `Ok`.
+  #[doc(hidden)]
+  pub fn read_available(&mut self, buffer: &mut [u8]) -> Result<usize, StringReadError> {
+    let mut read = 0;
+    while read < buffer.len() {
+      if self.position == self.len {
+        let Some(char) = self.chars.next() else { break };
+        let char = char.map_err(StringReadError::StringError)?;
+        self.len = char.encode_utf8(&mut self.encoded).len();
+        self.position = 0;
+      }
+      let len = (self.len - self.position).min(buffer.len() - read);
+      buffer[read .. (read + len)]
+        .copy_from_slice(&self.encoded[self.position .. (self.position + len)]);
+      self.position += len;
+      read += len;
+    }
+    Ok(read)
+  }
+}
+
+// The characters are commonly yielded by an iterator which doesn't implement `Debug`
+impl<I: Iterator<Item = Result<char, ErrorKind>>> core::fmt::Debug for StringRead<I> {
+  fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fmt.debug_struct("StringRead").finish_non_exhaustive()
+  }
+}
+
+impl<I: Iterator<Item = Result<char, ErrorKind>>> Read<'_> for StringRead<I> {
+  type Error = StringReadError;
+
+  #[inline(always)]
+  fn read_exact(&mut self, slice: &mut [u8]) -> Result<(), Self::Error> {
+    if self.read_available(slice)? != slice.len() {
+      Err(StringReadError::Ended)?;
+    }
+    Ok(())
+  }
+
+  #[inline(always)]
+  fn read_into_buffer(&mut self, buffer: &mut [u8]) -> Result<usize, Self::Error> {
+    match self.read_available(buffer)? {
+      0 => Err(StringReadError::Ended),
+      read => Ok(read),
+    }
+  }
+}
+
+impl<'read, 'parent, R: Read<'read>, S: Stack> Value<'read, 'parent, R, S> {
+  /// Get the current item as a reader of the contents of the string.
+  ///
+  /// This is `Value::to_str`, with the characters yielded encoded as UTF-8 bytes by a `StringRead`.
+  /// This allows a JSON document embedded within a string (such as `"{\"a\": 1}"`) to be
+  /// deserialized by a second `Deserializer`, as it's read. Errors when reading the string are
+  /// returned as `StringReadError::StringError`, within the second deserializer's
+  /// `JsonError::ReadError`.
+  #[inline(always)]
+  pub fn to_read(
+    self,
+  ) -> Result<
+    StringRead<impl use<'read, 'parent, R, S> + Iterator<Item = Result<char, ErrorKind>>>,
+    JsonError<'read, R, S>,
+  > {
+    Ok(StringRead::new(self.to_str()?.map(|char| char.map_err(|e| e.kind()))))
+  }
+}
+
+#[test]
+fn nested() {
+  let json = r#"{"payload": "{\"a\": [1, \"\\u00e9\\n\"], \"b\": \"😀\"}", "after": true}"#;
+  let mut deserializer = Deserializer::<_, ConstStack<4>>::new(json.as_bytes()).unwrap();
+  let mut fields = deserializer.value().unwrap().fields().unwrap();
+
+  let mut field = fields.next().unwrap().unwrap();
+  assert!(field.key_matches("payload").unwrap());
+  {
+    let mut nested =
+      Deserializer::<_, ConstStack<4>>::new(field.value().unwrap().to_read().unwrap()).unwrap();
+    let mut nested_fields = nested.value().unwrap().fields().unwrap();
+    let mut nested_field = nested_fields.next().unwrap().unwrap();
+    assert!(nested_field.key_matches("a").unwrap());
+    let mut values = nested_field.value().unwrap().iterate().unwrap();
+    assert_eq!(values.next().unwrap().unwrap().to_number().unwrap().i64(), Some(1));
+    let string = values.next().unwrap().unwrap().to_str().unwrap();
+    assert!(string.map(Result::unwrap).eq(['\u{e9}', '\n']));
+    assert!(values.next().is_none());
+    drop(values);
+    let mut nested_field = nested_fields.next().unwrap().unwrap();
+    assert!(nested_field.key_matches("b").unwrap());
+    let string = nested_field.value().unwrap().to_str().unwrap();
+    assert!(string.map(Result::unwrap).eq(['\u{1F600}']));
+    assert!(nested_fields.next().is_none());
+  }
+  // The outer deserializer remains usable after the nested document
+  let mut field = fields.next().unwrap().unwrap();
+  assert!(field.key_matches("after").unwrap());
+  assert!(field.value().unwrap().to_bool().unwrap());
+  drop(fields);
+
+  // Reading a string which isn't valid JSON, or a value which isn't a string
+  let json = r#"["{\"a\": ", 1]"#;
+  let mut deserializer = Deserializer::<_, ConstStack<4>>::new(json.as_bytes()).unwrap();
+  let mut values = deserializer.value().unwrap().iterate().unwrap();
+  let read = values.next().unwrap().unwrap().to_read().unwrap();
+  let mut nested = Deserializer::<_, ConstStack<4>>::new(read).unwrap();
+  let mut nested_fields = nested.value().unwrap().fields().unwrap();
+  assert!(nested_fields.next().unwrap().unwrap().value().is_err());
+  drop(nested_fields);
+  drop(nested);
+  assert!(matches!(
+    values.next().unwrap().unwrap().to_read(),
+    Err(JsonError::TypeError(TypeError))
+  ));
+
+  // Errors within the string are returned
+  let mut read = StringRead::new([Ok('a'), Err(ErrorKind::NotUtf8)].into_iter());
+  assert_eq!(read.read_byte(), Ok(b'a'));
+  assert_eq!(read.read_byte(), Err(StringReadError::StringError(ErrorKind::NotUtf8)));
+  let mut read = StringRead::new("\u{e9}".chars().map(Ok));
+  assert_eq!(read.read_into_buffer(&mut [0; 4]), Ok(2));
+  assert_eq!(read.read_byte(), Err(StringReadError::Ended));
+}
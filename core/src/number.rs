@@ -1,6 +1,7 @@
-use core::{str::FromStr, fmt::Write};
+use core::str::FromStr;
+use core::fmt::Write;
 
-use crate::{Read, PeekableRead, Stack, JsonError};
+use crate::{Read, PeekableRead, Stack, SyntaxError, IoError, JsonError, Dialect};
 
 /// An implementor of `core::fmt::Write` which writes to a slice.
 struct SliceWrite<'a>(&'a mut [u8], usize);
@@ -20,10 +21,17 @@ impl<'a> Write for SliceWrite<'a> {
 // `+ 1` as `ilog10` rounds down, `+ 1` as `10` has a logarithm of `1` yet requires two digits
 const I64_SIGNIFICANT_DIGITS: usize = (i64::MAX.ilog10() + 1 + 1) as usize;
 const F64_SIGNIFICANT_DIGITS: usize = f64::DIGITS as usize;
-const SIGNIFICANT_DIGITS: usize = if I64_SIGNIFICANT_DIGITS > F64_SIGNIFICANT_DIGITS {
-  I64_SIGNIFICANT_DIGITS
-} else {
-  F64_SIGNIFICANT_DIGITS
+// The significant digits of a 96-bit integer, as used by common decimal types
+const DECIMAL_SIGNIFICANT_DIGITS: usize = 29;
+const SIGNIFICANT_DIGITS: usize = {
+  let mut significant_digits = I64_SIGNIFICANT_DIGITS;
+  if F64_SIGNIFICANT_DIGITS > significant_digits {
+    significant_digits = F64_SIGNIFICANT_DIGITS;
+  }
+  if DECIMAL_SIGNIFICANT_DIGITS > significant_digits {
+    significant_digits = DECIMAL_SIGNIFICANT_DIGITS;
+  }
+  significant_digits
 };
 
 /// A sink for a number string.
@@ -86,6 +94,13 @@ pub struct NumberSink {
   /// Truncated zero digits will be reflected in the correction to the exponent, making them
   /// losslessly dropped.
   imprecise: bool,
+  /// The place, relative to the decimal point, of the least-significant non-zero digit truncated.
+  ///
+  /// This is `0` for the ones place, `-1` for the tenths place, and so on, allowing whether the
+  /// number is an integer to be determined despite truncated digits.
+  truncated_place: Option<i64>,
+  /// The amount of digits read after the decimal point.
+  fraction_digits: i64,
 
   /// If this value was invalid per RFC-8259 syntax.
   ///
@@ -111,6 +126,8 @@ impl NumberSink {
       absolute_exponent: Some(0),
       exponent_correction: 0,
       imprecise: false,
+      truncated_place: None,
+      fraction_digits: 0,
       invalid: false,
     }
   }
@@ -164,12 +181,18 @@ impl NumberSink {
             // If we're truncating '0', this is still precise due to correctly tweaking the
             // exponent
             self.imprecise |= c != b'0';
+            if c != b'0' {
+              self.truncated_place = Some(0);
+            } else if let Some(place) = &mut self.truncated_place {
+              *place += 1;
+            }
           }
         }
 
         // separator, array closure, object closure, whitespace
         // https://datatracker.ietf.org/doc/html/rfc8259#section-2
-        b',' | b']' | b'}' | b'\x20' | b'\x09' | b'\x0A' | b'\x0D' => return false,
+        // `NUL` is additionally used to represent the end of the input, after a scalar root value
+        b',' | b']' | b'}' | b'\x20' | b'\x09' | b'\x0A' | b'\x0D' | b'\0' => return false,
 
         b'.' => {
           self.invalid |= !self.digits_in_current_part;
@@ -194,6 +217,7 @@ impl NumberSink {
       match c {
         b'0' ..= b'9' => {
           self.digits_in_current_part = true;
+          self.fraction_digits += 1;
 
           let within_precision = self.i != self.digits.len();
           if within_precision {
@@ -207,11 +231,15 @@ impl NumberSink {
             // If this is after the decimal, but within precision, we need to shift down by 1
             self.exponent_correction -= 1;
           } else {
-            self.imprecise = true;
+            // Truncating a '0' after the decimal doesn't change the value
+            self.imprecise |= c != b'0';
+            if c != b'0' {
+              self.truncated_place = Some(-self.fraction_digits);
+            }
           }
         }
 
-        b',' | b']' | b'}' | b'\x20' | b'\x09' | b'\x0A' | b'\x0D' => return false,
+        b',' | b']' | b'}' | b'\x20' | b'\x09' | b'\x0A' | b'\x0D' | b'\0' => return false,
 
         // This block is duplicated with `before_decimal`
         b'e' | b'E' => {
@@ -238,7 +266,7 @@ impl NumberSink {
         });
       }
 
-      b',' | b']' | b'}' | b'\x20' | b'\x09' | b'\x0A' | b'\x0D' => return false,
+      b',' | b']' | b'}' | b'\x20' | b'\x09' | b'\x0A' | b'\x0D' | b'\0' => return false,
 
       _ => self.invalid = true,
     }
@@ -246,17 +274,21 @@ impl NumberSink {
     true
   }
 
+  /// The exponent written for the number, if it didn't overflow.
+  #[inline(always)]
+  fn embedded_exponent(&self) -> Option<i64> {
+    let absolute_exponent = self.absolute_exponent?;
+    // This negation is infallible as `i16::MIN.abs() > i16::MAX` and it's currently positive
+    Some(i64::from(if self.negative_exponent { -absolute_exponent } else { absolute_exponent }))
+  }
+
   /// Get the significant digits, exponent for the number.
   ///
   /// If this has an unnecessarily large negative exponent, it will reduce it as possible. This
   /// allows "100e-1" to still be detected as not having a fractional part.
   #[inline(always)]
   fn significant_digits_and_exponent(&self) -> Option<(usize, i64)> {
-    let absolute_exponent = self.absolute_exponent?;
-    // This negation is infallible as `i16::MIN.abs() > i16::MAX` and it's currently positive
-    let embedded_exponent =
-      if self.negative_exponent { -absolute_exponent } else { absolute_exponent };
-    let mut exponent = i64::from(embedded_exponent).checked_add(self.exponent_correction)?;
+    let mut exponent = self.embedded_exponent()?.checked_add(self.exponent_correction)?;
 
     let mut significant_digits = self.i;
     // Normalize this number's negative exponent, as possible
@@ -270,6 +302,49 @@ impl NumberSink {
     Some((significant_digits, exponent))
   }
 
+  /// If this number is an integer, regardless of if it's representable within an `i64`.
+  #[inline(always)]
+  pub(crate) fn is_integer(&self) -> bool {
+    match (self.significant_digits_and_exponent(), self.embedded_exponent()) {
+      // The truncated digits must also be integral
+      (Some((_, exponent)), Some(embedded_exponent)) => {
+        (exponent >= 0) &&
+          self.truncated_place.is_none_or(|place| place.saturating_add(embedded_exponent) >= 0)
+      }
+      // The exponent overflowed, leaving the number either massive or minuscule
+      _ => !self.negative_exponent,
+    }
+  }
+
+  /// If this number was written with an exponent.
+  #[inline(always)]
+  pub(crate) fn has_exponent(&self) -> bool {
+    !self.before_exponent
+  }
+
+  /// If a non-zero significant digit was truncated from this number.
+  #[inline(always)]
+  pub(crate) fn precision_lost(&self) -> bool {
+    self.imprecise
+  }
+
+  /// If the exponent written for this number overflowed an `i16`.
+  #[inline(always)]
+  pub(crate) fn exponent_overflowed(&self) -> bool {
+    self.absolute_exponent.is_none()
+  }
+
+  /// The float for a number whose exponent overflowed, if representable.
+  ///
+  /// Unless the number was written with more than `i16::MAX` digits, it's either zero, minuscule
+  /// (rounding to zero), or massive (exceeding the range of an `f64`).
+  #[inline(always)]
+  fn f64_with_overflowed_exponent(&self) -> Option<f64> {
+    // Truncated digits are only considered if they're non-zero
+    let zero = (!self.imprecise) && self.digits[.. self.i].iter().all(|digit| *digit == b'0');
+    (zero || self.negative_exponent).then_some(if self.negative { -0.0 } else { 0.0 })
+  }
+
   #[inline(always)]
   fn strictly_valid(&self) -> bool {
     // It has to not have been marked invalid and the last part must not have been empty
@@ -290,31 +365,31 @@ impl NumberSink {
     /*
       We do this manually, instead of using `i64::from_str`, to avoid the overhead of
       `str::from_utf8`/usage of `unsafe`. We also do the first loop, with wrapping arithmetic, when
-      we know the value won't overflow, only doing the final steps with checked arithmetic, when
-      the value might overflow.
+      we know the value won't overflow (as `i64::MAX` has 19 digits, for the first 18 digits), only
+      doing the final steps with checked arithmetic, when the value might overflow.
     */
     let mut accum = 0i64;
     if self.negative {
-      for digit in self.digits.iter().take(significant_digits.min(I64_SIGNIFICANT_DIGITS - 1)) {
+      for digit in self.digits.iter().take(significant_digits.min(I64_SIGNIFICANT_DIGITS - 2)) {
         accum = accum.wrapping_mul(10);
         let digit = i64::from(digit - b'0');
         accum = accum.wrapping_sub(digit);
       }
       for digit in &self.digits
-        [(I64_SIGNIFICANT_DIGITS - 1) .. significant_digits.max(I64_SIGNIFICANT_DIGITS - 1)]
+        [(I64_SIGNIFICANT_DIGITS - 2) .. significant_digits.max(I64_SIGNIFICANT_DIGITS - 2)]
       {
         accum = accum.checked_mul(10)?;
         let digit = i64::from(digit - b'0');
         accum = accum.checked_sub(digit)?;
       }
     } else {
-      for digit in self.digits.iter().take(significant_digits.min(I64_SIGNIFICANT_DIGITS - 1)) {
+      for digit in self.digits.iter().take(significant_digits.min(I64_SIGNIFICANT_DIGITS - 2)) {
         accum = accum.wrapping_mul(10);
         let digit = i64::from(digit - b'0');
         accum = accum.wrapping_add(digit);
       }
       for digit in &self.digits
-        [(I64_SIGNIFICANT_DIGITS - 1) .. significant_digits.max(I64_SIGNIFICANT_DIGITS - 1)]
+        [(I64_SIGNIFICANT_DIGITS - 2) .. significant_digits.max(I64_SIGNIFICANT_DIGITS - 2)]
       {
         accum = accum.checked_mul(10)?;
         let digit = i64::from(digit - b'0');
@@ -330,6 +405,77 @@ impl NumberSink {
     Some(accum)
   }
 
+  /// Extract the exact number as its sign and absolute value, if possible.
+  ///
+  /// Zero is always considered non-negative.
+  #[inline(always)]
+  pub(crate) fn magnitude(&self) -> Option<(bool, u64)> {
+    let (significant_digits, exponent) = self.significant_digits_and_exponent()?;
+
+    // As with `i64`, this must be precise and without a fractional part
+    if self.imprecise || (exponent < 0) {
+      None?;
+    }
+
+    let mut accum = 0u64;
+    for digit in &self.digits[.. significant_digits] {
+      accum = accum.checked_mul(10)?.checked_add(u64::from(digit - b'0'))?;
+    }
+    for _ in 0 .. exponent {
+      accum = accum.checked_mul(10)?;
+    }
+
+    Some((self.negative && (accum != 0), accum))
+  }
+
+  /// Decompose the exact number into its sign, significand, and exponent, if possible.
+  #[inline(always)]
+  pub(crate) fn decompose(&self) -> Option<(bool, u64, i64)> {
+    let (mut significant_digits, mut exponent) = self.significant_digits_and_exponent()?;
+
+    // If this number had a loss of precision, we should not return it here
+    if self.imprecise {
+      None?;
+    }
+
+    // Also normalize the trailing zeroes of integers, so they don't overflow the significand
+    while (significant_digits > 0) && (self.digits[significant_digits - 1] == b'0') {
+      significant_digits -= 1;
+      exponent = exponent.checked_add(1)?;
+    }
+
+    let mut significand = 0u64;
+    for digit in &self.digits[.. significant_digits] {
+      significand = significand.checked_mul(10)?.checked_add(u64::from(digit - b'0'))?;
+    }
+
+    // Zero is consistently decomposed as `0 * 10^0`
+    if significand == 0 {
+      return Some((false, 0, 0));
+    }
+    Some((self.negative, significand, exponent))
+  }
+
+  /// The significant digits and exponent for the number, without leading or trailing zeroes.
+  ///
+  /// Zero is consistently represented as no digits with an exponent of `0`.
+  #[inline(always)]
+  pub(crate) fn digits(&self) -> Option<(bool, &[u8], i64)> {
+    let (significant_digits, mut exponent) = self.significant_digits_and_exponent()?;
+    let mut digits = &self.digits[.. significant_digits];
+    while let [b'0', rest @ ..] = digits {
+      digits = rest;
+    }
+    while let [rest @ .., b'0'] = digits {
+      digits = rest;
+      exponent = exponent.checked_add(1)?;
+    }
+    if digits.is_empty() {
+      return Some((false, digits, 0));
+    }
+    Some((self.negative, digits, exponent))
+  }
+
   /// The imprecise string representing this number.
   ///
   /// This returns an owned `u8` array and the length of the string (in bytes) written within it.
@@ -350,6 +496,18 @@ impl NumberSink {
   #[inline(always)]
   pub fn imprecise_str(
     &self,
+  ) -> Option<([u8; 1 + SIGNIFICANT_DIGITS + 1 + 1 + I64_SIGNIFICANT_DIGITS], usize)> {
+    self.str_with_significant_digits(F64_SIGNIFICANT_DIGITS)
+  }
+
+  /// Get the current number as an RFC-8259-compliant string, with at most the specified amount
+  /// of significant digits.
+  ///
+  /// This is `NumberSink::imprecise_str`, with the bound on the significant digits specified.
+  #[inline(always)]
+  fn str_with_significant_digits(
+    &self,
+    bound: usize,
   ) -> Option<([u8; 1 + SIGNIFICANT_DIGITS + 1 + 1 + I64_SIGNIFICANT_DIGITS], usize)> {
     let (original_significant_digits, mut exponent) = self.significant_digits_and_exponent()?;
 
@@ -368,16 +526,16 @@ impl NumberSink {
     // Copy the significant digits
     /*
       While we support `SIGNIFICANT_DIGITS` as necessary for exact conversions to integers, for
-      floats (as assumed by this function), we only use the amount of significant digits Rust can
+      floats (as assumed by `imprecise_str`), we only use the amount of significant digits Rust can
       accurately round-trip: `f64::DIGITS`.
 
       We do add an additional significant digit if we have a leading zero present.
     */
-    let significant_digits =
-      original_significant_digits.min(usize::from(self.digits[0] == b'0') + (f64::DIGITS as usize));
+    let significant_digits = original_significant_digits
+      .min(usize::from(self.digits[0] == b'0') + bound.min(SIGNIFICANT_DIGITS));
     {
       // If we're truncating digits from the tail, shift the number back up accordingly
-      // This is a safe cast so long as `|SIGNIFICANT_DIGITS - f64::DIGITS| < i64::MAX`.
+      // This is a safe cast so long as `SIGNIFICANT_DIGITS < i64::MAX`.
       #[allow(clippy::cast_possible_wrap)]
       let further_exponent_correction = (original_significant_digits - significant_digits) as i64;
       exponent = exponent.checked_add(further_exponent_correction)?;
@@ -413,8 +571,12 @@ impl NumberSink {
   /// This will only return the number if it's finite, as RFC-8259 JSON is not able to represent
   /// infinite values, so deserializing into an infinite value demonstrates we weren't able to
   /// capture the range of this value.
+  #[cfg(not(feature = "small-float"))]
   #[inline(always)]
   pub(crate) fn f64(&self) -> Option<f64> {
+    if self.exponent_overflowed() {
+      return self.f64_with_overflowed_exponent();
+    }
     let (str, len) = self.imprecise_str()?;
 
     /*
@@ -424,6 +586,86 @@ impl NumberSink {
     */
     let str = core::str::from_utf8(&str[.. len]).ok()?;
     let candidate = f64::from_str(str).ok()?;
+    // The string for zero is always written without its sign
+    let candidate = if self.negative { -candidate.abs() } else { candidate };
+
+    candidate.is_finite().then_some(candidate)
+  }
+}
+
+impl NumberSink {
+  /// Extract the number as a float, from all of the significant digits retained.
+  ///
+  /// This is `NumberSink::f64`, except up to `SIGNIFICANT_DIGITS` significant digits are used
+  /// (instead of `f64::DIGITS`) and the sign of zero is preserved.
+  #[inline(always)]
+  pub(crate) fn f64_precise(&self) -> Option<f64> {
+    if self.exponent_overflowed() {
+      return self.f64_with_overflowed_exponent();
+    }
+    let (str, len) = self.str_with_significant_digits(SIGNIFICANT_DIGITS)?;
+    let str = core::str::from_utf8(&str[.. len]).ok()?;
+    let candidate = f64::from_str(str).ok()?;
+    // The string for zero is always written without its sign
+    let candidate = if self.negative { -candidate.abs() } else { candidate };
+    candidate.is_finite().then_some(candidate)
+  }
+}
+
+/// The powers of ten exactly representable by an `f64`.
+#[cfg(feature = "small-float")]
+const EXACT_POWERS_OF_TEN: [f64; 23] = [
+  1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11, 1e12, 1e13, 1e14, 1e15, 1e16, 1e17,
+  1e18, 1e19, 1e20, 1e21, 1e22,
+];
+
+#[cfg(feature = "small-float")]
+impl NumberSink {
+  /// Extract the number as a float, without using `f64::from_str`.
+  ///
+  /// The significant digits (bounded to `f64::DIGITS`, as with `f64::from_str`) are accumulated
+  /// into an integer, which is exactly representable, and then scaled by the exponent. If the
+  /// absolute value of the exponent is at most 22, this is a single multiplication or division by
+  /// an exactly-representable power of ten, making the result correctly rounded (and identical to
+  /// `f64::from_str`'s). Otherwise, the result is scaled by `1e22` repeatedly, with each step
+  /// incurring at most half of an ULP of error, and results which are subnormal may lose further
+  /// precision.
+  #[inline(always)]
+  pub(crate) fn f64(&self) -> Option<f64> {
+    if self.exponent_overflowed() {
+      return self.f64_with_overflowed_exponent();
+    }
+    let (original_significant_digits, exponent) = self.significant_digits_and_exponent()?;
+
+    // Truncate the significant digits as `imprecise_str` does, shifting the number back up
+    let significant_digits =
+      original_significant_digits.min(usize::from(self.digits[0] == b'0') + F64_SIGNIFICANT_DIGITS);
+    let mut exponent = exponent
+      .checked_add(i64::try_from(original_significant_digits - significant_digits).ok()?)?;
+
+    let mut mantissa = 0u64;
+    for digit in &self.digits[.. significant_digits] {
+      mantissa = (mantissa * 10) + u64::from(digit - b'0');
+    }
+    // This is lossless as the mantissa has at most `f64::DIGITS` digits, so it's less than 2**53
+    #[allow(clippy::cast_precision_loss)]
+    let mut candidate = mantissa as f64;
+
+    // Bound the exponent, as any non-zero mantissa will be infinite or zero beyond these bounds
+    exponent = exponent.clamp(-400, 400);
+    while exponent > 22 {
+      candidate *= EXACT_POWERS_OF_TEN[22];
+      exponent -= 22;
+    }
+    while exponent < -22 {
+      candidate /= EXACT_POWERS_OF_TEN[22];
+      exponent += 22;
+    }
+    let power = EXACT_POWERS_OF_TEN[usize::try_from(exponent.unsigned_abs()).ok()?];
+    candidate = if exponent < 0 { candidate / power } else { candidate * power };
+    if self.negative {
+      candidate = -candidate;
+    }
 
     candidate.is_finite().then_some(candidate)
   }
@@ -443,23 +685,156 @@ impl Write for NumberSink {
 #[inline(always)]
 pub(crate) fn to_number_str<'read, R: Read<'read>, S: Stack>(
   reader: &mut PeekableRead<'read, R>,
+  dialect: Dialect,
 ) -> Result<Number, JsonError<'read, R, S>> {
+  reader.counters.number();
   let mut result = NumberSink::new();
+  // The position the number must end by, per `Dialect::number_length_limit`
+  let end = dialect.number_length_limit.map(|limit| reader.consumed().saturating_add(limit));
+
+  // Handle the extensions to RFC 8259's syntax for numbers this dialect accepts
+  if dialect.non_finite_numbers || dialect.radix_integers {
+    let negative = reader.peek() == b'-';
+    if negative {
+      result.push_byte(b'-');
+      reader.read_byte().map_err(IoError)?;
+    }
+    match reader.peek() {
+      b'N' if dialect.non_finite_numbers && (!negative) => {
+        let mut nan = [0; 3];
+        reader.read_exact_into_non_empty_slice(&mut nan).map_err(IoError)?;
+        if nan != *b"NaN" {
+          Err(SyntaxError::InvalidValue)?;
+        }
+        return Ok(Number(Representation::NonFinite(f64::NAN)));
+      }
+      b'I' if dialect.non_finite_numbers => {
+        let mut infinity = [0; 8];
+        reader.read_exact_into_non_empty_slice(&mut infinity).map_err(IoError)?;
+        if infinity != *b"Infinity" {
+          Err(SyntaxError::InvalidValue)?;
+        }
+        return Ok(Number(Representation::NonFinite(if negative {
+          f64::NEG_INFINITY
+        } else {
+          f64::INFINITY
+        })));
+      }
+      b'0' if dialect.radix_integers => {
+        result.push_byte(b'0');
+        reader.read_byte().map_err(IoError)?;
+        let radix = match reader.peek() {
+          b'x' => 16,
+          b'o' => 8,
+          b'b' => 2,
+          // This is a decimal number, which we continue to read as usual
+          _ => 0,
+        };
+        if radix != 0 {
+          reader.read_byte().map_err(IoError)?;
+          return to_radix_integer(reader, negative, radix, end);
+        }
+      }
+      _ => {}
+    }
+  }
 
   // Read until a byte which isn't part of the number, sinking along the way
   while result.push_byte(reader.peek()) {
-    reader.read_byte().map_err(JsonError::ReadError)?;
+    check_length(reader, end)?;
+    reader.read_byte().map_err(IoError)?;
   }
 
   if !result.strictly_valid() {
-    Err(JsonError::InvalidValue)?;
+    Err(SyntaxError::InvalidValue)?;
+  }
+
+  Ok(Number(Representation::Finite(result)))
+}
+
+/// Check another byte of a number may be read without it extending past `end`.
+#[inline(always)]
+fn check_length<'read, R: Read<'read>, S: Stack>(
+  reader: &PeekableRead<'read, R>,
+  end: Option<usize>,
+) -> Result<(), JsonError<'read, R, S>> {
+  if end.is_some_and(|end| reader.consumed() >= end) {
+    Err(JsonError::LengthLimitExceeded)?;
+  }
+  Ok(())
+}
+
+/// Handle the immediate value within the reader as the digits of an integer of the specified
+/// radix.
+fn to_radix_integer<'read, R: Read<'read>, S: Stack>(
+  reader: &mut PeekableRead<'read, R>,
+  negative: bool,
+  radix: u32,
+  end: Option<usize>,
+) -> Result<Number, JsonError<'read, R, S>> {
+  let mut magnitude = 0u64;
+  let mut digits = false;
+  while let Some(digit) = char::from(reader.peek()).to_digit(radix) {
+    check_length(reader, end)?;
+    reader.read_byte().map_err(IoError)?;
+    magnitude = magnitude
+      .checked_mul(u64::from(radix))
+      .and_then(|magnitude| magnitude.checked_add(u64::from(digit)))
+      .ok_or(SyntaxError::InvalidValue)?;
+    digits = true;
+  }
+  if !digits {
+    Err(SyntaxError::InvalidValue)?;
+  }
+
+  // Represent the integer in decimal, which the sink is able to exactly handle
+  let mut result = NumberSink::new();
+  if negative {
+    result.push_byte(b'-');
   }
+  write!(&mut result, "{magnitude}").map_err(|_| JsonError::InternalError)?;
+  Ok(Number(Representation::Finite(result)))
+}
+
+/// Parse a number from the characters of a string.
+///
+/// This returns `None` if the string isn't a number per RFC-8259's syntax.
+#[inline(always)]
+pub(crate) fn to_number_chars<E>(
+  chars: impl Iterator<Item = Result<char, E>>,
+) -> Result<Option<Number>, E> {
+  let mut result = NumberSink::new();
+  let mut valid = true;
+  for char in chars {
+    let char = char?;
+    valid &= u8::try_from(char).is_ok_and(|byte| result.push_byte(byte));
+  }
+  Ok((valid && result.strictly_valid()).then_some(Number(Representation::Finite(result))))
+}
+
+enum Representation {
+  Finite(NumberSink),
+  /// `NaN` or an infinity, as accepted by `Dialect::non_finite_numbers`.
+  NonFinite(f64),
+}
 
-  Ok(Number(result))
+/// Why a number could not be represented by the type requested.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NotRepresentable {
+  /// The number has a fractional part, which the type requested could not represent.
+  LossyFraction,
+  /// The number exceeded the range of the type requested.
+  OutOfRange,
+  /// The exponent written for the number exceeded the range of an `i16`, with the number
+  /// exceeding the range of the type requested.
+  ExponentOverflow,
+  /// The number was outside of the bounds required of it (such as by a field's `range` attribute,
+  /// when deriving `JsonDeserialize`).
+  OutOfBounds,
 }
 
 /// A number deserialized from JSON.
-pub struct Number(NumberSink);
+pub struct Number(Representation);
 impl Number {
   /// Get the current number as an `i64`.
   ///
@@ -471,7 +846,94 @@ impl Number {
   /// This is _exact_. It does not go through `f64` and does not experience its approximations.
   #[inline(always)]
   pub fn i64(&self) -> Option<i64> {
-    self.0.i64()
+    match &self.0 {
+      Representation::Finite(sink) => sink.i64(),
+      Representation::NonFinite(_) => None,
+    }
+  }
+
+  /// Get the current number as a `u64`.
+  ///
+  /// This is `Number::i64`, except for non-negative integers representable within a `u64`.
+  #[inline(always)]
+  pub fn u64(&self) -> Option<u64> {
+    match self.magnitude()? {
+      (false, magnitude) => Some(magnitude),
+      (true, _) => None,
+    }
+  }
+
+  /// Get the current number as its sign and absolute value, if it's an integer whose absolute
+  /// value is representable within a `u64`.
+  ///
+  /// This is exact, as `Number::i64` is, and covers every integer representable by either an
+  /// `i64` or a `u64`. The sign is `true` if the number is negative, with zero always considered
+  /// non-negative.
+  #[inline(always)]
+  pub fn magnitude(&self) -> Option<(bool, u64)> {
+    match &self.0 {
+      Representation::Finite(sink) => sink.magnitude(),
+      Representation::NonFinite(_) => None,
+    }
+  }
+
+  /// Get the current number as its sign and absolute value, or why it isn't representable as such.
+  ///
+  /// This is `Number::magnitude`, except it distinguishes numbers with fractional parts from
+  /// integers exceeding the range of a `u64`.
+  #[inline(always)]
+  pub fn try_magnitude(&self) -> Result<(bool, u64), NotRepresentable> {
+    self.magnitude().ok_or(if self.has_fraction() {
+      NotRepresentable::LossyFraction
+    } else {
+      NotRepresentable::OutOfRange
+    })
+  }
+
+  /// Decompose the current number into its sign, significand, and exponent.
+  ///
+  /// The number is `significand * 10^exponent`, negated if the sign is `true` (with zero always
+  /// considered non-negative), and the significand has no trailing zeroes. This is exact, allowing
+  /// conversions into decimal types without going through `f64`, and is yielded if the number's
+  /// significant digits were entirely preserved (at least the first nineteen are) and fit within a
+  /// `u64`.
+  #[inline(always)]
+  pub fn decompose(&self) -> Option<(bool, u64, i64)> {
+    match &self.0 {
+      Representation::Finite(sink) => sink.decompose(),
+      Representation::NonFinite(_) => None,
+    }
+  }
+
+  /// Get the current number's sign, significant digits, and exponent.
+  ///
+  /// The number is the significant digits (as ASCII characters, without leading or trailing
+  /// zeroes) multiplied by `10^exponent`, negated if the sign is `true` (with zero always
+  /// considered non-negative, and represented as no digits). Unlike `Number::decompose`, this is
+  /// yielded regardless of how many significant digits the number has, with solely the retained
+  /// digits yielded if `Number::precision_lost`. This is `None` for non-finite numbers and numbers
+  /// whose exponent overflowed.
+  #[inline(always)]
+  pub fn digits(&self) -> Option<(bool, &[u8], i64)> {
+    match &self.0 {
+      Representation::Finite(sink) => sink.digits(),
+      Representation::NonFinite(_) => None,
+    }
+  }
+
+  /// Get the current number as an RFC-8259-compliant string, with at most the first
+  /// `f64::DIGITS` significant digits.
+  ///
+  /// This is `NumberSink::imprecise_str`, yielding `None` for non-finite numbers.
+  #[doc(hidden)]
+  #[inline(always)]
+  pub fn imprecise_str(
+    &self,
+  ) -> Option<([u8; 1 + SIGNIFICANT_DIGITS + 1 + 1 + I64_SIGNIFICANT_DIGITS], usize)> {
+    match &self.0 {
+      Representation::Finite(sink) => sink.imprecise_str(),
+      Representation::NonFinite(_) => None,
+    }
   }
 
   /// Get the current item as an `f64`.
@@ -481,10 +943,163 @@ impl Number {
   /// - Rust's bounds on precision
   /// - This library's precision bounds, truncating additional detail
   ///
-  /// This returns `None` if the value's range exceed `f64`'s.
+  /// This returns `None` if the value's range exceed `f64`'s. Numbers too small to be represented
+  /// are yielded as zero, and the sign of zero is preserved (so `-0` and `-1e-400` are yielded as
+  /// `-0.0`). Numbers whose exponent overflowed an `i16` (see `Number::exponent_overflowed`) are
+  /// yielded as zero if the exponent is negative or the significant digits are all zero, and
+  /// `None` otherwise, which is exact unless the number was written with more than `i16::MAX`
+  /// digits.
   #[inline(always)]
   pub fn f64(&self) -> Option<f64> {
-    self.0.f64()
+    match &self.0 {
+      Representation::Finite(sink) => sink.f64(),
+      Representation::NonFinite(_) => None,
+    }
+  }
+
+  /// If the current number is an integer.
+  ///
+  /// This is determined from the number as written, once normalized (so "10.0" and "1e1" are
+  /// integers), and holds even for integers which aren't representable within an `i64`. Digits
+  /// truncated from the number (see `Number::precision_lost`) are considered, so
+  /// "1.00000000000000000000000001" isn't an integer. This allows dispatching on whether a number
+  /// is an integer or a float without parsing it twice.
+  #[inline(always)]
+  pub fn is_integer(&self) -> bool {
+    match &self.0 {
+      Representation::Finite(sink) => sink.is_integer(),
+      Representation::NonFinite(_) => false,
+    }
+  }
+
+  /// If the current number has a fractional part.
+  ///
+  /// This is determined from the number as written, once normalized (so "10.0" doesn't have a
+  /// fractional part). `NaN` and the infinities don't have fractional parts.
+  #[inline(always)]
+  pub fn has_fraction(&self) -> bool {
+    match &self.0 {
+      Representation::Finite(sink) => !sink.is_integer(),
+      Representation::NonFinite(_) => false,
+    }
+  }
+
+  /// If the current number was written with an exponent.
+  ///
+  /// This is determined from the number as written, so "1e1" has an exponent despite being an
+  /// integer. `NaN` and the infinities don't have exponents.
+  #[inline(always)]
+  pub fn has_exponent(&self) -> bool {
+    match &self.0 {
+      Representation::Finite(sink) => sink.has_exponent(),
+      Representation::NonFinite(_) => false,
+    }
+  }
+
+  /// If significant digits of the current number were truncated when it was read.
+  ///
+  /// Up to 29 significant digits of a number are retained. If a non-zero digit beyond those was
+  /// written, it's truncated, and `Number::i64` (and the methods premised on it) won't yield the
+  /// number while `Number::f64` and `Number::f64_precise` yield an approximation of it. This allows
+  /// callers to decide whether to trust the values yielded, or to handle the number with an
+  /// arbitrary-precision path instead. Truncated zeroes (as in "1.000000000000000000000") don't
+  /// lose precision.
+  #[inline(always)]
+  pub fn precision_lost(&self) -> bool {
+    match &self.0 {
+      Representation::Finite(sink) => sink.precision_lost(),
+      Representation::NonFinite(_) => false,
+    }
+  }
+
+  /// If the exponent written for the current number overflowed an `i16`.
+  ///
+  /// Exponents are accumulated into an `i16`. When the exponent written exceeds that (as in
+  /// "1e99999"), the number is only known to be zero, minuscule, or massive, and `Number::i64`
+  /// and the methods premised on it won't yield it. `NaN` and the infinities don't have exponents.
+  #[inline(always)]
+  pub fn exponent_overflowed(&self) -> bool {
+    match &self.0 {
+      Representation::Finite(sink) => sink.exponent_overflowed(),
+      Representation::NonFinite(_) => false,
+    }
+  }
+
+  /// Get the current number as an `f64`, or why it isn't representable as one.
+  ///
+  /// This is `Number::f64`, except it distinguishes numbers whose exponent overflowed an `i16`
+  /// from numbers which otherwise exceed the range of an `f64`.
+  #[inline(always)]
+  pub fn try_f64(&self) -> Result<f64, NotRepresentable> {
+    self.f64().ok_or(if self.exponent_overflowed() {
+      NotRepresentable::ExponentOverflow
+    } else {
+      NotRepresentable::OutOfRange
+    })
+  }
+
+  /// If the current number is an integer representable within an `i64`.
+  ///
+  /// This is equivalent to `Number::i64` returning `Some`.
+  #[inline(always)]
+  pub fn fits_i64(&self) -> bool {
+    self.i64().is_some()
+  }
+
+  /// Get the current number as an `i64`, or why it isn't representable as one.
+  ///
+  /// This is `Number::i64`, except it distinguishes numbers with fractional parts from integers
+  /// exceeding the range of an `i64`.
+  #[inline(always)]
+  pub fn try_i64(&self) -> Result<i64, NotRepresentable> {
+    self.i64().ok_or(if self.has_fraction() {
+      NotRepresentable::LossyFraction
+    } else {
+      NotRepresentable::OutOfRange
+    })
+  }
+
+  /// Get the current number as an `f64`, from all of the significant digits retained.
+  ///
+  /// This is `Number::f64`, except the number is parsed from up to 29 of its significant digits
+  /// (instead of `f64::DIGITS`), and `-0` is yielded as `-0.0`. As the shortest representation
+  /// which round-trips any `f64` has at most 17 significant digits, this exactly recovers any
+  /// `f64` from such a representation. This always uses `f64::from_str`, even with the
+  /// `small-float` feature.
+  #[inline(always)]
+  pub fn f64_precise(&self) -> Option<f64> {
+    match &self.0 {
+      Representation::Finite(sink) => sink.f64_precise(),
+      Representation::NonFinite(_) => None,
+    }
+  }
+
+  /// Get the current number as an `f64`, including non-finite values.
+  ///
+  /// This is `Number::f64`, except `NaN`, `Infinity`, and `-Infinity` (as accepted by
+  /// `Dialect::non_finite_numbers`) are yielded as `f64::NAN` and the infinities. These are never
+  /// yielded for numbers which were written as digits, even if they exceed the range of an `f64`.
+  #[inline(always)]
+  pub fn f64_extended(&self) -> Option<f64> {
+    match &self.0 {
+      Representation::Finite(sink) => sink.f64(),
+      Representation::NonFinite(float) => Some(*float),
+    }
+  }
+
+  /// Get the current number as an `i64`, leniently.
+  ///
+  /// This yields any number whose decimal value is exactly an integer representable within an
+  /// `i64`, however it was written (such as `1.0`, `1.5e1`, or `100e-2`). Numbers which solely
+  /// approximate an integer (such as `1e-400` or `1.00000000000000000000000001`) aren't yielded.
+  #[inline(always)]
+  pub fn i64_lenient(&self) -> Option<i64> {
+    /*
+      As numbers are normalized, `Number::i64` already yields every such integer. The only
+      integers it doesn't yield are those whose digits were truncated, which have more significant
+      digits than any `i64` does.
+    */
+    self.i64()
   }
 }
 
@@ -536,5 +1151,340 @@ fn number_sink() {
       let str = format!("{}", i64::MIN);
       test(&str, i64::MIN);
     }
+
+    // Integers of 19 digits may still overflow
+    for overflowing in ["9223372036854775808", "-9223372036854775809", "9999999999999999999"] {
+      let mut sink = NumberSink::new();
+      write!(&mut sink, "{}", overflowing).unwrap();
+      assert_eq!(sink.i64(), None);
+    }
   }
 }
+
+#[test]
+fn is_integer() {
+  use crate::{ConstStack, Deserializer};
+
+  let serialization =
+    b"[0, -1, 10.0, 1e1, 100e-2, 123456789012345678901234567890, 1e99999, 1.5, 1e-1, 1e-99999, \
+    1.00000000000000000000000001, 1.00000000000000000000000001e26, \
+    1.00000000000000000000000001e25, 12345678901234567890123450000, \
+    12345678901234567890123450000e-4, 12345678901234567890123450000e-5, \
+    1.00000000000000000000000000]";
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+  let mut elements = deserializer.value().unwrap().iterate().unwrap();
+  let mut integers = vec![];
+  while let Some(element) = elements.next() {
+    integers.push(element.unwrap().to_number().unwrap().is_integer());
+  }
+  assert_eq!(
+    integers,
+    [
+      true, true, true, true, true, true, true, false, false, false, false, true, false, true,
+      true, false, true
+    ]
+  );
+}
+
+#[test]
+fn introspection() {
+  use crate::{ConstStack, Deserializer, Dialect};
+
+  let serialization = b"[1, 1.5, 1e3, 1.5E-3, 12345678901234567890, \
+    123456789012345678901234567890123, 1.0000000000000000000000000000000, \
+    1.00000000000000000000000000000001, 0x1F, NaN]";
+  let mut deserializer =
+    Deserializer::<_, ConstStack<32>>::with_dialect(&serialization[..], Dialect::RELAXED).unwrap();
+  let mut elements = deserializer.value().unwrap().iterate().unwrap();
+  let mut numbers = vec![];
+  while let Some(element) = elements.next() {
+    let number = element.unwrap().to_number().unwrap();
+    numbers.push((number.is_integer(), number.has_fraction(), number.has_exponent()));
+    numbers.push((number.precision_lost(), number.i64().is_some(), number.f64().is_some()));
+  }
+  assert_eq!(
+    numbers,
+    [
+      (true, false, false),
+      (false, true, true),
+      (false, true, false),
+      (false, false, true),
+      (true, false, true),
+      (false, true, true),
+      (false, true, true),
+      (false, false, true),
+      (true, false, false),
+      (false, false, true),
+      (true, false, false),
+      (true, false, true),
+      (true, false, false),
+      (false, true, true),
+      (false, true, false),
+      (true, false, true),
+      (true, false, false),
+      (false, true, true),
+      (false, false, false),
+      (false, false, false),
+    ]
+  );
+
+  // A fraction solely within the truncated digits is still a fraction
+  let serialization = b"[1.00000000000000000000000000000001]";
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+  let mut elements = deserializer.value().unwrap().iterate().unwrap();
+  let number = elements.next().unwrap().unwrap().to_number().unwrap();
+  assert!(!number.is_integer());
+  assert!(number.has_fraction());
+  assert!(number.precision_lost());
+  assert_eq!(number.try_i64(), Err(NotRepresentable::LossyFraction));
+}
+
+#[test]
+fn non_finite() {
+  use crate::{ConstStack, Deserializer};
+
+  let serialization = b"[NaN, Infinity, -Infinity, 1.5, 1e-99999]";
+  let invalid = |serialization: &[u8], dialect| {
+    let mut deserializer =
+      Deserializer::<_, ConstStack<32>>::with_dialect(serialization, dialect).unwrap();
+    let mut elements = deserializer.value().unwrap().iterate().unwrap();
+    elements.next().unwrap().and_then(crate::Value::to_number).is_err()
+  };
+  assert!(invalid(&serialization[..], Dialect::STRICT));
+
+  let dialect = Dialect { non_finite_numbers: true, ..Dialect::STRICT };
+  let mut deserializer =
+    Deserializer::<_, ConstStack<32>>::with_dialect(&serialization[..], dialect).unwrap();
+  let mut elements = deserializer.value().unwrap().iterate().unwrap();
+  let mut numbers = vec![];
+  while let Some(element) = elements.next() {
+    let number = element.unwrap().to_number().unwrap();
+    assert_eq!(number.i64(), None);
+    assert!(!number.is_integer());
+    numbers.push((number.f64(), number.f64_extended()));
+  }
+  assert!(numbers[0].1.unwrap().is_nan());
+  assert_eq!(
+    &numbers[1 ..],
+    [
+      (None, Some(f64::INFINITY)),
+      (None, Some(f64::NEG_INFINITY)),
+      (Some(1.5), Some(1.5)),
+      (Some(0.0), Some(0.0))
+    ]
+  );
+
+  for serialization in ["[-NaN]", "[Inf]", "[NaNa]", "[Infinity1]", "[-]", "[N]"] {
+    assert!(invalid(serialization.as_bytes(), dialect));
+  }
+}
+
+#[test]
+fn signed_zero_and_overflowed_exponents() {
+  use crate::{ConstStack, Deserializer};
+
+  let serialization = b"[-0, -0.0e5, -1e-400, 0, 1e-99999, -1e-99999, 0e99999, -0.0e+99999, \
+    1e99999, 1e400, 1e32767, 1e32768]";
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+  let mut elements = deserializer.value().unwrap().iterate().unwrap();
+  let mut numbers = vec![];
+  while let Some(element) = elements.next() {
+    let number = element.unwrap().to_number().unwrap();
+    // The bits are compared so the sign of zero is checked
+    let bits = |float: Option<f64>| float.map(f64::to_bits);
+    assert_eq!(bits(number.f64()), bits(number.f64_precise()));
+    assert_eq!(bits(number.f64()), bits(number.try_f64().ok()));
+    numbers.push((number.exponent_overflowed(), number.try_f64().map(f64::to_bits)));
+  }
+  let zero = Ok(0.0f64.to_bits());
+  let negative_zero = Ok((-0.0f64).to_bits());
+  assert_eq!(
+    numbers,
+    [
+      (false, negative_zero),
+      (false, negative_zero),
+      (false, negative_zero),
+      (false, zero),
+      (true, zero),
+      (true, negative_zero),
+      (true, zero),
+      (true, negative_zero),
+      (true, Err(NotRepresentable::ExponentOverflow)),
+      (false, Err(NotRepresentable::OutOfRange)),
+      (false, Err(NotRepresentable::OutOfRange)),
+      (true, Err(NotRepresentable::ExponentOverflow)),
+    ]
+  );
+}
+
+#[test]
+fn radix_integers() {
+  use crate::{ConstStack, Deserializer};
+
+  let number = |serialization: &str, dialect| {
+    let mut deserializer =
+      Deserializer::<_, ConstStack<32>>::with_dialect(serialization.as_bytes(), dialect).unwrap();
+    let mut elements = deserializer.value().unwrap().iterate().unwrap();
+    elements.next().unwrap().and_then(crate::Value::to_number).ok().map(|number| number.i64())
+  };
+
+  let dialect = Dialect { radix_integers: true, ..Dialect::STRICT };
+  for (serialization, expected) in [
+    ("[0xFf]", Some(255)),
+    ("[-0x10]", Some(-16)),
+    ("[0o17]", Some(15)),
+    ("[0b101]", Some(5)),
+    ("[0x8000000000000000]", None),
+    ("[-0x8000000000000000]", Some(i64::MIN)),
+    ("[0]", Some(0)),
+    ("[0.5e1]", Some(5)),
+    ("[-0]", Some(0)),
+  ] {
+    assert_eq!(number(serialization, dialect).unwrap(), expected);
+    assert_eq!(number(serialization, Dialect::RELAXED).unwrap(), expected);
+  }
+  for serialization in
+    ["[0x]", "[0xg]", "[0o8]", "[0b2]", "[0X1]", "[0x1.5]", "[0x10000000000000000]"]
+  {
+    assert!(number(serialization, dialect).is_none());
+  }
+  assert!(number("[0x1]", Dialect::STRICT).is_none());
+}
+
+#[test]
+fn not_representable() {
+  use crate::{ConstStack, Deserializer};
+
+  let serialization = b"[1, 1.0, 1.5, 1e-1, 1e19, -1e19, 1e99999]";
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+  let mut elements = deserializer.value().unwrap().iterate().unwrap();
+  let mut results = vec![];
+  while let Some(element) = elements.next() {
+    let number = element.unwrap().to_number().unwrap();
+    assert_eq!(number.fits_i64(), number.try_i64().is_ok());
+    assert_eq!(number.has_fraction(), number.try_i64() == Err(NotRepresentable::LossyFraction));
+    results.push(number.try_i64());
+  }
+  assert_eq!(
+    results,
+    [
+      Ok(1),
+      Ok(1),
+      Err(NotRepresentable::LossyFraction),
+      Err(NotRepresentable::LossyFraction),
+      Err(NotRepresentable::OutOfRange),
+      Err(NotRepresentable::OutOfRange),
+      Err(NotRepresentable::OutOfRange)
+    ]
+  );
+}
+
+#[cfg(feature = "small-float")]
+#[test]
+fn small_float() {
+  let f64 = |str: &str| {
+    let mut sink = NumberSink::new();
+    write!(&mut sink, "{str}").unwrap();
+    sink.f64()
+  };
+
+  // Correctly rounded when the exponent is at most 22 in magnitude
+  for str in ["0", "-0.5", "0.1", "123.456", "1e22", "-987654321012345e-22", "4.35", "1e-7"] {
+    assert_eq!(f64(str).unwrap().to_bits(), f64::from_str(str).unwrap().to_bits(), "{str}");
+  }
+
+  // Otherwise, approximate
+  for str in ["1.7976931348623157e308", "2.2250738585072014e-308", "6.02214076e23", "1.5e-200"] {
+    let expected = f64::from_str(str).unwrap();
+    assert!(((f64(str).unwrap() - expected) / expected).abs() < 1e-14, "{str}");
+  }
+
+  assert_eq!(f64("1e309"), None);
+  assert_eq!(f64("-1e99999"), None);
+  assert_eq!(f64("1e-400").unwrap().to_bits(), 0.0f64.to_bits());
+}
+
+#[test]
+fn f64_precise() {
+  let sink = |str: &str| {
+    let mut sink = NumberSink::new();
+    write!(&mut sink, "{str}").unwrap();
+    sink
+  };
+
+  // The shortest representations which round-trip, as `f64::DIGITS` digits don't suffice for them
+  for str in [
+    "0.30000000000000004",
+    "1.7976931348623157e308",
+    "2.2250738585072014e-308",
+    "5e-324",
+    "-123456.78901234567",
+    "9007199254740993",
+    "0.1",
+  ] {
+    let expected = f64::from_str(str).unwrap();
+    assert_eq!(sink(str).f64_precise().unwrap().to_bits(), expected.to_bits(), "{str}");
+  }
+  assert_ne!(sink("0.30000000000000004").f64().unwrap().to_bits(), (0.1f64 + 0.2).to_bits());
+
+  assert_eq!(sink("-0.0").f64_precise().unwrap().to_bits(), (-0.0f64).to_bits());
+  assert_eq!(sink("0").f64_precise().unwrap().to_bits(), 0.0f64.to_bits());
+  assert_eq!(sink("1e309").f64_precise(), None);
+}
+
+#[test]
+fn magnitude() {
+  let number = |str: &str| {
+    let mut sink = NumberSink::new();
+    write!(&mut sink, "{str}").unwrap();
+    Number(Representation::Finite(sink))
+  };
+
+  for (str, expected) in [
+    ("0", Ok((false, 0))),
+    ("-0", Ok((false, 0))),
+    ("-1", Ok((true, 1))),
+    ("1.0e2", Ok((false, 100))),
+    ("18446744073709551615", Ok((false, u64::MAX))),
+    ("-18446744073709551615", Ok((true, u64::MAX))),
+    ("-9223372036854775808", Ok((true, 1 << 63))),
+    ("18446744073709551616", Err(NotRepresentable::OutOfRange)),
+    ("1844674407370955161.6", Err(NotRepresentable::LossyFraction)),
+  ] {
+    assert_eq!(number(str).try_magnitude(), expected, "{str}");
+    assert_eq!(number(str).magnitude(), expected.ok());
+  }
+
+  for (str, expected) in [
+    ("0", Some((false, 0, 0))),
+    ("-0.00e5", Some((false, 0, 0))),
+    ("-1.50", Some((true, 15, -1))),
+    ("1200", Some((false, 12, 2))),
+    ("184467440737095516150000000000", Some((false, u64::MAX, 10))),
+    ("0.001e-2", Some((false, 1, -5))),
+    ("123456789.0123456789", Some((false, 1234567890123456789, -10))),
+    ("12345678901.0123456789", None),
+    ("99999999999999999999", None),
+    ("1.8446744073709551615e28", Some((false, u64::MAX, 9))),
+  ] {
+    assert_eq!(number(str).decompose(), expected, "{str}");
+  }
+
+  for (str, expected) in [
+    ("0", Some((false, &b""[..], 0))),
+    ("-0.00e5", Some((false, &b""[..], 0))),
+    ("-1.50", Some((true, &b"15"[..], -1))),
+    ("1200", Some((false, &b"12"[..], 2))),
+    ("0.001e-2", Some((false, &b"1"[..], -5))),
+    ("99999999999999999999", Some((false, &b"99999999999999999999"[..], 0))),
+    ("1e400", Some((false, &b"1"[..], 400))),
+    ("1e99999", None),
+  ] {
+    assert_eq!(number(str).digits(), expected, "{str}");
+  }
+
+  assert_eq!(number("18446744073709551615").u64(), Some(u64::MAX));
+  assert_eq!(number("18446744073709551615").i64(), None);
+  assert_eq!(number("-1").u64(), None);
+  assert_eq!(number("-0").u64(), Some(0));
+}
@@ -0,0 +1,275 @@
+#[cfg(feature = "path")]
+use core::fmt;
+
+/// The kind of structure a segment of the path is within.
+#[cfg(feature = "path")]
+#[derive(Clone, Copy, Debug)]
+enum Structure {
+  Object,
+  Array,
+}
+
+/// A structure along the path.
+#[cfg(feature = "path")]
+#[derive(Clone, Copy, Debug)]
+struct Segment {
+  structure: Structure,
+  /// The amount of fields/elements advanced to within this structure.
+  count: usize,
+  /// The bytes of the key of the current field, if this is an object.
+  key: [u8; Path::KEY_CAPACITY],
+  key_len: usize,
+  key_truncated: bool,
+}
+
+/// The path to the current position within a JSON structure.
+///
+/// This records the keys of the fields, and the indexes of the elements, the deserializer has
+/// advanced into. The path is bounded, solely recording the outermost `Path::CAPACITY` segments and
+/// the first `Path::KEY_CAPACITY` bytes of each key, allowing it to be maintained without
+/// allocating. If the path was truncated, its `Display` implementation will end with `...`.
+///
+/// When an error is returned, the path will be the position where the error was encountered,
+/// allowing errors to be diagnosed (such as an error at `friends[3].age`).
+#[cfg(feature = "path")]
+#[derive(Clone, Debug)]
+pub struct Path {
+  segments: [Segment; Path::CAPACITY],
+  /// The depth of the path, which may exceed `Path::CAPACITY`.
+  depth: usize,
+}
+
+/// A segment within a `Path`.
+#[cfg(feature = "path")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PathSegment<'path> {
+  /// The key of a field within an object.
+  ///
+  /// This may be truncated to `Path::KEY_CAPACITY` bytes.
+  Key(&'path str),
+  /// The index of an element within an array.
+  Index(usize),
+}
+
+#[cfg(feature = "path")]
+impl Path {
+  /// The maximum depth of segments recorded.
+  pub const CAPACITY: usize = 8;
+  /// The maximum length of a key recorded, in bytes.
+  pub const KEY_CAPACITY: usize = 32;
+
+  pub(crate) fn new() -> Self {
+    Path {
+      segments: [Segment {
+        structure: Structure::Array,
+        count: 0,
+        key: [0; Path::KEY_CAPACITY],
+        key_len: 0,
+        key_truncated: false,
+      }; Path::CAPACITY],
+      depth: 0,
+    }
+  }
+
+  #[inline(always)]
+  fn current(&mut self) -> Option<&mut Segment> {
+    self.depth.checked_sub(1).and_then(|i| self.segments.get_mut(i))
+  }
+
+  #[inline(always)]
+  fn open(&mut self, structure: Structure) {
+    self.depth += 1;
+    if let Some(segment) = self.current() {
+      segment.structure = structure;
+      segment.count = 0;
+      segment.key_len = 0;
+      segment.key_truncated = false;
+    }
+  }
+
+  /// Record an object was opened.
+  #[inline(always)]
+  pub(crate) fn open_object(&mut self) {
+    self.open(Structure::Object);
+  }
+
+  /// Record an array was opened.
+  #[inline(always)]
+  pub(crate) fn open_array(&mut self) {
+    self.open(Structure::Array);
+  }
+
+  /// Record the current structure was closed.
+  #[inline(always)]
+  pub(crate) fn close(&mut self) {
+    self.depth = self.depth.saturating_sub(1);
+  }
+
+  /// Record a field, or an element, was advanced to within the current structure.
+  #[inline(always)]
+  pub(crate) fn advance(&mut self) {
+    if let Some(segment) = self.current() {
+      segment.count += 1;
+      segment.key_len = 0;
+      segment.key_truncated = false;
+    }
+  }
+
+  /// Record a character of the current field's key.
+  #[inline(always)]
+  pub(crate) fn key_char(&mut self, char: char) {
+    if let Some(segment) = self.current() {
+      let mut encoded = [0; 4];
+      let encoded = char.encode_utf8(&mut encoded).as_bytes();
+      match segment.key.get_mut(segment.key_len .. (segment.key_len + encoded.len())) {
+        Some(destination) if !segment.key_truncated => {
+          destination.copy_from_slice(encoded);
+          segment.key_len += encoded.len();
+        }
+        _ => segment.key_truncated = true,
+      }
+    }
+  }
+
+  /// If this path was truncated.
+  ///
+  /// This occurs if the path exceeded `Path::CAPACITY` segments, or a key within the path
+  /// exceeded `Path::KEY_CAPACITY` bytes.
+  pub fn truncated(&self) -> bool {
+    (self.depth > Path::CAPACITY) ||
+      self.segments[.. self.depth.min(Path::CAPACITY)]
+        .iter()
+        .any(|segment| matches!(segment.structure, Structure::Object) && segment.key_truncated)
+  }
+
+  /// The segments of this path, from the outermost to the innermost.
+  pub fn segments(&self) -> impl Iterator<Item = PathSegment<'_>> {
+    self.segments[.. self.depth.min(Path::CAPACITY)].iter().map_while(|segment| {
+      // If no field/element has been advanced to yet, the path ends
+      let index = segment.count.checked_sub(1)?;
+      Some(match segment.structure {
+        Structure::Object => PathSegment::Key(
+          core::str::from_utf8(segment.key.get(.. segment.key_len).unwrap_or(&[])).unwrap_or(""),
+        ),
+        Structure::Array => PathSegment::Index(index),
+      })
+    })
+  }
+}
+
+#[cfg(feature = "path")]
+impl Default for Path {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(feature = "path")]
+impl fmt::Display for Path {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (i, segment) in self.segments().enumerate() {
+      match segment {
+        PathSegment::Key(key) => {
+          if i != 0 {
+            f.write_str(".")?;
+          }
+          f.write_str(key)?;
+        }
+        PathSegment::Index(index) => write!(f, "[{index}]")?,
+      }
+    }
+    if self.truncated() {
+      f.write_str("...")?;
+    }
+    Ok(())
+  }
+}
+
+/// A no-op path, used when the `path` feature is disabled.
+#[cfg(not(feature = "path"))]
+pub(crate) struct Path;
+
+#[cfg(not(feature = "path"))]
+#[allow(clippy::unused_self)]
+impl Path {
+  #[inline(always)]
+  pub(crate) fn new() -> Self {
+    Path
+  }
+  #[inline(always)]
+  pub(crate) fn open_object(&mut self) {}
+  #[inline(always)]
+  pub(crate) fn open_array(&mut self) {}
+  #[inline(always)]
+  pub(crate) fn close(&mut self) {}
+  #[inline(always)]
+  pub(crate) fn advance(&mut self) {}
+  #[inline(always)]
+  pub(crate) fn key_char(&mut self, _char: char) {}
+}
+
+#[cfg(feature = "path")]
+#[test]
+fn path() {
+  use crate::{ConstStack, Deserializer, JsonError};
+
+  let serialization =
+    br#"{ "name": "a", "friends": [{ "age": 1 }, { "age": 2 }, { "age": "3" }] }"#;
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+  let error = (|| {
+    let mut fields = deserializer.value()?.fields()?;
+    while let Some(field) = fields.next() {
+      let mut field = field?;
+      if field.key()?.collect::<Result<String, _>>()? != "friends" {
+        continue;
+      }
+      let mut friends = field.value()?.iterate()?;
+      while let Some(friend) = friends.next() {
+        let mut friend = friend?.fields()?;
+        while let Some(field) = friend.next() {
+          // Don't read the key, so it's recorded as the deserializer advances past it
+          field?.value()?.to_number()?;
+        }
+      }
+    }
+    Ok::<_, JsonError<'_, &[u8], ConstStack<32>>>(())
+  })()
+  .unwrap_err();
+  assert!(matches!(error, JsonError::TypeError(_)));
+
+  let path = deserializer.path();
+  assert_eq!(
+    path.segments().collect::<Vec<_>>(),
+    [PathSegment::Key("friends"), PathSegment::Index(2), PathSegment::Key("age")],
+  );
+  assert!(!path.truncated());
+  assert_eq!(path.to_string(), "friends[2].age");
+
+  // Check truncation
+  fn descend<'read, R: crate::Read<'read>, S: crate::Stack>(
+    value: crate::Value<'read, '_, R, S>,
+  ) -> Result<(), JsonError<'read, R, S>> {
+    let mut elements = value.iterate()?;
+    while let Some(element) = elements.next() {
+      let element = element?;
+      if matches!(element.kind(), crate::Type::Array) {
+        descend(element)?;
+      } else {
+        element.to_number()?;
+      }
+    }
+    Ok(())
+  }
+  let serialization = br#"[[[[[[[[[[1, "a"]]]]]]]]]]"#;
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+  assert!(descend(deserializer.value().unwrap()).is_err());
+  assert!(deserializer.path().truncated());
+  assert_eq!(deserializer.path().to_string(), "[0][0][0][0][0][0][0][0]...");
+
+  let serialization = br#"{ "a key which is longer than the capacity for keys": true }"#;
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+  let mut fields = deserializer.value().unwrap().fields().unwrap();
+  assert!(fields.next().unwrap().unwrap().value().unwrap().to_number().is_err());
+  drop(fields);
+  assert_eq!(deserializer.path().to_string(), "a key which is longer than the c...");
+}
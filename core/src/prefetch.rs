@@ -0,0 +1,163 @@
+use std::{
+  vec,
+  vec::Vec,
+  io::ErrorKind,
+  sync::mpsc::{Receiver, SyncSender, sync_channel},
+  thread,
+};
+
+use crate::Read;
+
+/// An error when reading from a `PrefetchRead`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PrefetchError {
+  /// The underlying reader returned an error.
+  ReadError(ErrorKind),
+  /// The underlying reader ended, yet more bytes were requested.
+  Ended,
+}
+
+/// A chunk read by the background thread.
+type Chunk = Result<Vec<u8>, PrefetchError>;
+
+/// A reader which prefetches from a [`std::io::Read`] implementor on a background thread.
+///
+/// The underlying reader (such as a socket or file) is read from a dedicated thread, in chunks,
+/// with the next chunk read while the deserializer consumes the current one. This hides the
+/// latency of the underlying reader without changing how the deserializer is used, at the cost of
+/// a thread and up to five chunks of memory (the chunk being consumed, the chunk read ahead, the
+/// chunk being read into, and up to two consumed chunks awaiting reuse by the background thread).
+///
+/// When this is dropped, the background thread exits once its current read completes.
+#[derive(Debug)]
+pub struct PrefetchRead {
+  chunks: Receiver<Chunk>,
+  /// Chunks already consumed, returned to the background thread to be reused.
+  consumed: SyncSender<Vec<u8>>,
+  chunk: Vec<u8>,
+  position: usize,
+  /// The error which ended the stream of chunks, if one has.
+  error: Option<PrefetchError>,
+}
+
+impl PrefetchRead {
+  /// Create a new reader, prefetching chunks of up to `chunk_size` bytes from `reader`.
+  ///
+  /// This spawns the background thread, which immediately starts reading.
+  pub fn new<R: 'static + Send + std::io::Read>(mut reader: R, chunk_size: usize) -> Self {
+    let chunk_size = chunk_size.max(1);
+    let (send_chunk, chunks) = sync_channel::<Chunk>(1);
+    let (consumed, recv_consumed) = sync_channel::<Vec<u8>>(2);
+    thread::spawn(move || {
+      loop {
+        // Reuse a chunk already consumed, if one is available
+        let mut chunk = recv_consumed.try_recv().unwrap_or_else(|_| vec![0; chunk_size]);
+        chunk.resize(chunk_size, 0);
+        let chunk = loop {
+          match reader.read(&mut chunk) {
+            Ok(0) => break Err(PrefetchError::Ended),
+            Ok(len) => {
+              chunk.truncate(len);
+              break Ok(chunk);
+            }
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => break Err(PrefetchError::ReadError(e.kind())),
+          }
+        };
+        let last = chunk.is_err();
+        // If the `PrefetchRead` was dropped, or this was the last chunk, stop reading
+        if send_chunk.send(chunk).is_err() || last {
+          break;
+        }
+      }
+    });
+    PrefetchRead { chunks, consumed, chunk: Vec::new(), position: 0, error: None }
+  }
+
+  /// Advance to the next chunk, if the current chunk has been consumed.
+  fn next_chunk(&mut self) -> Result<(), PrefetchError> {
+    while self.position == self.chunk.len() {
+      if let Some(error) = self.error {
+        Err(error)?;
+      }
+      // The background thread solely exits without sending an error if this was dropped
+      let chunk = self.chunks.recv().unwrap_or(Err(PrefetchError::Ended));
+      match chunk {
+        Ok(chunk) => {
+          let consumed = core::mem::replace(&mut self.chunk, chunk);
+          // If the background thread already has chunks to reuse, drop this one
+          let _ = self.consumed.try_send(consumed);
+          self.position = 0;
+        }
+        Err(e) => self.error = Some(e),
+      }
+    }
+    Ok(())
+  }
+}
+
+impl Read<'_> for PrefetchRead {
+  type Error = PrefetchError;
+
+  #[inline(always)]
+  fn read_exact(&mut self, mut slice: &mut [u8]) -> Result<(), Self::Error> {
+    while !slice.is_empty() {
+      let len = self.read_into_buffer(slice)?;
+      slice = &mut slice[len ..];
+    }
+    Ok(())
+  }
+
+  #[inline(always)]
+  fn read_into_buffer(&mut self, buffer: &mut [u8]) -> Result<usize, Self::Error> {
+    self.next_chunk()?;
+    let len = buffer.len().min(self.chunk.len() - self.position);
+    buffer[.. len].copy_from_slice(&self.chunk[self.position .. (self.position + len)]);
+    self.position += len;
+    Ok(len)
+  }
+}
+
+#[test]
+fn prefetch() {
+  use std::string::String;
+  use crate::{ConstStack, Deserializer};
+
+  // A reader which yields a byte at a time, as a slow socket may
+  struct Trickle(Vec<u8>, usize);
+  impl std::io::Read for Trickle {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+      let Some(byte) = self.0.get(self.1) else { return Ok(0) };
+      buf[0] = *byte;
+      self.1 += 1;
+      Ok(1)
+    }
+  }
+
+  let mut serialization = String::from("[");
+  for i in 0 .. 1000 {
+    serialization.push_str(&std::format!("{i}, "));
+  }
+  serialization.push_str(r#""end"]"#);
+
+  for (reader, chunk_size) in [
+    (PrefetchRead::new(std::io::Cursor::new(serialization.clone().into_bytes()), 7), 7),
+    (PrefetchRead::new(Trickle(serialization.clone().into_bytes(), 0), 64), 64),
+  ] {
+    let mut deserializer = Deserializer::<_, ConstStack<1>>::new(reader).unwrap();
+    let mut elements = deserializer.value().unwrap().iterate().unwrap();
+    for i in 0 .. 1000 {
+      let number = elements.next().unwrap().unwrap().to_number().unwrap();
+      assert_eq!(number.i64(), Some(i), "{chunk_size}");
+    }
+    let end = elements.next().unwrap().unwrap().to_str().unwrap();
+    assert_eq!(end.collect::<Result<String, _>>().unwrap(), "end");
+    assert!(elements.next().is_none());
+  }
+
+  // Reading past the end of the underlying reader errors
+  let mut reader = PrefetchRead::new(std::io::Cursor::new(b"ab".to_vec()), 1);
+  let mut buf = [0; 3];
+  assert_eq!(reader.read_exact(&mut buf), Err(PrefetchError::Ended));
+  assert_eq!(reader.read_byte(), Err(PrefetchError::Ended));
+}
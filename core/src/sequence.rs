@@ -0,0 +1,322 @@
+use crate::{Read, Stack, JsonError, Deserializer};
+
+/// The record separator prefixing each document within a JSON text sequence.
+const RECORD_SEPARATOR: u8 = 0x1E;
+
+/// How the documents within a stream are delimited.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Framing {
+  /// Documents are concatenated, optionally separated by whitespace (as with newline-delimited
+  /// JSON).
+  Concatenated,
+  /// Documents are prefixed with a record separator (`0x1E`), per
+  /// [RFC 7464](https://datatracker.ietf.org/doc/html/rfc7464).
+  RecordSeparated,
+}
+
+/// An error when reading from a `Document`.
+#[derive(Clone, Copy, Debug)]
+pub enum DocumentError<E: Copy + core::fmt::Debug> {
+  /// An error from the underlying reader.
+  ReadError(E),
+  /// The document ended, yet more bytes were requested.
+  Ended,
+}
+
+/// A scanner for the extent of a document.
+///
+/// This solely tracks the nesting of structures (outside of strings) to find where the document
+/// ends, without validating it. Validation is left to the deserializer.
+#[derive(Clone, Copy, Default, Debug)]
+struct Extent {
+  depth: usize,
+  opened: bool,
+  in_string: bool,
+  escaped: bool,
+}
+
+impl Extent {
+  #[inline(always)]
+  fn update(&mut self, byte: u8) {
+    if self.in_string {
+      if self.escaped {
+        self.escaped = false;
+      } else if byte == b'\\' {
+        self.escaped = true;
+      } else if byte == b'"' {
+        self.in_string = false;
+      }
+      return;
+    }
+    match byte {
+      b'"' => self.in_string = true,
+      b'{' | b'[' => {
+        self.depth = self.depth.saturating_add(1);
+        self.opened = true;
+      }
+      b'}' | b']' => self.depth = self.depth.saturating_sub(1),
+      _ => {}
+    }
+  }
+
+  /// If the document's structure has been closed.
+  #[inline(always)]
+  fn closed(&self) -> bool {
+    self.opened && (self.depth == 0)
+  }
+}
+
+/// A stream of JSON documents.
+///
+/// This yields a `Deserializer` for each document within the stream, reading no further than the
+/// end of the document. This allows deserializing a stream of documents (such as a log) without
+/// knowing the length of each document in advance.
+///
+/// If a document isn't read to its end (such as due to being malformed), the rest of it is
+/// skipped when the next document is requested. With `Framing::RecordSeparated`, this skips to the
+/// next record separator. With `Framing::Concatenated`, which lacks any explicit delimiter, this
+/// skips to the next line feed. Any bytes before the first record separator, or after a document
+/// yet before the next record separator, are ignored. A document's `Deserializer` should have
+/// `Deserializer::buffered` called before it's dropped, completing any pending work, so that a
+/// document whose values were dropped is still read to its end.
+///
+/// As `Read` has no notion of the end of the underlying reader, an error from the underlying
+/// reader while looking for the next document is considered the end of the stream.
+#[derive(Debug)]
+pub struct Documents<'read, R: Read<'read>> {
+  reader: R,
+  framing: Framing,
+  /// The bytes lent by the reader which have yet to be consumed.
+  lent: &'read [u8],
+  /// A byte read from the reader which has yet to be consumed.
+  peeked: Option<u8>,
+  /// The extent of the current document.
+  extent: Extent,
+  /// If a document has been yielded.
+  yielded: bool,
+}
+
+impl<'read, R: Read<'read>> Documents<'read, R> {
+  /// Create a new stream of documents with the specified framing.
+  pub fn new(reader: R, framing: Framing) -> Self {
+    Documents {
+      reader,
+      framing,
+      lent: &[],
+      peeked: None,
+      extent: Extent::default(),
+      yielded: false,
+    }
+  }
+
+  /// Consume this, returning the underlying reader.
+  ///
+  /// Any bytes lent by, or read from, the underlying reader which have yet to be consumed will be
+  /// lost.
+  pub fn into_inner(self) -> R {
+    self.reader
+  }
+
+  /// Peek at the next byte, without consuming it.
+  #[inline(always)]
+  fn peek(&mut self) -> Result<u8, R::Error> {
+    if let Some(byte) = self.lent.first() {
+      return Ok(*byte);
+    }
+    if let Some(byte) = self.peeked {
+      return Ok(byte);
+    }
+    if let Some(lent) = self.reader.lend() &&
+      let Some(byte) = lent.first()
+    {
+      self.lent = lent;
+      return Ok(*byte);
+    }
+    let byte = self.reader.read_byte()?;
+    self.peeked = Some(byte);
+    Ok(byte)
+  }
+
+  /// Consume the byte which was peeked at.
+  #[inline(always)]
+  fn consume(&mut self) {
+    if self.lent.is_empty() {
+      self.peeked = None;
+    } else {
+      self.lent = &self.lent[1 ..];
+    }
+  }
+
+  /// Advance to the start of the next document.
+  fn advance(&mut self) -> Result<(), R::Error> {
+    // Skip the rest of the prior document, if it wasn't read to its end
+    if self.yielded && (!self.extent.closed()) && (self.framing == Framing::Concatenated) {
+      while self.peek()? != b'\n' {
+        self.consume();
+      }
+    }
+
+    match self.framing {
+      Framing::Concatenated => {
+        while matches!(self.peek()?, b'\x20' | b'\x09' | b'\x0A' | b'\x0D') {
+          self.consume();
+        }
+      }
+      Framing::RecordSeparated => loop {
+        while self.peek()? != RECORD_SEPARATOR {
+          self.consume();
+        }
+        self.consume();
+        while matches!(self.peek()?, b'\x20' | b'\x09' | b'\x0A' | b'\x0D') {
+          self.consume();
+        }
+        // Skip empty records
+        if self.peek()? != RECORD_SEPARATOR {
+          break;
+        }
+      },
+    }
+    Ok(())
+  }
+
+  /// The next document within the stream.
+  ///
+  /// This is approximate to `Iterator::next` yet each item maintains a mutable reference to the
+  /// stream. Accordingly, we cannot use `Iterator::next` which requires items not borrow from the
+  /// iterator.
+  #[allow(clippy::type_complexity, clippy::should_implement_trait)]
+  pub fn next<S: Stack>(
+    &mut self,
+  ) -> Option<
+    Result<
+      Deserializer<'read, Document<'read, '_, R>, S>,
+      JsonError<'read, Document<'read, '_, R>, S>,
+    >,
+  > {
+    self.advance().ok()?;
+    self.extent = Extent::default();
+    self.yielded = true;
+    Some(Deserializer::new(Document { documents: self }))
+  }
+}
+
+/// A document within a stream of documents.
+///
+/// This reads from the underlying reader until the end of the document.
+#[derive(Debug)]
+pub struct Document<'read, 'documents, R: Read<'read>> {
+  documents: &'documents mut Documents<'read, R>,
+}
+
+impl<'read, R: Read<'read>> Read<'read> for Document<'read, '_, R> {
+  type Error = DocumentError<R::Error>;
+
+  #[inline(always)]
+  fn read_byte(&mut self) -> Result<u8, Self::Error> {
+    let documents = &mut self.documents;
+    if documents.extent.closed() {
+      Err(DocumentError::Ended)?;
+    }
+    let byte = documents.peek().map_err(DocumentError::ReadError)?;
+    if (documents.framing == Framing::RecordSeparated) && (byte == RECORD_SEPARATOR) {
+      Err(DocumentError::Ended)?;
+    }
+    documents.consume();
+    documents.extent.update(byte);
+    Ok(byte)
+  }
+
+  #[inline(always)]
+  fn read_exact(&mut self, slice: &mut [u8]) -> Result<(), Self::Error> {
+    for byte in slice {
+      *byte = self.read_byte()?;
+    }
+    Ok(())
+  }
+
+  #[inline(always)]
+  fn lend(&mut self) -> Option<&'read [u8]> {
+    let documents = &mut self.documents;
+    if documents.extent.closed() || documents.peeked.is_some() {
+      None?;
+    }
+    if documents.lent.is_empty() {
+      documents.lent = documents.reader.lend()?;
+    }
+
+    /*
+      Lend the bytes up to the end of the document. Bytes lent are lost if the deserializer errors,
+      so we don't lend past the point we'd resynchronize at: the next record separator, or the next
+      line feed for concatenated documents.
+    */
+    let mut len = 0;
+    while let Some(byte) = documents.lent.get(len) {
+      match documents.framing {
+        Framing::Concatenated if (*byte == b'\n') && (len != 0) => break,
+        Framing::RecordSeparated if *byte == RECORD_SEPARATOR => break,
+        _ => {}
+      }
+      documents.extent.update(*byte);
+      len += 1;
+      if documents.extent.closed() {
+        break;
+      }
+    }
+    let (lent, rest) = documents.lent.split_at(len);
+    documents.lent = rest;
+    (!lent.is_empty()).then_some(lent)
+  }
+}
+
+#[test]
+fn sequence() {
+  use crate::{ConstStack, io::SliceError};
+
+  // A reader which reads one byte at a time
+  #[derive(Debug)]
+  struct Bytes<'read>(&'read [u8]);
+  impl<'read> Read<'read> for Bytes<'read> {
+    type Error = SliceError;
+    fn read_exact(&mut self, slice: &mut [u8]) -> Result<(), Self::Error> {
+      self.0.read_exact(slice)
+    }
+  }
+
+  fn check<'read, R: Read<'read> + core::fmt::Debug>(
+    mut documents: Documents<'read, R>,
+    expected: &[Option<i64>],
+  ) {
+    for expected in expected {
+      let mut deserializer = documents.next::<ConstStack<32>>().unwrap().unwrap();
+      let a = (|| {
+        let mut fields = deserializer.value()?.fields()?;
+        let mut a = None;
+        while let Some(field) = fields.next() {
+          let mut field = field?;
+          let key = field.key()?.collect::<Result<String, _>>()?;
+          let value = field.value()?;
+          if key == "a" {
+            a = value.to_number()?.i64();
+          }
+        }
+        Ok::<_, JsonError<_, _>>(a)
+      })();
+      assert_eq!(a.ok().flatten(), *expected);
+    }
+    assert!(documents.next::<ConstStack<32>>().is_none());
+  }
+
+  // The malformed documents are skipped until the next line
+  let concatenated = b"{\"a\":1}{\"a\":2,\"b\":\"\\\"}\"} {\"a\":3}\n\
+    {\"a\": [} {\"a\":0}\n{\"a\": 1 2}\n  {\"a\":4}\n";
+  let expected = [Some(1), Some(2), Some(3), None, None, Some(4)];
+  check(Documents::new(&concatenated[..], Framing::Concatenated), &expected);
+  check(Documents::new(Bytes(concatenated), Framing::Concatenated), &expected);
+
+  // The malformed records are skipped until the next record separator
+  let records =
+    b"junk\x1E{\"a\":1}\n\x1E{\"a\":\x1E\x1E\n\x1E{\"a\":\"}\"} junk\n\x1E [1]\n\x1E{\"a\":4}\n";
+  let expected = [Some(1), None, None, None, Some(4)];
+  check(Documents::new(&records[..], Framing::RecordSeparated), &expected);
+  check(Documents::new(Bytes(records), Framing::RecordSeparated), &expected);
+}
@@ -2,10 +2,12 @@ use crate::{State, Stack};
 
 /// An array of `State`, using `u2` for each value.
 #[derive(Debug)]
-struct PackedStates<const ONE_FOURTH_OF_MAX_DEPTH: usize>([u8; ONE_FOURTH_OF_MAX_DEPTH]);
+pub(super) struct PackedStates<const ONE_FOURTH_OF_MAX_DEPTH: usize>(
+  pub(super) [u8; ONE_FOURTH_OF_MAX_DEPTH],
+);
 impl<const ONE_FOURTH_OF_MAX_DEPTH: usize> PackedStates<ONE_FOURTH_OF_MAX_DEPTH> {
   #[inline(always)]
-  fn get(&self, i: usize) -> State {
+  pub(super) fn get(&self, i: usize) -> State {
     let mut entry = self.0[i >> 2];
     entry >>= (i & 0b11) * 2;
     entry &= 0b11;
@@ -19,7 +21,7 @@ impl<const ONE_FOURTH_OF_MAX_DEPTH: usize> PackedStates<ONE_FOURTH_OF_MAX_DEPTH>
   }
 
   #[inline(always)]
-  fn set(&mut self, i: usize, kind: State) {
+  pub(super) fn set(&mut self, i: usize, kind: State) {
     let two_bits = match kind {
       State::Object => 0,
       State::Array => 1,
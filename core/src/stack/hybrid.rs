@@ -0,0 +1,111 @@
+use crate::{State, Stack};
+use super::r#const::{PackedStates, StackError};
+
+/// The amount of states held within the register.
+const REGISTER_DEPTH: usize = 16;
+
+#[inline(always)]
+fn to_bits(state: State) -> u32 {
+  match state {
+    State::Object => 0,
+    State::Array => 1,
+    State::Unknown => 2,
+  }
+}
+
+#[inline(always)]
+fn from_bits(bits: u32) -> State {
+  match bits & 0b11 {
+    0 => State::Object,
+    1 => State::Array,
+    2 => State::Unknown,
+    3 => panic!("`HybridStack` was written to with a non-existent `State`"),
+    _ => unreachable!("masked by 0b11"),
+  }
+}
+
+/// A non-allocating `Stack` which keeps the top of the stack within a register.
+///
+/// The top 16 states are kept within a `u32`, spilling to an array (as used by `ConstStack`) only
+/// for states below them. As most documents are nested fewer than 16 levels deep, this lets
+/// peeking, pushing, and popping avoid indexing into the array. The maximum depth is the same as
+/// `ConstStack`'s for the same constant parameter.
+#[derive(Debug)]
+pub struct HybridStack<const ONE_FOURTH_OF_MAX_DEPTH: usize> {
+  /// The top states on the stack, with the top state in the least-significant bits.
+  register: u32,
+  /// The states spilled from the register.
+  ///
+  /// The state at depth `i` is spilled to `i - REGISTER_DEPTH`.
+  spilled: PackedStates<{ ONE_FOURTH_OF_MAX_DEPTH }>,
+  /// The current depth of the stack.
+  depth: usize,
+}
+
+impl<const ONE_FOURTH_OF_MAX_DEPTH: usize> Stack for HybridStack<ONE_FOURTH_OF_MAX_DEPTH> {
+  type Error = StackError;
+
+  #[inline(always)]
+  fn empty() -> Self {
+    Self { register: 0, spilled: PackedStates([0; ONE_FOURTH_OF_MAX_DEPTH]), depth: 0 }
+  }
+
+  #[inline(always)]
+  fn depth(&self) -> usize {
+    self.depth
+  }
+
+  #[inline(always)]
+  fn peek(&self) -> Option<State> {
+    (self.depth != 0).then(|| from_bits(self.register))
+  }
+
+  #[inline(always)]
+  fn pop(&mut self) -> Option<State> {
+    self.depth = self.depth.checked_sub(1)?;
+    let state = from_bits(self.register);
+    self.register >>= 2;
+    // Restore the state which was spilled, now that there's room for it within the register
+    if self.depth >= REGISTER_DEPTH {
+      self.register |= to_bits(self.spilled.get(self.depth - REGISTER_DEPTH)) << 30;
+    }
+    Some(state)
+  }
+
+  #[inline(always)]
+  fn push(&mut self, state: State) -> Result<(), StackError> {
+    if self.depth == (4 * ONE_FOURTH_OF_MAX_DEPTH) {
+      Err(StackError::StackTooDeep)?;
+    }
+    // Spill the bottom state within the register, if the register is full
+    if self.depth >= REGISTER_DEPTH {
+      self.spilled.set(self.depth - REGISTER_DEPTH, from_bits(self.register >> 30));
+    }
+    self.register = (self.register << 2) | to_bits(state);
+    self.depth += 1;
+    Ok(())
+  }
+}
+
+#[test]
+fn hybrid_stack() {
+  use crate::ConstStack;
+
+  // Compare against `ConstStack` for a variety of pushes and pops
+  let mut hybrid = HybridStack::<16>::empty();
+  let mut r#const = ConstStack::<16>::empty();
+  let mut seed = 0x243f_6a88_85a3_08d3u64;
+  for _ in 0 .. 10_000 {
+    seed ^= seed << 13;
+    seed ^= seed >> 7;
+    seed ^= seed << 17;
+    if (seed % 5) < 3 {
+      let state = [State::Object, State::Array, State::Unknown][usize::try_from(seed % 3).unwrap()];
+      assert_eq!(hybrid.push(state).is_ok(), r#const.push(state).is_ok());
+    } else {
+      assert_eq!(hybrid.pop().map(to_bits), r#const.pop().map(to_bits));
+    }
+    assert_eq!(hybrid.depth(), r#const.depth());
+    assert_eq!(hybrid.peek().map(to_bits), r#const.peek().map(to_bits));
+  }
+}
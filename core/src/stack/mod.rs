@@ -2,6 +2,14 @@ use core::fmt::Debug;
 
 mod r#const;
 pub use r#const::ConstStack;
+mod hybrid;
+pub use hybrid::HybridStack;
+
+/// A `Stack` with a depth limit of `128`, for use cases without particular requirements.
+///
+/// Using a single type of stack throughout a project (alongside `ErasedRead`) allows the
+/// deserializer to solely be instantiated once.
+pub type DefaultStack = ConstStack<32>;
 
 /// An item within the stack, representing the state during deserialization.
 #[derive(Clone, Copy, Debug)]
@@ -0,0 +1,117 @@
+use crate::*;
+
+/// Statistics on a value, as gathered by `Value::advance_to_end`.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct Statistics {
+  /// The maximum depth of nested structures, where a scalar has depth `0` and a structure
+  /// containing solely scalars has depth `1`.
+  pub max_depth: usize,
+  /// The amount of objects.
+  pub objects: u64,
+  /// The amount of arrays.
+  pub arrays: u64,
+  /// The amount of strings, not including keys.
+  pub strings: u64,
+  /// The amount of numbers.
+  pub numbers: u64,
+  /// The amount of booleans.
+  pub bools: u64,
+  /// The amount of `null`s.
+  pub nulls: u64,
+  /// The amount of bytes consumed from the reader while advancing past the value.
+  ///
+  /// This includes any whitespace, and the delimiter, following the value.
+  pub bytes: usize,
+}
+
+impl<'read, R: Read<'read>, S: Stack> Value<'read, '_, R, S> {
+  /// Advance past this value, returning statistics on it.
+  ///
+  /// This reads the entire value (validating it) without yielding any of its contents, allowing
+  /// unknown payloads to be profiled. The values within are counted, including the value itself.
+  pub fn advance_to_end(mut self) -> Result<Statistics, JsonError<'read, R, S>> {
+    // Take the deserializer so this value's `Drop` implementation doesn't queue dropping it
+    let deserializer = self.deserializer.take().ok_or(JsonError::InternalError)?;
+    let start = deserializer.bytes_consumed();
+
+    let mut statistics = Statistics::default();
+    let mut depth = 0;
+    loop {
+      let result = deserializer.single_step()?;
+      match result {
+        SingleStepResult::Object(SingleStepObjectResult::Field) => {
+          StringKey::drop_string_key(deserializer, false)
+            .inspect_err(|e| deserializer.poison(*e))?;
+          continue;
+        }
+        SingleStepResult::Array(SingleStepArrayResult::Value) => continue,
+        SingleStepResult::Unknown(SingleStepUnknownResult::ObjectOpened) => {
+          statistics.objects += 1;
+          depth += 1;
+          statistics.max_depth = statistics.max_depth.max(depth);
+          continue;
+        }
+        SingleStepResult::Unknown(SingleStepUnknownResult::ArrayOpened) => {
+          statistics.arrays += 1;
+          depth += 1;
+          statistics.max_depth = statistics.max_depth.max(depth);
+          continue;
+        }
+        SingleStepResult::Object(SingleStepObjectResult::Closed) |
+        SingleStepResult::Array(SingleStepArrayResult::Closed) => depth -= 1,
+        SingleStepResult::Unknown(SingleStepUnknownResult::String) => {
+          statistics.strings += 1;
+          StringValue::drop_string_value(deserializer, false)
+            .inspect_err(|e| deserializer.poison(*e))?;
+        }
+        SingleStepResult::Unknown(SingleStepUnknownResult::Number(_)) => statistics.numbers += 1,
+        SingleStepResult::Unknown(SingleStepUnknownResult::Bool(_)) => statistics.bools += 1,
+        SingleStepResult::Unknown(SingleStepUnknownResult::Null) => statistics.nulls += 1,
+      }
+      // If we've advanced past this value, return
+      if depth == 0 {
+        break;
+      }
+    }
+
+    statistics.bytes = deserializer.bytes_consumed() - start;
+    Ok(statistics)
+  }
+}
+
+#[test]
+fn statistics() {
+  let serialization = br#"{ "a": [1, "b", null, { "c": true }], "d": {}, "e": "f" } "#;
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+  let statistics = deserializer.value().unwrap().advance_to_end().unwrap();
+  assert_eq!(
+    statistics,
+    Statistics {
+      max_depth: 3,
+      objects: 3,
+      arrays: 1,
+      strings: 2,
+      numbers: 1,
+      bools: 1,
+      nulls: 1,
+      bytes: serialization.len() - 1,
+    }
+  );
+  assert_eq!(deserializer.buffered().unwrap(), b" ");
+
+  // Values within a structure may be individually profiled
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+  let mut fields = deserializer.value().unwrap().fields().unwrap();
+  let mut field = fields.next().unwrap().unwrap();
+  assert!(field.key_matches("a").unwrap());
+  let statistics = field.value().unwrap().advance_to_end().unwrap();
+  assert_eq!((statistics.max_depth, statistics.objects, statistics.arrays), (2, 1, 1));
+  assert_eq!(statistics.bytes, r#"[1, "b", null, { "c": true }], "#.len());
+  let mut field = fields.next().unwrap().unwrap();
+  assert!(field.key_matches("d").unwrap());
+  assert_eq!(field.value().unwrap().advance_to_end().unwrap().objects, 1);
+
+  // Invalid values error
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&br#"[1, [2, "\x"]]"#[..]).unwrap();
+  assert!(deserializer.value().unwrap().advance_to_end().is_err());
+}
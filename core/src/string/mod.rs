@@ -1,4 +1,6 @@
-use crate::{Read, Stack, Deserializer, JsonError};
+use crate::{
+  Read, PeekableRead, Stack, ValidationProfile, Deserializer, SyntaxError, IoError, JsonError,
+};
 
 mod unicode;
 mod hex;
@@ -6,6 +8,29 @@ mod hex;
 use unicode::*;
 use hex::*;
 
+/// Read past the rest of a string, without validating it.
+///
+/// This solely finds the closing quote, skipping the byte after each escaping character. If `end`
+/// is specified, this errors once the string extends past it.
+#[inline(always)]
+pub(crate) fn skip_unvalidated<'read, R: Read<'read>, S: Stack>(
+  reader: &mut PeekableRead<'read, R>,
+  end: Option<usize>,
+) -> Result<(), JsonError<'read, R, S>> {
+  loop {
+    if end.is_some_and(|end| reader.consumed() > end) {
+      Err(JsonError::LengthLimitExceeded)?;
+    }
+    match reader.read_byte().map_err(IoError)? {
+      b'\\' => {
+        reader.read_byte().map_err(IoError)?;
+      }
+      b'"' => return Ok(()),
+      _ => {}
+    }
+  }
+}
+
 /// An iterator which validates a string, yielding the items within.
 ///
 /// This will yield `None` upon reaching a `"`, but is not fused and has undefined behavior upon
@@ -21,7 +46,11 @@ pub(crate) struct ValidateString<'read, 'parent, R: Read<'read>, S: Stack> {
 impl<'read, 'parent, R: Read<'read>, S: Stack> ValidateString<'read, 'parent, R, S> {
   #[inline(always)]
   fn next_char(&mut self) -> Result<Option<StringCharacter>, JsonError<'read, R, S>> {
-    let this = self.deserializer.reader.read_byte().map_err(JsonError::ReadError)?;
+    // If this is a key, check it hasn't exceeded its length limit
+    if self.deserializer.key_end.is_some_and(|end| self.deserializer.reader.consumed() > end) {
+      Err(JsonError::LengthLimitExceeded)?;
+    }
+    let this = self.deserializer.reader.read_byte().map_err(IoError)?;
 
     // https://datatracker.ietf.org/doc/html/rfc8259#section-7
     Ok(match this {
@@ -36,7 +65,8 @@ impl<'read, 'parent, R: Read<'read>, S: Stack> ValidateString<'read, 'parent, R,
       b'\\' => {
         // All characters which are valid to be escaped are ASCII, allowing us to use `read_byte`
         // here
-        let escaped = self.deserializer.reader.read_byte().map_err(JsonError::ReadError)?;
+        let escaped = self.deserializer.reader.read_byte().map_err(IoError)?;
+        self.deserializer.reader.counters.escape();
         match escaped {
           b'"' | b'\\' | b'/' => Some(StringCharacter::Character(escaped as char)),
           b'b' => Some(StringCharacter::Character('\x08')),
@@ -48,29 +78,39 @@ impl<'read, 'parent, R: Read<'read>, S: Stack> ValidateString<'read, 'parent, R,
           b'\x75' => {
             // We can use `read_byte` here as valid hex characters will be ASCII (one-byte)
             let bytes = [
-              self.deserializer.reader.read_byte().map_err(JsonError::ReadError)?,
-              self.deserializer.reader.read_byte().map_err(JsonError::ReadError)?,
-              self.deserializer.reader.read_byte().map_err(JsonError::ReadError)?,
-              self.deserializer.reader.read_byte().map_err(JsonError::ReadError)?,
+              self.deserializer.reader.read_byte().map_err(IoError)?,
+              self.deserializer.reader.read_byte().map_err(IoError)?,
+              self.deserializer.reader.read_byte().map_err(IoError)?,
+              self.deserializer.reader.read_byte().map_err(IoError)?,
             ];
             if !validate_hex(bytes) {
-              Err(JsonError::InvalidValue)?;
+              Err(SyntaxError::InvalidValue)?;
             }
             Some(StringCharacter::EscapedUnicode(bytes))
           }
-          _ => Err(JsonError::InvalidValue)?,
+          _ => Err(SyntaxError::InvalidValue)?,
         }
       }
       b'"' => {
         self.done = true;
         None
       }
-      _ => Err(JsonError::InvalidValue)?,
+      // Control characters, which are solely allowed to be unescaped by the dialect
+      b'\x00' ..= b'\x1f' if self.deserializer.dialect.control_characters => {
+        Some(StringCharacter::Character(char::from(this)))
+      }
+      _ => Err(SyntaxError::InvalidValue)?,
     })
   }
 
   #[inline(always)]
   fn drop(&mut self) -> Result<(), JsonError<'read, R, S>> {
+    if (!self.done) &&
+      (self.deserializer.dialect.skipped_values != ValidationProfile::FullValidation)
+    {
+      skip_unvalidated(&mut self.deserializer.reader, self.deserializer.key_end)?;
+      self.done = true;
+    }
     while !self.done {
       self.next_char()?;
     }
@@ -79,6 +119,7 @@ impl<'read, 'parent, R: Read<'read>, S: Stack> ValidateString<'read, 'parent, R,
 }
 
 /// A character within a JSON-serialized string.
+#[derive(Clone, Copy)]
 pub(crate) enum StringCharacter {
   /// The character itself.
   Character(char),
@@ -106,22 +147,33 @@ impl<'read, 'parent, R: Read<'read>, S: Stack> Iterator for ValidateString<'read
 pub(crate) struct String<'read, 'parent, R: Read<'read>, S: Stack> {
   validation: ValidateString<'read, 'parent, R, S>,
   errored: bool,
+  /// A character read after an unpaired surrogate, which has yet to be yielded.
+  pending: Option<StringCharacter>,
 }
 
 impl<'read, 'parent, R: Read<'read>, S: Stack> String<'read, 'parent, R, S> {
   /// Read a just-opened string from a JSON serialization.
   #[inline(always)]
   pub(crate) fn read(deserializer: &'parent mut Deserializer<'read, R, S>) -> Self {
-    String { validation: ValidateString { deserializer, done: false }, errored: false }
+    String {
+      validation: ValidateString { deserializer, done: false },
+      errored: false,
+      pending: None,
+    }
   }
 }
 
+/// The codepoint of `char::REPLACEMENT_CHARACTER`.
+const REPLACEMENT_CHARACTER: u32 = 0xfffd;
+
 #[inline(always)]
 fn handle_escaped_unicode<'read, 'parent, R: Read<'read>, S: Stack>(
   hex: [u8; 4],
   validation: &mut ValidateString<'read, 'parent, R, S>,
+  pending: &mut Option<StringCharacter>,
 ) -> Result<char, JsonError<'read, R, S>> {
   let next = read_hex(hex)?;
+  let lossy = validation.deserializer.dialect.lone_surrogates;
 
   /*
     If the intended value of this codepoint exceeds 0xffff, it's specified to be encoded
@@ -150,41 +202,72 @@ fn handle_escaped_unicode<'read, 'parent, R: Read<'read>, S: Stack>(
       codepoints within their strings. We just won't support converting them to
       characters with this iterator. This iterator failing will not cause the
       deserializer as a whole to fail.
-    */
-    let Some(Ok(StringCharacter::EscapedUnicode(hex))) = validation.next() else {
-      Err(JsonError::NotUtf8)?
-    };
-    let low = read_hex(hex)?;
 
-    let Some(low) = low.checked_sub(0xdc00) else { Err(JsonError::NotUtf8)? };
-    high + low + 0x10000
+      If the dialect decodes unpaired surrogates lossily, the high surrogate is instead decoded as
+      the replacement character, with the character after it yielded next.
+    */
+    match validation.next() {
+      Some(Ok(StringCharacter::EscapedUnicode(hex))) => {
+        let low = read_hex(hex)?;
+        match low.checked_sub(0xdc00).filter(|low| *low < 0x400) {
+          Some(low) => high + low + 0x10000,
+          None if lossy => {
+            *pending = Some(StringCharacter::EscapedUnicode(hex));
+            REPLACEMENT_CHARACTER
+          }
+          None => Err(JsonError::NotUtf8)?,
+        }
+      }
+      Some(Ok(character @ StringCharacter::Character(_))) if lossy => {
+        *pending = Some(character);
+        REPLACEMENT_CHARACTER
+      }
+      // The string ended after the high surrogate
+      None if lossy => REPLACEMENT_CHARACTER,
+      _ => Err(JsonError::NotUtf8)?,
+    }
   } else {
     // If `next` isn't a surrogate, it's interpreted as a codepoint as-is
     next
   };
 
-  // Yield the codepoint
-  char::from_u32(codepoint).ok_or(JsonError::NotUtf8)
+  // Yield the codepoint, which will only be invalid if it's an unpaired low surrogate
+  match char::from_u32(codepoint) {
+    Some(char) => Ok(char),
+    None if lossy => Ok(char::REPLACEMENT_CHARACTER),
+    None => Err(JsonError::NotUtf8),
+  }
 }
 
 impl<'read, 'parent, R: Read<'read>, S: Stack> Iterator for String<'read, 'parent, R, S> {
   type Item = Result<char, JsonError<'read, R, S>>;
   #[inline(always)]
   fn next(&mut self) -> Option<Self::Item> {
-    if self.errored | self.validation.done {
+    if self.errored {
       None?;
     }
 
-    Some(match self.validation.next()? {
-      Ok(StringCharacter::Character(char)) => Ok(char),
-      Ok(StringCharacter::EscapedUnicode(hex)) => {
-        let res = handle_escaped_unicode(hex, &mut self.validation);
+    let next = match self.pending.take() {
+      Some(next) => next,
+      None => {
+        if self.validation.done {
+          None?;
+        }
+        match self.validation.next()? {
+          Ok(next) => next,
+          Err(e) => return Some(Err(e)),
+        }
+      }
+    };
+    Some(match next {
+      StringCharacter::Character(char) => Ok(char),
+      StringCharacter::EscapedUnicode(hex) => {
+        let res = handle_escaped_unicode(hex, &mut self.validation, &mut self.pending);
         if res.is_err() {
           self.errored = true;
         }
         res
       }
-      Err(e) => Err(e),
     })
   }
 }
@@ -197,13 +280,37 @@ pub(crate) struct StringKey<'read, 'parent, R: Read<'read>, S: Stack>(
   pub(crate) String<'read, 'parent, R, S>,
 );
 impl<'read, 'parent, R: Read<'read>, S: Stack> StringKey<'read, 'parent, R, S> {
+  /// Read a just-opened key from a JSON serialization.
+  #[inline(always)]
+  pub(crate) fn read(deserializer: &'parent mut Deserializer<'read, R, S>) -> Self {
+    deserializer.key_end = deserializer
+      .dialect
+      .key_length_limit
+      .map(|limit| deserializer.reader.consumed().saturating_add(limit));
+    StringKey(String::read(deserializer))
+  }
+
   #[inline(always)]
   pub(crate) fn drop_string_key(
     deserializer: &mut Deserializer<'read, R, S>,
     done: bool,
   ) -> Result<(), JsonError<'read, R, S>> {
-    (ValidateString { deserializer, done }).drop()?;
-    crate::advance_past_colon(&mut deserializer.reader)
+    let mut key =
+      String { validation: ValidateString { deserializer, done }, errored: false, pending: None };
+    // Record the rest of the key within the path
+    #[cfg(feature = "path")]
+    while let Some(char) = key.next() {
+      match char {
+        Ok(char) => key.validation.deserializer.path.key_char(char),
+        // If the JSON was invalid, return the error
+        Err(e) if key.validation.done => Err(e)?,
+        // If the key was solely not UTF-8, read past the rest of it
+        Err(_) => break,
+      }
+    }
+    key.validation.drop()?;
+    key.validation.deserializer.key_end = None;
+    crate::advance_past_colon(&mut key.validation.deserializer.reader)
   }
 }
 impl<'read, 'parent, R: Read<'read>, S: Stack> StringKey<'read, 'parent, R, S> {
@@ -213,11 +320,56 @@ impl<'read, 'parent, R: Read<'read>, S: Stack> StringKey<'read, 'parent, R, S> {
     self.0.validation.deserializer
   }
 }
+impl<'read, 'parent, R: Read<'read>, S: Stack> StringKey<'read, 'parent, R, S> {
+  /// If the rest of the key matches the expected key.
+  ///
+  /// This compares the raw bytes of the key while they're unescaped ASCII, only decoding the key
+  /// once an escape sequence or non-ASCII byte is encountered. If the key doesn't match, the rest
+  /// of the key is left unread.
+  #[inline(always)]
+  pub(crate) fn matches(&mut self, expected: &str) -> Result<bool, JsonError<'read, R, S>> {
+    let mut expected = expected.as_bytes();
+    if !(self.0.errored | self.0.validation.done | self.0.pending.is_some()) {
+      let deserializer = &mut *self.0.validation.deserializer;
+      loop {
+        let byte = deserializer.reader.peek();
+        // Fall back to decoding the key upon its end, an escape sequence, or a non-ASCII byte
+        if (byte == b'"') || (byte == b'\\') || (!(b'\x20' ..= b'\x7f').contains(&byte)) {
+          break;
+        }
+        let Some((expected_byte, rest)) = expected.split_first() else { return Ok(false) };
+        if byte != *expected_byte {
+          return Ok(false);
+        }
+        expected = rest;
+        deserializer.reader.read_byte().map_err(IoError)?;
+        deserializer.path.key_char(char::from(byte));
+      }
+    }
+
+    let Ok(expected) = core::str::from_utf8(expected) else { return Ok(false) };
+    let mut expected = expected.chars();
+    loop {
+      match (self.next(), expected.next()) {
+        (Some(Ok(char)), Some(expected)) if char == expected => {}
+        (None, None) => return Ok(true),
+        // If the key isn't UTF-8, it can't match the expected key
+        (Some(Err(JsonError::NotUtf8)), _) => return Ok(false),
+        (Some(Err(e)), _) => Err(e)?,
+        _ => return Ok(false),
+      }
+    }
+  }
+}
 impl<'read, 'parent, R: Read<'read>, S: Stack> Iterator for StringKey<'read, 'parent, R, S> {
   type Item = Result<char, JsonError<'read, R, S>>;
   #[inline(always)]
   fn next(&mut self) -> Option<Self::Item> {
-    self.0.next()
+    let res = self.0.next();
+    if let Some(Ok(char)) = res {
+      self.0.validation.deserializer.path.key_char(char);
+    }
+    res
   }
 }
 
@@ -236,6 +388,15 @@ impl<'read, 'parent, R: Read<'read>, S: Stack> StringValue<'read, 'parent, R, S>
     (ValidateString { deserializer, done }).drop()?;
     crate::advance_past_comma_or_to_close(&mut deserializer.reader)
   }
+
+  /// Check the end of the input was reached, with solely whitespace after this string.
+  ///
+  /// This is for a string which is the root value, and must be called once the string has been
+  /// entirely read.
+  #[inline(always)]
+  pub(crate) fn check_end(&mut self) -> Result<(), JsonError<'read, R, S>> {
+    crate::check_end(&mut self.0.validation.deserializer.reader)
+  }
 }
 impl<'read, 'parent, R: Read<'read>, S: Stack> Drop for StringValue<'read, 'parent, R, S> {
   #[inline(always)]
@@ -250,3 +411,96 @@ impl<'read, 'parent, R: Read<'read>, S: Stack> Iterator for StringValue<'read, '
     self.0.next()
   }
 }
+
+#[test]
+fn unvalidated_skipped_strings() {
+  use crate::{ConstStack, Dialect};
+
+  let dialect = Dialect { skipped_values: ValidationProfile::ValidateRead, ..Dialect::STRICT };
+  let skip = |json: &'static str, dialect| {
+    let mut deserializer =
+      Deserializer::<_, ConstStack<32>>::with_dialect(json.as_bytes(), dialect).unwrap();
+    let mut fields = deserializer.value().unwrap().fields().unwrap();
+    let mut b = None;
+    while let Some(field) = fields.next() {
+      let mut field = field?;
+      if field.key()?.next().transpose()? == Some('b') {
+        b = field.value()?.to_number()?.i64();
+      }
+    }
+    Ok::<_, JsonError<'_, _, _>>(b)
+  };
+
+  // Valid strings are skipped identically
+  let valid = r#"{"a":"\"\\A😀é","b":1}"#;
+  assert_eq!(skip(valid, Dialect::STRICT).unwrap(), Some(1));
+  assert_eq!(skip(valid, dialect).unwrap(), Some(1));
+
+  // Invalid escape sequences and control characters within skipped strings are solely accepted
+  // when skipped strings aren't validated
+  for invalid in [r#"{"a":"\uzzzz\q","b":2}"#, "{\"a\":\"\t\",\"b\":2}"] {
+    assert!(skip(invalid, Dialect::STRICT).is_err());
+    assert_eq!(skip(invalid, dialect).unwrap(), Some(2));
+  }
+
+  // The closing quote must still be found
+  assert!(skip(r#"{"a":"\"}"#, dialect).is_err());
+}
+
+#[test]
+fn lone_surrogates() {
+  use crate::{ConstStack, Dialect};
+
+  let read = |json: &'static str, lone_surrogates| {
+    let dialect = Dialect { lone_surrogates, ..Dialect::STRICT };
+    let mut deserializer =
+      Deserializer::<_, ConstStack<32>>::with_dialect(json.as_bytes(), dialect).unwrap();
+    deserializer.string().unwrap().collect::<Result<std::string::String, _>>()
+  };
+
+  for (json, lossy) in [
+    (r#""\ud800""#, "\u{fffd}"),
+    (r#""\udfaa""#, "\u{fffd}"),
+    (r#""\ud800abc""#, "\u{fffd}abc"),
+    (r#""\ud800\n""#, "\u{fffd}\n"),
+    (r#""\ud800\ud800\n""#, "\u{fffd}\u{fffd}\n"),
+    (r#""\ud800A""#, "\u{fffd}A"),
+    (r#""\udd1e\ud834""#, "\u{fffd}\u{fffd}"),
+    (r#""\ud888\ue000""#, "\u{fffd}\u{e000}"),
+  ] {
+    assert!(matches!(read(json, false), Err(JsonError::NotUtf8)), "{json}");
+    assert_eq!(read(json, true).unwrap(), lossy, "{json}");
+  }
+
+  // Surrogate pairs are unaffected
+  assert_eq!(read(r#""\ud834\udd1e""#, false).unwrap(), "\u{1d11e}");
+  assert_eq!(read(r#""\ud834\udd1e""#, true).unwrap(), "\u{1d11e}");
+}
+
+#[test]
+fn control_characters() {
+  use crate::{ConstStack, Dialect};
+
+  let read = |json: &'static str, control_characters| {
+    let dialect = Dialect { control_characters, ..Dialect::STRICT };
+    let mut deserializer =
+      Deserializer::<_, ConstStack<32>>::with_dialect(json.as_bytes(), dialect).unwrap();
+    let mut fields = deserializer.value()?.fields()?;
+    let mut field = fields.next().unwrap()?;
+    let key = field.key()?.collect::<Result<std::string::String, _>>()?;
+    let value = field.value()?.to_str()?.collect::<Result<std::string::String, _>>()?;
+    Ok::<_, JsonError<_, _>>((key, value))
+  };
+
+  let json = "{\"a\tb\": \"c\r\n\x00d\\n\"}";
+  assert!(matches!(read(json, false), Err(JsonError::SyntaxError(SyntaxError::InvalidValue))));
+  assert_eq!(read(json, true).unwrap(), ("a\tb".into(), "c\r\n\x00d\n".into()));
+  // Keys with control characters may still be matched
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::with_dialect(
+    json.as_bytes(),
+    Dialect { control_characters: true, ..Dialect::STRICT },
+  )
+  .unwrap();
+  let mut fields = deserializer.value().unwrap().fields().unwrap();
+  assert!(fields.next().unwrap().unwrap().key_matches("a\tb").unwrap());
+}
@@ -1,4 +1,4 @@
-use crate::{Read, PeekableRead, Stack, JsonError};
+use crate::{Read, PeekableRead, Stack, SyntaxError, IoError, JsonError};
 
 /// Calculate the length of the non-ASCII UTF-8 codepoint from its first byte.
 ///
@@ -18,7 +18,7 @@ fn utf8_codepoint_to_char<'read, R: Read<'read>, S: Stack>(
   // The last six bits of every byte, except the first for which it depends on the length of the
   // entire codepoint
   const SIX_BITS: u8 = 0b0011_1111;
-  char::from_u32(match c.len() {
+  let codepoint = match c.len() {
     1 => u32::from(c[0]),
     2 => (u32::from(c[0] & 0b0001_1111) << 6) | u32::from(c[1] & SIX_BITS),
     3 => {
@@ -32,8 +32,18 @@ fn utf8_codepoint_to_char<'read, R: Read<'read>, S: Stack>(
         u32::from(c[3] & SIX_BITS)
     }
     _ => unreachable!("non-ASCII codepoints have length in `2 ..= 4`"),
-  })
-  .ok_or(JsonError::InvalidValue)
+  };
+  // Reject overlong encodings, which are invalid UTF-8 despite decoding to a codepoint
+  let minimum = match c.len() {
+    1 => 0,
+    2 => 0x80,
+    3 => 0x800,
+    _ => 0x10000,
+  };
+  if codepoint < minimum {
+    Err(SyntaxError::InvalidValue)?;
+  }
+  char::from_u32(codepoint).ok_or(JsonError::SyntaxError(SyntaxError::InvalidValue))
 }
 
 /// Read a non-ASCII UTF-8 character from a `Read`.
@@ -42,13 +52,21 @@ pub(super) fn read_non_ascii_utf8<'read, R: Read<'read>, S: Stack>(
   reader: &mut PeekableRead<'read, R>,
   first_byte: u8,
 ) -> Result<char, JsonError<'read, R, S>> {
+  // Continuation bytes, and bytes which would start a codepoint exceeding `U+10FFFF` (or one of
+  // five or six bytes), are never valid as the first byte of a codepoint
+  if !(0xc2 ..= 0xf4).contains(&first_byte) {
+    Err(SyntaxError::InvalidValue)?;
+  }
   let utf8_codepoint_len = non_ascii_utf8_codepoint_len(first_byte);
 
   let mut utf8_codepoint = [0; 4];
   let utf8_codepoint = &mut utf8_codepoint[.. utf8_codepoint_len];
   utf8_codepoint[0] = first_byte;
   for byte in &mut utf8_codepoint[1 ..] {
-    *byte = reader.read_byte().map_err(JsonError::ReadError)?;
+    *byte = reader.read_byte().map_err(IoError)?;
+    if (*byte & 0b1100_0000) != 0b1000_0000 {
+      Err(SyntaxError::InvalidValue)?;
+    }
   }
   utf8_codepoint_to_char(utf8_codepoint)
 }
@@ -143,6 +161,17 @@ fn test_utf8_codepoint_to_char() {
     .unwrap(),
     char::from_u32(unicode).unwrap()
   );
+
+  // Overlong encodings, surrogates, and codepoints exceeding `U+10FFFF` are rejected
+  for invalid in [
+    &[0xc0, 0xaf][..],
+    &[0xe0, 0x80, 0xaf],
+    &[0xf0, 0x80, 0x80, 0xaf],
+    &[0xed, 0xa0, 0x80],
+    &[0xf4, 0x90, 0x80, 0x80],
+  ] {
+    assert!(utf8_codepoint_to_char::<&[u8], crate::ConstStack<0>>(invalid).is_err());
+  }
 }
 
 #[test]
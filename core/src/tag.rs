@@ -0,0 +1,131 @@
+use crate::*;
+
+/// A handler for the rest of an object with a specific tag, as used by `match_tag`.
+#[allow(type_alias_bounds)]
+pub type TagHandler<'handler, 'read, 'parent, R: Read<'read>, S: Stack, T> =
+  &'handler mut dyn FnMut(FieldIterator<'read, 'parent, R, S>) -> Result<T, JsonError<'read, R, S>>;
+
+/// Dispatch an object to a handler by its tag.
+///
+/// This reads the object's first field, which must have the key `tag` and a string value. The
+/// handler whose tag equals the value is then called with the rest of the object's fields,
+/// allowing polymorphic objects (such as `{ "type": "circle", "radius": 1 }`) to be decoded
+/// without allocating or buffering the object. If multiple handlers have the same tag, the first
+/// is called.
+///
+/// As the object is read in a single pass, the tag MUST be the first field within the object. If
+/// the value isn't an object, the object's first field isn't the tag, the tag isn't a string, or
+/// no handler has the tag, `JsonError::TypeError` is returned.
+#[allow(clippy::type_complexity)]
+pub fn match_tag<'read, 'parent, R: Read<'read>, S: Stack, T>(
+  value: Value<'read, 'parent, R, S>,
+  tag: &str,
+  handlers: &mut [(&str, TagHandler<'_, 'read, 'parent, R, S, T>)],
+) -> Result<T, JsonError<'read, R, S>> {
+  let mut fields = value.fields()?;
+  let handler = {
+    let mut field = fields.next().ok_or(JsonError::TypeError(TypeError))??;
+    if !field.key_matches(tag)? {
+      Err(JsonError::TypeError(TypeError))?;
+    }
+
+    /*
+      Find the handler for this tag without collecting it, by tracking the first handler whose tag
+      starts with the characters read so far. As every such handler shares the same prefix, the
+      next such handler is solely searched for once the current handler stops matching.
+    */
+    let mut handler = 0;
+    let mut len = 0;
+    let matches = |handler: &str, read: &str, next: &[u8]| {
+      handler.as_bytes().get(.. read.len()) == Some(read.as_bytes()) &&
+        handler.as_bytes()[read.len() ..].starts_with(next)
+    };
+    for char in field.value()?.to_str()? {
+      let mut encoded = [0; 4];
+      let char = char?.encode_utf8(&mut encoded).as_bytes();
+      let read = handlers
+        .get(handler)
+        .map(|(tag, _)| &tag[.. len])
+        .ok_or(JsonError::TypeError(TypeError))?;
+      handler = (handler .. handlers.len())
+        .find(|i| matches(handlers[*i].0, read, char))
+        .ok_or(JsonError::TypeError(TypeError))?;
+      len += char.len();
+    }
+    let read =
+      handlers.get(handler).map(|(tag, _)| &tag[.. len]).ok_or(JsonError::TypeError(TypeError))?;
+    (handler .. handlers.len())
+      .find(|i| (handlers[*i].0.len() == len) && matches(handlers[*i].0, read, &[]))
+      .ok_or(JsonError::TypeError(TypeError))?
+  };
+  (handlers[handler].1)(fields)
+}
+
+#[test]
+fn match_tag_dispatch() {
+  #[derive(PartialEq, Debug)]
+  enum Shape {
+    Circle(i64),
+    Rectangle(i64, i64),
+    Empty,
+  }
+
+  fn decode(json: &str) -> Result<Shape, ErrorKind> {
+    let mut deserializer = Deserializer::<_, ConstStack<4>>::new(json.as_bytes())?;
+    let value = deserializer.value()?;
+
+    // Read the `i64` value of each field with one of the specified keys, in order
+    fn read<'read, R: Read<'read>, S: Stack, const N: usize>(
+      mut fields: FieldIterator<'read, '_, R, S>,
+      keys: [&str; N],
+    ) -> Result<[i64; N], JsonError<'read, R, S>> {
+      let mut result = [0; N];
+      for (key, result) in keys.into_iter().zip(&mut result) {
+        let mut field = fields.next().ok_or(JsonError::TypeError(TypeError))??;
+        if !field.key_matches(key)? {
+          Err(JsonError::TypeError(TypeError))?;
+        }
+        *result = field.value()?.to_number()?.i64().ok_or(JsonError::TypeError(TypeError))?;
+      }
+      Ok(result)
+    }
+
+    Ok(match_tag(
+      value,
+      "type",
+      &mut [
+        ("rect", &mut |_| Err(JsonError::InternalError)),
+        ("", &mut |_| Ok(Shape::Empty)),
+        ("circle", &mut |fields| read(fields, ["radius"]).map(|[radius]| Shape::Circle(radius))),
+        ("rectangle", &mut |fields| {
+          read(fields, ["width", "height"]).map(|[width, height]| Shape::Rectangle(width, height))
+        }),
+        ("circle", &mut |_| Err(JsonError::InternalError)),
+      ],
+    )?)
+  }
+
+  assert_eq!(decode(r#"{ "type": "circle", "radius": 1 }"#), Ok(Shape::Circle(1)));
+  assert_eq!(
+    decode(r#"{ "type": "rectangle", "width": 2, "height": 3 }"#),
+    Ok(Shape::Rectangle(2, 3))
+  );
+  assert_eq!(decode(r#"{ "type": "" }"#), Ok(Shape::Empty));
+  // The handler may leave the rest of the object unread
+  assert_eq!(decode(r#"{ "type": "", "extra": [{}] }"#), Ok(Shape::Empty));
+  assert_eq!(decode(r#"{ "type": "rect" }"#), Err(ErrorKind::InternalError));
+
+  // Unknown tags, tags which aren't first, and values which aren't tagged objects
+  for json in [
+    r#"{ "type": "circl" }"#,
+    r#"{ "type": "circles" }"#,
+    r#"{ "type": "square" }"#,
+    r#"{ "radius": 1, "type": "circle" }"#,
+    r#"{ "type": 1 }"#,
+    "{}",
+    "[]",
+  ] {
+    assert_eq!(decode(json), Err(ErrorKind::TypeError), "{json}");
+  }
+  assert!(decode(r#"{ "type": "circle", "radius": "#).is_err());
+}
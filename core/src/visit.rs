@@ -0,0 +1,220 @@
+use crate::*;
+
+/// A visitor of the contents of a value, as driven by `walk`.
+///
+/// Every method has a default implementation which does nothing, so visitors solely need to
+/// implement the methods for the events they're interested in. Keys and strings are provided as
+/// iterators of their characters, which don't have to be exhausted.
+pub trait JsonVisitor {
+  /// An object was opened.
+  fn object_start(&mut self) {}
+  /// A field within the current object was advanced to, with its key.
+  ///
+  /// The field's value will be visited next.
+  fn field_start<'read, R: Read<'read>, S: Stack>(
+    &mut self,
+    _key: &mut impl Iterator<Item = Result<char, JsonError<'read, R, S>>>,
+  ) {
+  }
+  /// The value of the current field was advanced past.
+  fn field_end(&mut self) {}
+  /// The current object was closed.
+  fn object_end(&mut self) {}
+
+  /// An array was opened.
+  fn array_start(&mut self) {}
+  /// An element within the current array was advanced to.
+  ///
+  /// The element will be visited next.
+  fn element_start(&mut self) {}
+  /// The current element was advanced past.
+  fn element_end(&mut self) {}
+  /// The current array was closed.
+  fn array_end(&mut self) {}
+
+  /// A string was encountered.
+  fn string<'read, R: Read<'read>, S: Stack>(
+    &mut self,
+    _string: &mut impl Iterator<Item = Result<char, JsonError<'read, R, S>>>,
+  ) {
+  }
+  /// A number was encountered.
+  fn number(&mut self, _number: Number) {}
+  /// A boolean was encountered.
+  fn bool(&mut self, _bool: bool) {}
+  /// A `null` was encountered.
+  fn null(&mut self) {}
+}
+
+/// Walk a value, calling the visitor for each event within it.
+///
+/// This drives a complete traversal of the value (validating it), without recursing, allowing
+/// tools to analyze values of arbitrary shape without implementing their own descent over
+/// `Value`. If the JSON is invalid, an error is returned once the deserializer advances to the
+/// invalid portion.
+///
+/// Keys and strings which are valid yet not UTF-8 will yield `JsonError::NotUtf8` to the visitor,
+/// yet will not cause the walk to fail.
+pub fn walk<'read, R: Read<'read>, S: Stack>(
+  mut value: Value<'read, '_, R, S>,
+  visitor: &mut impl JsonVisitor,
+) -> Result<(), JsonError<'read, R, S>> {
+  // Take the deserializer so this value's `Drop` implementation doesn't queue dropping it
+  let deserializer = value.deserializer.take().ok_or(JsonError::InternalError)?;
+
+  let mut depth = 0usize;
+  // If the last event completed a value, leaving the entry it was the value of to be ended
+  let mut completed = false;
+  loop {
+    match deserializer.single_step()? {
+      SingleStepResult::Object(SingleStepObjectResult::Field) => {
+        if completed {
+          visitor.field_end();
+        }
+        completed = false;
+        let mut key = StringKey::read(deserializer);
+        visitor.field_start(&mut key);
+        key.drop();
+        continue;
+      }
+      SingleStepResult::Array(SingleStepArrayResult::Value) => {
+        if completed {
+          visitor.element_end();
+        }
+        completed = false;
+        visitor.element_start();
+        continue;
+      }
+      SingleStepResult::Unknown(SingleStepUnknownResult::ObjectOpened) => {
+        visitor.object_start();
+        depth += 1;
+        completed = false;
+        continue;
+      }
+      SingleStepResult::Unknown(SingleStepUnknownResult::ArrayOpened) => {
+        visitor.array_start();
+        depth += 1;
+        completed = false;
+        continue;
+      }
+      SingleStepResult::Object(SingleStepObjectResult::Closed) => {
+        if completed {
+          visitor.field_end();
+        }
+        visitor.object_end();
+        depth -= 1;
+      }
+      SingleStepResult::Array(SingleStepArrayResult::Closed) => {
+        if completed {
+          visitor.element_end();
+        }
+        visitor.array_end();
+        depth -= 1;
+      }
+      SingleStepResult::Unknown(SingleStepUnknownResult::String) => {
+        visitor.string(&mut handle_string_value(deserializer));
+      }
+      SingleStepResult::Unknown(SingleStepUnknownResult::Number(number)) => visitor.number(number),
+      SingleStepResult::Unknown(SingleStepUnknownResult::Bool(bool)) => visitor.bool(bool),
+      SingleStepResult::Unknown(SingleStepUnknownResult::Null) => visitor.null(),
+    }
+    completed = true;
+    // If we've advanced past this value, return
+    if depth == 0 {
+      break;
+    }
+  }
+
+  // Complete the pending work of advancing past the last string, if the value ended with one
+  DelayedDrop::drop(deserializer)
+}
+
+#[test]
+fn walk_events() {
+  use std::{vec::Vec, string::String, format};
+
+  #[derive(Default)]
+  struct Events(Vec<String>);
+  impl JsonVisitor for Events {
+    fn object_start(&mut self) {
+      self.0.push("{".into());
+    }
+    fn field_start<'read, R: Read<'read>, S: Stack>(
+      &mut self,
+      key: &mut impl Iterator<Item = Result<char, JsonError<'read, R, S>>>,
+    ) {
+      match key.collect::<Result<String, _>>() {
+        Ok(key) => self.0.push(format!("key {key}")),
+        Err(_) => self.0.push("key ?".into()),
+      }
+    }
+    fn field_end(&mut self) {
+      self.0.push("/field".into());
+    }
+    fn object_end(&mut self) {
+      self.0.push("}".into());
+    }
+    fn array_start(&mut self) {
+      self.0.push("[".into());
+    }
+    fn element_start(&mut self) {
+      self.0.push("element".into());
+    }
+    fn element_end(&mut self) {
+      self.0.push("/element".into());
+    }
+    fn array_end(&mut self) {
+      self.0.push("]".into());
+    }
+    fn string<'read, R: Read<'read>, S: Stack>(
+      &mut self,
+      string: &mut impl Iterator<Item = Result<char, JsonError<'read, R, S>>>,
+    ) {
+      // Solely read the first character, leaving the rest to be advanced past
+      match string.next() {
+        Some(Ok(char)) => self.0.push(format!("string {char}")),
+        _ => self.0.push("string".into()),
+      }
+    }
+    fn number(&mut self, number: Number) {
+      self.0.push(format!("number {}", number.i64().unwrap()));
+    }
+    fn bool(&mut self, bool: bool) {
+      self.0.push(format!("bool {bool}"));
+    }
+    fn null(&mut self) {
+      self.0.push("null".into());
+    }
+  }
+
+  let serialization = br#"{"a": [1, "bc", {}], "\ud800": [[]], "d": {"e": true}, "f": null} "#;
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+  let mut events = Events::default();
+  walk(deserializer.value().unwrap(), &mut events).unwrap();
+  assert_eq!(
+    events.0.join(", "),
+    "{, key a, [, element, number 1, /element, element, string b, /element, element, {, }, \
+     /element, ], /field, key ?, [, element, [, ], /element, ], /field, key d, {, key e, \
+     bool true, /field, }, /field, key f, null, /field, }"
+  );
+  assert_eq!(deserializer.buffered().unwrap(), b" ");
+
+  // Values within a structure may be individually walked
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&serialization[..]).unwrap();
+  let mut fields = deserializer.value().unwrap().fields().unwrap();
+  let mut field = fields.next().unwrap().unwrap();
+  assert!(field.key_matches("a").unwrap());
+  let mut events = Events::default();
+  walk(field.value().unwrap(), &mut events).unwrap();
+  assert_eq!(events.0.first().map(String::as_str), Some("["));
+  assert_eq!(events.0.last().map(String::as_str), Some("]"));
+  let mut field = fields.next().unwrap().unwrap();
+  assert!(field.key().unwrap().next().unwrap().is_err());
+  drop(field);
+  let mut field = fields.next().unwrap().unwrap();
+  assert!(field.key_matches("d").unwrap());
+
+  // Invalid values error
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&br#"[1, ["\x"]]"#[..]).unwrap();
+  assert!(walk(deserializer.value().unwrap(), &mut Events::default()).is_err());
+}
@@ -70,11 +70,125 @@ fn parse_comma_delimited(iter: &mut Peekable<impl Iterator<Item: Borrow<TokenTre
   }
 }
 
+// The name of a type, as written, without its path or generic arguments.
+fn type_name(ty: &TokenStream) -> Option<String> {
+  let mut last_ident = None;
+  for item in ty.clone() {
+    match item {
+      TokenTree::Ident(ident) => last_ident = Some(ident.to_string()),
+      TokenTree::Punct(punct) if punct.as_char() == '<' => break,
+      TokenTree::Punct(_) => {}
+      _ => return None,
+    }
+  }
+  last_ident
+}
+
+// If a type is `Option`, as written.
+fn is_option(ty: &TokenStream) -> bool {
+  type_name(ty).as_deref() == Some("Option")
+}
+
+struct Field {
+  name: TokenStream,
+  ty: TokenStream,
+  serialization_name: String,
+  omit_none: bool,
+  // The inclusive bounds to validate the field's value against when deserializing, if specified
+  range: Option<(Option<String>, Option<String>)>,
+  // The maximum amount of characters for the field's string, if specified
+  max_len: Option<String>,
+  // If the field's string must solely contain ASCII characters
+  ascii_only: bool,
+}
+
+// The type within an `Option<...>`, as written.
+fn option_inner_type(ty: &TokenStream) -> TokenStream {
+  let ty = ty.to_string();
+  let start = ty.find('<').expect("`Option` field's type wasn't `Option<...>`");
+  let end = ty.rfind('>').expect("`Option` field's type wasn't `Option<...>`");
+  TokenStream::from_str(&ty[(start + 1) .. end]).expect("couldn't parse `Option`'s type")
+}
+
+// The expression deserializing a field from `value`, validating it's within its `range` and
+// satisfies its string constraints.
+fn field_deserialization(Field { ty, range, max_len, ascii_only, .. }: &Field) -> String {
+  if max_len.is_some() || *ascii_only {
+    let max_len = max_len.as_ref().map(|max_len| format!("Some({max_len})"));
+    let max_len = max_len.as_deref().unwrap_or("None");
+    let deserialization =
+      format!("core_json_traits::deserialize_constrained_string(value, {max_len}, {ascii_only})?");
+    if !is_option(ty) {
+      return deserialization;
+    }
+    // `null` is accepted for fields written as `Option<...>`, as `Option` does
+    return format!(
+      r#"
+      if matches!(value.kind(), core_json_traits::Type::Null) {{
+        let () = value.to_null()?;
+        None
+      }} else {{
+        Some({deserialization})
+      }}
+      "#
+    );
+  }
+
+  let deserialization = "core_json_traits::JsonDeserialize::deserialize(value)?".to_string();
+  let Some((min, max)) = range else { return deserialization };
+
+  // A field written as `Option<...>` is solely validated if it's `Some`
+  let (bounded, inner_ty) =
+    if is_option(ty) { ("value", option_inner_type(ty)) } else { ("Some(value)", ty.clone()) };
+  // The JSON-compatible floats are compared as `f64`s
+  let conversion = match type_name(&inner_ty).as_deref() {
+    Some("JsonF64" | "ExactF64") => "f64::from(bounded)",
+    _ => "bounded",
+  };
+  let below = min.as_ref().map(|min| format!("(bounded < ({min}))"));
+  let above = max.as_ref().map(|max| format!("(bounded > ({max}))"));
+  let out_of_bounds = below.into_iter().chain(above).collect::<Vec<_>>().join(" || ");
+
+  format!(
+    r#"{{
+      let value: {ty} = {deserialization};
+      if let Some(bounded) = {bounded} {{
+        let bounded = {conversion};
+        #[allow(
+          unused_comparisons,
+          clippy::absurd_extreme_comparisons,
+          clippy::manual_range_contains
+        )]
+        let out_of_bounds = {out_of_bounds};
+        if out_of_bounds {{
+          Err(core_json_traits::NotRepresentable::OutOfBounds)?;
+        }}
+      }}
+      value
+    }}"#
+  )
+}
+
 struct Struct {
   generic_bounds: String,
   generics: String,
   name: String,
-  fields: Vec<(TokenStream, String)>,
+  fields: Vec<Field>,
+  // The names of the fields skipped
+  skipped: Vec<TokenStream>,
+  from_str: Option<String>,
+  transparent: bool,
+  no_default: bool,
+  // If this is a unit `struct` (de)serialized as `null` instead of `{}`
+  null: bool,
+  // If `JsonWireFormat` should be derived alongside `JsonSerialize`
+  wire_format: bool,
+  // If the serialization should be assembled from static segments, between the fields' values
+  static_template: bool,
+  // If the fields should first be matched in the order they're declared, before by their keys
+  fields_in_order: bool,
+  // The constructors of the samples to test round-trip, if a test should be generated
+  round_trip_test: Option<Vec<String>>,
 }
 
 // This is somewhat comparable to `syn::Generics`, especially its `split_for_impl` method.
@@ -82,13 +196,89 @@ fn parse_struct(object: TokenStream) -> Struct {
   let mut object = object.into_iter().peekable();
 
   // Handle attributes applied to the `struct`
+  let mut from_str = None;
+  let mut omit_none = false;
+  let mut transparent = false;
+  let mut no_default = false;
+  let mut null = false;
+  let mut wire_format = false;
+  let mut static_template = false;
+  let mut fields_in_order = false;
+  let mut round_trip_test = None;
   loop {
     match object.peek() {
       Some(TokenTree::Punct(punct)) if punct.as_char() == '#' => {
         let _ = object.next().expect("peeked but not present");
-        let TokenTree::Group(_) = object.next().expect("`#` but no `[ ... ]`") else {
+        let TokenTree::Group(attribute) = object.next().expect("`#` but no `[ ... ]`") else {
           panic!("`#` not followed by a `TokenTree::Group` for its `[ ... ]`")
         };
+        let mut iter = attribute.stream().into_iter();
+        match iter.next() {
+          Some(TokenTree::Ident(ident)) if ident.to_string() == "from_str" => {}
+          Some(TokenTree::Ident(ident)) if ident.to_string() == "omit_none" => {
+            omit_none = true;
+            continue;
+          }
+          Some(TokenTree::Ident(ident)) if ident.to_string() == "transparent" => {
+            transparent = true;
+            continue;
+          }
+          Some(TokenTree::Ident(ident)) if ident.to_string() == "no_default" => {
+            no_default = true;
+            continue;
+          }
+          Some(TokenTree::Ident(ident)) if ident.to_string() == "null" => {
+            null = true;
+            continue;
+          }
+          Some(TokenTree::Ident(ident)) if ident.to_string() == "wire_format" => {
+            wire_format = true;
+            continue;
+          }
+          Some(TokenTree::Ident(ident)) if ident.to_string() == "static_template" => {
+            static_template = true;
+            continue;
+          }
+          Some(TokenTree::Ident(ident)) if ident.to_string() == "fields_in_order" => {
+            fields_in_order = true;
+            continue;
+          }
+          Some(TokenTree::Ident(ident)) if ident.to_string() == "round_trip_test" => {
+            let mut samples = vec!["core::default::Default::default".to_string()];
+            if let Some(group) = iter.next() {
+              let TokenTree::Group(group) = group else {
+                panic!("`round_trip_test` attribute not followed with `(...)`")
+              };
+              assert_eq!(
+                group.delimiter(),
+                Delimiter::Parenthesis,
+                "`round_trip_test` attribute with a non-parentheses group"
+              );
+              samples.push(group.stream().to_string());
+            }
+            round_trip_test = Some(samples);
+            continue;
+          }
+          _ => continue,
+        }
+        let Some(TokenTree::Group(group)) = iter.next() else {
+          panic!("`from_str` attribute not followed with `(...)`")
+        };
+        assert_eq!(
+          group.delimiter(),
+          Delimiter::Parenthesis,
+          "`from_str` attribute with a non-parentheses group"
+        );
+        let mut stack = group.stream().into_iter();
+        let (Some(TokenTree::Literal(stack)), None) = (stack.next(), stack.next()) else {
+          panic!("`from_str` attribute without a single literal for the `ConstStack` parameter")
+        };
+        let stack = stack.to_string();
+        assert!(
+          stack.parse::<usize>().is_ok(),
+          "`from_str` attribute's `ConstStack` parameter wasn't an unsuffixed integer literal"
+        );
+        from_str = Some(stack);
       }
       _ => break,
     }
@@ -136,19 +326,32 @@ fn parse_struct(object: TokenStream) -> Struct {
   let generics = TokenStream::from_iter(generics_tree).to_string();
 
   // This presumably means we don't support `struct`s defined with `where` bounds
-  let Some(TokenTree::Group(struct_body)) = object.next() else {
-    panic!("`struct`'s name was not followed by its body");
+  let struct_body = match object.next() {
+    // A unit `struct`, which is treated as a `struct` without fields
+    Some(TokenTree::Punct(punct)) if punct.as_char() == ';' => TokenStream::default(),
+    Some(TokenTree::Group(struct_body)) => {
+      if struct_body.delimiter() != Delimiter::Brace {
+        panic!("`JsonDeserialize` derivation applied to `struct` with anonymous fields");
+      }
+      struct_body.stream()
+    }
+    _ => panic!("`struct`'s name was not followed by its body"),
   };
-  if struct_body.delimiter() != Delimiter::Brace {
-    panic!("`JsonDeserialize` derivation applied to `struct` with anonymous fields");
-  }
+  let unit = struct_body.is_empty();
+  assert!(!null || unit, "`null` attribute applied to a `struct` which isn't a unit `struct`");
+  assert!(!null || from_str.is_none(), "`from_str` attribute applied to a `null` `struct`");
 
   let mut fields = vec![];
+  let mut skipped = vec![];
 
   // Read each field within this `struct`'s body
-  for field in parse_struct_fields(&mut struct_body.stream().into_iter().peekable()) {
+  for field in parse_struct_fields(&mut struct_body.into_iter().peekable()) {
     let mut serialization_field_name = None;
     let mut skip = false;
+    let mut omit_none = omit_none && is_option(&field.ty);
+    let mut range = None;
+    let mut max_len = None;
+    let mut ascii_only = false;
 
     for attribute in field.attributes {
       let mut iter = attribute.into_iter();
@@ -158,6 +361,10 @@ fn parse_struct(object: TokenStream) -> Struct {
       });
       match ident.as_deref() {
         Some("skip") => skip = true,
+        Some("omit_none") => {
+          assert!(is_option(&field.ty), "`omit_none` attribute applied to a non-`Option` field");
+          omit_none = true;
+        }
         Some("key") => {
           let TokenTree::Group(group) = iter.next().expect("`key` attribute without arguments")
           else {
@@ -182,39 +389,283 @@ fn parse_struct(object: TokenStream) -> Struct {
           serialization_field_name =
             Some(literal.trim_start_matches('"').trim_end_matches('"').to_string());
         }
+        Some("range") => {
+          let TokenTree::Group(group) = iter.next().expect("`range` attribute without arguments")
+          else {
+            panic!("`range` attribute not followed with `(...)`")
+          };
+          assert_eq!(
+            group.delimiter(),
+            Delimiter::Parenthesis,
+            "`range` attribute with a non-parentheses group"
+          );
+          let (mut min, mut max) = (None, None);
+          let mut bounds = group.stream().into_iter().peekable();
+          while let Some(bound) = bounds.next() {
+            let bound = match bound {
+              TokenTree::Ident(ident) if ident.to_string() == "min" => &mut min,
+              TokenTree::Ident(ident) if ident.to_string() == "max" => &mut max,
+              _ => panic!("`range` attribute with an argument other than `min`, `max`"),
+            };
+            match bounds.next() {
+              Some(TokenTree::Punct(punct)) if punct.as_char() == '=' => {}
+              _ => panic!("`range` attribute's bound wasn't followed by `=`"),
+            }
+            // Read the expression for this bound, until the next comma
+            let mut expression = TokenStream::new();
+            while let Some(token) = bounds
+              .next_if(|token| !matches!(token, TokenTree::Punct(punct) if punct.as_char() == ','))
+            {
+              expression.extend([token]);
+            }
+            bounds.next();
+            assert!(!expression.is_empty(), "`range` attribute's bound had no value");
+            assert!(bound.is_none(), "`range` attribute specified a bound multiple times");
+            *bound = Some(expression.to_string());
+          }
+          assert!(
+            min.is_some() || max.is_some(),
+            "`range` attribute without a `min` or `max` bound"
+          );
+          range = Some((min, max));
+        }
+        Some("max_len") => {
+          let TokenTree::Group(group) = iter.next().expect("`max_len` attribute without arguments")
+          else {
+            panic!("`max_len` attribute not followed with `(...)`")
+          };
+          assert_eq!(
+            group.delimiter(),
+            Delimiter::Parenthesis,
+            "`max_len` attribute with a non-parentheses group"
+          );
+          assert!(!group.stream().is_empty(), "`max_len` attribute without a length");
+          max_len = Some(group.stream().to_string());
+        }
+        Some("ascii_only") => ascii_only = true,
         _ => {}
       }
     }
 
     let serialization_field_name = serialization_field_name.unwrap_or(field.identifier.ident());
 
-    if !skip {
-      fields.push((field.identifier.stream(), serialization_field_name));
+    if max_len.is_some() || ascii_only {
+      let ty = if is_option(&field.ty) { option_inner_type(&field.ty) } else { field.ty.clone() };
+      assert_eq!(
+        type_name(&ty).as_deref(),
+        Some("String"),
+        "`max_len`, `ascii_only` attributes applied to a field which isn't a `String`"
+      );
+      assert!(range.is_none(), "`range` attribute applied to a `String` field");
     }
+
+    if skip {
+      skipped.push(field.identifier.stream());
+    } else {
+      fields.push(Field {
+        name: field.identifier.stream(),
+        ty: field.ty,
+        serialization_name: serialization_field_name,
+        omit_none,
+        range,
+        max_len,
+        ascii_only,
+      });
+    }
+  }
+
+  if transparent {
+    assert_eq!(
+      fields.len(),
+      1,
+      "`transparent` attribute applied to a `struct` without exactly one non-skipped field"
+    );
   }
 
-  Struct { generic_bounds, generics, name, fields }
+  if static_template {
+    assert!(!transparent, "`static_template` attribute applied to a `transparent` `struct`");
+    assert!(!null, "`static_template` attribute applied to a `null` `struct`");
+    for Field { ty, serialization_name, omit_none, .. } in &fields {
+      // Every field must be serialized, for the segments between them to be static
+      assert!(!omit_none, "`static_template` attribute applied to a `struct` with `omit_none`");
+      assert!(
+        type_name(ty).as_deref() != Some("Tri"),
+        "`static_template` attribute applied to a `struct` with a `Tri` field"
+      );
+      assert!(
+        !serialization_name.chars().any(|char| (char == '\\') || char.is_control()),
+        "`static_template` attribute applied to a `struct` with a key requiring escaping"
+      );
+    }
+  }
+
+  if fields_in_order {
+    assert!(!transparent, "`fields_in_order` attribute applied to a `transparent` `struct`");
+    assert!(!null, "`fields_in_order` attribute applied to a `null` `struct`");
+  }
+
+  if round_trip_test.is_some() {
+    assert!(generic_bounds.is_empty(), "`round_trip_test` attribute applied to a generic `struct`");
+    assert!(!no_default, "`round_trip_test` attribute applied to a `no_default` `struct`");
+  }
+
+  Struct {
+    generic_bounds,
+    generics,
+    name,
+    fields,
+    skipped,
+    from_str,
+    transparent,
+    // A `struct` without fields is constructed without `Default`
+    no_default: no_default || unit,
+    null,
+    wire_format,
+    static_template,
+    fields_in_order,
+    round_trip_test,
+  }
 }
 
 /// Derive an implementation of the `JsonDeserialize` trait.
 ///
-/// This _requires_ the `struct` derived for implement `Default`. Fields which aren't present in
-/// the serialization will be left to their `Default` initialization. If you wish to detect if a
-/// field was omitted, please wrap it in `Option`.
+/// This _requires_ the `struct` derived for implement `Default`, unless it has the `no_default`
+/// attribute. Fields which aren't present in the serialization will be left to their `Default`
+/// initialization. If you wish to detect if a field was omitted, please wrap it in `Option`.
 ///
 /// Fields may deserialized from a distinct key using the `key` attribute, accepting a string
 /// literal for the key to deserialize from (`key("key")`). Fields may be omitted from
 /// deserialization with the `skip` attribute.
 ///
+/// Fields may have their values validated with the `range` attribute, accepting inclusive bounds
+/// (`range(min = 0, max = 100)`, with either bound optional). If a value deserialized is outside
+/// of these bounds, `NotRepresentable::OutOfBounds` is returned. The bounds are compared against
+/// the field's value, and must be of its type (so `0.0`, not `0`, for `f64`s), except for fields
+/// written as `JsonF64` or `ExactF64` (compared as `f64`s) and fields written as `Option<...>`
+/// (solely validated if `Some`).
+///
+/// `String` fields (and fields written as `Option<String>`) may be constrained with the `max_len`
+/// attribute, accepting the maximum amount of characters (`max_len(64)`), and the `ascii_only`
+/// attribute. These are checked as each character is read, so a string exceeding the length is
+/// rejected without being read in its entirety. `JsonError::LengthLimitExceeded` is returned if
+/// the string is too long, and `JsonError::TypeError` if it has a non-ASCII character.
+///
+/// This additionally derives `JsonFields`, enumerating the keys of the fields deserialized.
+///
+/// If the `struct` has the `transparent` attribute, it's deserialized exactly as its sole
+/// non-skipped field would be (instead of as an object), with any other fields left to their
+/// `Default` initialization. As their field may not be a structure, neither `JsonStructure` nor
+/// `JsonFields` are derived for such `struct`s.
+///
+/// If the `struct` has the `from_str` attribute, accepting the constant parameter for the
+/// `ConstStack` to use (`from_str(32)`), implementations of `FromStr` and `TryFrom<&[u8]>` are
+/// also derived. These deserialize the `struct` as a structure, returning the `ErrorKind` on
+/// error.
+///
+/// If the `struct` has the `no_default` attribute, it isn't required to implement `Default`.
+/// Instead, each field is deserialized into a slot, with the `struct` solely assembled once the
+/// object has been read. If a field isn't present in the serialization, `JsonError::TypeError` is
+/// returned, unless its type is written as `Option<...>` or `Tri<...>` (in which case it's left as
+/// `None`) or `PhantomData<...>`. Skipped fields are initialized to their `Default` value.
+///
+/// If the `struct` has the `fields_in_order` attribute, each key is first compared against the
+/// key of the field expected next (the field declared after the field last deserialized), solely
+/// being compared against the keys of every field if it doesn't match. This speeds up
+/// deserializing objects from producers which serialize their fields in the order they're
+/// declared, while still deserializing objects whose fields are in any other order.
+///
+/// Unit `struct`s (and `struct`s without fields) are deserialized from an object (of any fields),
+/// without requiring they implement `Default`, with any other value returning
+/// `JsonError::TypeError`. If the unit `struct` has the `null` attribute, it's instead
+/// deserialized from `null`, and neither `JsonStructure` nor `JsonFields` are derived.
+///
+/// If the `struct` has the `round_trip_test` attribute, a test is generated which serializes its
+/// `Default` value, deserializes it, and checks the result serializes identically. A path to a
+/// function constructing an additional sample may be specified (`round_trip_test(sample)`). This
+/// requires the `struct` also implement `JsonSerialize`, and is not supported for generic
+/// `struct`s nor `struct`s with the `no_default` attribute.
+///
 /// As a procedural macro, this will panic causing a compile-time error on any unexpected input.
-#[proc_macro_derive(JsonDeserialize, attributes(key, skip))]
+#[proc_macro_derive(
+  JsonDeserialize,
+  attributes(
+    key,
+    skip,
+    range,
+    max_len,
+    ascii_only,
+    from_str,
+    transparent,
+    no_default,
+    null,
+    fields_in_order,
+    round_trip_test
+  )
+)]
 pub fn derive_json_deserialize(object: TokenStream) -> TokenStream {
-  let Struct { generic_bounds, generics, name, fields } = parse_struct(object);
+  let Struct {
+    generic_bounds,
+    generics,
+    name,
+    fields,
+    skipped,
+    from_str,
+    transparent,
+    no_default,
+    null,
+    wire_format: _,
+    static_template: _,
+    fields_in_order,
+    round_trip_test,
+  } = parse_struct(object);
+
+  /*
+    Without `Default`, the fields are deserialized into a tuple of `Option`s, from which the
+    `struct` is assembled once all of the fields have been read.
+  */
+  let (default_bound, initialization, assembly) = if no_default {
+    let mut slots = String::new();
+    let mut assembly = String::new();
+    for (i, Field { name: field_name, ty, .. }) in fields.iter().enumerate() {
+      slots.push_str(&format!("Option<{ty}>,"));
+      let missing = if matches!(type_name(ty).as_deref(), Some("Option" | "Tri" | "PhantomData")) {
+        "unwrap_or_default()"
+      } else {
+        "ok_or(core_json_traits::JsonError::TypeError(core_json_traits::TypeError))?"
+      };
+      assembly.push_str(&format!("{field_name}: slots.{i}.{missing},"));
+    }
+    for field_name in &skipped {
+      assembly.push_str(&format!("{field_name}: core::default::Default::default(),"));
+    }
+    let none = "None,".repeat(fields.len());
+    ("", format!("let mut slots: ({slots}) = ({none});"), format!("Self {{ {assembly} }}"))
+  } else {
+    (
+      "where Self: core::default::Default",
+      "let mut result = <Self as core::default::Default>::default();".to_string(),
+      "result".to_string(),
+    )
+  };
+  let assign = |i: usize, field_name: &TokenStream, deserialization: &str| {
+    if no_default {
+      format!("slots.{i} = Some({deserialization})")
+    } else {
+      format!("result.{field_name} = {deserialization}")
+    }
+  };
 
   let mut largest_key = 0;
   let mut fields_deserialization = String::new();
-  for (field_name, serialization_field_name) in &fields {
+  let mut field_names = String::new();
+  // The arms matching the key against the next field in order, and against any field
+  let mut positional_arms = String::new();
+  let mut index_arms = String::new();
+  let mut indexed_deserialization = String::new();
+  for (i, field) in fields.iter().enumerate() {
+    let Field { name: field_name, serialization_name: serialization_field_name, .. } = field;
     largest_key = largest_key.max(serialization_field_name.len());
+    field_names.push_str(&format!(r#""{serialization_field_name}","#));
 
     let mut serialization_field_name_array = "&[".to_string();
     for char in serialization_field_name.chars() {
@@ -225,19 +676,223 @@ pub fn derive_json_deserialize(object: TokenStream) -> TokenStream {
     }
     serialization_field_name_array.push(']');
 
+    let assign = assign(i, field_name, &field_deserialization(field));
     fields_deserialization.push_str(&format!(
       r#"
       {serialization_field_name_array} => {{
-        result.{field_name} = core_json_traits::JsonDeserialize::deserialize(value)?
+        {assign}
+      }},
+      "#
+    ));
+
+    let serialization_field_name_slice = &serialization_field_name_array[1 ..];
+    positional_arms.push_str(&format!("{i} if *key == {serialization_field_name_slice} => {i},"));
+    index_arms.push_str(&format!("{serialization_field_name_array} => {i},"));
+    indexed_deserialization.push_str(&format!(
+      r#"
+      {i} => {{
+        {assign}
       }},
       "#
     ));
   }
 
+  /*
+    With `fields_in_order`, the key is first compared against the key of the field expected next,
+    solely falling back to matching it against every field's key if it doesn't match. Once a field
+    is found, the field after it is expected next.
+  */
+  let (next_field, match_key) = if fields_in_order {
+    (
+      "let mut next_field = 0usize;",
+      format!(
+        r#"
+        let index = match next_field {{
+          {positional_arms}
+          _ => match key {{
+            {index_arms}
+            // Skip unknown fields
+            _ => continue,
+          }},
+        }};
+        next_field = index + 1;
+        match index {{
+          {indexed_deserialization}
+          _ => {{}}
+        }}
+        "#
+      ),
+    )
+  } else {
+    (
+      "",
+      format!(
+        r#"
+        match key {{
+          {fields_deserialization}
+          // Skip unknown fields
+          _ => {{}}
+        }}
+        "#
+      ),
+    )
+  };
+
+  let deserialization = if null {
+    "value.to_null()?;".to_string()
+  } else if transparent {
+    let assign = assign(0, &fields[0].name, &field_deserialization(&fields[0]));
+    format!(
+      r#"
+      {initialization}
+      {assign};
+      "#
+    )
+  } else {
+    format!(
+      r#"
+      {initialization}
+      if {largest_key} == 0 {{
+        // Still require an object, advancing past its (unknown) fields
+        let mut object = value.fields()?;
+        while let Some(field) = object.next() {{
+          field?;
+        }}
+        return Ok({assembly});
+      }}
+
+      let mut key_chars = ['\0'; {largest_key}];
+      {next_field}
+      let mut object = value.fields()?;
+      'serialized_field: while let Some(field) = object.next() {{
+        let mut field = field?;
+
+        let key = {{
+          let key = field.key()?;
+          let mut key_len = 0;
+          while let Some(key_char) = key.next() {{
+            key_chars[key_len] = match key_char {{
+              Ok(key_char) => key_char,
+              /*
+                This occurs when the key specifies an invalid UTF codepoint, which is technically
+                allowed by RFC 8259. While it means we can't interpret the key, it also means
+                this isn't a field we're looking for.
+
+                Continue to the next serialized field accordingly.
+              */
+              Err(core_json_traits::JsonError::SyntaxError(
+                core_json_traits::SyntaxError::InvalidValue,
+              )) => continue 'serialized_field,
+              // Propagate all other errors.
+              Err(e) => Err(e)?,
+            }};
+            key_len += 1;
+            if key_len == {largest_key} {{
+              break;
+            }}
+          }}
+          match key.next() {{
+            None => {{}},
+            // This key is larger than our largest key
+            Some(Ok(_)) => continue,
+            Some(Err(e)) => Err(e)?,
+          }}
+          &key_chars[.. key_len]
+        }};
+        let value = field.value()?;
+
+        {match_key}
+      }}
+      "#
+    )
+  };
+
+  // A transparent `struct` is only a structure if its field is, which we can't express as a bound
+  let structure_and_fields_impls = if transparent || null {
+    String::new()
+  } else {
+    format!(
+      r#"
+      impl{generic_bounds} core_json_traits::JsonStructure for {name}{generics}
+        {default_bound} {{}}
+      impl{generic_bounds} core_json_traits::JsonFields for {name}{generics} {{
+        const FIELDS: &'static [&'static str] = &[{field_names}];
+      }}
+      "#
+    )
+  };
+
+  let from_str = from_str
+    .map(|stack| {
+      let generic_bounds_with_lifetime = match generic_bounds.strip_prefix('<') {
+        Some(generic_bounds) => format!("<'json, {generic_bounds}"),
+        None => "<'json>".to_string(),
+      };
+      format!(
+        r#"
+        impl{generic_bounds} core::str::FromStr for {name}{generics}
+          {default_bound} {{
+          type Err = core_json_traits::ErrorKind;
+          fn from_str(json: &str) -> Result<Self, core_json_traits::ErrorKind> {{
+            <Self as core::convert::TryFrom<&[u8]>>::try_from(json.as_bytes())
+          }}
+        }}
+        impl{generic_bounds_with_lifetime} core::convert::TryFrom<&'json [u8]> for {name}{generics}
+          {default_bound} {{
+          type Error = core_json_traits::ErrorKind;
+          fn try_from(json: &'json [u8]) -> Result<Self, core_json_traits::ErrorKind> {{
+            let mut deserializer = core_json_traits::Deserializer::<
+              _,
+              core_json_traits::ConstStack<{stack}>,
+            >::new(json)?;
+            Ok(<Self as core_json_traits::JsonDeserialize>::deserialize(deserializer.value()?)?)
+          }}
+        }}
+        "#
+      )
+    })
+    .unwrap_or_default();
+
+  let round_trip_test = round_trip_test
+    .map(|samples| {
+      let samples =
+        samples.iter().map(|sample| format!("round_trip({sample}());")).collect::<String>();
+      format!(
+        r#"
+        #[cfg(test)]
+        #[test]
+        #[allow(non_snake_case)]
+        fn {name}_round_trip() {{
+          extern crate std;
+          use std::string::String;
+
+          // Serialize within an array, as the serialization may not be a structure
+          fn round_trip(sample: {name}) {{
+            let serialization =
+              core_json_traits::JsonSerialize::serialize(&[sample]).collect::<String>();
+            let deserialized =
+              <[{name}; 1] as core_json_traits::JsonStructure>::deserialize_structure::<
+                _,
+                core_json_traits::ConstStack<128>,
+              >(serialization.as_bytes())
+              .expect("couldn't deserialize the serialization of `{name}`");
+            assert_eq!(
+              core_json_traits::JsonSerialize::serialize(&deserialized).collect::<String>(),
+              serialization,
+              "`{name}` didn't round-trip",
+            );
+          }}
+          {samples}
+        }}
+        "#
+      )
+    })
+    .unwrap_or_default();
+
   TokenStream::from_str(&format!(
     r#"
     impl{generic_bounds} core_json_traits::JsonDeserialize for {name}{generics}
-      where Self: core::default::Default {{
+      {default_bound} {{
       fn deserialize<
         'read,
         'parent,
@@ -246,65 +901,78 @@ pub fn derive_json_deserialize(object: TokenStream) -> TokenStream {
       >(
         value: core_json_traits::Value<'read, 'parent, B, S>,
       ) -> Result<Self, core_json_traits::JsonError<'read, B, S>> {{
-        use core::default::Default;
+        {deserialization}
+        Ok({assembly})
+      }}
+    }}
+    {structure_and_fields_impls}
+    {from_str}
+    {round_trip_test}
+    "#
+  ))
+  .expect("typo in implementation of `JsonDeserialize`")
+}
 
-        let mut result = Self::default();
-        if {largest_key} == 0 {{
-          return Ok(result);
+// The implementation of `JsonWireFormat`, describing the serialization derived for `JsonSerialize`.
+fn derive_json_wire_format(
+  generic_bounds: &str,
+  generics: &str,
+  name: &str,
+  fields: &[Field],
+  transparent: bool,
+  null: bool,
+) -> String {
+  let mut bounds = String::new();
+  let mut description = String::new();
+  if null {
+    description.push_str(r#"f.write_str("null")?;"#);
+  } else if transparent {
+    let ty = &fields[0].ty;
+    bounds.push_str(&format!("{ty}: core_json_traits::JsonWireFormat,"));
+    description.push_str(&format!("<{ty} as core_json_traits::JsonWireFormat>::wire_format(f)?;"));
+  } else {
+    description.push_str(r#"f.write_str("{")?;"#);
+    for (i, Field { ty, serialization_name, omit_none, .. }) in fields.iter().enumerate() {
+      // A field omitted when `None` is described by the type within the `Option`
+      let ty = if *omit_none {
+        let ty = ty.to_string();
+        let start = ty.find('<').expect("`omit_none` field's type wasn't `Option<...>`");
+        let end = ty.rfind('>').expect("`omit_none` field's type wasn't `Option<...>`");
+        ty[(start + 1) .. end].to_string()
+      } else {
+        ty.to_string()
+      };
+      let optional = if *omit_none { "?" } else { "" };
+      let separator = if i == 0 { " " } else { ", " };
+      bounds.push_str(&format!("{ty}: core_json_traits::JsonWireFormat,"));
+      description.push_str(&format!(
+        r#"
+        f.write_str("{separator}")?;
+        for key_char in core_json_traits::JsonSerialize::serialize("{serialization_name}") {{
+          f.write_char(key_char)?;
         }}
+        f.write_str("{optional}: ")?;
+        <{ty} as core_json_traits::JsonWireFormat>::wire_format(f)?;
+        "#
+      ));
+    }
+    let end = if fields.is_empty() { "}" } else { " }" };
+    description.push_str(&format!(r#"f.write_str("{end}")?;"#));
+  }
 
-        let mut key_chars = ['\0'; {largest_key}];
-        let mut object = value.fields()?;
-        'serialized_field: while let Some(field) = object.next() {{
-          let mut field = field?;
-
-          let key = {{
-            let key = field.key()?;
-            let mut key_len = 0;
-            while let Some(key_char) = key.next() {{
-              key_chars[key_len] = match key_char {{
-                Ok(key_char) => key_char,
-                /*
-                  This occurs when the key specifies an invalid UTF codepoint, which is technically
-                  allowed by RFC 8259. While it means we can't interpret the key, it also means
-                  this isn't a field we're looking for.
-
-                  Continue to the next serialized field accordingly.
-                */
-                Err(core_json_traits::JsonError::InvalidValue) => continue 'serialized_field,
-                // Propagate all other errors.
-                Err(e) => Err(e)?,
-              }};
-              key_len += 1;
-              if key_len == {largest_key} {{
-                break;
-              }}
-            }}
-            match key.next() {{
-              None => {{}},
-              // This key is larger than our largest key
-              Some(Ok(_)) => continue,
-              Some(Err(e)) => Err(e)?,
-            }}
-            &key_chars[.. key_len]
-          }};
-          let value = field.value()?;
-
-          match key {{
-            {fields_deserialization}
-            // Skip unknown fields
-            _ => {{}}
-          }}
-        }}
+  format!(
+    r#"
+    impl{generic_bounds} core_json_traits::JsonWireFormat for {name}{generics} where {bounds} {{
+      fn wire_format(f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {{
+        #[allow(unused_imports)]
+        use core::fmt::Write;
 
-        Ok(result)
+        {description}
+        Ok(())
       }}
     }}
-    impl{generic_bounds} core_json_traits::JsonStructure for {name}{generics}
-      where Self: core::default::Default {{}}
     "#
-  ))
-  .expect("typo in implementation of `JsonDeserialize`")
+  )
 }
 
 /// Derive an implementation of the `JsonSerialize` trait.
@@ -313,13 +981,130 @@ pub fn derive_json_deserialize(object: TokenStream) -> TokenStream {
 /// literal for the key to serialize as (`key("key")`). Fields may be omitted from serialization
 /// with the `skip` attribute.
 ///
+/// `Option` fields with the `omit_none` attribute will be omitted from serialization when `None`,
+/// instead of being serialized as `null`. If the `struct` has the `omit_none` attribute, this
+/// applies to all of its fields whose type is written as `Option<...>`.
+///
+/// If the `struct` has the `transparent` attribute, it's serialized exactly as its sole
+/// non-skipped field would be (instead of as an object).
+///
+/// Unit `struct`s are serialized as `{}`, or as `null` if they have the `null` attribute.
+///
+/// If the `struct` has the `static_template` attribute, the keys and punctuation between the
+/// fields' values are assembled into static segments when derived, instead of being serialized at
+/// run time. This requires no fields have the `omit_none` attribute or are written as `Tri<...>`,
+/// and that no keys require escaping.
+///
+/// If the `struct` has the `wire_format` attribute, `JsonWireFormat` is also derived, describing
+/// the shape of the serialization. This requires the types of the fields implement
+/// `JsonWireFormat`.
+///
+/// If `core-json-traits` has the `validate-output` feature enabled, the serialization is checked
+/// to be valid JSON when debug assertions are enabled, panicking otherwise.
+///
 /// As a procedural macro, this will panic causing a compile-time error on any unexpected input.
-#[proc_macro_derive(JsonSerialize, attributes(key, skip))]
+#[proc_macro_derive(
+  JsonSerialize,
+  attributes(key, skip, omit_none, transparent, null, wire_format, static_template)
+)]
 pub fn derive_json_serialize(object: TokenStream) -> TokenStream {
-  let Struct { generic_bounds, generics, name, fields } = parse_struct(object);
+  let Struct {
+    generic_bounds,
+    generics,
+    name,
+    fields,
+    skipped: _,
+    from_str: _,
+    transparent,
+    no_default: _,
+    null,
+    wire_format,
+    static_template,
+    fields_in_order: _,
+    round_trip_test: _,
+  } = parse_struct(object);
+
+  let wire_format = if wire_format {
+    derive_json_wire_format(&generic_bounds, &generics, &name, &fields, transparent, null)
+  } else {
+    String::new()
+  };
+
+  if null {
+    return TokenStream::from_str(&format!(
+      r#"
+      impl core_json_traits::JsonSerialize for {name} {{
+        fn serialize(&self) -> impl Iterator<Item = char> {{
+          core_json_traits::validate_serialization(move || "null".chars())
+        }}
+      }}
+      {wire_format}
+      "#
+    ))
+    .expect("typo in implementation of `JsonSerialize`");
+  }
+
+  if transparent {
+    let field_name = &fields[0].name;
+    return TokenStream::from_str(&format!(
+      r#"
+      impl{generic_bounds} core_json_traits::JsonSerialize for {name}{generics} {{
+        fn serialize(&self) -> impl Iterator<Item = char> {{
+          core_json_traits::validate_serialization(move || {{
+            core_json_traits::JsonSerialize::serialize(&self.{field_name})
+          }})
+        }}
+      }}
+      {wire_format}
+      "#
+    ))
+    .expect("typo in implementation of `JsonSerialize`");
+  }
+
+  if static_template {
+    /*
+      As every field is serialized and no key requires escaping, the keys and punctuation between
+      the fields' values are constant. They're assembled here, into the segments yielded between
+      the values, instead of serializing each key at run time.
+    */
+    let mut segment = "{".to_string();
+    let mut serialization = String::new();
+    for (i, Field { name: field_name, serialization_name, .. }) in fields.iter().enumerate() {
+      if i != 0 {
+        segment.push(',');
+      }
+      segment.push_str(&format!(r#""{serialization_name}":"#));
+      serialization.push_str(&format!(
+        ".chain({segment:?}.chars())
+        .chain(core_json_traits::JsonSerialize::serialize(&self.{field_name}))"
+      ));
+      segment = String::new();
+    }
+    segment.push('}');
+    return TokenStream::from_str(&format!(
+      r#"
+      impl{generic_bounds} core_json_traits::JsonSerialize for {name}{generics} {{
+        fn serialize(&self) -> impl Iterator<Item = char> {{
+          core_json_traits::validate_serialization(move || {{
+            core::iter::empty(){serialization}.chain({segment:?}.chars())
+          }})
+        }}
+      }}
+      {wire_format}
+      "#
+    ))
+    .expect("typo in implementation of `JsonSerialize`");
+  }
 
   let mut fields_serialization = String::new();
-  for (field_name, serialization_field_name) in &fields {
+  for Field { name: field_name, serialization_name: serialization_field_name, omit_none, .. } in
+    &fields
+  {
+    let tri = if *omit_none {
+      format!("match &self.{field_name} {{ Some(value) => Tri::Some(value), None => Tri::None }}")
+    } else {
+      format!("Tri::from(&self.{field_name})")
+    };
     fields_serialization.push_str(&format!(
       r#"
       .chain({{
@@ -328,7 +1113,7 @@ pub fn derive_json_serialize(object: TokenStream) -> TokenStream {
             .chain("{serialization_field_name}".serialize())
             .chain(core::iter::once(':'));
 
-        let (field, value, value_null) = match Tri::from(&self.{field_name}) {{
+        let (field, value, value_null) = match {tri} {{
           Tri::Some(value) => {{
             writ_prior_field = true;
             (Some(field), Some(core_json_traits::JsonSerialize::serialize(value)), None)
@@ -353,12 +1138,15 @@ pub fn derive_json_serialize(object: TokenStream) -> TokenStream {
       fn serialize(&self) -> impl Iterator<Item = char> {{
         use core_json_traits::Tri;
 
-        let mut writ_prior_field = false;
-        core::iter::once('{{')
-        {fields_serialization}
-        .chain(core::iter::once('}}'))
+        core_json_traits::validate_serialization(move || {{
+          let mut writ_prior_field = false;
+          core::iter::once('{{')
+          {fields_serialization}
+          .chain(core::iter::once('}}'))
+        }})
       }}
     }}
+    {wire_format}
     "#
   ))
   .expect("typo in implementation of `JsonSerialize`")
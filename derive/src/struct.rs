@@ -10,6 +10,7 @@ pub(crate) struct StructField {
   pub(crate) attributes: Vec<TokenStream>,
   pub(crate) visibility: TokenStream,
   pub(crate) identifier: Identifier,
+  pub(crate) ty: TokenStream,
 }
 
 /// Parse a potentially-present `OuterAttribute`, returning the contained `Attr`.
@@ -66,19 +67,22 @@ impl StructField {
       "colon between identifier and type wasn't independent"
     );
 
-    // TODO: Parse types properly, instead of just skipping them
+    // TODO: Parse types properly, instead of just collecting them
+    let mut ty = vec![];
     while let Some(item) = {
-      crate::take_angle_expression(iter);
+      ty.extend(crate::take_angle_expression(iter));
       iter.next()
     } {
-      if let TokenTree::Punct(comma) = item {
+      if let TokenTree::Punct(comma) = &item {
         if (comma.as_char() == ',') && matches!(comma.spacing(), Spacing::Alone) {
           break;
         }
       }
+      ty.push(item);
     }
+    let ty = TokenStream::from_iter(ty);
 
-    Some(StructField { attributes, visibility, identifier })
+    Some(StructField { attributes, visibility, identifier, ty })
   }
 }
 
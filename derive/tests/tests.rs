@@ -145,6 +145,18 @@ mod test_mod {
       [res],
     );
   }
+
+  #[test]
+  fn fields() {
+    use core_json_traits::JsonFields;
+
+    assert_eq!(
+      MyStruct::<WithoutT>::FIELDS,
+      ["abc", "de", "ghij", "klmo", "missing", "float"],
+      "`skip` field included or fields out of order",
+    );
+    assert_eq!(WithoutT::field_names(), ["xyza", "de", "ghij", "hash"]);
+  }
 }
 
 #[test]
@@ -171,3 +183,581 @@ fn tri() {
     );
   }
 }
+
+#[test]
+fn from_str() {
+  use core_json_traits::{ErrorKind, SyntaxError};
+  use core_json_derive::JsonDeserialize;
+
+  #[derive(PartialEq, Eq, Default, Debug, JsonDeserialize)]
+  #[from_str(1)]
+  struct Config<T: Default + core_json_traits::JsonDeserialize> {
+    name: String,
+    value: T,
+  }
+
+  let config: Config<u64> = r#"{ "name": "a", "value": 1 }"#.parse().unwrap();
+  assert_eq!(config, Config { name: "a".to_string(), value: 1 });
+  assert_eq!(
+    Config::<u64>::try_from(br#"{ "name": "a", "value": 1 }"#.as_slice()).unwrap(),
+    config
+  );
+  assert_eq!(
+    r#"{ "name": "a", }"#.parse::<Config<u64>>(),
+    Err(ErrorKind::SyntaxError(SyntaxError::TrailingComma))
+  );
+  // The depth is bounded by the `ConstStack` specified
+  assert_eq!(
+    r#"{ "name": "a", "value": [[[[1]]]] }"#.parse::<Config<Vec<Vec<Vec<Vec<u64>>>>>>(),
+    Err(ErrorKind::StackError)
+  );
+}
+
+#[test]
+fn omit_none() {
+  use core_json_traits::{Tri, JsonSerialize};
+  use core_json_derive::JsonSerialize;
+
+  #[derive(Default, JsonSerialize)]
+  struct Fields {
+    #[omit_none]
+    omitted: Option<u64>,
+    null: Option<u64>,
+  }
+  assert_eq!(Fields::default().serialize().collect::<String>(), r#"{"null":null}"#);
+  assert_eq!(
+    Fields { omitted: Some(1), null: Some(2) }.serialize().collect::<String>(),
+    r#"{"omitted":1,"null":2}"#
+  );
+
+  #[derive(Default, JsonSerialize)]
+  #[omit_none]
+  struct Container {
+    a: core::option::Option<u64>,
+    b: u64,
+    c: Option<Option<u64>>,
+    d: Tri<u64>,
+  }
+  assert_eq!(Container::default().serialize().collect::<String>(), r#"{"b":0}"#);
+  assert_eq!(
+    Container { a: Some(1), b: 2, c: Some(None), d: Tri::Null }.serialize().collect::<String>(),
+    r#"{"a":1,"b":2,"c":null,"d":null}"#
+  );
+}
+
+#[test]
+fn transparent() {
+  use core_json_traits::{JsonDeserialize, JsonStructure, JsonSerialize, ConstStack};
+  use core_json_derive::{JsonDeserialize, JsonSerialize};
+
+  #[derive(PartialEq, Eq, Default, Debug, JsonDeserialize, JsonSerialize)]
+  #[transparent]
+  struct UserId {
+    id: u64,
+  }
+
+  #[derive(PartialEq, Eq, Default, Debug, JsonDeserialize, JsonSerialize)]
+  #[transparent]
+  #[from_str(2)]
+  struct Users<T: Default + JsonDeserialize + JsonSerialize> {
+    #[skip]
+    cached: bool,
+    ids: Vec<T>,
+  }
+
+  #[derive(PartialEq, Eq, Default, Debug, JsonDeserialize, JsonSerialize)]
+  struct User {
+    id: UserId,
+  }
+
+  let user = User { id: UserId { id: 5 } };
+  assert_eq!(user.serialize().collect::<String>(), r#"{"id":5}"#);
+  assert_eq!(User::deserialize_structure::<_, ConstStack<2>>(&br#"{"id":5}"#[..]).unwrap(), user);
+
+  let users = Users { cached: false, ids: vec![UserId { id: 1 }, UserId { id: 2 }] };
+  assert_eq!(users.serialize().collect::<String>(), "[1,2]");
+  assert_eq!("[1, 2]".parse::<Users<UserId>>().unwrap(), users);
+  assert!(r#"{"ids":[1,2]}"#.parse::<Users<UserId>>().is_err());
+}
+
+mod round_trip {
+  use core_json_derive::{JsonDeserialize, JsonSerialize};
+
+  #[derive(Default, JsonDeserialize, JsonSerialize)]
+  #[round_trip_test(sample)]
+  struct Message {
+    id: u64,
+    #[key("sentBy")]
+    sent_by: Option<String>,
+    #[skip]
+    #[allow(dead_code)]
+    received: bool,
+    tags: Vec<String>,
+  }
+
+  fn sample() -> Message {
+    Message {
+      id: 1,
+      sent_by: Some("\"a\"".to_string()),
+      received: true,
+      tags: vec!["b".to_string(), String::new()],
+    }
+  }
+
+  #[derive(Default, JsonDeserialize, JsonSerialize)]
+  #[transparent]
+  #[round_trip_test]
+  struct Id {
+    id: u64,
+  }
+
+  #[test]
+  fn generated() {
+    // The tests themselves are generated, yet we ensure they were by calling them
+    Message_round_trip();
+    Id_round_trip();
+  }
+}
+
+#[test]
+fn no_default() {
+  use core_json_traits::{ErrorKind, JsonDeserialize, JsonStructure, ConstStack, Tri};
+  use core_json_derive::JsonDeserialize;
+
+  // Neither the `struct` nor the type of its required field implement `Default`
+  #[derive(PartialEq, Eq, Debug)]
+  struct NonZero(core::num::NonZeroU64);
+  impl JsonDeserialize for NonZero {
+    fn deserialize<'read, 'parent, B: core_json_traits::Read<'read>, S: core_json_traits::Stack>(
+      value: core_json_traits::Value<'read, 'parent, B, S>,
+    ) -> Result<Self, core_json_traits::JsonError<'read, B, S>> {
+      core::num::NonZeroU64::new(u64::deserialize(value)?)
+        .map(NonZero)
+        .ok_or(core_json_traits::JsonError::TypeError(core_json_traits::TypeError))
+    }
+  }
+
+  #[derive(PartialEq, Eq, Debug, JsonDeserialize)]
+  #[no_default]
+  #[from_str(2)]
+  struct Account<T: JsonDeserialize> {
+    id: NonZero,
+    #[key("displayName")]
+    name: Option<String>,
+    flags: Tri<T>,
+    #[skip]
+    cached: bool,
+  }
+
+  assert_eq!(
+    r#"{ "id": 1, "displayName": "a", "flags": 2, "cached": true }"#.parse::<Account<u8>>(),
+    Ok(Account {
+      id: NonZero(1.try_into().unwrap()),
+      name: Some("a".to_string()),
+      flags: Tri::Some(2),
+      cached: false,
+    })
+  );
+  // Optional fields may be omitted
+  assert_eq!(
+    r#"{ "id": 2 }"#.parse::<Account<u8>>(),
+    Ok(Account { id: NonZero(2.try_into().unwrap()), name: None, flags: Tri::None, cached: false })
+  );
+  // Required fields may not
+  assert_eq!(r#"{ "displayName": "a" }"#.parse::<Account<u8>>(), Err(ErrorKind::TypeError));
+  assert_eq!(r#"{ "id": 0 }"#.parse::<Account<u8>>(), Err(ErrorKind::TypeError));
+
+  #[derive(PartialEq, Eq, Debug, JsonDeserialize)]
+  #[no_default]
+  #[transparent]
+  struct Id {
+    id: NonZero,
+  }
+  assert_eq!(
+    <Vec<Id>>::deserialize_structure::<_, ConstStack<2>>(&b"[3]"[..]).unwrap(),
+    [Id { id: NonZero(3.try_into().unwrap()) }]
+  );
+}
+
+#[test]
+fn range() {
+  use core_json_traits::{ErrorKind, NotRepresentable, JsonF64, JsonStructure, ConstStack};
+  use core_json_derive::JsonDeserialize;
+
+  #[derive(PartialEq, Debug, JsonDeserialize)]
+  #[no_default]
+  #[from_str(2)]
+  struct Settings {
+    #[range(min = 0, max = 100)]
+    volume: u8,
+    #[range(min = -1)]
+    offset: i64,
+    #[range(max = 1.0)]
+    ratio: JsonF64,
+    #[key("limit")]
+    #[range(min = 0.5, max = 2.5)]
+    optional_limit: Option<f64>,
+  }
+
+  #[derive(Default, PartialEq, Eq, Debug, JsonDeserialize)]
+  #[transparent]
+  struct Percent {
+    #[range(max = 100)]
+    percent: u8,
+  }
+
+  let settings = |volume, offset, ratio, limit: &str| {
+    format!(r#"{{ "volume": {volume}, "offset": {offset}, "ratio": {ratio}, "limit": {limit} }}"#)
+      .parse::<Settings>()
+  };
+  assert_eq!(
+    settings(100, -1, 1.0, "0.5"),
+    Ok(Settings {
+      volume: 100,
+      offset: -1,
+      ratio: JsonF64::try_from(1.0).unwrap(),
+      optional_limit: Some(0.5)
+    })
+  );
+  assert!(settings(0, i64::MAX, -1e300, "null").is_ok());
+  let out_of_bounds = ErrorKind::NumberNotRepresentable(NotRepresentable::OutOfBounds);
+  assert_eq!(settings(101, 0, 0.0, "null"), Err(out_of_bounds));
+  assert_eq!(settings(0, -2, 0.0, "null"), Err(out_of_bounds));
+  assert_eq!(settings(0, 0, 1.5, "null"), Err(out_of_bounds));
+  assert_eq!(settings(0, 0, 0.0, "2.6"), Err(out_of_bounds));
+
+  let percent = |serialization: &str| {
+    <[Percent; 1]>::deserialize_structure::<_, ConstStack<1>>(serialization.as_bytes())
+      .map_err(ErrorKind::from)
+  };
+  assert_eq!(percent("[50]"), Ok([Percent { percent: 50 }]));
+  assert_eq!(percent("[101]"), Err(out_of_bounds));
+}
+
+#[test]
+fn string_constraints() {
+  use core_json_traits::ErrorKind;
+  use core_json_derive::JsonDeserialize;
+
+  #[derive(Default, PartialEq, Eq, Debug, JsonDeserialize)]
+  #[from_str(1)]
+  struct User {
+    #[max_len(4)]
+    name: String,
+    #[ascii_only]
+    #[max_len(2)]
+    code: Option<String>,
+  }
+
+  assert_eq!(
+    r#"{ "name": "\u00e9t\u00e9", "code": "ab" }"#.parse::<User>(),
+    Ok(User { name: "été".to_string(), code: Some("ab".to_string()) })
+  );
+  assert_eq!(
+    r#"{ "name": "abcd", "code": null }"#.parse::<User>(),
+    Ok(User { name: "abcd".to_string(), code: None })
+  );
+  assert_eq!(r#"{ "name": "abcde" }"#.parse::<User>(), Err(ErrorKind::LengthLimitExceeded));
+  assert_eq!(r#"{ "code": "abc" }"#.parse::<User>(), Err(ErrorKind::LengthLimitExceeded));
+  assert_eq!(r#"{ "code": "\u00e9" }"#.parse::<User>(), Err(ErrorKind::TypeError));
+
+  // The string is rejected once its length is exceeded, without reading the rest of it
+  let mut serialization = r#"{ "name": "abcde"#.to_string();
+  serialization.push_str(&"\\x".repeat(1024));
+  assert_eq!(serialization.parse::<User>(), Err(ErrorKind::LengthLimitExceeded));
+}
+
+#[test]
+fn unit() {
+  use core::marker::PhantomData;
+  use core_json_traits::{ErrorKind, JsonFields, JsonSerialize, JsonStructure, ConstStack};
+  use core_json_derive::{JsonDeserialize, JsonSerialize};
+
+  // Neither `struct` implements `Default`
+  #[derive(PartialEq, Eq, Debug, JsonDeserialize, JsonSerialize)]
+  #[from_str(1)]
+  struct Ping;
+  #[derive(PartialEq, Eq, Debug, JsonDeserialize, JsonSerialize)]
+  #[null]
+  struct Pong;
+
+  assert_eq!(Ping.serialize().collect::<String>(), "{}");
+  assert_eq!("{}".parse::<Ping>(), Ok(Ping));
+  assert!(Ping::FIELDS.is_empty());
+  assert_eq!(Pong.serialize().collect::<String>(), "null");
+  assert_eq!(
+    <Vec<Pong>>::deserialize_structure::<_, ConstStack<2>>(&b"[null]"[..]).unwrap(),
+    [Pong]
+  );
+  assert!(<Vec<Pong>>::deserialize_structure::<_, ConstStack<2>>(&b"[{}]"[..]).is_err());
+  assert_eq!(
+    <Vec<Ping>>::deserialize_structure::<_, ConstStack<3>>(&br#"[{}, {"a": [1]}]"#[..]).unwrap(),
+    [Ping, Ping]
+  );
+  for invalid in [&b"[5]"[..], br#"["s"]"#, b"[[]]"] {
+    assert!(matches!(
+      <Vec<Ping>>::deserialize_structure::<_, ConstStack<3>>(invalid),
+      Err(core_json_traits::JsonError::TypeError(_))
+    ));
+    assert!(matches!(
+      <Vec<Pong>>::deserialize_structure::<_, ConstStack<3>>(invalid),
+      Err(core_json_traits::JsonError::TypeError(_))
+    ));
+  }
+
+  // `PhantomData` fields may be omitted from `no_default` `struct`s
+  #[derive(PartialEq, Eq, Debug, JsonDeserialize)]
+  #[no_default]
+  #[from_str(1)]
+  struct Message<T> {
+    id: u64,
+    kind: PhantomData<T>,
+  }
+  assert_eq!(r#"{"id": 1}"#.parse::<Message<Ping>>(), Ok(Message { id: 1, kind: PhantomData }));
+  assert_eq!("{}".parse::<Message<Ping>>(), Err(ErrorKind::TypeError));
+}
+
+#[test]
+fn wire_format() {
+  use std::collections::BTreeMap;
+  use core_json_traits::{JsonSerialize, JsonWireFormat, WireFormat};
+  use core_json_derive::JsonSerialize;
+
+  #[derive(JsonSerialize)]
+  #[wire_format]
+  #[null]
+  struct Empty;
+  #[derive(JsonSerialize)]
+  #[wire_format]
+  #[transparent]
+  struct Id {
+    id: u64,
+  }
+  #[derive(JsonSerialize)]
+  #[wire_format]
+  struct Tag<T: JsonSerialize + JsonWireFormat> {
+    #[key("na\"me")]
+    name: T,
+    #[skip]
+    _skipped: Empty,
+  }
+  #[derive(JsonSerialize)]
+  #[wire_format]
+  #[omit_none]
+  struct Item {
+    id: Id,
+    tags: Vec<Tag<String>>,
+    description: Option<String>,
+    attributes: BTreeMap<String, Option<bool>>,
+    empty: Empty,
+  }
+
+  assert_eq!(WireFormat::<Empty>::new().to_string(), "null");
+  assert_eq!(WireFormat::<Id>::new().to_string(), "integer");
+  assert_eq!(
+    WireFormat::<Item>::new().to_string(),
+    concat!(
+      r#"{ "id": integer, "tags": [{ "na\"me": string }], "description"?: string, "#,
+      r#""attributes": { [string]: boolean | null }, "empty": null }"#,
+    )
+  );
+}
+
+#[test]
+fn static_template() {
+  use core_json_traits::JsonSerialize;
+  use core_json_derive::JsonSerialize;
+
+  #[derive(JsonSerialize)]
+  #[static_template]
+  struct Empty {}
+  #[derive(JsonSerialize)]
+  struct Dynamic<T: JsonSerialize> {
+    id: u64,
+    #[key("displayName")]
+    name: String,
+    description: Option<String>,
+    #[skip]
+    _skipped: u8,
+    inner: T,
+  }
+  #[derive(JsonSerialize)]
+  #[static_template]
+  struct Static<T: JsonSerialize> {
+    id: u64,
+    #[key("displayName")]
+    name: String,
+    description: Option<String>,
+    #[skip]
+    _skipped: u8,
+    inner: T,
+  }
+
+  assert_eq!(Empty {}.serialize().collect::<String>(), "{}");
+  for description in [None, Some("A \"description\"".to_string())] {
+    let dynamic = Dynamic {
+      id: 1,
+      name: "name".to_string(),
+      description: description.clone(),
+      _skipped: 0,
+      inner: vec![1u8, 2],
+    };
+    let r#static =
+      Static { id: 1, name: "name".to_string(), description, _skipped: 0, inner: Empty {} };
+    assert_eq!(
+      r#static.serialize().collect::<String>(),
+      dynamic.serialize().collect::<String>().replace("[1,2]", "{}"),
+    );
+  }
+}
+
+#[test]
+fn fields_in_order() {
+  use core_json_derive::JsonDeserialize;
+
+  #[derive(PartialEq, Eq, Default, Debug, JsonDeserialize)]
+  #[from_str(2)]
+  struct ByKey {
+    id: u64,
+    #[key("displayName")]
+    name: String,
+    tags: Vec<String>,
+    #[skip]
+    cached: bool,
+    description: Option<String>,
+  }
+  #[derive(PartialEq, Eq, Default, Debug, JsonDeserialize)]
+  #[fields_in_order]
+  #[from_str(2)]
+  struct InOrder {
+    id: u64,
+    #[key("displayName")]
+    name: String,
+    tags: Vec<String>,
+    #[skip]
+    cached: bool,
+    description: Option<String>,
+  }
+  #[derive(PartialEq, Eq, Debug, JsonDeserialize)]
+  #[fields_in_order]
+  #[no_default]
+  #[from_str(2)]
+  struct InOrderWithoutDefault {
+    id: u64,
+    #[key("displayName")]
+    name: String,
+    tags: Option<Vec<String>>,
+  }
+
+  for serialization in [
+    // In order
+    r#"{"id": 1, "displayName": "a", "tags": ["b"], "description": "c"}"#,
+    // Out of order
+    r#"{"description": "c", "tags": ["b"], "displayName": "a", "id": 1}"#,
+    r#"{"displayName": "a", "id": 1, "description": "c", "tags": ["b"]}"#,
+    // With unknown fields, and fields which are skipped, interspersed
+    r#"{"unknown": 0, "id": 1, "cached": true, "displayName": "a", "tags": ["b"], "i": 2,
+      "description": "c", "displayNameLonger": 3}"#,
+    // With fields missing
+    r#"{"id": 1, "tags": ["b"]}"#,
+    r#"{"tags": ["b"], "displayName": "a"}"#,
+    // With fields present multiple times
+    r#"{"id": 1, "displayName": "a", "id": 2, "displayName": "b", "tags": []}"#,
+    r#"{"id": 1, "id": 2, "tags": [], "tags": ["b"]}"#,
+    // With keys which are escaped
+    r#"{"\u0069d": 1, "displayName": "a"}"#,
+    "{}",
+  ] {
+    let by_key = serialization.parse::<ByKey>().unwrap();
+    let in_order = serialization.parse::<InOrder>().unwrap();
+    assert_eq!(
+      (by_key.id, by_key.name, by_key.tags, by_key.cached, by_key.description),
+      (in_order.id, in_order.name, in_order.tags, in_order.cached, in_order.description),
+    );
+  }
+
+  assert_eq!(
+    r#"{"id": 1, "displayName": "a", "tags": ["b"]}"#.parse::<InOrderWithoutDefault>(),
+    Ok(InOrderWithoutDefault { id: 1, name: "a".to_string(), tags: Some(vec!["b".to_string()]) })
+  );
+  assert_eq!(
+    r#"{"displayName": "a", "id": 1}"#.parse::<InOrderWithoutDefault>(),
+    Ok(InOrderWithoutDefault { id: 1, name: "a".to_string(), tags: None })
+  );
+  assert!(r#"{"id": 1, "tags": []}"#.parse::<InOrderWithoutDefault>().is_err());
+}
+
+#[test]
+#[should_panic(expected = "serialization wasn't valid JSON")]
+fn validate_output() {
+  use core_json_traits::JsonSerialize;
+  use core_json_derive::JsonSerialize;
+
+  // A serializer which doesn't quote its string
+  struct Unquoted;
+  impl JsonSerialize for Unquoted {
+    fn serialize(&self) -> impl Iterator<Item = char> {
+      "unquoted".chars()
+    }
+  }
+
+  #[derive(JsonSerialize)]
+  struct Valid {
+    value: u64,
+  }
+  assert_eq!(Valid { value: 1 }.serialize().collect::<String>(), r#"{"value":1}"#);
+
+  #[derive(JsonSerialize)]
+  struct Invalid {
+    value: Unquoted,
+  }
+  let _ = Invalid { value: Unquoted }.serialize();
+}
+
+#[test]
+fn validate_output_once() {
+  use core::sync::atomic::{AtomicUsize, Ordering};
+  use core_json_traits::JsonSerialize;
+  use core_json_derive::JsonSerialize;
+
+  // A serializer which counts how many times it was called
+  static SERIALIZATIONS: AtomicUsize = AtomicUsize::new(0);
+  struct Counted;
+  impl JsonSerialize for Counted {
+    fn serialize(&self) -> impl Iterator<Item = char> {
+      SERIALIZATIONS.fetch_add(1, Ordering::Relaxed);
+      "null".chars()
+    }
+  }
+
+  #[derive(JsonSerialize)]
+  struct First {
+    value: Counted,
+  }
+  #[derive(JsonSerialize)]
+  struct Second {
+    first: First,
+  }
+  #[derive(JsonSerialize)]
+  struct Third {
+    second: Second,
+  }
+  #[derive(JsonSerialize)]
+  struct Fourth {
+    third: Third,
+  }
+
+  let fourth = Fourth { third: Third { second: Second { first: First { value: Counted } } } };
+  assert_eq!(
+    fourth.serialize().collect::<String>(),
+    r#"{"third":{"second":{"first":{"value":null}}}}"#
+  );
+  // Solely the outermost serialization was validated, serializing the value one additional time
+  let expected = if cfg!(debug_assertions) { 2 } else { 1 };
+  assert_eq!(SERIALIZATIONS.load(Ordering::Relaxed), expected);
+
+  // Nested serializations are still validated when serialized on their own
+  SERIALIZATIONS.store(0, Ordering::Relaxed);
+  assert_eq!(First { value: Counted }.serialize().collect::<String>(), r#"{"value":null}"#);
+  assert_eq!(SERIALIZATIONS.load(Ordering::Relaxed), expected);
+}
@@ -32,6 +32,14 @@ impl<R: Read<Error: Copy>> CjRead<'static> for ReadAdapter<R> {
   fn read_exact(&mut self, slice: &mut [u8]) -> Result<(), Self::Error> {
     self.reader.read_exact(slice)
   }
+
+  #[inline(always)]
+  fn read_into_buffer(&mut self, buffer: &mut [u8]) -> Result<usize, Self::Error> {
+    match self.reader.read(buffer).map_err(ReadExactError::Other)? {
+      0 => Err(ReadExactError::UnexpectedEof),
+      len => Ok(len),
+    }
+  }
 }
 
 #[test]
@@ -10,6 +10,8 @@ mod tests {
     let mut serde = core::time::Duration::ZERO;
     #[cfg(not(debug_assertions))]
     let mut core = core::time::Duration::ZERO;
+    #[cfg(not(debug_assertions))]
+    let mut core_hybrid = core::time::Duration::ZERO;
 
     let mut i = 0;
     let mut folders = vec![std::path::PathBuf::from("./vectors")];
@@ -59,6 +61,20 @@ mod tests {
           }
           core += start.elapsed();
         }
+
+        #[cfg(not(debug_assertions))]
+        for _ in 0 .. 5000 {
+          let start = std::time::Instant::now();
+          let mut value =
+            core_json::Deserializer::<_, core_json::HybridStack<32>>::new(encoding.as_slice())
+              .unwrap();
+          let value = value.value().unwrap();
+          let mut fields = value.iterate().unwrap();
+          while let Some(field) = fields.next() {
+            field.unwrap();
+          }
+          core_hybrid += start.elapsed();
+        }
       }
     }
     assert_eq!(i, 80);
@@ -67,5 +83,127 @@ mod tests {
     dbg!(serde.as_millis());
     #[cfg(not(debug_assertions))]
     dbg!(core.as_millis());
+    #[cfg(not(debug_assertions))]
+    dbg!(core_hybrid.as_millis());
+  }
+  #[test]
+  fn conformance() {
+    use core_json_traits::{ConstStack, JsonStructure, JsonValue, JsonSchema};
+
+    // If a schema uses keywords which aren't supported by `JsonSchema`
+    fn unsupported(schema: &JsonValue) -> bool {
+      match schema {
+        JsonValue::Object(fields) => fields.iter().any(|(key, value)| {
+          matches!(
+            key.as_str(),
+            "pattern" |
+              "patternProperties" |
+              "unevaluatedItems" |
+              "unevaluatedProperties" |
+              "$id" |
+              "$anchor" |
+              "$dynamicRef" |
+              "$dynamicAnchor" |
+              "$vocabulary"
+          ) || ((key == "$ref") &&
+            !matches!(value, JsonValue::String(reference) if reference.starts_with('#'))) ||
+            unsupported(value)
+        }),
+        JsonValue::Array(elements) => elements.iter().any(unsupported),
+        _ => false,
+      }
+    }
+
+    // The vectors within `optional/` are for behavior not required by the specification
+    let mut i = 0;
+    for file in fs::read_dir("./vectors").unwrap() {
+      let file = file.unwrap();
+      if !file.file_name().to_str().unwrap().ends_with(".json") {
+        continue;
+      }
+
+      let encoding = fs::read(file.path()).unwrap();
+      let JsonValue::Array(cases) =
+        JsonValue::deserialize_structure::<_, ConstStack<128>>(encoding.as_slice()).unwrap()
+      else {
+        panic!("vectors weren't an array of cases")
+      };
+      for case in cases {
+        let schema = case.get("schema").unwrap();
+        if unsupported(schema) {
+          continue;
+        }
+        let schema = JsonSchema::compile(schema).unwrap();
+        let Some(JsonValue::Array(tests)) = case.get("tests") else {
+          panic!("case didn't have tests")
+        };
+        for test in tests {
+          let valid = test.get("valid") == Some(&JsonValue::Bool(true));
+          assert_eq!(
+            schema.is_valid(test.get("data").unwrap()),
+            valid,
+            "{:?}: {:?}, {:?}",
+            file.file_name(),
+            case.get("description"),
+            test.get("description"),
+          );
+          i += 1;
+        }
+      }
+    }
+    assert_eq!(i, 879);
+  }
+
+  #[test]
+  fn reference_cycles() {
+    use core_json_traits::{ConstStack, JsonStructure, JsonValue, JsonSchema, InvalidSchema};
+
+    let compile = |schema: &str| {
+      JsonSchema::compile(
+        &JsonValue::deserialize_structure::<_, ConstStack<32>>(schema.as_bytes()).unwrap(),
+      )
+    };
+
+    // References which apply themselves to the same instance are rejected
+    for schema in [
+      r##"{"$ref": "#"}"##,
+      r##"{"allOf": [{"not": {"$ref": "#"}}]}"##,
+      r##"{"$defs": {"a": {"$ref": "#/$defs/b"}, "b": {"anyOf": [{"$ref": "#/$defs/a"}]}},
+        "items": {"$ref": "#/$defs/a"}}"##,
+    ] {
+      assert_eq!(compile(schema).unwrap_err(), InvalidSchema, "{schema}");
+    }
+
+    // References which recurse into the instance are allowed
+    let schema = compile(r##"{"type": "array", "items": {"$ref": "#"}, "maxItems": 1}"##).unwrap();
+    let valid = |json: &str| {
+      schema
+        .is_valid(&JsonValue::deserialize_structure::<_, ConstStack<32>>(json.as_bytes()).unwrap())
+    };
+    assert!(valid("[[[]]]"));
+    assert!(!valid("[[[], []]]"));
+  }
+
+  #[test]
+  fn large_bounds() {
+    use core_json_traits::{ConstStack, JsonStructure, JsonValue, JsonSchema, InvalidSchema};
+
+    let value =
+      |json: &str| JsonValue::deserialize_structure::<_, ConstStack<32>>(json.as_bytes()).unwrap();
+
+    // Bounds beyond `u32::MAX`, including those beyond an `i64`, are accepted
+    for (schema, instance, valid) in [
+      (r#"{"items": {"maxLength": 4294967296}}"#, r#"["a"]"#, true),
+      (r#"{"maxItems": 18446744073709551615}"#, "[1]", true),
+      (r#"{"items": {"maxProperties": 1e30}}"#, r#"[{"a": 1}]"#, true),
+      (r#"{"minItems": 4294967296}"#, "[1]", false),
+      (r#"{"minItems": 18446744073709551615}"#, "[1]", false),
+    ] {
+      let compiled = JsonSchema::compile(&value(schema)).unwrap();
+      assert_eq!(compiled.is_valid(&value(instance)), valid, "{schema}");
+    }
+    for schema in [r#"{"maxItems": -1}"#, r#"{"maxItems": 1.5}"#] {
+      assert_eq!(JsonSchema::compile(&value(schema)).unwrap_err(), InvalidSchema, "{schema}");
+    }
   }
 }
@@ -26,15 +26,13 @@ mod tests {
       if matches!(name, "y_object_duplicated_key.json" | "y_object_duplicated_key_and_value.json") {
         let mut deserializer =
           core_json::Deserializer::<_, core_json::ConstStack<4>>::new(encoding.as_slice()).unwrap();
-        let mut value = deserializer.value().unwrap();
-        let is_object = matches!(value.kind().unwrap(), core_json::Type::Object);
-        let is_array = matches!(value.kind().unwrap(), core_json::Type::Array);
-        if is_object {
+        let value = deserializer.value().unwrap();
+        if value.is_object() {
           let mut fields = value.fields().unwrap();
           while let Some(field) = fields.next() {
             let _ = field.unwrap();
           }
-        } else if is_array {
+        } else if value.is_array() {
           let mut values = value.iterate().unwrap();
           while let Some(value) = values.next() {
             value.unwrap();
@@ -90,21 +88,15 @@ mod tests {
       else {
         continue;
       };
-      let Ok(mut value) = deserializer.value() else { continue };
-      let Ok(is_object) = value.kind().map(|kind| matches!(kind, core_json::Type::Object)) else {
-        continue;
-      };
-      let Ok(is_array) = value.kind().map(|kind| matches!(kind, core_json::Type::Array)) else {
-        continue;
-      };
-      if is_object {
+      let Ok(value) = deserializer.value() else { continue };
+      if value.is_object() {
         let Ok(mut fields) = value.fields() else { continue };
         while let Some(field) = fields.next() {
           if field.is_err() {
             continue 'outer;
           }
         }
-      } else if is_array {
+      } else if value.is_array() {
         let Ok(mut values) = value.iterate() else { continue };
         while let Some(value) = values.next() {
           if value.is_err() {
@@ -117,4 +109,186 @@ mod tests {
     }
     assert_eq!(i, 188);
   }
+
+  /// The outcome of deserializing a document.
+  #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+  enum Outcome {
+    /// The document was accepted, with all of its strings being UTF-8.
+    Accepted,
+    /// The document was accepted, yet a string within it wasn't UTF-8.
+    NotUtf8,
+    /// The document was rejected.
+    Rejected,
+  }
+
+  /// Deserialize a document, reading every value within it.
+  fn outcome(bytes: &[u8], dialect: core_json::Dialect) -> Outcome {
+    use core_json::{ConstStack, Deserializer, JsonError, Type, Value};
+
+    type Error<'read> = JsonError<'read, &'read [u8], ConstStack<512>>;
+    fn read<'read>(
+      value: Value<'read, '_, &'read [u8], ConstStack<512>>,
+      utf8: &mut bool,
+    ) -> Result<(), Error<'read>> {
+      match value.kind() {
+        Type::Object => {
+          let mut fields = value.fields()?;
+          while let Some(field) = fields.next() {
+            let mut field = field?;
+            match field.key()?.collect::<Result<String, _>>() {
+              Ok(_) => {}
+              Err(JsonError::NotUtf8) => *utf8 = false,
+              Err(e) => Err(e)?,
+            }
+            read(field.value()?, utf8)?;
+          }
+        }
+        Type::Array => {
+          let mut elements = value.iterate()?;
+          while let Some(element) = elements.next() {
+            read(element?, utf8)?;
+          }
+        }
+        Type::String => match value.to_str()?.collect::<Result<String, _>>() {
+          Ok(_) => {}
+          Err(JsonError::NotUtf8) => *utf8 = false,
+          Err(e) => Err(e)?,
+        },
+        Type::Number => {
+          value.to_number()?;
+        }
+        Type::Bool => {
+          value.to_bool()?;
+        }
+        Type::Null => value.to_null()?,
+      }
+      Ok(())
+    }
+
+    let mut utf8 = true;
+    let result = Deserializer::<_, ConstStack<512>>::with_dialect(bytes, dialect)
+      .and_then(|mut deserializer| read(deserializer.value()?, &mut utf8));
+    match result {
+      Ok(()) if utf8 => Outcome::Accepted,
+      Ok(()) => Outcome::NotUtf8,
+      Err(_) => Outcome::Rejected,
+    }
+  }
+
+  /*
+    The `i_` vectors are those whose acceptance is left to the implementation. The behavior chosen
+    for each, by default (`Dialect::STRICT`), is:
+
+    - Numbers which aren't representable by an `i64`/`f64` are accepted. Their conversions
+      (`Number::i64`, `Number::f64`, etc.) return `None` when they can't represent the number.
+    - Unpaired UTF-16 surrogates (escaped within strings) are accepted, yet the strings are
+      considered not UTF-8 and error with `JsonError::NotUtf8` when read. With
+      `Dialect::lone_surrogates`, they're decoded as `U+FFFD` instead.
+    - Invalid UTF-8 (including overlong encodings and encoded surrogates) is rejected.
+    - UTF-16 documents are rejected, with or without a byte order mark.
+    - A UTF-8 byte order mark is rejected, unless `Dialect::byte_order_mark` is set.
+    - Nesting is solely limited by the `Stack` used.
+  */
+  #[test]
+  fn implementation_defined() {
+    use core_json::Dialect;
+
+    let lenient = Dialect { byte_order_mark: true, lone_surrogates: true, ..Dialect::STRICT };
+
+    let mut i = 0;
+    for file in fs::read_dir("./vectors").unwrap() {
+      let file = file.unwrap();
+      let name = file.file_name();
+      let name = name.to_str().unwrap();
+      if !name.starts_with("i_") {
+        continue;
+      }
+      i += 1;
+
+      let (strict, with_switches) = match name {
+        _ if name.starts_with("i_number_") => (Outcome::Accepted, Outcome::Accepted),
+        "i_structure_500_nested_arrays.json" => (Outcome::Accepted, Outcome::Accepted),
+        "i_structure_UTF-8_BOM_empty_object.json" => (Outcome::Rejected, Outcome::Accepted),
+        "i_object_key_lone_2nd_surrogate.json" |
+        "i_string_1st_surrogate_but_2nd_missing.json" |
+        "i_string_1st_valid_surrogate_2nd_invalid.json" |
+        "i_string_incomplete_surrogate_and_escape_valid.json" |
+        "i_string_incomplete_surrogate_pair.json" |
+        "i_string_incomplete_surrogates_escape_valid.json" |
+        "i_string_invalid_lonely_surrogate.json" |
+        "i_string_invalid_surrogate.json" |
+        "i_string_inverted_surrogates_U+1D11E.json" |
+        "i_string_lone_second_surrogate.json" => (Outcome::NotUtf8, Outcome::Accepted),
+        _ if name.starts_with("i_string_") => (Outcome::Rejected, Outcome::Rejected),
+        _ => panic!("unrecognized implementation-defined vector {name}"),
+      };
+
+      let bytes = fs::read(file.path()).unwrap();
+      // Run with a larger stack, as the values are read recursively
+      let (strict_outcome, outcome_with_switches) = std::thread::scope(|scope| {
+        std::thread::Builder::new()
+          .stack_size(64 * 1024 * 1024)
+          .spawn_scoped(scope, || (outcome(&bytes, Dialect::STRICT), outcome(&bytes, lenient)))
+          .unwrap()
+          .join()
+          .unwrap()
+      });
+      assert_eq!(strict_outcome, strict, "{name}");
+      assert_eq!(outcome_with_switches, with_switches, "{name}");
+    }
+    assert_eq!(i, 35);
+  }
+
+  /*
+    The implementation-defined numbers are accepted, with their conversions defined as follows.
+
+    - Numbers exceeding the range of an `f64` aren't yielded by `Number::f64`, with
+      `Number::try_f64` distinguishing if their exponent overflowed an `i16`.
+    - Numbers too small to be represented by an `f64` are yielded as zero, with their sign.
+    - Integers exceeding the range of an `i64` aren't yielded by `Number::i64`.
+  */
+  #[test]
+  fn implementation_defined_numbers() {
+    use core_json::NotRepresentable;
+
+    let mut i = 0;
+    for file in fs::read_dir("./vectors").unwrap() {
+      let file = file.unwrap();
+      let name = file.file_name();
+      let name = name.to_str().unwrap();
+      if !name.starts_with("i_number_") {
+        continue;
+      }
+      i += 1;
+
+      let expected = match name {
+        "i_number_double_huge_neg_exp.json" => (false, Ok(0.0f64.to_bits())),
+        "i_number_real_underflow.json" => (true, Ok(0.0f64.to_bits())),
+        "i_number_neg_int_huge_exp.json" | "i_number_pos_double_huge_exp.json" => {
+          (false, Err(NotRepresentable::OutOfRange))
+        }
+        "i_number_huge_exp.json" |
+        "i_number_real_neg_overflow.json" |
+        "i_number_real_pos_overflow.json" => (true, Err(NotRepresentable::ExponentOverflow)),
+        // `Number::f64` solely uses the `f64::DIGITS` most significant digits
+        "i_number_too_big_neg_int.json" => (false, Ok((-123123123123123e15f64).to_bits())),
+        "i_number_too_big_pos_int.json" => (false, Ok(1e20f64.to_bits())),
+        "i_number_very_big_negative_int.json" => (false, Ok((-237462374673276e33f64).to_bits())),
+        _ => panic!("unrecognized implementation-defined number {name}"),
+      };
+
+      let bytes = fs::read(file.path()).unwrap();
+      let mut deserializer =
+        core_json::Deserializer::<_, core_json::ConstStack<1>>::new(bytes.as_slice()).unwrap();
+      let mut values = deserializer.value().unwrap().iterate().unwrap();
+      let number = values.next().unwrap().unwrap().to_number().unwrap();
+      assert_eq!(number.i64(), None, "{name}");
+      assert_eq!(
+        (number.exponent_overflowed(), number.try_f64().map(f64::to_bits)),
+        expected,
+        "{name}"
+      );
+    }
+    assert_eq!(i, 10);
+  }
 }
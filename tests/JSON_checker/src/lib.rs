@@ -53,21 +53,15 @@ mod tests {
       else {
         continue;
       };
-      let Ok(mut value) = deserializer.value() else { continue };
-      let Ok(is_object) = value.kind().map(|kind| matches!(kind, core_json::Type::Object)) else {
-        continue;
-      };
-      let Ok(is_array) = value.kind().map(|kind| matches!(kind, core_json::Type::Array)) else {
-        continue;
-      };
-      if is_object {
+      let Ok(value) = deserializer.value() else { continue };
+      if value.is_object() {
         let Ok(mut fields) = value.fields() else { continue };
         while let Some(field) = fields.next() {
           if field.is_err() {
             continue 'outer;
           }
         }
-      } else if is_array {
+      } else if value.is_array() {
         let Ok(mut values) = value.iterate() else { continue };
         while let Some(value) = values.next() {
           if value.is_err() {
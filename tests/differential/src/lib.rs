@@ -0,0 +1,554 @@
+use core::fmt;
+
+use core_json::{Type, ConstStack, Read, Stack, Deserializer};
+
+/*
+  A differential tester, deserializing each document with every implementation into a common
+  representation, then comparing the representations and reporting every path at which they
+  disagree.
+*/
+
+/// A value, as deserialized by an implementation.
+#[derive(Clone, Debug)]
+pub enum Semantic {
+  Null,
+  Bool(bool),
+  /// A number which is an integer representable within an `i64` or `u64`.
+  Integer(i128),
+  /// Any other number.
+  Float(f64),
+  String(String),
+  Array(Vec<Semantic>),
+  /// The fields of an object, sorted by their keys, with the last instance of a duplicated key
+  /// retained.
+  Object(Vec<(String, Semantic)>),
+}
+
+impl Semantic {
+  fn object(mut fields: Vec<(String, Semantic)>) -> Self {
+    // Reverse the fields so the stable sort places the last instance of each key first
+    fields.reverse();
+    fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+    fields.dedup_by(|(a, _), (b, _)| a == b);
+    Semantic::Object(fields)
+  }
+
+  fn kind(&self) -> &'static str {
+    match self {
+      Semantic::Null => "null",
+      Semantic::Bool(_) => "bool",
+      Semantic::Integer(_) | Semantic::Float(_) => "number",
+      Semantic::String(_) => "string",
+      Semantic::Array(_) => "array",
+      Semantic::Object(_) => "object",
+    }
+  }
+}
+
+/// An implementation of JSON.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Implementation {
+  CoreJson,
+  SerdeJson,
+  JsonRust,
+}
+
+impl Implementation {
+  /// Every implementation, with `core-json` first.
+  pub const ALL: [Implementation; 3] =
+    [Implementation::CoreJson, Implementation::SerdeJson, Implementation::JsonRust];
+
+  /// Deserialize a document, returning `None` if it was rejected.
+  pub fn deserialize(self, encoding: &[u8]) -> Option<Semantic> {
+    match self {
+      Implementation::CoreJson => core_json_document(encoding),
+      Implementation::SerdeJson => {
+        serde_json::from_slice::<serde_json::Value>(encoding).ok().map(|value| serde_json(&value))
+      }
+      Implementation::JsonRust => {
+        json::parse(str::from_utf8(encoding).ok()?).ok().map(|value| json_rust(&value))
+      }
+    }
+  }
+}
+
+fn core_json_number(number: &core_json::Number) -> Option<Semantic> {
+  if let Some(integer) = number.i64() {
+    return Some(Semantic::Integer(integer.into()));
+  }
+  if let Some(integer) = number.u64() {
+    return Some(Semantic::Integer(integer.into()));
+  }
+  number.f64_precise().map(Semantic::Float)
+}
+
+fn core_json_str<E>(chars: impl Iterator<Item = Result<char, E>>) -> Option<Semantic> {
+  chars.collect::<Result<String, _>>().ok().map(Semantic::String)
+}
+
+fn core_json_value<'read, R: Read<'read>, S: Stack>(
+  value: core_json::Value<'read, '_, R, S>,
+) -> Option<Semantic> {
+  Some(match value.kind() {
+    Type::Null => {
+      value.to_null().ok()?;
+      Semantic::Null
+    }
+    Type::Bool => Semantic::Bool(value.to_bool().ok()?),
+    Type::Number => core_json_number(&value.to_number().ok()?)?,
+    Type::String => core_json_str(value.to_str().ok()?)?,
+    Type::Array => {
+      let mut elements = value.iterate().ok()?;
+      let mut array = vec![];
+      while let Some(element) = elements.next() {
+        array.push(core_json_value(element.ok()?)?);
+      }
+      Semantic::Array(array)
+    }
+    Type::Object => {
+      let mut fields = value.fields().ok()?;
+      let mut object = vec![];
+      while let Some(field) = fields.next() {
+        let mut field = field.ok()?;
+        let key = field.key().ok()?.collect::<Result<String, _>>().ok()?;
+        object.push((key, core_json_value(field.value().ok()?)?));
+      }
+      Semantic::object(object)
+    }
+  })
+}
+
+fn core_json_document(encoding: &[u8]) -> Option<Semantic> {
+  let mut deserializer = Deserializer::<_, ConstStack<128>>::new(encoding).ok()?;
+  match encoding.iter().find(|byte| !matches!(byte, b' ' | b'\t' | b'\n' | b'\r')) {
+    Some(b'{' | b'[') => {
+      let value = core_json_value(deserializer.value().ok()?)?;
+      // Documents may solely be followed by whitespace, which the deserializer doesn't check
+      let mut trailing = deserializer.buffered().ok()?.to_vec();
+      trailing.extend(deserializer.into_reader());
+      trailing.iter().all(|byte| matches!(byte, b' ' | b'\t' | b'\n' | b'\r')).then_some(value)
+    }
+    Some(b'"') => core_json_str(deserializer.string().ok()?),
+    Some(b't' | b'f') => deserializer.bool().ok().map(Semantic::Bool),
+    Some(b'n') => deserializer.null().ok().map(|()| Semantic::Null),
+    _ => core_json_number(&deserializer.number().ok()?),
+  }
+}
+
+fn serde_json(value: &serde_json::Value) -> Semantic {
+  match value {
+    serde_json::Value::Null => Semantic::Null,
+    serde_json::Value::Bool(bool) => Semantic::Bool(*bool),
+    serde_json::Value::Number(number) => {
+      if let Some(integer) = number.as_i64() {
+        Semantic::Integer(integer.into())
+      } else if let Some(integer) = number.as_u64() {
+        Semantic::Integer(integer.into())
+      } else {
+        Semantic::Float(number.as_f64().unwrap())
+      }
+    }
+    serde_json::Value::String(string) => Semantic::String(string.clone()),
+    serde_json::Value::Array(array) => Semantic::Array(array.iter().map(serde_json).collect()),
+    // `serde_json::Map` already only retains the last instance of a key
+    serde_json::Value::Object(object) => {
+      Semantic::object(object.iter().map(|(key, value)| (key.clone(), serde_json(value))).collect())
+    }
+  }
+}
+
+fn json_rust(value: &json::JsonValue) -> Semantic {
+  match value {
+    json::JsonValue::Null => Semantic::Null,
+    json::JsonValue::Boolean(bool) => Semantic::Bool(*bool),
+    json::JsonValue::Number(number) => {
+      // Numbers are held as a mantissa and a decimal exponent, from which integers are recovered
+      let (positive, mut mantissa, mut exponent) = number.as_parts();
+      if mantissa == 0 {
+        exponent = 0;
+      }
+      while (exponent < 0) && ((mantissa % 10) == 0) {
+        mantissa /= 10;
+        exponent += 1;
+      }
+      u32::try_from(exponent)
+        .ok()
+        .and_then(|exponent| i128::from(mantissa).checked_mul(10i128.checked_pow(exponent)?))
+        .map(|integer| if positive { integer } else { -integer })
+        .filter(|integer| (i128::from(i64::MIN) ..= i128::from(u64::MAX)).contains(integer))
+        .map_or_else(|| Semantic::Float(f64::from(*number)), Semantic::Integer)
+    }
+    json::JsonValue::Short(string) => Semantic::String(string.as_str().to_string()),
+    json::JsonValue::String(string) => Semantic::String(string.clone()),
+    json::JsonValue::Array(array) => Semantic::Array(array.iter().map(json_rust).collect()),
+    json::JsonValue::Object(object) => Semantic::object(
+      object.iter().map(|(key, value)| (key.to_string(), json_rust(value))).collect(),
+    ),
+  }
+}
+
+/// What two implementations disagreed on.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MismatchKind {
+  /// The implementation accepted a document `core-json` rejected.
+  Accepted,
+  /// The implementation rejected a document `core-json` accepted.
+  Rejected,
+  /// The values were of different types.
+  Type,
+  /// The integers differed, or an integer differed from a float.
+  Integer,
+  /// The floats differed.
+  Float,
+  String,
+  /// The arrays were of different lengths.
+  Length,
+  /// The objects had different keys.
+  Keys,
+}
+
+/// A disagreement between two implementations.
+#[derive(Clone, Debug)]
+pub struct Mismatch {
+  pub kind: MismatchKind,
+  /// The JSON Pointer to the value disagreed on.
+  pub pointer: String,
+  pub expected: String,
+  pub actual: String,
+}
+
+impl fmt::Display for Mismatch {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{:?} mismatch at \"{}\": expected {}, found {}",
+      self.kind, self.pointer, self.expected, self.actual
+    )
+  }
+}
+
+/// If two floats are identical, considering zeroes of opposing signs as equal.
+///
+/// The sign of zero isn't compared as integers don't have one, so `-0` may be yielded as the
+/// integer `0` by one implementation and as `-0.0` by another.
+fn floats_equal(a: f64, b: f64) -> bool {
+  (a.to_bits() == b.to_bits()) || ((a == 0.0) && (b == 0.0))
+}
+
+fn compare(
+  expected: &Semantic,
+  actual: &Semantic,
+  pointer: &mut String,
+  mismatches: &mut Vec<Mismatch>,
+) {
+  let mut mismatch = |kind, expected: &dyn fmt::Debug, actual: &dyn fmt::Debug| {
+    mismatches.push(Mismatch {
+      kind,
+      pointer: pointer.clone(),
+      expected: format!("{expected:?}"),
+      actual: format!("{actual:?}"),
+    })
+  };
+  match (expected, actual) {
+    (Semantic::Null, Semantic::Null) => {}
+    (Semantic::Bool(a), Semantic::Bool(b)) => {
+      if a != b {
+        mismatch(MismatchKind::Type, a, b);
+      }
+    }
+    (Semantic::Integer(a), Semantic::Integer(b)) => {
+      if a != b {
+        mismatch(MismatchKind::Integer, a, b);
+      }
+    }
+    (Semantic::Float(a), Semantic::Float(b)) => {
+      if !floats_equal(*a, *b) {
+        mismatch(MismatchKind::Float, a, b);
+      }
+    }
+    // An implementation may yield an integer written with a fractional part or exponent as a float
+    (Semantic::Integer(integer), Semantic::Float(float)) |
+    (Semantic::Float(float), Semantic::Integer(integer)) => {
+      #[allow(clippy::cast_precision_loss)]
+      if !floats_equal(*integer as f64, *float) {
+        mismatch(MismatchKind::Integer, expected, actual);
+      }
+    }
+    (Semantic::String(a), Semantic::String(b)) => {
+      if a != b {
+        mismatch(MismatchKind::String, a, b);
+      }
+    }
+    (Semantic::Array(a), Semantic::Array(b)) => {
+      if a.len() != b.len() {
+        mismatch(MismatchKind::Length, &a.len(), &b.len());
+        return;
+      }
+      for (i, (a, b)) in a.iter().zip(b).enumerate() {
+        let len = pointer.len();
+        pointer.push_str(&format!("/{i}"));
+        compare(a, b, pointer, mismatches);
+        pointer.truncate(len);
+      }
+    }
+    (Semantic::Object(a), Semantic::Object(b)) => {
+      if a.iter().map(|(key, _)| key).ne(b.iter().map(|(key, _)| key)) {
+        let keys = |object: &[(String, Semantic)]| {
+          object.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>()
+        };
+        mismatch(MismatchKind::Keys, &keys(a), &keys(b));
+        return;
+      }
+      for ((key, a), (_, b)) in a.iter().zip(b) {
+        let len = pointer.len();
+        pointer.push('/');
+        pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+        compare(a, b, pointer, mismatches);
+        pointer.truncate(len);
+      }
+    }
+    _ => mismatch(MismatchKind::Type, &expected.kind(), &actual.kind()),
+  }
+}
+
+/// The mismatches between `core-json` and another implementation for a document.
+pub fn differential(encoding: &[u8], implementation: Implementation) -> Vec<Mismatch> {
+  let expected = Implementation::CoreJson.deserialize(encoding);
+  let actual = implementation.deserialize(encoding);
+  let mut mismatches = vec![];
+  match (&expected, &actual) {
+    (Some(expected), Some(actual)) => {
+      compare(expected, actual, &mut String::new(), &mut mismatches);
+    }
+    (None, None) => {}
+    (None, Some(_)) => mismatches.push(Mismatch {
+      kind: MismatchKind::Accepted,
+      pointer: String::new(),
+      expected: "rejection".to_string(),
+      actual: "acceptance".to_string(),
+    }),
+    (Some(_), None) => mismatches.push(Mismatch {
+      kind: MismatchKind::Rejected,
+      pointer: String::new(),
+      expected: "acceptance".to_string(),
+      actual: "rejection".to_string(),
+    }),
+  }
+  mismatches
+}
+
+#[cfg(test)]
+mod tests {
+  use std::fs;
+
+  use rand_core::{RngCore, OsRng};
+
+  use super::*;
+
+  /// The kinds of mismatches tolerated between `core-json` and an implementation.
+  fn tolerated(implementation: Implementation) -> &'static [MismatchKind] {
+    match implementation {
+      Implementation::CoreJson | Implementation::SerdeJson => &[],
+      // json-rust doesn't round floats correctly, and accepts some invalid documents
+      Implementation::JsonRust => &[MismatchKind::Float, MismatchKind::Accepted],
+    }
+  }
+
+  /// Check a document, returning the amount of mismatches tolerated.
+  fn check(name: &str, encoding: &[u8]) -> usize {
+    let mut tolerated_mismatches = 0;
+    for implementation in Implementation::ALL {
+      for mismatch in differential(encoding, implementation) {
+        let report =
+          format!("{name:?}: `core-json` and `{implementation:?}` disagreed: {mismatch}");
+        assert!(tolerated(implementation).contains(&mismatch.kind), "{report}");
+        println!("{report}");
+        tolerated_mismatches += 1;
+      }
+    }
+    tolerated_mismatches
+  }
+
+  #[test]
+  fn corpus() {
+    // The other implementations recurse for each level of nesting, which the vectors nest deeply
+    std::thread::Builder::new()
+      .stack_size(64 * 1024 * 1024)
+      .spawn(check_corpus)
+      .unwrap()
+      .join()
+      .unwrap();
+  }
+
+  fn check_corpus() {
+    let mut documents = 0;
+    let mut tolerated_mismatches = 0;
+    for (directory, prefixes) in
+      [("../JSONTestSuite/vectors", &["y_", "n_"]), ("../JSON_checker/vectors", &["pass", "fail"])]
+    {
+      for file in fs::read_dir(directory).unwrap() {
+        let file = file.unwrap();
+        let name = file.file_name();
+        let name = name.to_str().unwrap();
+        // The implementation-defined vectors are checked alongside `core-json`'s choices for them
+        if !prefixes.iter().any(|prefix| name.starts_with(prefix)) {
+          continue;
+        }
+        documents += 1;
+        tolerated_mismatches += check(name, &fs::read(file.path()).unwrap());
+      }
+    }
+    assert_eq!(documents, 95 + 188 + 3 + 33);
+    println!("{tolerated_mismatches} tolerated mismatches across {documents} documents");
+  }
+
+  #[allow(clippy::cast_possible_truncation)]
+  fn random_whitespace(serialization: &mut String) {
+    for _ in 0 .. (OsRng.next_u64() % 3) {
+      serialization.push([' ', '\t', '\n', '\r'][(OsRng.next_u64() % 4) as usize]);
+    }
+  }
+
+  #[allow(clippy::cast_possible_truncation)]
+  fn random_digits(serialization: &mut String, len: u64) {
+    for _ in 0 .. len {
+      serialization.push(char::from(b'0' + (OsRng.next_u64() % 10) as u8));
+    }
+  }
+
+  /// Write a random number, in any of the forms RFC 8259 permits.
+  fn random_number(serialization: &mut String) {
+    match OsRng.next_u64() % 4 {
+      0 => serialization.push_str(&(OsRng.next_u64() >> (OsRng.next_u64() % 64)).to_string()),
+      1 => serialization.push_str(&(OsRng.next_u64().cast_signed() >> 8).to_string()),
+      // The shortest representation of a float, as written by `serde_json`
+      2 => serialization.push_str(
+        &loop {
+          if let Some(float) = serde_json::Number::from_f64(f64::from_bits(OsRng.next_u64())) {
+            break float;
+          }
+        }
+        .to_string(),
+      ),
+      3 => {
+        if (OsRng.next_u64() % 2) == 1 {
+          serialization.push('-');
+        }
+        if (OsRng.next_u64() % 4) == 0 {
+          serialization.push('0');
+        } else {
+          serialization.push(char::from(b'1' + u8::try_from(OsRng.next_u64() % 9).unwrap()));
+          random_digits(serialization, OsRng.next_u64() % 8);
+        }
+        if (OsRng.next_u64() % 2) == 1 {
+          serialization.push('.');
+          random_digits(serialization, 1 + (OsRng.next_u64() % 8));
+        }
+        if (OsRng.next_u64() % 2) == 1 {
+          serialization.push(if (OsRng.next_u64() % 2) == 1 { 'e' } else { 'E' });
+          match OsRng.next_u64() % 3 {
+            0 => {}
+            1 => serialization.push('+'),
+            2 => serialization.push('-'),
+            _ => unreachable!(),
+          }
+          random_digits(serialization, 1 + (OsRng.next_u64() % 2));
+        }
+      }
+      _ => unreachable!(),
+    }
+  }
+
+  /// Write a random string, with characters randomly escaped.
+  #[allow(clippy::cast_possible_truncation)]
+  fn random_string(serialization: &mut String) {
+    serialization.push('"');
+    for _ in 0 .. (OsRng.next_u64() % 16) {
+      let char = loop {
+        let char = if (OsRng.next_u64() % 2) == 1 {
+          char::from_u32((OsRng.next_u64() % 128) as u32)
+        } else {
+          char::from_u32(OsRng.next_u64() as u32)
+        };
+        // Skip the byte-order mark as implementations are allowed to ignore/reject it
+        if let Some(char) = char.filter(|char| *char != '\u{feff}') {
+          break char;
+        }
+      };
+      match char {
+        '"' | '\\' => {
+          serialization.push('\\');
+          serialization.push(char);
+        }
+        _ if char.is_control() || ((OsRng.next_u64() % 4) == 0) => {
+          let mut utf16 = [0; 2];
+          for unit in char.encode_utf16(&mut utf16) {
+            serialization.push_str(&format!("\\u{unit:04x}"));
+          }
+        }
+        _ => serialization.push(char),
+      }
+    }
+    serialization.push('"');
+  }
+
+  fn random_value(serialization: &mut String, depth: usize) {
+    random_whitespace(serialization);
+    let modulus = if depth == 8 { 4 } else { 6 };
+    match OsRng.next_u64() % modulus {
+      0 => serialization.push_str("null"),
+      1 => serialization.push_str(if (OsRng.next_u64() % 2) == 1 { "true" } else { "false" }),
+      2 => random_number(serialization),
+      3 => random_string(serialization),
+      4 => {
+        serialization.push('[');
+        for i in 0 .. (OsRng.next_u64() % 8) {
+          if i != 0 {
+            serialization.push(',');
+          }
+          random_value(serialization, depth + 1);
+        }
+        random_whitespace(serialization);
+        serialization.push(']');
+      }
+      5 => {
+        serialization.push('{');
+        for i in 0 .. (OsRng.next_u64() % 8) {
+          if i != 0 {
+            serialization.push(',');
+          }
+          random_whitespace(serialization);
+          random_string(serialization);
+          random_whitespace(serialization);
+          serialization.push(':');
+          random_value(serialization, depth + 1);
+        }
+        random_whitespace(serialization);
+        serialization.push('}');
+      }
+      _ => unreachable!(),
+    }
+    random_whitespace(serialization);
+  }
+
+  #[test]
+  fn random() {
+    let mut tolerated_mismatches = 0;
+    for _ in 0 .. 1000 {
+      let mut serialization = String::new();
+      random_value(&mut serialization, 0);
+      tolerated_mismatches += check(&serialization, serialization.as_bytes());
+
+      // Corrupt the serialization with a random byte, which should be rejected (or accepted)
+      // identically
+      let mut serialization = serialization.into_bytes();
+      let i =
+        usize::try_from(OsRng.next_u64() % u64::try_from(serialization.len()).unwrap()).unwrap();
+      let corruption = b"{}[]\",:\\0.e-+ tfnu";
+      serialization[i] = corruption
+        [usize::try_from(OsRng.next_u64() % u64::try_from(corruption.len()).unwrap()).unwrap()];
+      tolerated_mismatches += check(&String::from_utf8_lossy(&serialization), &serialization);
+    }
+    println!("{tolerated_mismatches} tolerated mismatches across 2000 documents");
+  }
+}
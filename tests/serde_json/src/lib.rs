@@ -59,8 +59,8 @@ fn check_null(encoding: &[u8], _value: &Value, path: &[PathElement]) {
     core_json::Deserializer::<_, core_json::ConstStack<128>>::new(encoding).unwrap();
   {
     let null = deserializer.value().unwrap();
-    descend(null, path, |mut null: core_json::Value<_, _>| {
-      assert!(matches!(null.kind().unwrap(), Type::Null));
+    descend(null, path, |null: core_json::Value<_, _>| {
+      assert!(matches!(null.kind(), Type::Null));
       let () = null.to_null().unwrap();
     });
   }
@@ -72,8 +72,8 @@ fn check_bool(encoding: &[u8], value: &Value, path: &[PathElement]) {
     core_json::Deserializer::<_, core_json::ConstStack<128>>::new(encoding).unwrap();
   {
     let boolean = deserializer.value().unwrap();
-    descend(boolean, path, |mut boolean: core_json::Value<_, _>| {
-      assert!(matches!(boolean.kind().unwrap(), Type::Bool));
+    descend(boolean, path, |boolean: core_json::Value<_, _>| {
+      assert!(matches!(boolean.kind(), Type::Bool));
       assert_eq!(boolean.to_bool().unwrap(), value.as_bool().unwrap())
     });
   }
@@ -91,8 +91,8 @@ fn check_number(encoding: &[u8], value: &Value, path: &[PathElement]) {
     core_json::Deserializer::<_, core_json::ConstStack<128>>::new(encoding).unwrap();
   {
     let number = deserializer.value().unwrap();
-    descend(number, path, |mut number: core_json::Value<_, _>| {
-      assert!(matches!(number.kind().unwrap(), Type::Number));
+    descend(number, path, |number: core_json::Value<_, _>| {
+      assert!(matches!(number.kind(), Type::Number));
       let expected = value.as_number().unwrap();
       if expected.is_i64() {
         assert_eq!(number.to_number().unwrap().i64().unwrap(), expected.as_i64().unwrap());
@@ -111,8 +111,8 @@ fn check_string(encoding: &[u8], value: &Value, path: &[PathElement]) {
     core_json::Deserializer::<_, core_json::ConstStack<128>>::new(encoding).unwrap();
   {
     let string = deserializer.value().unwrap();
-    descend(string, path, |mut string: core_json::Value<_, _>| {
-      assert!(matches!(string.kind().unwrap(), Type::String));
+    descend(string, path, |string: core_json::Value<_, _>| {
+      assert!(matches!(string.kind(), Type::String));
       assert!(
         value.as_str().unwrap() == string.to_str().unwrap().collect::<Result<String, _>>().unwrap()
       );
@@ -130,8 +130,8 @@ fn check_object(encoding: &[u8], value: &Value, path: &mut Vec<PathElement>) {
       core_json::Deserializer::<_, core_json::ConstStack<128>>::new(encoding).unwrap();
     {
       let object = deserializer.value().unwrap();
-      descend(object, path, |mut object: core_json::Value<_, _>| {
-        assert!(matches!(object.kind().unwrap(), Type::Object));
+      descend(object, path, |object: core_json::Value<_, _>| {
+        assert!(matches!(object.kind(), Type::Object));
         let mut fields = object.fields().unwrap();
         let mut len = 0;
         while let Some(next) = fields.next() {
@@ -161,8 +161,8 @@ fn check_array(encoding: &[u8], value: &Value, path: &mut Vec<PathElement>) {
       core_json::Deserializer::<_, core_json::ConstStack<128>>::new(encoding).unwrap();
     {
       let array = deserializer.value().unwrap();
-      descend(array, path, |mut array: core_json::Value<_, _>| {
-        assert!(matches!(array.kind().unwrap(), Type::Array));
+      descend(array, path, |array: core_json::Value<_, _>| {
+        assert!(matches!(array.kind(), Type::Array));
         let mut values = array.iterate().unwrap();
         let mut len = 0;
         while let Some(next) = values.next() {
@@ -200,7 +200,6 @@ pub fn check_value(encoding: &[u8], value: &Value) {
 
 #[cfg(test)]
 mod tests {
-  use std::str::FromStr;
   use rand_core::{RngCore, OsRng};
   use super::*;
 
@@ -300,29 +299,6 @@ mod tests {
     }
   }
 
-  fn check_values_equivalent(a: &Value, b: &Value) {
-    match a {
-      Value::Null | Value::Bool(_) | Value::String(_) => assert_eq!(a, b),
-      Value::Number(number) => {
-        check_float(number.as_f64().unwrap(), b.as_number().unwrap().as_f64().unwrap());
-      }
-      Value::Array(array) => {
-        let b = b.as_array().unwrap();
-        assert_eq!(array.len(), b.len());
-        for (a, b) in array.iter().zip(b) {
-          check_values_equivalent(a, b);
-        }
-      }
-      Value::Object(object) => {
-        let b = b.as_object().unwrap();
-        assert_eq!(object.len(), b.len());
-        for (key, value) in object {
-          check_values_equivalent(value, &b[key]);
-        }
-      }
-    }
-  }
-
   #[test]
   fn fuzz() {
     for i in 0 .. 100 {
@@ -337,10 +313,100 @@ mod tests {
       let bytes = bytes.as_slice();
 
       check_value(bytes, &value);
-      check_values_equivalent(
-        &value,
-        &serde_json::Value::from_str(&serialize_value(&value)).unwrap(),
+      // 0.1% of the smaller number, as with `check_float`
+      assert!(
+        core_json_traits::eq::json_eq::<_, _, core_json::ConstStack<128>>(
+          bytes,
+          serialize_value(&value).as_bytes(),
+          0.001,
+        )
+        .unwrap()
       );
     }
   }
+
+  /*
+    Read a random portion of the value, dropping the rest (potentially midway through a string or
+    structure). This drives the deserializer's delayed dropping of values through arbitrary
+    patterns of partially-read values, checking everything read matches the expected value.
+  */
+  fn skim<'encoding, S: core_json::Stack>(
+    value: core_json::Value<'encoding, '_, &'encoding [u8], S>,
+    expected: &Value,
+  ) {
+    // Drop the value without reading it
+    if (OsRng.next_u64() % 4) == 0 {
+      return;
+    }
+    match expected {
+      Value::Null => value.to_null().unwrap(),
+      Value::Bool(bool) => assert_eq!(value.to_bool().unwrap(), *bool),
+      Value::Number(_) => {
+        value.to_number().unwrap();
+      }
+      Value::String(str) => {
+        let len = usize::try_from(OsRng.next_u64() % 8).unwrap();
+        let read = value.to_str().unwrap().take(len).collect::<Result<String, _>>().unwrap();
+        assert!(str.starts_with(&read));
+      }
+      Value::Array(array) => {
+        let mut iterator = value.iterate().unwrap();
+        for expected in array {
+          // Drop the rest of the array
+          if (OsRng.next_u64() % 8) == 0 {
+            return;
+          }
+          skim(iterator.next().unwrap().unwrap(), expected);
+        }
+        assert!(iterator.next().is_none());
+      }
+      Value::Object(object) => {
+        let mut iterator = value.fields().unwrap();
+        for (key, expected) in object {
+          // Drop the rest of the object
+          if (OsRng.next_u64() % 8) == 0 {
+            return;
+          }
+          let mut field = iterator.next().unwrap().unwrap();
+          match OsRng.next_u64() % 3 {
+            // Drop the field without reading its key
+            0 => continue,
+            1 => assert!(field.key_matches(key).unwrap()),
+            _ => {
+              let len = usize::try_from(OsRng.next_u64() % 8).unwrap();
+              let read = field.key().unwrap().take(len).collect::<Result<String, _>>().unwrap();
+              assert!(key.starts_with(&read));
+            }
+          }
+          skim(field.value().unwrap(), expected);
+        }
+        assert!(iterator.next().is_none());
+      }
+    }
+  }
+
+  #[test]
+  fn fuzz_drops() {
+    for _ in 0 .. 100 {
+      let value = random_value(0);
+      // Nest the value within a random amount of arrays, followed by a sentinel
+      let depth = usize::try_from(OsRng.next_u64() % 100).unwrap();
+      let bytes = format!("{}{}{}", "[".repeat(depth), value, ", 1]".repeat(depth));
+      let value = (0 .. depth).fold(value, |value, _| Value::Array(vec![value, Value::from(1)]));
+      let bytes = format!("[{bytes}, 2]");
+      let bytes = bytes.as_bytes();
+
+      let mut deserializer =
+        core_json::Deserializer::<_, core_json::ConstStack<128>>::new(bytes).unwrap();
+      {
+        let mut iterator = deserializer.value().unwrap().iterate().unwrap();
+        skim(iterator.next().unwrap().unwrap(), &value);
+        // Every dropped value was advanced past
+        assert_eq!(iterator.next().unwrap().unwrap().to_number().unwrap().i64(), Some(2));
+        assert!(iterator.next().is_none());
+      }
+      assert!(deserializer.buffered().unwrap().is_empty());
+      assert_eq!(deserializer.bytes_consumed(), bytes.len());
+    }
+  }
 }
@@ -0,0 +1,380 @@
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+#![deny(missing_docs)]
+
+use std::{fmt, io};
+
+use core_json::{
+  Read, Stack, JsonError, ErrorKind, Number, Type, Value, Deserializer, DefaultStack, Dialect,
+  JsonVisitor, walk,
+};
+use core_json_traits::{JsonSerialize, JsonPointer};
+
+/// A command to run on a document.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Command {
+  /// Check the document is valid JSON.
+  Validate,
+  /// Write the document without whitespace.
+  Minify,
+  /// Write the document, indenting each level of nesting by the amount of spaces specified.
+  Pretty(usize),
+  /// Write the value at the JSON Pointer, without whitespace.
+  Get(JsonPointer),
+}
+
+/// An error from running a command.
+#[derive(Debug)]
+pub enum Error {
+  /// The document was invalid, with the JSON Pointer to where the error was encountered.
+  Json(ErrorKind, String),
+  /// The document was followed by content other than whitespace.
+  TrailingContent,
+  /// The JSON Pointer didn't refer to a value within the document.
+  NotFound,
+  /// The output couldn't be written.
+  Io(io::Error),
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Error::Json(kind, pointer) => write!(f, "invalid JSON at `{pointer}`: {kind:?}"),
+      Error::TrailingContent => write!(f, "the document was followed by content"),
+      Error::NotFound => write!(f, "no value was present at the pointer"),
+      Error::Io(e) => write!(f, "couldn't write the output: {e}"),
+    }
+  }
+}
+
+impl From<io::Error> for Error {
+  fn from(e: io::Error) -> Self {
+    Error::Io(e)
+  }
+}
+
+/// A visitor which writes the events visited as JSON.
+struct Emitter<W: io::Write> {
+  writer: W,
+  // The amount of spaces to indent each level of nesting by, if pretty-printing
+  indent: Option<usize>,
+  depth: usize,
+  // If the current structure has yet to have an entry written
+  empty: bool,
+  // The first error when writing, as the visitor can't return errors
+  error: Option<io::Error>,
+}
+
+impl<W: io::Write> Emitter<W> {
+  fn new(writer: W, indent: Option<usize>) -> Self {
+    Emitter { writer, indent, depth: 0, empty: false, error: None }
+  }
+
+  fn write(&mut self, str: &str) {
+    if self.error.is_none() {
+      self.error = self.writer.write_all(str.as_bytes()).err();
+    }
+  }
+
+  fn serialize(&mut self, serialization: impl Iterator<Item = char>) {
+    self.write(&serialization.collect::<String>());
+  }
+
+  fn newline(&mut self) {
+    if let Some(indent) = self.indent {
+      self.write("\n");
+      self.write(&" ".repeat(indent * self.depth));
+    }
+  }
+
+  fn open(&mut self, delimiter: &str) {
+    self.write(delimiter);
+    self.depth += 1;
+    self.empty = true;
+  }
+
+  fn entry(&mut self) {
+    if !self.empty {
+      self.write(",");
+    }
+    self.empty = false;
+    self.newline();
+  }
+
+  fn close(&mut self, delimiter: &str) {
+    self.depth -= 1;
+    if !self.empty {
+      self.newline();
+    }
+    // The structure itself was an entry within its parent
+    self.empty = false;
+    self.write(delimiter);
+  }
+
+  // Write a string, from its characters
+  fn string<'read, R: Read<'read>, S: Stack>(
+    &mut self,
+    string: &mut impl Iterator<Item = Result<char, JsonError<'read, R, S>>>,
+  ) {
+    // If the string was invalid, `walk` will return the error
+    let string = string.map_while(Result::ok).collect::<String>();
+    self.serialize(string.serialize());
+  }
+}
+
+impl<W: io::Write> JsonVisitor for Emitter<W> {
+  fn object_start(&mut self) {
+    self.open("{");
+  }
+  fn field_start<'read, R: Read<'read>, S: Stack>(
+    &mut self,
+    key: &mut impl Iterator<Item = Result<char, JsonError<'read, R, S>>>,
+  ) {
+    self.entry();
+    self.string(key);
+    self.write(if self.indent.is_some() { ": " } else { ":" });
+  }
+  fn object_end(&mut self) {
+    self.close("}");
+  }
+
+  fn array_start(&mut self) {
+    self.open("[");
+  }
+  fn element_start(&mut self) {
+    self.entry();
+  }
+  fn array_end(&mut self) {
+    self.close("]");
+  }
+
+  fn string<'read, R: Read<'read>, S: Stack>(
+    &mut self,
+    string: &mut impl Iterator<Item = Result<char, JsonError<'read, R, S>>>,
+  ) {
+    Emitter::string(self, string);
+  }
+  fn number(&mut self, number: Number) {
+    self.serialize(number.serialize());
+  }
+  fn bool(&mut self, bool: bool) {
+    self.write(if bool { "true" } else { "false" });
+  }
+  fn null(&mut self) {
+    self.write("null");
+  }
+}
+
+/// A visitor which ignores every event, as solely the validity of the document is checked.
+struct Ignore;
+impl JsonVisitor for Ignore {}
+
+/// Advance to the value at the reference tokens, calling `then` with it.
+///
+/// Returns `None` if there's no value at the reference tokens.
+fn resolve<'read, R: Read<'read>, S: Stack, T>(
+  value: Value<'read, '_, R, S>,
+  tokens: &[String],
+  then: impl FnOnce(Value<'read, '_, R, S>) -> Result<T, JsonError<'read, R, S>>,
+) -> Result<Option<T>, JsonError<'read, R, S>> {
+  let Some((token, tokens)) = tokens.split_first() else { return then(value).map(Some) };
+  match value.kind() {
+    Type::Object => {
+      let mut fields = value.fields()?;
+      while let Some(field) = fields.next() {
+        let mut field = field?;
+        if field.key_matches(token)? {
+          return resolve(field.value()?, tokens, then);
+        }
+      }
+    }
+    Type::Array => {
+      // RFC 6901 doesn't allow indexes to have leading zeroes
+      let Some(index) =
+        token.parse::<usize>().ok().filter(|index| index.to_string() == token.as_str())
+      else {
+        return Ok(None);
+      };
+      let mut elements = value.iterate()?;
+      let mut i = 0;
+      while let Some(element) = elements.next() {
+        let element = element?;
+        if i == index {
+          return resolve(element, tokens, then);
+        }
+        i += 1;
+      }
+    }
+    _ => {}
+  }
+  Ok(None)
+}
+
+/// Visit a scalar root value, as `walk` does for values within structures.
+///
+/// `Deserializer::value` solely yields structures, so scalar roots are read via the accessors for
+/// each type of scalar, dispatched on by the first byte of the value.
+fn walk_scalar<'read, R: Read<'read>, S: Stack>(
+  deserializer: &mut Deserializer<'read, R, S>,
+  visitor: &mut impl JsonVisitor,
+) -> Result<(), JsonError<'read, R, S>> {
+  match deserializer.buffered()?.first() {
+    Some(b'"') => {
+      // Collect the string so an error within it is returned, not solely yielded to the visitor
+      let string = deserializer.string()?.collect::<Result<String, _>>()?;
+      visitor.string(&mut string.chars().map(Ok::<_, JsonError<'read, R, S>>));
+    }
+    Some(b't' | b'f') => visitor.bool(deserializer.bool()?),
+    Some(b'n') => {
+      deserializer.null()?;
+      visitor.null();
+    }
+    _ => visitor.number(deserializer.number()?),
+  }
+  Ok(())
+}
+
+/// Run a command on the document read from `reader`, writing the output to `writer`.
+///
+/// The document is deserialized as it's read, without being collected into memory.
+pub fn run<'read, R: Read<'read>>(
+  command: &Command,
+  reader: R,
+  mut writer: impl io::Write,
+) -> Result<(), Error> {
+  let dialect = match command {
+    Command::Validate => Dialect::STRICT,
+    // Write strings which aren't UTF-8 lossily, instead of failing
+    _ => Dialect { lone_surrogates: true, ..Dialect::STRICT },
+  };
+  let mut deserializer = Deserializer::<_, DefaultStack>::with_dialect(reader, dialect)
+    .map_err(|e| Error::Json(e.kind(), String::new()))?;
+
+  let indent = match command {
+    Command::Pretty(indent) => Some(*indent),
+    _ => None,
+  };
+  let mut emitter = Emitter::new(&mut writer, indent);
+  let root = deserializer.buffered().map(|buffered| buffered.first().copied());
+  let result = root.and_then(|root| match (root, command) {
+    (Some(b'{' | b'['), _) => deserializer.value().and_then(|value| match command {
+      Command::Validate => walk(value, &mut Ignore).map(Some),
+      Command::Minify | Command::Pretty(_) => walk(value, &mut emitter).map(Some),
+      Command::Get(pointer) => {
+        let tokens = pointer.tokens().map(String::from).collect::<Vec<_>>();
+        resolve(value, &tokens, |value| walk(value, &mut emitter))
+      }
+    }),
+    (_, Command::Validate) => walk_scalar(&mut deserializer, &mut Ignore).map(Some),
+    (_, Command::Minify | Command::Pretty(_)) => {
+      walk_scalar(&mut deserializer, &mut emitter).map(Some)
+    }
+    // A scalar solely has a value at the empty pointer, yet is still checked to be valid
+    (_, Command::Get(pointer)) if pointer.tokens().next().is_none() => {
+      walk_scalar(&mut deserializer, &mut emitter).map(Some)
+    }
+    (_, Command::Get(_)) => walk_scalar(&mut deserializer, &mut Ignore).map(|()| None),
+  });
+  // Check the rest of the document, and that solely whitespace follows it
+  let is_whitespace = |byte: &u8| matches!(byte, b' ' | b'\t' | b'\n' | b'\r');
+  let result = result.and_then(|found| {
+    deserializer.buffered().map(|buffered| (found, buffered.iter().all(is_whitespace)))
+  });
+  let (found, whitespace) = result
+    .map_err(|e| Error::Json(e.kind(), JsonPointer::from(deserializer.path()).to_string()))?;
+  if !whitespace {
+    Err(Error::TrailingContent)?;
+  }
+  let mut reader = deserializer.into_reader();
+  let mut buffer = [0; 64];
+  // `Read` has no notion of the end of the reader, so this reads until the reader errors
+  while let Ok(len) = reader.read_into_buffer(&mut buffer) {
+    if !buffer[.. len].iter().all(is_whitespace) {
+      Err(Error::TrailingContent)?;
+    }
+  }
+
+  if let Some(e) = emitter.error {
+    Err(e)?;
+  }
+  found.ok_or(Error::NotFound)?;
+  if !matches!(command, Command::Validate) {
+    writer.write_all(b"\n")?;
+  }
+  Ok(())
+}
+
+#[test]
+fn commands() {
+  let run = |command: Command, document: &str| {
+    let mut output = vec![];
+    run(&command, document.as_bytes(), &mut output).map(|()| String::from_utf8(output).unwrap())
+  };
+  let document = r#" {"a": [1, 2.50, {"b": "cé\ud800"}], "d~/": {}, "e": [], "f": [true, null]} "#;
+
+  assert_eq!(run(Command::Validate, document).unwrap(), "");
+  assert_eq!(
+    run(Command::Minify, document).unwrap(),
+    "{\"a\":[1,2.5,{\"b\":\"c\u{e9}\u{fffd}\"}],\"d~/\":{},\"e\":[],\"f\":[true,null]}\n"
+  );
+  assert_eq!(
+    run(Command::Pretty(2), r#"{"a": [1, {"b": {}}], "c": []}"#).unwrap(),
+    "{\n  \"a\": [\n    1,\n    {\n      \"b\": {}\n    }\n  ],\n  \"c\": []\n}\n"
+  );
+
+  for (pointer, value) in [
+    ("", None),
+    ("/a/1", Some("2.5")),
+    ("/a/2/b", Some("\"c\u{e9}\u{fffd}\"")),
+    ("/d~0~1", Some("{}")),
+    ("/f/1", Some("null")),
+    ("/a/01", None),
+    ("/a/3", None),
+    ("/g", None),
+    ("/a/0/b", None),
+  ] {
+    let result = run(Command::Get(pointer.parse().unwrap()), document);
+    match value {
+      Some(value) => assert_eq!(result.unwrap(), value.to_string() + "\n", "{pointer}"),
+      None if pointer.is_empty() => assert!(result.unwrap().starts_with("{\"a\"")),
+      None => assert!(matches!(result, Err(Error::NotFound)), "{pointer}"),
+    }
+  }
+
+  assert!(matches!(
+    run(Command::Validate, r#"{"a": [1, 2,]}"#),
+    Err(Error::Json(ErrorKind::SyntaxError(_), pointer)) if pointer == "/a/1"
+  ));
+  // Even when the invalid portion is skipped
+  assert!(matches!(
+    run(Command::Get("/a".parse().unwrap()), r#"{"a": 1, "b": [}"#),
+    Err(Error::Json(..))
+  ));
+  assert!(matches!(run(Command::Minify, "[] 1"), Err(Error::TrailingContent)));
+  assert_eq!(run(Command::Minify, "[] \n").unwrap(), "[]\n");
+
+  // Numbers are written with all of their retained significant digits
+  assert_eq!(
+    run(Command::Minify, "[0.1000000000000000000001, -1.5e-400, 18446744073709551616]").unwrap(),
+    "[0.1000000000000000000001,-15e-401,18446744073709551616]\n"
+  );
+
+  // Scalar roots
+  for (document, value) in
+    [(" 5 ", "5"), ("-0.5e1", "-5"), (r#""s\u0074""#, "\"st\""), ("true", "true"), ("null", "null")]
+  {
+    assert_eq!(run(Command::Validate, document).unwrap(), "");
+    assert_eq!(run(Command::Minify, document).unwrap(), value.to_string() + "\n");
+    assert_eq!(run(Command::Pretty(2), document).unwrap(), value.to_string() + "\n");
+    assert_eq!(run(Command::Get("".parse().unwrap()), document).unwrap(), value.to_string() + "\n");
+    assert!(matches!(run(Command::Get("/a".parse().unwrap()), document), Err(Error::NotFound)));
+  }
+  for invalid in ["5 6", "\"s", "tru", "null,", "-"] {
+    assert!(matches!(run(Command::Validate, invalid), Err(Error::Json(..))), "{invalid}");
+    assert!(
+      matches!(run(Command::Get("/a".parse().unwrap()), invalid), Err(Error::Json(..))),
+      "{invalid}"
+    );
+  }
+  assert!(matches!(run(Command::Validate, ""), Err(Error::Json(..))));
+}
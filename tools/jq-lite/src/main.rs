@@ -0,0 +1,45 @@
+use std::{io::Write, process::ExitCode};
+
+use core_json::ReadAdapter;
+use core_json_jq_lite::{Command, run};
+
+const USAGE: &str = "usage: jq-lite <validate | minify | pretty [INDENT] | get POINTER>";
+
+fn main() -> ExitCode {
+  let args = std::env::args().skip(1).collect::<Vec<_>>();
+  let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+  let command = match args.as_slice() {
+    ["validate"] => Command::Validate,
+    ["minify"] => Command::Minify,
+    ["pretty"] => Command::Pretty(2),
+    ["pretty", indent] => match indent.parse() {
+      Ok(indent) => Command::Pretty(indent),
+      Err(_) => {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+      }
+    },
+    ["get", pointer] => match pointer.parse() {
+      Ok(pointer) => Command::Get(pointer),
+      Err(_) => {
+        eprintln!("invalid JSON Pointer: {pointer}");
+        return ExitCode::from(2);
+      }
+    },
+    _ => {
+      eprintln!("{USAGE}");
+      return ExitCode::from(2);
+    }
+  };
+
+  let mut stdout = std::io::BufWriter::new(std::io::stdout().lock());
+  let result = run(&command, ReadAdapter::from(std::io::stdin().lock()), &mut stdout);
+  let result = result.and_then(|()| Ok(stdout.flush()?));
+  match result {
+    Ok(()) => ExitCode::SUCCESS,
+    Err(e) => {
+      eprintln!("{e}");
+      ExitCode::FAILURE
+    }
+  }
+}
@@ -0,0 +1,131 @@
+use arbitrary::{Arbitrary, Unstructured, Result};
+
+use crate::{Tri, JsonF64, JsonNumber, JsonValue};
+
+/// Generate an arbitrary finite `f64`.
+///
+/// Non-finite values are mapped to zero, so every bit pattern consumed yields a value.
+fn finite_f64(u: &mut Unstructured<'_>) -> Result<JsonF64> {
+  let float = f64::arbitrary(u)?;
+  Ok(JsonF64::try_from(float).unwrap_or_default())
+}
+
+impl<'a> Arbitrary<'a> for JsonF64 {
+  fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+    finite_f64(u)
+  }
+
+  fn size_hint(depth: usize) -> (usize, Option<usize>) {
+    f64::size_hint(depth)
+  }
+}
+
+#[cfg(feature = "zmij")]
+impl<'a> Arbitrary<'a> for crate::ExactF64 {
+  fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+    finite_f64(u).map(Into::into)
+  }
+
+  fn size_hint(depth: usize) -> (usize, Option<usize>) {
+    f64::size_hint(depth)
+  }
+}
+
+impl<'a, T: Arbitrary<'a>> Arbitrary<'a> for Tri<T> {
+  fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+    Ok(match u.int_in_range(0 ..= 2)? {
+      0 => Tri::Some(T::arbitrary(u)?),
+      1 => Tri::Null,
+      _ => Tri::None,
+    })
+  }
+
+  fn size_hint(depth: usize) -> (usize, Option<usize>) {
+    arbitrary::size_hint::and(u8::size_hint(depth), (0, T::size_hint(depth).1))
+  }
+}
+
+impl<'a> Arbitrary<'a> for JsonNumber {
+  /// Floats which are integers representable by an `i64` are generated as `JsonNumber::Integer`,
+  /// as they would be deserialized.
+  #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+  fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+    if bool::arbitrary(u)? {
+      return Ok(JsonNumber::Integer(i64::arbitrary(u)?));
+    }
+    let float = finite_f64(u)?;
+    let float_f64 = f64::from(float);
+    // `i64::MAX as f64` rounds up to 2**63, hence the exclusive bound
+    Ok(
+      if (float_f64 % 1.0 == 0.0) &&
+        (float_f64 >= (i64::MIN as f64)) &&
+        (float_f64 < (i64::MAX as f64))
+      {
+        JsonNumber::Integer(float_f64 as i64)
+      } else {
+        JsonNumber::Float(float)
+      },
+    )
+  }
+
+  fn size_hint(depth: usize) -> (usize, Option<usize>) {
+    arbitrary::size_hint::and(bool::size_hint(depth), f64::size_hint(depth))
+  }
+}
+
+impl<'a> Arbitrary<'a> for JsonValue {
+  /// Arrays and objects are nested as deeply as the data allows, which may be bounded by bounding
+  /// the length of the data.
+  fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+    Ok(match u.int_in_range(0 ..= 5)? {
+      0 => JsonValue::Null,
+      1 => JsonValue::Bool(bool::arbitrary(u)?),
+      2 => JsonValue::Number(JsonNumber::arbitrary(u)?),
+      3 => JsonValue::String(Arbitrary::arbitrary(u)?),
+      4 => JsonValue::Array(Arbitrary::arbitrary(u)?),
+      _ => JsonValue::Object(Arbitrary::arbitrary(u)?),
+    })
+  }
+
+  fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+    (1, None)
+  }
+}
+
+#[cfg(feature = "sequences")]
+#[test]
+fn arbitrary() {
+  use crate::{JsonStructure, JsonSerialize};
+
+  // Random data, via xorshift
+  let mut state = 0x2545_f491_4f6c_dd1du64;
+  let mut data = vec![0; 256];
+  for _ in 0 .. 256 {
+    for byte in &mut data {
+      state ^= state << 13;
+      state ^= state >> 7;
+      state ^= state << 17;
+      *byte = state.to_le_bytes()[0];
+    }
+
+    let mut u = Unstructured::new(&data);
+    // Every value generated has a valid serialization, with its integers round-tripping
+    let values = <Vec<JsonValue>>::arbitrary(&mut u).unwrap();
+    let serialization = values.serialize().collect::<String>();
+    let deserialized =
+      <Vec<JsonValue>>::deserialize_structure::<_, Vec<_>>(serialization.as_bytes()).unwrap();
+    assert_eq!(deserialized.len(), values.len());
+    for (deserialized, value) in deserialized.iter().zip(&values) {
+      if let JsonValue::Number(JsonNumber::Integer(integer)) = value {
+        assert_eq!(deserialized, &JsonValue::Number(JsonNumber::Integer(*integer)));
+      }
+    }
+
+    let tri = <[Tri<JsonF64>; 4]>::arbitrary(&mut u).unwrap();
+    for tri in tri {
+      if let Tri::Some(float) = tri {
+        assert!(f64::from(float).is_finite());
+      }
+    }
+  }
+}
@@ -0,0 +1,90 @@
+use bumpalo::{
+  Bump,
+  collections::{String, Vec},
+};
+
+use crate::{Read, Stack, JsonError, Value, JsonDeserializeSeed, seeds::Sequence};
+
+/// A seed for deserializing a string into a `bumpalo` arena.
+///
+/// The string is allocated within the arena, and freed when the arena is (such as when it's reset
+/// after processing each message), instead of being individually freed.
+#[derive(Clone, Copy)]
+pub struct ArenaStr<'bump>(pub &'bump Bump);
+
+impl<'bump> JsonDeserializeSeed for ArenaStr<'bump> {
+  type Value = &'bump str;
+  fn deserialize<'read, 'parent, R: Read<'read>, S: Stack>(
+    &mut self,
+    value: Value<'read, 'parent, R, S>,
+  ) -> Result<&'bump str, JsonError<'read, R, S>> {
+    let mut string = String::new_in(self.0);
+    for char in value.to_str()? {
+      string.push(char?);
+    }
+    Ok(string.into_bump_str())
+  }
+}
+
+/// A seed for deserializing a JSON array into a `bumpalo` arena, deserializing each element with a
+/// seed.
+///
+/// The elements are collected into a `bumpalo::collections::Vec` allocated within the arena, and
+/// may themselves be allocated within the arena (as with `ArenaStr`).
+pub struct ArenaVec<'bump, D: JsonDeserializeSeed> {
+  bump: &'bump Bump,
+  seed: D,
+}
+
+impl<'bump, D: JsonDeserializeSeed> ArenaVec<'bump, D> {
+  /// Create a seed for an array, within the specified arena, from the seed for its elements.
+  pub fn new(bump: &'bump Bump, seed: D) -> Self {
+    Self { bump, seed }
+  }
+
+  /// Consume this seed, returning the seed for the elements.
+  pub fn into_inner(self) -> D {
+    self.seed
+  }
+}
+
+impl<'bump, D: JsonDeserializeSeed<Value: 'bump>> JsonDeserializeSeed for ArenaVec<'bump, D> {
+  type Value = Vec<'bump, D::Value>;
+  fn deserialize<'read, 'parent, R: Read<'read>, S: Stack>(
+    &mut self,
+    value: Value<'read, 'parent, R, S>,
+  ) -> Result<Vec<'bump, D::Value>, JsonError<'read, R, S>> {
+    let mut vec = Vec::new_in(self.bump);
+    for element in (Sequence { iterator: value.iterate()?, seed: &mut self.seed }) {
+      vec.push(element?);
+    }
+    Ok(vec)
+  }
+}
+
+#[test]
+fn arena() {
+  use core::marker::PhantomData;
+  use crate::ConstStack;
+
+  let mut bump = Bump::new();
+  for _ in 0 .. 2 {
+    {
+      let mut seed = ArenaVec::new(&bump, ArenaVec::new(&bump, ArenaStr(&bump)));
+      let strings = seed
+        .deserialize_structure::<_, ConstStack<2>>(r#"[["a", "b\n"], [], ["é"]]"#.as_bytes())
+        .unwrap();
+      assert_eq!(strings, [&["a", "b\n"][..], &[], &["é"]]);
+
+      let mut seed = ArenaVec::new(&bump, PhantomData::<u8>);
+      assert_eq!(
+        seed.deserialize_structure::<_, ConstStack<1>>(&b"[1, 2, 3]"[..]).unwrap(),
+        [1, 2, 3]
+      );
+      assert!(seed.deserialize_structure::<_, ConstStack<1>>(&b"[1, 256]"[..]).is_err());
+    }
+    assert!(bump.allocated_bytes() > 0);
+    // Everything deserialized is freed at once
+    bump.reset();
+  }
+}
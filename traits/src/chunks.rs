@@ -0,0 +1,87 @@
+use crate::IteratorRead;
+
+/// An iterator over a serialization, yielding its bytes in fixed-size chunks.
+///
+/// This encodes the serialization as UTF-8, as `IteratorRead` does, yielding chunks of `N` bytes
+/// to feed transports which send fixed-size packets (such as DMA transfers or radio frames). The
+/// final chunk is padded with spaces, which are insignificant whitespace within JSON, so the
+/// concatenated chunks remain a valid serialization. `Chunks::padding` returns the amount of
+/// padding within the last chunk yielded, for transports which need to flag it.
+#[derive(Clone, Debug)]
+pub struct Chunks<I: Iterator<Item = char>, const N: usize> {
+  read: IteratorRead<I>,
+  padding: usize,
+}
+
+impl<I: Iterator<Item = char>, const N: usize> Chunks<I, N> {
+  /// Yield the bytes of a serialization in chunks of `N` bytes.
+  ///
+  /// This will fail to compile if `N` is zero.
+  #[inline(always)]
+  pub fn new(iter: I) -> Self {
+    const { assert!(N != 0, "`Chunks` must have a non-zero chunk size") };
+    Chunks { read: IteratorRead::new(iter), padding: 0 }
+  }
+
+  /// The amount of bytes of padding at the end of the last chunk yielded.
+  ///
+  /// This is solely non-zero for the final chunk.
+  #[inline(always)]
+  pub fn padding(&self) -> usize {
+    self.padding
+  }
+}
+
+impl<I: Iterator<Item = char>, const N: usize> Iterator for Chunks<I, N> {
+  type Item = [u8; N];
+
+  fn next(&mut self) -> Option<[u8; N]> {
+    // If the last chunk was padded, the serialization has ended
+    if self.padding != 0 {
+      None?;
+    }
+    let mut chunk = [b' '; N];
+    let read = self.read.read_available(&mut chunk);
+    if read == 0 {
+      None?;
+    }
+    self.padding = N - read;
+    Some(chunk)
+  }
+}
+
+#[cfg(all(feature = "alloc", feature = "sequences"))]
+#[test]
+fn chunks() {
+  use alloc::{vec, vec::Vec, string::String};
+  use crate::{ConstStack, JsonSerialize, JsonStructure};
+
+  fn check<const N: usize>() {
+    let value = vec![String::from("a\"\u{e9}\u{1F600}")];
+    let serialization = value.serialize().collect::<String>();
+    let mut chunks = Chunks::<_, N>::new(value.serialize());
+    let mut bytes = Vec::new();
+    let mut paddings = Vec::new();
+    while let Some(chunk) = chunks.next() {
+      bytes.extend(chunk);
+      paddings.push(chunks.padding());
+    }
+    assert_eq!(bytes.len(), serialization.len().next_multiple_of(N));
+    assert_eq!(&bytes[.. serialization.len()], serialization.as_bytes());
+    assert!(bytes[serialization.len() ..].iter().all(|byte| *byte == b' '));
+    // Solely the final chunk is padded
+    assert_eq!(paddings.pop(), Some(bytes.len() - serialization.len()));
+    assert!(paddings.iter().all(|padding| *padding == 0));
+    assert!(chunks.next().is_none());
+    drop(chunks);
+
+    // The padded serialization remains valid
+    let deserialized =
+      Vec::<String>::deserialize_structure::<_, ConstStack<32>>(bytes.as_slice()).ok();
+    assert_eq!(deserialized, Some(value));
+  }
+  check::<1>();
+  check::<3>();
+  check::<4>();
+  check::<64>();
+}
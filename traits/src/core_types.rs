@@ -0,0 +1,257 @@
+use core::{
+  marker::PhantomData,
+  ops::{Bound, Range, RangeInclusive},
+  cmp::Ordering,
+};
+
+use crate::{
+  Read, Stack, JsonError, TypeError, Type, Value, JsonDeserialize, JsonStructure, JsonSerialize,
+  GetMany, many::find_str,
+};
+
+/// Deserialize `null` as `PhantomData`.
+impl<T: ?Sized> JsonDeserialize for PhantomData<T> {
+  fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+    value: Value<'read, 'parent, B, S>,
+  ) -> Result<Self, JsonError<'read, B, S>> {
+    value.to_null()?;
+    Ok(PhantomData)
+  }
+}
+
+/// Serialize `PhantomData` as `null`.
+impl<T: ?Sized> JsonSerialize for PhantomData<T> {
+  fn serialize(&self) -> impl Iterator<Item = char> {
+    "null".chars()
+  }
+}
+
+/// A value which was present yet ignored.
+///
+/// Deserializing this advances past the value, of any type, without reading its contents. This
+/// allows explicitly consuming a value (such as a field which is expected yet unused, or the
+/// elements of an array which solely need to be counted) while documenting the intent to ignore
+/// it. As with any value not read, it's advanced past as the deserializer continues, with any
+/// errors within it returned then.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct Ignored;
+
+impl JsonDeserialize for Ignored {
+  #[inline(always)]
+  fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+    value: Value<'read, 'parent, B, S>,
+  ) -> Result<Self, JsonError<'read, B, S>> {
+    drop(value);
+    Ok(Ignored)
+  }
+}
+
+/// Deserialize the bounds of a range, from an object with the fields `start` and `end`.
+fn deserialize_bounds<'read, 'parent, B: Read<'read>, S: Stack, T: JsonDeserialize>(
+  value: Value<'read, 'parent, B, S>,
+) -> Result<(T, T), JsonError<'read, B, S>> {
+  let (Some(start), Some(end)) = <(Option<T>, Option<T>)>::get_many(value, ["start", "end"])?
+  else {
+    Err(JsonError::TypeError(TypeError))?
+  };
+  Ok((start, end))
+}
+
+/// Serialize the bounds of a range, as an object with the fields `start` and `end`.
+fn serialize_bounds<'serializing>(
+  start: &'serializing impl JsonSerialize,
+  end: &'serializing impl JsonSerialize,
+) -> impl Iterator<Item = char> {
+  r#"{"start":"#
+    .chars()
+    .chain(start.serialize())
+    .chain(r#","end":"#.chars())
+    .chain(end.serialize())
+    .chain(core::iter::once('}'))
+}
+
+/// Deserialize a range from an object with the fields `start` and `end`.
+impl<T: JsonDeserialize> JsonDeserialize for Range<T> {
+  fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+    value: Value<'read, 'parent, B, S>,
+  ) -> Result<Self, JsonError<'read, B, S>> {
+    let (start, end) = deserialize_bounds(value)?;
+    Ok(start .. end)
+  }
+}
+impl<T: JsonDeserialize> JsonStructure for Range<T> {}
+
+/// Serialize a range as an object with the fields `start` and `end`.
+impl<T: JsonSerialize> JsonSerialize for Range<T> {
+  fn serialize(&self) -> impl Iterator<Item = char> {
+    serialize_bounds(&self.start, &self.end)
+  }
+}
+
+/// Deserialize an inclusive range from an object with the fields `start` and `end`.
+impl<T: JsonDeserialize> JsonDeserialize for RangeInclusive<T> {
+  fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+    value: Value<'read, 'parent, B, S>,
+  ) -> Result<Self, JsonError<'read, B, S>> {
+    let (start, end) = deserialize_bounds(value)?;
+    Ok(start ..= end)
+  }
+}
+impl<T: JsonDeserialize> JsonStructure for RangeInclusive<T> {}
+
+/// Serialize an inclusive range as an object with the fields `start` and `end`.
+impl<T: JsonSerialize> JsonSerialize for RangeInclusive<T> {
+  fn serialize(&self) -> impl Iterator<Item = char> {
+    serialize_bounds(self.start(), self.end())
+  }
+}
+
+/// Deserialize a bound from `{"Included":value}`, `{"Excluded":value}`, or `"Unbounded"`.
+impl<T: JsonDeserialize> JsonDeserialize for Bound<T> {
+  fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+    value: Value<'read, 'parent, B, S>,
+  ) -> Result<Self, JsonError<'read, B, S>> {
+    if matches!(value.kind(), Type::String) {
+      let Some(0) = find_str(value.to_str()?, &["Unbounded"])? else {
+        Err(JsonError::TypeError(TypeError))?
+      };
+      return Ok(Bound::Unbounded);
+    }
+
+    let mut fields = value.fields()?;
+    let Some(field) = fields.next() else { Err(JsonError::TypeError(TypeError))? };
+    let result = {
+      let mut field = field?;
+      match find_str(field.key()?, &["Included", "Excluded"])? {
+        Some(0) => Bound::Included(T::deserialize(field.value()?)?),
+        Some(1) => Bound::Excluded(T::deserialize(field.value()?)?),
+        _ => Err(JsonError::TypeError(TypeError))?,
+      }
+    };
+    // The bound must be the sole field
+    if fields.next().is_some() {
+      Err(JsonError::TypeError(TypeError))?;
+    }
+    Ok(result)
+  }
+}
+
+/// Serialize a bound as `{"Included":value}`, `{"Excluded":value}`, or `"Unbounded"`.
+impl<T: JsonSerialize> JsonSerialize for Bound<T> {
+  fn serialize(&self) -> impl Iterator<Item = char> {
+    let (variant, value) = match self {
+      Bound::Included(value) => ("Included", Some(value)),
+      Bound::Excluded(value) => ("Excluded", Some(value)),
+      Bound::Unbounded => ("Unbounded", None),
+    };
+    let bounded = value.map(|value| {
+      core::iter::once('{')
+        .chain(variant.serialize())
+        .chain(core::iter::once(':'))
+        .chain(value.serialize())
+        .chain(core::iter::once('}'))
+    });
+    let unbounded = value.is_none().then(|| variant.serialize());
+    bounded.into_iter().flatten().chain(unbounded.into_iter().flatten())
+  }
+}
+
+/// Deserialize an ordering from `"Less"`, `"Equal"`, or `"Greater"`.
+impl JsonDeserialize for Ordering {
+  fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+    value: Value<'read, 'parent, B, S>,
+  ) -> Result<Self, JsonError<'read, B, S>> {
+    match find_str(value.to_str()?, &["Less", "Equal", "Greater"])? {
+      Some(0) => Ok(Ordering::Less),
+      Some(1) => Ok(Ordering::Equal),
+      Some(2) => Ok(Ordering::Greater),
+      _ => Err(JsonError::TypeError(TypeError)),
+    }
+  }
+}
+
+/// Serialize an ordering as `"Less"`, `"Equal"`, or `"Greater"`.
+impl JsonSerialize for Ordering {
+  fn serialize(&self) -> impl Iterator<Item = char> {
+    match self {
+      Ordering::Less => "Less",
+      Ordering::Equal => "Equal",
+      Ordering::Greater => "Greater",
+    }
+    .serialize()
+  }
+}
+
+#[cfg(all(feature = "alloc", feature = "sequences"))]
+#[test]
+fn core_types() {
+  use alloc::{vec::Vec, string::String};
+  use crate::{ConstStack, Deserializer};
+
+  fn round_trip<T: PartialEq + core::fmt::Debug + JsonDeserialize + JsonSerialize>(
+    value: &T,
+    serialization: &str,
+  ) {
+    assert_eq!(value.serialize().collect::<String>(), serialization);
+    let json = alloc::format!("[{serialization}]");
+    let deserialized = Vec::<T>::deserialize_structure::<_, ConstStack<32>>(json.as_bytes())
+      .map_err(|_| ())
+      .unwrap();
+    assert_eq!(deserialized.as_slice(), core::slice::from_ref(value));
+  }
+
+  round_trip(&PhantomData::<u8>, "null");
+  round_trip(&(1u8 .. 5), r#"{"start":1,"end":5}"#);
+  round_trip(&(1u8 ..= 5), r#"{"start":1,"end":5}"#);
+  round_trip(&Bound::Included(1u8), r#"{"Included":1}"#);
+  round_trip(&Bound::Excluded(1u8), r#"{"Excluded":1}"#);
+  round_trip(&Bound::<u8>::Unbounded, r#""Unbounded""#);
+  round_trip(&Ordering::Less, r#""Less""#);
+  round_trip(&Ordering::Equal, r#""Equal""#);
+  round_trip(&Ordering::Greater, r#""Greater""#);
+
+  // Fields may be in any order
+  assert_eq!(
+    Range::<u8>::deserialize_structure::<_, ConstStack<32>>(br#"{"end":5,"start":1}"#.as_slice())
+      .map_err(|_| ())
+      .unwrap(),
+    1 .. 5
+  );
+
+  // Values of any type may be ignored
+  let ignored = Vec::<Ignored>::deserialize_structure::<_, ConstStack<32>>(
+    br#"[1, "a", null, true, [2, [3]], {"b": {"c": 4}}]"#.as_slice(),
+  )
+  .map_err(|_| ())
+  .unwrap();
+  assert_eq!(ignored.len(), 6);
+  let (ignored, kept) = <(Option<Ignored>, Option<u8>)>::get_many(
+    Deserializer::<_, ConstStack<32>>::new(br#"{"a": [1, 2], "b": 3}"#.as_slice())
+      .unwrap()
+      .value()
+      .unwrap(),
+    ["a", "b"],
+  )
+  .map_err(|_| ())
+  .unwrap();
+  assert_eq!((ignored, kept), (Some(Ignored), Some(3)));
+  // Ignored values are still validated
+  assert!(
+    Vec::<Ignored>::deserialize_structure::<_, ConstStack<32>>(br#"[[1, }]"#.as_slice()).is_err()
+  );
+
+  for invalid in [
+    r#"{"start":1}"#,
+    r#"{"Included":1,"Excluded":2}"#,
+    r#"{"Unbounded":1}"#,
+    r#""Bounded""#,
+    r#""Greatest""#,
+    "1",
+  ] {
+    let json = alloc::format!("[{invalid}]");
+    let json = json.as_bytes();
+    assert!(Vec::<Range<u8>>::deserialize_structure::<_, ConstStack<32>>(json).is_err());
+    assert!(Vec::<Bound<u8>>::deserialize_structure::<_, ConstStack<32>>(json).is_err());
+    assert!(Vec::<Ordering>::deserialize_structure::<_, ConstStack<32>>(json).is_err());
+  }
+}
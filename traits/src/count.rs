@@ -0,0 +1,50 @@
+/// An iterator over a serialization which counts the bytes yielded.
+///
+/// This counts the length of the serialization when encoded as UTF-8, allowing applications to
+/// enforce quotas or report progress while serializing.
+#[derive(Clone, Debug)]
+pub struct ByteCount<I: Iterator<Item = char>> {
+  iter: I,
+  bytes: usize,
+}
+
+impl<I: Iterator<Item = char>> ByteCount<I> {
+  /// Count the bytes yielded by a serialization.
+  #[inline(always)]
+  pub fn new(iter: I) -> Self {
+    ByteCount { iter, bytes: 0 }
+  }
+
+  /// The amount of bytes yielded so far.
+  #[inline(always)]
+  pub fn bytes(&self) -> usize {
+    self.bytes
+  }
+}
+
+impl<I: Iterator<Item = char>> Iterator for ByteCount<I> {
+  type Item = char;
+
+  #[inline(always)]
+  fn next(&mut self) -> Option<char> {
+    let char = self.iter.next()?;
+    self.bytes += char.len_utf8();
+    Some(char)
+  }
+
+  #[inline(always)]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+}
+
+#[test]
+fn byte_count() {
+  use crate::JsonSerialize;
+
+  let mut serialization = ByteCount::new("a\u{e9}\u{1F600}".serialize());
+  assert_eq!(serialization.next(), Some('"'));
+  assert_eq!(serialization.bytes(), 1);
+  let serialized = serialization.by_ref().collect::<String>();
+  assert_eq!(serialization.bytes(), serialized.len() + 1);
+}
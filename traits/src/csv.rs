@@ -0,0 +1,348 @@
+//! Converting between CSV, as specified by
+//! [RFC 4180](https://datatracker.ietf.org/doc/html/rfc4180), and JSON arrays of flat objects.
+//!
+//! The first row of the CSV is a header, specifying the keys of the objects. Each following row is
+//! an object. Neither direction builds a representation of the entire document, instead
+//! converting one row (or object) at a time.
+//!
+//! Within CSV, values are untyped. When converting CSV to JSON, empty cells are converted to
+//! `null`, cells which are `true`, `false`, or a valid JSON number are converted as such, and all
+//! other cells are converted to strings. When converting JSON to CSV, `null` is converted to an
+//! empty cell. Accordingly, strings which appear as another type (such as `"1"`) will not be
+//! preserved when converted to CSV and back.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+
+use crate::{
+  Read, Stack, ConstStack, JsonError, Type, Value, Deserializer, JsonDeserialize, JsonSerialize,
+  JsonValue,
+};
+
+/// An error when converting between CSV and JSON.
+#[derive(Clone, Copy, Debug)]
+pub enum CsvError<E> {
+  /// An error from the source being converted (the reader of the CSV, or the deserializer of the
+  /// JSON).
+  SourceError(E),
+  /// The CSV was invalid, not UTF-8, or had a row whose length didn't match the header's.
+  InvalidCsv,
+  /// The JSON wasn't an array of flat objects, or an object had a key not within the header.
+  InvalidStructure,
+  /// An error from the writer.
+  WriteError,
+}
+
+impl<E> From<core::fmt::Error> for CsvError<E> {
+  #[inline(always)]
+  fn from(_: core::fmt::Error) -> Self {
+    CsvError::WriteError
+  }
+}
+
+/// Write a cell, quoting it if necessary.
+fn write_cell<E>(out: &mut impl Write, cell: &str) -> Result<(), CsvError<E>> {
+  if !cell.contains([',', '"', '\r', '\n']) {
+    return Ok(out.write_str(cell)?);
+  }
+  out.write_char('"')?;
+  for char in cell.chars() {
+    if char == '"' {
+      out.write_char('"')?;
+    }
+    out.write_char(char)?;
+  }
+  Ok(out.write_char('"')?)
+}
+
+/// Write a row, terminated by a CRLF.
+fn write_row<'cell, E>(
+  out: &mut impl Write,
+  row: impl Iterator<Item = &'cell str>,
+) -> Result<(), CsvError<E>> {
+  for (i, cell) in row.enumerate() {
+    if i != 0 {
+      out.write_char(',')?;
+    }
+    write_cell(out, cell)?;
+  }
+  Ok(out.write_str("\r\n")?)
+}
+
+/// Convert a JSON array of flat objects to CSV.
+///
+/// The header is the keys of the first object, in the order they were serialized. Following
+/// objects may omit keys (leaving their cells empty) or serialize them in a different order. If an
+/// array is empty, nothing is written.
+pub fn json_to_csv<'read, R: Read<'read>, S: Stack>(
+  value: Value<'read, '_, R, S>,
+  out: &mut impl Write,
+) -> Result<(), CsvError<JsonError<'read, R, S>>> {
+  let mut header = None::<Vec<String>>;
+  let mut row = Vec::<Option<String>>::new();
+  let mut objects = value.iterate().map_err(CsvError::SourceError)?;
+  while let Some(object) = objects.next() {
+    let object = object.map_err(CsvError::SourceError)?;
+    if object.kind() != Type::Object {
+      Err(CsvError::InvalidStructure)?;
+    }
+
+    let mut fields = object.fields().map_err(CsvError::SourceError)?;
+    let mut keys = Vec::new();
+    row.iter_mut().for_each(|cell| *cell = None);
+    while let Some(field) = fields.next() {
+      let mut field = field.map_err(CsvError::SourceError)?;
+      let key = field
+        .key()
+        .map_err(CsvError::SourceError)?
+        .collect::<Result<String, _>>()
+        .map_err(CsvError::SourceError)?;
+      let value = field.value().map_err(CsvError::SourceError)?;
+      if matches!(value.kind(), Type::Object | Type::Array) {
+        Err(CsvError::InvalidStructure)?;
+      }
+      // Numbers are reserialized exactly where possible, instead of via `JsonNumber`
+      let cell = if matches!(value.kind(), Type::Number) {
+        value.to_number().map_err(CsvError::SourceError)?.serialize().collect()
+      } else {
+        match JsonValue::deserialize(value).map_err(CsvError::SourceError)? {
+          JsonValue::Null => String::new(),
+          JsonValue::String(string) => string,
+          value => value.serialize().collect(),
+        }
+      };
+
+      if let Some(header) = &header {
+        let i =
+          header.iter().position(|column| *column == key).ok_or(CsvError::InvalidStructure)?;
+        row[i] = Some(cell);
+      } else {
+        keys.push(key);
+        row.push(Some(cell));
+      }
+    }
+
+    if header.is_none() {
+      write_row(out, keys.iter().map(String::as_str))?;
+      header = Some(keys);
+    }
+    write_row(out, row.iter().map(|cell| cell.as_deref().unwrap_or("")))?;
+  }
+  Ok(())
+}
+
+/// If this string is a number, as defined by RFC 8259.
+///
+/// This uses `core-json`'s own parser, so solely cells which would be deserialized as numbers are
+/// converted as such.
+fn is_number(string: &str) -> bool {
+  // The deserializer allows whitespace around the number, which would be lost
+  let whitespace = [' ', '\t', '\n', '\r'];
+  if string.starts_with(whitespace) || string.ends_with(whitespace) {
+    return false;
+  }
+  Deserializer::<_, ConstStack<1>>::new(string.as_bytes())
+    .and_then(|mut deserializer| deserializer.number())
+    .is_ok()
+}
+
+/// Read a row of CSV, returning `None` if the reader ended before the row.
+///
+/// As `Read` has no notion of the end of the underlying reader, an error from the underlying
+/// reader is considered the end of the CSV unless it occurs within a quoted cell.
+fn read_row<'read, R: Read<'read>>(
+  reader: &mut R,
+) -> Result<Option<Vec<String>>, CsvError<R::Error>> {
+  let mut row = Vec::new();
+  let mut cell = Vec::new();
+  let mut read = false;
+  let mut quoted = false;
+  let mut in_quotes = false;
+  let mut quote_pending = false;
+  // If a CR was read outside of quotes, which must solely be as part of a CRLF
+  let mut carriage_return = false;
+  loop {
+    let byte = match reader.read_byte() {
+      Ok(byte) => byte,
+      Err(e) => {
+        if in_quotes && (!quote_pending) {
+          Err(CsvError::SourceError(e))?;
+        }
+        if !read {
+          return Ok(None);
+        }
+        break;
+      }
+    };
+    read = true;
+    // RFC 4180 requires cells containing a CR be quoted, so a CR may not be within a cell
+    if core::mem::take(&mut carriage_return) && (byte != b'\n') {
+      Err(CsvError::InvalidCsv)?;
+    }
+
+    if in_quotes {
+      if quote_pending {
+        quote_pending = false;
+        // An escaped quote
+        if byte == b'"' {
+          cell.push(b'"');
+          continue;
+        }
+        in_quotes = false;
+      } else {
+        if byte == b'"' {
+          quote_pending = true;
+        } else {
+          cell.push(byte);
+        }
+        continue;
+      }
+    }
+
+    match byte {
+      b',' | b'\n' => {
+        row.push(String::from_utf8(core::mem::take(&mut cell)).map_err(|_| CsvError::InvalidCsv)?);
+        quoted = false;
+        if byte == b'\n' {
+          return Ok(Some(row));
+        }
+      }
+      // CRLF is the line terminator specified by RFC 4180, yet we also accept solely LF
+      b'\r' => carriage_return = true,
+      b'"' if cell.is_empty() && (!quoted) => {
+        quoted = true;
+        in_quotes = true;
+      }
+      _ => {
+        // Bytes may not follow a quoted cell, nor may quotes be within an unquoted cell
+        if quoted || (byte == b'"') {
+          Err(CsvError::InvalidCsv)?;
+        }
+        cell.push(byte);
+      }
+    }
+  }
+  row.push(String::from_utf8(cell).map_err(|_| CsvError::InvalidCsv)?);
+  Ok(Some(row))
+}
+
+/// Convert CSV to a JSON array of flat objects.
+///
+/// Each row must have as many cells as the header. Empty lines are ignored. Rows may be terminated
+/// by a CRLF or solely an LF, yet a CR must otherwise be within a quoted cell.
+pub fn csv_to_json<'read, R: Read<'read>>(
+  mut reader: R,
+  out: &mut impl Write,
+) -> Result<(), CsvError<R::Error>> {
+  // Read the next row, skipping empty lines
+  fn next_row<'read, R: Read<'read>>(
+    reader: &mut R,
+  ) -> Result<Option<Vec<String>>, CsvError<R::Error>> {
+    loop {
+      match read_row(reader)? {
+        Some(row) if (row.len() == 1) && row[0].is_empty() => {}
+        row => return Ok(row),
+      }
+    }
+  }
+
+  out.write_char('[')?;
+  if let Some(header) = next_row(&mut reader)? {
+    let mut first = true;
+    while let Some(row) = next_row(&mut reader)? {
+      if row.len() != header.len() {
+        Err(CsvError::InvalidCsv)?;
+      }
+      if !first {
+        out.write_char(',')?;
+      }
+      first = false;
+
+      out.write_char('{')?;
+      for (i, (key, cell)) in header.iter().zip(&row).enumerate() {
+        if i != 0 {
+          out.write_char(',')?;
+        }
+        key.serialize().try_for_each(|char| out.write_char(char))?;
+        out.write_char(':')?;
+        if cell.is_empty() {
+          out.write_str("null")?;
+        } else if (cell == "true") || (cell == "false") || is_number(cell) {
+          out.write_str(cell)?;
+        } else {
+          cell.serialize().try_for_each(|char| out.write_char(char))?;
+        }
+      }
+      out.write_char('}')?;
+    }
+  }
+  Ok(out.write_char(']')?)
+}
+
+#[test]
+fn csv() {
+  use alloc::string::ToString;
+  use crate::{ConstStack, Deserializer};
+
+  let csv = "id,name,temperature,ok\r\n1,\"Smith, \"\"J\"\"\",-1.5e3,true\r\n2,,0,false\n\n";
+  let json = r#"[{"id":1,"name":"Smith, \"J\"","temperature":-1.5e3,"ok":true},"#.to_string() +
+    r#"{"id":2,"name":null,"temperature":0,"ok":false}]"#;
+
+  let mut converted = String::new();
+  csv_to_json(csv.as_bytes(), &mut converted).unwrap();
+  assert_eq!(converted, json);
+
+  // The objects may omit keys and reorder them
+  let json = r#"[{"id":1,"name":"Smith, \"J\"","temperature":-1.5e3,"ok":true},"#.to_string() +
+    r#"{"ok":false,"temperature":0,"id":2}]"#;
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(json.as_bytes()).unwrap();
+  let mut converted = String::new();
+  json_to_csv(deserializer.value().unwrap(), &mut converted).unwrap();
+  assert_eq!(
+    converted,
+    "id,name,temperature,ok\r\n1,\"Smith, \"\"J\"\"\",-1500,true\r\n2,,0,false\r\n"
+  );
+
+  // Numbers are written with all of their retained significant digits
+  let mut deserializer =
+    Deserializer::<_, ConstStack<32>>::new(r#"[{"a":-98765432109876543210.5}]"#.as_bytes())
+      .unwrap();
+  let mut converted = String::new();
+  json_to_csv(deserializer.value().unwrap(), &mut converted).unwrap();
+  assert_eq!(converted, "a\r\n-98765432109876543210.5\r\n");
+
+  let mut deserializer =
+    Deserializer::<_, ConstStack<32>>::new(r#"[{"a":1},{"b":2}]"#.as_bytes()).unwrap();
+  assert!(matches!(
+    json_to_csv(deserializer.value().unwrap(), &mut String::new()),
+    Err(CsvError::InvalidStructure)
+  ));
+  let mut deserializer =
+    Deserializer::<_, ConstStack<32>>::new(r#"[{"a":[]}]"#.as_bytes()).unwrap();
+  assert!(matches!(
+    json_to_csv(deserializer.value().unwrap(), &mut String::new()),
+    Err(CsvError::InvalidStructure)
+  ));
+
+  // Cells containing a CR are quoted, with the CR kept
+  let mut deserializer =
+    Deserializer::<_, ConstStack<32>>::new(r#"[{"a":"x\ry","b\r":"\r"}]"#.as_bytes()).unwrap();
+  let mut converted = String::new();
+  json_to_csv(deserializer.value().unwrap(), &mut converted).unwrap();
+  assert_eq!(converted, "a,\"b\r\"\r\n\"x\ry\",\"\r\"\r\n");
+  let mut reconverted = String::new();
+  csv_to_json(converted.as_bytes(), &mut reconverted).unwrap();
+  assert_eq!(reconverted, r#"[{"a":"x\u000dy","b\u000d":"\u000d"}]"#);
+
+  for invalid in ["a,b\n1\n", "a\n\"1\"2\n", "a\n1\"2\n", "a\n\"1", "a\nx\ry\n", "a\r,b\n"] {
+    assert!(csv_to_json(invalid.as_bytes(), &mut String::new()).is_err());
+  }
+  for number in ["0", "-1", "1.5", "1e5", "-0.5E-3", "123456789012345678901234567890123"] {
+    assert!(is_number(number));
+  }
+  for not_number in
+    ["", "-", "01", "1.", ".5", "1e", "+1", "0x1", "1 ", " 1", "1\n", "NaN", "Infinity", "1,2"]
+  {
+    assert!(!is_number(not_number));
+  }
+}
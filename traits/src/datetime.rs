@@ -0,0 +1,298 @@
+use crate::{
+  Read, Stack, JsonError, TypeError, Value, JsonDeserialize, JsonSerialize,
+  string::{read_ascii, serialize_ascii},
+};
+
+/// The maximum length of an RFC 3339 date-time (de)serialized.
+///
+/// The longest date-time serialized is 36 characters. This leaves room for additional fractional
+/// digits when deserializing.
+const MAX_LEN: usize = 64;
+
+/// The components of an RFC 3339 date-time.
+struct DateTime {
+  year: i32,
+  month: u32,
+  day: u32,
+  hour: u32,
+  minute: u32,
+  /// The second, which may be `60` for a leap second.
+  second: u32,
+  nanosecond: u32,
+  /// The offset from UTC, in minutes, if this date-time has an offset.
+  offset: Option<i16>,
+}
+
+/// Write the specified amount of the least-significant decimal digits of a value.
+fn write_digits(buf: &mut [u8; MAX_LEN], len: &mut usize, mut value: u32, digits: usize) {
+  for i in (0 .. digits).rev() {
+    // Safe to cast as this is less than ten
+    #[allow(clippy::cast_possible_truncation)]
+    let digit = (value % 10) as u8;
+    buf[*len + i] = b'0' + digit;
+    value /= 10;
+  }
+  *len += digits;
+}
+
+impl DateTime {
+  /// Format this date-time per RFC 3339.
+  ///
+  /// Years outside of `0 ..= 9999` aren't representable by RFC 3339, and are formatted per ISO
+  /// 8601's expanded representation (with a sign and six digits). Trailing zeroes of the fractional
+  /// second are omitted, as is the fractional second itself if zero.
+  fn format(&self) -> ([u8; MAX_LEN], usize) {
+    let mut buf = [0; MAX_LEN];
+    let mut len = 0;
+
+    if (0 ..= 9999).contains(&self.year) {
+      write_digits(&mut buf, &mut len, self.year.unsigned_abs(), 4);
+    } else {
+      buf[len] = if self.year < 0 { b'-' } else { b'+' };
+      len += 1;
+      write_digits(&mut buf, &mut len, self.year.unsigned_abs(), 6);
+    }
+    for (separator, value) in [
+      (b'-', self.month),
+      (b'-', self.day),
+      (b'T', self.hour),
+      (b':', self.minute),
+      (b':', self.second),
+    ] {
+      buf[len] = separator;
+      len += 1;
+      write_digits(&mut buf, &mut len, value, 2);
+    }
+
+    if self.nanosecond != 0 {
+      buf[len] = b'.';
+      len += 1;
+      write_digits(&mut buf, &mut len, self.nanosecond, 9);
+      while buf[len - 1] == b'0' {
+        len -= 1;
+      }
+    }
+
+    match self.offset {
+      None => {}
+      Some(0) => {
+        buf[len] = b'Z';
+        len += 1;
+      }
+      Some(offset) => {
+        buf[len] = if offset < 0 { b'-' } else { b'+' };
+        len += 1;
+        let offset = u32::from(offset.unsigned_abs());
+        write_digits(&mut buf, &mut len, offset / 60, 2);
+        buf[len] = b':';
+        len += 1;
+        write_digits(&mut buf, &mut len, offset % 60, 2);
+      }
+    }
+
+    (buf, len)
+  }
+}
+
+/// Read an RFC 3339 date-time, returning the buffer it was read into and its length.
+///
+/// The buffer is one byte longer than the longest date-time accepted, allowing a suffix to be
+/// appended.
+fn read<'read, 'parent, B: Read<'read>, S: Stack>(
+  value: Value<'read, 'parent, B, S>,
+) -> Result<([u8; MAX_LEN + 1], usize), JsonError<'read, B, S>> {
+  let (buf, len) = read_ascii::<_, _, MAX_LEN>(value)?;
+  let mut extended = [0; MAX_LEN + 1];
+  extended[.. MAX_LEN].copy_from_slice(&buf);
+  Ok((extended, len))
+}
+
+/// Interpret bytes read by `read` as a `str`.
+fn as_str<'read, B: Read<'read>, S: Stack>(bytes: &[u8]) -> Result<&str, JsonError<'read, B, S>> {
+  // `read_ascii` only reads ASCII, making this unreachable
+  core::str::from_utf8(bytes).map_err(|_| JsonError::InternalError)
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+  use time::{OffsetDateTime, PrimitiveDateTime, UtcOffset, format_description::well_known::Rfc3339};
+
+  use super::*;
+
+  fn components(date_time: PrimitiveDateTime, offset: Option<i16>) -> DateTime {
+    DateTime {
+      year: date_time.year(),
+      month: u32::from(u8::from(date_time.month())),
+      day: u32::from(date_time.day()),
+      hour: u32::from(date_time.hour()),
+      minute: u32::from(date_time.minute()),
+      second: u32::from(date_time.second()),
+      nanosecond: date_time.nanosecond(),
+      offset,
+    }
+  }
+
+  /// Deserialize an RFC 3339 date-time.
+  impl JsonDeserialize for OffsetDateTime {
+    fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+      value: Value<'read, 'parent, B, S>,
+    ) -> Result<Self, JsonError<'read, B, S>> {
+      let (buf, len) = read(value)?;
+      OffsetDateTime::parse(as_str(&buf[.. len])?, &Rfc3339)
+        .map_err(|_| JsonError::TypeError(TypeError))
+    }
+  }
+
+  /// Serialize as an RFC 3339 date-time.
+  ///
+  /// RFC 3339 can't represent offsets with seconds, so date-times with such offsets are serialized
+  /// in UTC.
+  impl JsonSerialize for OffsetDateTime {
+    fn serialize(&self) -> impl Iterator<Item = char> {
+      let mut date_time = *self;
+      if date_time.offset().seconds_past_minute() != 0 {
+        date_time = date_time.checked_to_offset(UtcOffset::UTC).unwrap_or(date_time);
+      }
+      let offset = date_time.offset().whole_minutes();
+      let (buf, len) =
+        components(PrimitiveDateTime::new(date_time.date(), date_time.time()), Some(offset))
+          .format();
+      serialize_ascii(buf, len)
+    }
+  }
+
+  /// Deserialize an RFC 3339 date-time without an offset.
+  impl JsonDeserialize for PrimitiveDateTime {
+    fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+      value: Value<'read, 'parent, B, S>,
+    ) -> Result<Self, JsonError<'read, B, S>> {
+      let (mut buf, len) = read(value)?;
+      // Parse as if in UTC, which will fail if the date-time already had an offset
+      buf[len] = b'Z';
+      let date_time = OffsetDateTime::parse(as_str(&buf[..= len])?, &Rfc3339)
+        .map_err(|_| JsonError::TypeError(TypeError))?;
+      Ok(PrimitiveDateTime::new(date_time.date(), date_time.time()))
+    }
+  }
+
+  /// Serialize as an RFC 3339 date-time without an offset.
+  impl JsonSerialize for PrimitiveDateTime {
+    fn serialize(&self) -> impl Iterator<Item = char> {
+      let (buf, len) = components(*self, None).format();
+      serialize_ascii(buf, len)
+    }
+  }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+  use chrono::{Datelike, Timelike, Utc};
+
+  use super::*;
+
+  /// Deserialize an RFC 3339 date-time, converting it to UTC.
+  impl JsonDeserialize for chrono::DateTime<Utc> {
+    fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+      value: Value<'read, 'parent, B, S>,
+    ) -> Result<Self, JsonError<'read, B, S>> {
+      let (buf, len) = read(value)?;
+      Ok(
+        chrono::DateTime::parse_from_rfc3339(as_str(&buf[.. len])?)
+          .map_err(|_| JsonError::TypeError(TypeError))?
+          .with_timezone(&Utc),
+      )
+    }
+  }
+
+  /// Serialize as an RFC 3339 date-time, in UTC.
+  impl JsonSerialize for chrono::DateTime<Utc> {
+    fn serialize(&self) -> impl Iterator<Item = char> {
+      let date_time = self.naive_utc();
+      // `chrono` represents leap seconds with a nanosecond exceeding one second
+      let leap_second = date_time.nanosecond() >= 1_000_000_000;
+      let components = DateTime {
+        year: date_time.year(),
+        month: date_time.month(),
+        day: date_time.day(),
+        hour: date_time.hour(),
+        minute: date_time.minute(),
+        second: date_time.second() + u32::from(leap_second),
+        nanosecond: date_time.nanosecond() % 1_000_000_000,
+        offset: Some(0),
+      };
+      let (buf, len) = components.format();
+      serialize_ascii(buf, len)
+    }
+  }
+}
+
+#[cfg(test)]
+fn deserialize<T: JsonDeserialize>(json: &str) -> Option<T> {
+  use crate::{ConstStack, Deserializer, GetMany};
+
+  let json = format!(r#"{{"at":"{json}"}}"#);
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(json.as_bytes()).unwrap();
+  <(Option<T>,)>::get_many(deserializer.value().unwrap(), ["at"]).ok()?.0
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time() {
+  use time::{Month, Date, Time, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+  let datetime = |(year, month, day), (hour, minute, second, nanosecond)| {
+    PrimitiveDateTime::new(
+      Date::from_calendar_date(year, Month::try_from(month).unwrap(), day).unwrap(),
+      Time::from_hms_nano(hour, minute, second, nanosecond).unwrap(),
+    )
+  };
+  let offset = |hours, minutes| UtcOffset::from_hms(hours, minutes, 0).unwrap();
+
+  for (date_time, serialization) in [
+    (datetime((2024, 2, 29), (13, 45, 6, 0)).assume_utc(), "2024-02-29T13:45:06Z"),
+    (
+      datetime((2024, 2, 29), (13, 45, 6, 500_000_000)).assume_offset(offset(-3, -30)),
+      "2024-02-29T13:45:06.5-03:30",
+    ),
+    (
+      datetime((1, 1, 1), (0, 0, 0, 1)).assume_offset(offset(14, 0)),
+      "0001-01-01T00:00:00.000000001+14:00",
+    ),
+  ] {
+    assert_eq!(date_time.serialize().collect::<String>(), format!(r#""{serialization}""#));
+    assert_eq!(deserialize::<OffsetDateTime>(serialization), Some(date_time));
+  }
+
+  // Offsets with seconds are serialized in UTC
+  let date_time = datetime((2024, 2, 29), (13, 45, 6, 0))
+    .assume_utc()
+    .to_offset(UtcOffset::from_hms(1, 2, 3).unwrap());
+  assert_eq!(date_time.serialize().collect::<String>(), r#""2024-02-29T13:45:06Z""#);
+
+  let date_time = datetime((2024, 2, 29), (13, 45, 6, 250_000_000));
+  assert_eq!(date_time.serialize().collect::<String>(), r#""2024-02-29T13:45:06.25""#);
+  assert_eq!(deserialize::<PrimitiveDateTime>("2024-02-29T13:45:06.25"), Some(date_time));
+  assert_eq!(deserialize::<PrimitiveDateTime>("2024-02-29T13:45:06.25Z"), None);
+
+  for invalid in ["2023-02-29T13:45:06Z", "2024-02-29 13:45:06", "2024-02-29T13:45:06", "é"] {
+    assert_eq!(deserialize::<OffsetDateTime>(invalid), None);
+  }
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono() {
+  use chrono::{TimeZone, Utc};
+
+  let date_time = Utc.with_ymd_and_hms(2024, 2, 29, 13, 45, 6).unwrap();
+  assert_eq!(date_time.serialize().collect::<String>(), r#""2024-02-29T13:45:06Z""#);
+  assert_eq!(deserialize::<chrono::DateTime<Utc>>("2024-02-29T13:45:06Z"), Some(date_time));
+  // Other offsets are converted to UTC
+  assert_eq!(deserialize::<chrono::DateTime<Utc>>("2024-02-29T15:15:06+01:30"), Some(date_time));
+
+  let date_time = date_time + chrono::Duration::nanoseconds(120_000_000);
+  assert_eq!(date_time.serialize().collect::<String>(), r#""2024-02-29T13:45:06.12Z""#);
+  assert_eq!(deserialize::<chrono::DateTime<Utc>>("2024-02-29T13:45:06.12Z"), Some(date_time));
+
+  assert_eq!(deserialize::<chrono::DateTime<Utc>>("2024-02-30T13:45:06Z"), None);
+}
@@ -0,0 +1,122 @@
+use rust_decimal::Decimal;
+
+use crate::{Read, Stack, JsonError, NotRepresentable, Value, JsonDeserialize, JsonSerialize};
+use crate::primitives::IntInterator;
+
+/// Deserialize a decimal from a number, exactly.
+///
+/// This is done from the number's digits (of which up to 29 are retained, as many as a decimal
+/// has), without going through `f64`. If the number has more significant digits than the decimal
+/// can represent, or a scale exceeding the maximum scale, an error is returned instead of
+/// rounding.
+impl JsonDeserialize for Decimal {
+  fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+    value: Value<'read, 'parent, B, S>,
+  ) -> Result<Self, JsonError<'read, B, S>> {
+    let number = value.to_number()?;
+    let unrepresentable = if number.has_fraction() {
+      NotRepresentable::LossyFraction
+    } else {
+      NotRepresentable::OutOfRange
+    };
+    let Some((negative, digits, exponent)) = number.digits().filter(|_| !number.precision_lost())
+    else {
+      Err(unrepresentable)?
+    };
+
+    // As at most 29 digits are retained, this won't overflow
+    let mut significand = 0i128;
+    for digit in digits {
+      significand = (significand * 10) + i128::from(digit - b'0');
+    }
+    let significand = if negative { -significand } else { significand };
+    let decimal = if exponent < 0 {
+      let scale = u32::try_from(exponent.unsigned_abs())
+        .ok()
+        .filter(|scale| *scale <= Decimal::MAX_SCALE)
+        .ok_or(NotRepresentable::LossyFraction)?;
+      Decimal::try_from_i128_with_scale(significand, scale)
+    } else {
+      let exponent = u32::try_from(exponent).map_err(|_| NotRepresentable::OutOfRange)?;
+      let mantissa = 10i128
+        .checked_pow(exponent)
+        .and_then(|power| significand.checked_mul(power))
+        .ok_or(NotRepresentable::OutOfRange)?;
+      Decimal::try_from_i128_with_scale(mantissa, 0)
+    };
+    Ok(decimal.map_err(|_| NotRepresentable::OutOfRange)?)
+  }
+}
+
+/// Serialize a decimal as a number, with its scale preserved.
+impl JsonSerialize for Decimal {
+  fn serialize(&self) -> impl Iterator<Item = char> {
+    // A sign, twenty-nine digits, a decimal point, and a leading zero
+    IntInterator::<32>::new(*self)
+  }
+}
+
+#[cfg(all(feature = "alloc", feature = "sequences"))]
+#[test]
+fn decimal() {
+  // `Decimal` has an inherent `serialize` method, so `JsonSerialize::serialize` is called by path
+  use core::str::FromStr;
+  use crate::{ConstStack, JsonStructure};
+
+  let deserialize = |json: &str| {
+    Vec::<Decimal>::deserialize_structure::<_, ConstStack<32>>(format!("[{json}]").as_bytes())
+      .map(|decimals| decimals[0])
+      .map_err(|e| match e {
+        JsonError::NumberNotRepresentable(e) => Some(e),
+        _ => None,
+      })
+  };
+
+  for (json, expected) in [
+    ("1.50", "1.5"),
+    ("-0.001", "-0.001"),
+    ("0", "0"),
+    ("-0", "0"),
+    ("1e3", "1000"),
+    ("0.1", "0.1"),
+    ("123456789.0123456789", "123456789.0123456789"),
+    ("1e-28", "0.0000000000000000000000000001"),
+    ("18446744073709551615", "18446744073709551615"),
+    ("1.8446744073709551615e28", "18446744073709551615000000000"),
+    ("-1844674407370955161.5", "-1844674407370955161.5"),
+    ("99999999999999999999", "99999999999999999999"),
+    ("12345678901234567890.12", "12345678901234567890.12"),
+    ("1234567890123456789012.5", "1234567890123456789012.5"),
+    ("-123456789012345678901234.56", "-123456789012345678901234.56"),
+    ("1234567890123456789012345678", "1234567890123456789012345678"),
+    ("0.1234567890123456789012345678", "0.1234567890123456789012345678"),
+    ("7922816251426433759354395033.5", "7922816251426433759354395033.5"),
+    ("79228162514264337593543950335", "79228162514264337593543950335"),
+  ] {
+    let decimal = Decimal::from_str(expected).unwrap();
+    assert_eq!(deserialize(json), Ok(decimal), "{json}");
+    // The serialization round-trips exactly
+    let serialization = JsonSerialize::serialize(&decimal).collect::<String>();
+    assert_eq!(serialization, expected);
+    assert_eq!(deserialize(&serialization), Ok(decimal));
+  }
+
+  assert_eq!(
+    JsonSerialize::serialize(&Decimal::from_str("-0.0000000000000000000000000001").unwrap())
+      .collect::<String>(),
+    "-0.0000000000000000000000000001"
+  );
+
+  for (json, error) in [
+    ("1e-29", NotRepresentable::LossyFraction),
+    ("1e29", NotRepresentable::OutOfRange),
+    ("79228162514264337593543950336", NotRepresentable::OutOfRange),
+    ("0.10000000000000000000000000001", NotRepresentable::LossyFraction),
+    ("792281625142643375935439503350", NotRepresentable::OutOfRange),
+    ("1e99999", NotRepresentable::OutOfRange),
+    ("1e-99999", NotRepresentable::LossyFraction),
+  ] {
+    assert_eq!(deserialize(json), Err(Some(error)), "{json}");
+  }
+  assert_eq!(deserialize(r#""1.5""#), Err(None));
+}
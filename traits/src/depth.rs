@@ -0,0 +1,101 @@
+use core::ops::{Deref, DerefMut};
+
+use crate::{Read, Stack, JsonError, Value, JsonDeserialize, JsonStructure, JsonSerialize};
+
+/// A value which may be nested at most `DEPTH` structures deep.
+///
+/// Deserializing recursive types (such as a tree of `Box`ed nodes) recurses on the call stack for
+/// each level of nesting within the document. With a `Stack` of unbounded depth, a malicious
+/// document may nest deeply enough to overflow the call stack. Wrapping the recursive field with
+/// this bounds the recursion, erroring with `JsonError::DepthLimitExceeded` once a value is nested
+/// deeper than `DEPTH` (where the root value has depth `0`), before deserializing it.
+///
+/// This is transparent to the serialization.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct DepthLimited<T, const DEPTH: usize>(pub T);
+
+impl<T, const DEPTH: usize> DepthLimited<T, DEPTH> {
+  /// Consume this, returning the value within.
+  #[inline(always)]
+  pub fn into_inner(self) -> T {
+    self.0
+  }
+}
+
+impl<T, const DEPTH: usize> Deref for DepthLimited<T, DEPTH> {
+  type Target = T;
+  #[inline(always)]
+  fn deref(&self) -> &T {
+    &self.0
+  }
+}
+
+impl<T, const DEPTH: usize> DerefMut for DepthLimited<T, DEPTH> {
+  #[inline(always)]
+  fn deref_mut(&mut self) -> &mut T {
+    &mut self.0
+  }
+}
+
+impl<T: JsonDeserialize, const DEPTH: usize> JsonDeserialize for DepthLimited<T, DEPTH> {
+  fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+    value: Value<'read, 'parent, B, S>,
+  ) -> Result<Self, JsonError<'read, B, S>> {
+    if value.depth() > DEPTH {
+      Err(JsonError::DepthLimitExceeded)?;
+    }
+    T::deserialize(value).map(DepthLimited)
+  }
+}
+
+impl<T: JsonStructure, const DEPTH: usize> JsonStructure for DepthLimited<T, DEPTH> {}
+
+impl<T: JsonSerialize, const DEPTH: usize> JsonSerialize for DepthLimited<T, DEPTH> {
+  fn serialize(&self) -> impl Iterator<Item = char> {
+    self.0.serialize()
+  }
+}
+
+#[cfg(all(feature = "alloc", feature = "sequences"))]
+#[test]
+fn depth_limited() {
+  use alloc::{boxed::Box, vec::Vec};
+  use crate::{ErrorKind, GetMany};
+
+  // A tree, where each node has a list of children
+  #[derive(PartialEq, Debug)]
+  struct Node(Vec<DepthLimited<Box<Node>, 8>>);
+  impl JsonDeserialize for Node {
+    fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+      value: Value<'read, 'parent, B, S>,
+    ) -> Result<Self, JsonError<'read, B, S>> {
+      let (children,) = <(Option<_>,)>::get_many(value, ["children"])?;
+      Ok(Node(children.unwrap_or_default()))
+    }
+  }
+  impl JsonStructure for Node {}
+
+  // Each level of the tree is two structures deep (the node, and the array of its children)
+  let tree = |levels: usize| {
+    let mut json = alloc::string::String::new();
+    for _ in 0 .. levels {
+      json.push_str(r#"{"children":["#);
+    }
+    for _ in 0 .. levels {
+      json.push_str("]}");
+    }
+    json
+  };
+  // A `Vec` is used as the stack, so solely the depth limit bounds the recursion
+  let deserialize =
+    |json: &str| Node::deserialize_structure::<_, Vec<_>>(json.as_bytes()).map_err(|e| e.kind());
+
+  let node = deserialize(&tree(5)).unwrap();
+  assert_eq!(node.0.len(), 1);
+  assert_eq!(node.0[0].0.0.len(), 1);
+  assert_eq!(deserialize(&tree(6)), Err(ErrorKind::DepthLimitExceeded));
+  // Even if the document is far deeper than the call stack could handle
+  assert_eq!(deserialize(&tree(1_000_000)), Err(ErrorKind::DepthLimitExceeded));
+
+  assert_eq!(DepthLimited::<_, 0>(Some(1u8)).serialize().collect::<alloc::string::String>(), "1");
+}
@@ -0,0 +1,36 @@
+use core::fmt;
+
+use crate::JsonSerialize;
+
+/// A wrapper whose `Display` implementation writes the serialization of the value wrapped.
+///
+/// This drives `JsonSerialize::serialize` directly into the formatter, allowing serializations to
+/// be embedded within `format!`, `write!`, and logging macros without first being collected. The
+/// formatter's options (such as its width) are ignored.
+#[derive(Debug)]
+pub struct DisplayJson<'a, T: ?Sized + JsonSerialize>(pub &'a T);
+
+impl<T: ?Sized + JsonSerialize> Clone for DisplayJson<'_, T> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+impl<T: ?Sized + JsonSerialize> Copy for DisplayJson<'_, T> {}
+
+impl<T: ?Sized + JsonSerialize> fmt::Display for DisplayJson<'_, T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    use fmt::Write;
+    self.0.serialize().try_for_each(|char| f.write_char(char))
+  }
+}
+
+#[cfg(all(feature = "alloc", feature = "sequences"))]
+#[test]
+fn display() {
+  use alloc::format;
+
+  assert_eq!(format!("{}", DisplayJson(&[1, 2])), "[1,2]");
+  assert_eq!(format!("value: {}.", DisplayJson("a\"b\n")), "value: \"a\\\"b\\u000a\".");
+  assert_eq!(format!("{}", DisplayJson(&Some(true))), "true");
+  assert_eq!(format!("{:>8}", DisplayJson(&None::<u8>)), "null");
+}
@@ -0,0 +1,298 @@
+//! Comparing documents for semantic equality.
+//!
+//! Values are equal if they represent the same value, as defined by
+//! [RFC 8259](https://datatracker.ietf.org/doc/html/rfc8259), regardless of how they're
+//! serialized:
+//! - Objects are equal if they have the same fields, regardless of the order they were serialized
+//!   in. If a key is present multiple times within an object, each instance must have an equal
+//!   instance within the other object.
+//! - Strings are equal if they represent the same string, regardless of their escape sequences.
+//! - Numbers are equal if they represent the same value (within the tolerance specified),
+//!   regardless of their representation (such as `1`, `1.0`, and `1e0`).
+//!
+//! The documents are walked in lockstep. Objects are solely deserialized from the point their
+//! fields diverge in order, allowing documents with the same shape to be compared
+//! without allocating for their values.
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+  Read, Stack, JsonError, Deserializer, ErrorKind, Type, Value, FieldIterator, Number,
+  JsonDeserialize,
+};
+
+/// A number, as its exact decimal representation (per `Number::digits`) and as an `f64`.
+type Representations<'number> = (Option<(bool, &'number [u8], i64)>, Option<f64>);
+
+/// Compare two numbers, as their exact decimal representations (if available) and `f64`s, within
+/// a relative tolerance.
+#[allow(clippy::float_cmp)]
+fn numbers_eq(a: Representations<'_>, b: Representations<'_>, tolerance: f64) -> bool {
+  if let (Some(a), Some(b)) = (a.0, b.0) &&
+    ((a == b) || (tolerance == 0.0))
+  {
+    return a == b;
+  }
+  let (Some(a), Some(b)) = (a.1, b.1) else { return false };
+  (a - b).abs() <= (tolerance * a.abs().min(b.abs()))
+}
+
+fn number(number: &Number) -> Representations<'_> {
+  (number.digits(), number.f64())
+}
+
+/// A value deserialized in order to be compared, once objects' fields diverge in order.
+///
+/// Unlike `JsonValue`, this retains numbers as `Number`s, so they're compared exactly as they are
+/// when walked in lockstep.
+enum Deserialized {
+  Null,
+  Bool(bool),
+  Number(Number),
+  String(String),
+  Array(Vec<Deserialized>),
+  Object(Vec<(String, Deserialized)>),
+}
+
+impl JsonDeserialize for Deserialized {
+  fn deserialize<'read, 'parent, R: Read<'read>, S: Stack>(
+    value: Value<'read, 'parent, R, S>,
+  ) -> Result<Self, JsonError<'read, R, S>> {
+    Ok(match value.kind() {
+      Type::Null => {
+        value.to_null()?;
+        Deserialized::Null
+      }
+      Type::Bool => Deserialized::Bool(value.to_bool()?),
+      Type::Number => Deserialized::Number(value.to_number()?),
+      Type::String => Deserialized::String(value.to_str()?.collect::<Result<_, _>>()?),
+      Type::Array => {
+        let mut array = Vec::new();
+        let mut elements = value.iterate()?;
+        while let Some(element) = elements.next() {
+          array.push(Deserialized::deserialize(element?)?);
+        }
+        Deserialized::Array(array)
+      }
+      Type::Object => {
+        let mut object = Vec::new();
+        push_fields(&mut value.fields()?, &mut object)?;
+        Deserialized::Object(object)
+      }
+    })
+  }
+}
+
+/// Deserialize the remaining fields of an object.
+fn push_fields<'read, R: Read<'read>, S: Stack>(
+  fields: &mut FieldIterator<'read, '_, R, S>,
+  deserialized: &mut Vec<(String, Deserialized)>,
+) -> Result<(), JsonError<'read, R, S>> {
+  while let Some(field) = fields.next() {
+    let mut field = field?;
+    let key = field.key()?.collect::<Result<String, _>>()?;
+    deserialized.push((key, Deserialized::deserialize(field.value()?)?));
+  }
+  Ok(())
+}
+
+/// Compare two lists of fields as multisets.
+fn fields_eq(a: &[(String, Deserialized)], b: &[(String, Deserialized)], tolerance: f64) -> bool {
+  if a.len() != b.len() {
+    return false;
+  }
+  let mut matched = Vec::from_iter(core::iter::repeat_n(false, b.len()));
+  a.iter().all(|(key, value)| {
+    let found = b.iter().zip(&mut matched).find(|((b_key, b_value), matched)| {
+      (!**matched) && (key == b_key) && deserialized_eq(value, b_value, tolerance)
+    });
+    found.map(|(_, matched)| *matched = true).is_some()
+  })
+}
+
+fn deserialized_eq(a: &Deserialized, b: &Deserialized, tolerance: f64) -> bool {
+  match (a, b) {
+    (Deserialized::Null, Deserialized::Null) => true,
+    (Deserialized::Bool(a), Deserialized::Bool(b)) => a == b,
+    (Deserialized::Number(a), Deserialized::Number(b)) => {
+      numbers_eq(number(a), number(b), tolerance)
+    }
+    (Deserialized::String(a), Deserialized::String(b)) => a == b,
+    (Deserialized::Array(a), Deserialized::Array(b)) => {
+      (a.len() == b.len()) && a.iter().zip(b).all(|(a, b)| deserialized_eq(a, b, tolerance))
+    }
+    (Deserialized::Object(a), Deserialized::Object(b)) => fields_eq(a, b, tolerance),
+    _ => false,
+  }
+}
+
+/// Compare two values for semantic equality.
+///
+/// Numbers are considered equal if they have the same decimal value, or if their difference is
+/// within the tolerance relative to the smaller number (with a tolerance of `0.0` requiring they
+/// be exactly equal). The decimal values are compared exactly, without going through `f64`, from
+/// the significant digits retained (see `Number::digits`). Numbers whose exponent overflowed are
+/// solely compared as `f64`s. Once the fields of two objects diverge in order, the rest of the
+/// objects are deserialized and compared as multisets, with their numbers compared identically.
+///
+/// This may return before the values have been entirely read, once they've been found to differ.
+/// This recurses for each nested structure (bounded by the depth allowed by the `Stack` used).
+pub fn values_eq<'read_a, 'read_b, A: Read<'read_a>, B: Read<'read_b>, S: Stack>(
+  a: Value<'read_a, '_, A, S>,
+  b: Value<'read_b, '_, B, S>,
+  tolerance: f64,
+) -> Result<bool, ErrorKind> {
+  let kind = a.kind();
+  if kind != b.kind() {
+    return Ok(false);
+  }
+  Ok(match kind {
+    Type::Object => {
+      let mut a = a.fields()?;
+      let mut b = b.fields()?;
+      let (mut a_rest, mut b_rest) = loop {
+        match (a.next(), b.next()) {
+          (Some(a_field), Some(b_field)) => {
+            let mut a_field = a_field?;
+            let mut b_field = b_field?;
+            let a_key = a_field.key()?.collect::<Result<String, _>>()?;
+            let b_key = b_field.key()?.collect::<Result<String, _>>()?;
+            if a_key == b_key {
+              if !values_eq(a_field.value()?, b_field.value()?, tolerance)? {
+                return Ok(false);
+              }
+              continue;
+            }
+
+            // As the objects diverged, deserialize the rest of them and compare them as multisets
+            break (
+              Vec::from([(a_key, Deserialized::deserialize(a_field.value()?)?)]),
+              Vec::from([(b_key, Deserialized::deserialize(b_field.value()?)?)]),
+            );
+          }
+          (None, None) => return Ok(true),
+          _ => return Ok(false),
+        }
+      };
+      push_fields(&mut a, &mut a_rest)?;
+      push_fields(&mut b, &mut b_rest)?;
+      fields_eq(&a_rest, &b_rest, tolerance)
+    }
+    Type::Array => {
+      let mut a = a.iterate()?;
+      let mut b = b.iterate()?;
+      loop {
+        match (a.next(), b.next()) {
+          (Some(a), Some(b)) => {
+            if !values_eq(a?, b?, tolerance)? {
+              break false;
+            }
+          }
+          (None, None) => break true,
+          _ => break false,
+        }
+      }
+    }
+    Type::String => {
+      let mut a = a.to_str()?;
+      let mut b = b.to_str()?;
+      loop {
+        match (a.next(), b.next()) {
+          (Some(a), Some(b)) => {
+            if a? != b? {
+              break false;
+            }
+          }
+          (None, None) => break true,
+          _ => break false,
+        }
+      }
+    }
+    Type::Number => numbers_eq(number(&a.to_number()?), number(&b.to_number()?), tolerance),
+    Type::Bool => a.to_bool()? == b.to_bool()?,
+    Type::Null => {
+      a.to_null()?;
+      b.to_null()?;
+      true
+    }
+  })
+}
+
+/// Compare two JSON-serialized documents for semantic equality.
+///
+/// This is `values_eq` for the structures serialized.
+pub fn json_eq<'read_a, 'read_b, A: Read<'read_a>, B: Read<'read_b>, S: Stack>(
+  a: A,
+  b: B,
+  tolerance: f64,
+) -> Result<bool, ErrorKind> {
+  let mut a = Deserializer::<A, S>::new(a)?;
+  let mut b = Deserializer::<B, S>::new(b)?;
+  values_eq(a.value()?, b.value()?, tolerance)
+}
+
+#[test]
+fn eq() {
+  use crate::ConstStack;
+
+  let eq = |a: &str, b: &str, tolerance| {
+    json_eq::<_, _, ConstStack<32>>(a.as_bytes(), b.as_bytes(), tolerance).unwrap()
+  };
+
+  let a = r#"{"a":[1,"b",null],"c":{"d":0.5,"e":true},"f":1,"f":2}"#;
+  assert!(eq(a, a, 0.0));
+  assert!(eq(
+    a,
+    r#"{ "f": 2, "c": { "e": true, "d": 5e-1 }, "f": 1, "a": [1.0, "b", null] }"#,
+    0.0
+  ));
+  for distinct in [
+    r#"{"a":[1,"b",null],"c":{"d":0.5},"f":1,"f":2}"#,
+    r#"{"a":[1,null,"b"],"c":{"d":0.5,"e":true},"f":1,"f":2}"#,
+    r#"{"a":[1,"b",null],"c":{"d":0.5,"e":false},"f":1,"f":2}"#,
+    r#"{"a":[1,"b",null],"c":{"d":0.25,"e":true},"f":1,"f":2}"#,
+    r#"{"a":[1,"b",null],"c":{"e":true,"d":0.25},"f":1,"f":2}"#,
+    r#"{"a":[1,"b",null],"c":[0.5,true],"f":1,"f":2}"#,
+    r#"{"a":[1,"b",null],"c":{"d":0.5,"e":true},"f":2,"f":2}"#,
+    r#"{"a":[1,"b"],"c":{"d":0.5,"e":true},"f":1,"f":2}"#,
+    r#"{"a":[1,"bc",null],"c":{"d":0.5,"e":true},"f":1,"f":2}"#,
+  ] {
+    assert!(!eq(a, distinct, 0.0));
+    assert!(!eq(distinct, a, 0.0));
+  }
+
+  assert!(!eq("[1.5]", "[1.5001]", 0.0));
+  assert!(eq("[1.5]", "[1.5001]", 0.001));
+  assert!(!eq("[1.5]", "[1.6]", 0.001));
+  assert!(eq(r#"{"b":1,"a":[1.5]}"#, r#"{"a":[1.5001],"b":1}"#, 0.001));
+
+  // Numbers are compared exactly, not as `f64`s
+  assert!(!eq("[9007199254740993]", "[9007199254740992]", 0.0));
+  assert!(!eq("[18446744073709551615]", "[18446744073709551614]", 0.0));
+  assert!(eq("[1e400]", "[1e400]", 0.0));
+  assert!(eq("[1e400]", "[10e399]", 0.0));
+  assert!(!eq("[1e400]", "[2e400]", 0.0));
+  assert!(eq("[0.1]", "[1e-1]", 0.0));
+  assert!(eq("[0, -0, 0.0]", "[-0.0, 0e5, 0]", 0.0));
+  assert!(eq("[100]", "[1e2]", 0.0));
+
+  // Numbers are compared identically regardless of if the objects' fields diverge in order
+  let a = r#"{"a":1,"b":0.30000000000000000001}"#;
+  for (b, equal) in [
+    (r#"{"a":1,"b":0.3}"#, false),
+    (r#"{"b":0.3,"a":1}"#, false),
+    (r#"{"a":1,"b":0.30000000000000000001}"#, true),
+    (r#"{"b":3.0000000000000000001e-1,"a":1}"#, true),
+    (r#"{"b":[9007199254740993],"a":1}"#, false),
+  ] {
+    assert_eq!(eq(a, b, 0.0), equal, "{b}");
+    assert_eq!(eq(b, a, 0.0), equal, "{b}");
+  }
+  assert!(!eq(r#"{"a":1,"b":[9007199254740993]}"#, r#"{"b":[9007199254740992],"a":1}"#, 0.0));
+  assert!(eq(r#"{"a":1,"b":0.3}"#, r#"{"b":0.30000000000000000001,"a":1}"#, 0.001));
+
+  assert_eq!(
+    json_eq::<_, _, ConstStack<32>>(&b"[1]"[..], &b"[1,]"[..], 0.0),
+    Err(ErrorKind::SyntaxError(crate::SyntaxError::TrailingComma))
+  );
+}
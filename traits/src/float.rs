@@ -1,11 +1,11 @@
 use core::num::FpCategory;
-use crate::{Read, Stack, JsonError, Value, JsonDeserialize, JsonSerialize};
+use crate::{Read, Stack, JsonError, TypeError, Value, JsonDeserialize, JsonSerialize};
 
 impl JsonDeserialize for f64 {
   fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
     value: Value<'read, 'parent, B, S>,
   ) -> Result<Self, JsonError<'read, B, S>> {
-    value.to_number()?.f64().ok_or(JsonError::TypeError)
+    Ok(value.to_number()?.try_f64()?)
   }
 }
 
@@ -13,7 +13,7 @@ impl JsonDeserialize for f64 {
 ///
 /// JSON does not support representing `NaN`, `inf`, but rather only well-defined values. This
 /// ensures the `f64` is representable within JSON.
-#[derive(Clone, Copy, Default, Debug)]
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
 pub struct JsonF64(f64);
 
 impl TryFrom<f64> for JsonF64 {
@@ -38,7 +38,69 @@ impl JsonDeserialize for JsonF64 {
   fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
     value: Value<'read, 'parent, B, S>,
   ) -> Result<Self, JsonError<'read, B, S>> {
-    JsonF64::try_from(f64::deserialize(value)?).map_err(|_| JsonError::TypeError)
+    JsonF64::try_from(f64::deserialize(value)?).map_err(|_| JsonError::TypeError(TypeError))
+  }
+}
+
+/// A JSON-compatible `f64` which round-trips exactly.
+///
+/// `JsonF64` is deserialized from the `f64::DIGITS` most significant digits of a number, which
+/// don't suffice to represent every `f64`. This is deserialized via `Number::f64_precise` and
+/// serialized as the shortest representation which round-trips (via `zmij`), so deserializing its
+/// serialization yields the identical `f64`, bit for bit, and the serialization of a value
+/// deserialized from such a representation is textually identical to it.
+#[cfg(feature = "zmij")]
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub struct ExactF64(JsonF64);
+
+#[cfg(feature = "zmij")]
+impl TryFrom<f64> for ExactF64 {
+  type Error = FpCategory;
+  fn try_from(value: f64) -> Result<Self, Self::Error> {
+    JsonF64::try_from(value).map(Self)
+  }
+}
+
+#[cfg(feature = "zmij")]
+impl From<JsonF64> for ExactF64 {
+  fn from(value: JsonF64) -> Self {
+    Self(value)
+  }
+}
+
+#[cfg(feature = "zmij")]
+impl From<ExactF64> for JsonF64 {
+  fn from(value: ExactF64) -> JsonF64 {
+    value.0
+  }
+}
+
+#[cfg(feature = "zmij")]
+impl From<ExactF64> for f64 {
+  fn from(value: ExactF64) -> f64 {
+    value.0.into()
+  }
+}
+
+#[cfg(feature = "zmij")]
+impl JsonDeserialize for ExactF64 {
+  fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+    value: Value<'read, 'parent, B, S>,
+  ) -> Result<Self, JsonError<'read, B, S>> {
+    let number = value.to_number()?;
+    let float = number.f64_precise().ok_or(if number.exponent_overflowed() {
+      crate::NotRepresentable::ExponentOverflow
+    } else {
+      crate::NotRepresentable::OutOfRange
+    })?;
+    ExactF64::try_from(float).map_err(|_| JsonError::TypeError(TypeError))
+  }
+}
+
+#[cfg(feature = "zmij")]
+impl JsonSerialize for ExactF64 {
+  fn serialize(&self) -> impl Iterator<Item = char> {
+    self.0.serialize()
   }
 }
 
@@ -81,3 +143,57 @@ mod serialize {
     }
   }
 }
+
+#[cfg(all(feature = "alloc", feature = "sequences"))]
+#[test]
+fn not_representable() {
+  use crate::{ConstStack, ErrorKind, NotRepresentable, JsonStructure};
+
+  fn kind<T: 'static + Default + JsonDeserialize>(json: &str) -> ErrorKind {
+    <[T; 1]>::deserialize_structure::<_, ConstStack<1>>(json.as_bytes())
+      .map(|_| ())
+      .unwrap_err()
+      .kind()
+  }
+
+  for (json, reason) in
+    [("[1e400]", NotRepresentable::OutOfRange), ("[1e99999]", NotRepresentable::ExponentOverflow)]
+  {
+    let expected = ErrorKind::NumberNotRepresentable(reason);
+    assert_eq!(kind::<f64>(json), expected, "{json}");
+    assert_eq!(kind::<JsonF64>(json), expected, "{json}");
+    #[cfg(feature = "zmij")]
+    assert_eq!(kind::<ExactF64>(json), expected, "{json}");
+  }
+  assert_eq!(kind::<f64>(r#"["1.5"]"#), ErrorKind::TypeError);
+}
+
+#[cfg(all(feature = "zmij", feature = "alloc", feature = "sequences"))]
+#[test]
+fn exact_round_trip() {
+  use crate::{ConstStack, JsonStructure};
+
+  let round_trip = |float: f64| {
+    let Ok(float) = ExactF64::try_from(float) else { return };
+    let serialization = [float].serialize().collect::<alloc::string::String>();
+    let [deserialized] =
+      <[ExactF64; 1]>::deserialize_structure::<_, ConstStack<1>>(serialization.as_bytes()).unwrap();
+    assert_eq!(f64::from(deserialized).to_bits(), f64::from(float).to_bits(), "{serialization}");
+    assert_eq!([deserialized].serialize().collect::<alloc::string::String>(), serialization);
+  };
+
+  for float in
+    [0.1 + 0.2, -0.0, 0.0, f64::MAX, f64::MIN_POSITIVE, 5e-324, 1e100, -123456.78901234567, 1.0]
+  {
+    round_trip(float);
+  }
+
+  // Random bit patterns, via xorshift, covering the entire range of exponents
+  let mut state = 0x2545_f491_4f6c_dd1du64;
+  for _ in 0 .. 100_000 {
+    state ^= state << 13;
+    state ^= state >> 7;
+    state ^= state << 17;
+    round_trip(f64::from_bits(state));
+  }
+}
@@ -0,0 +1,61 @@
+use fugit::{Duration, Instant, Rate};
+
+use crate::{Read, Stack, JsonError, Value, JsonDeserialize, JsonSerialize};
+use crate::primitives::IntInterator;
+
+macro_rules! fugit_type {
+  ($kind: ident, $int: ident, $from: ident, $to: ident) => {
+    /// Deserialize from the integer amount of ticks, with the tick rate encoded within the type.
+    impl<const NOM: u32, const DENOM: u32> JsonDeserialize for $kind<$int, NOM, DENOM> {
+      fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+        value: Value<'read, 'parent, B, S>,
+      ) -> Result<Self, JsonError<'read, B, S>> {
+        Ok(Self::$from($int::deserialize(value)?))
+      }
+    }
+
+    /// Serialize as the integer amount of ticks, with the tick rate encoded within the type.
+    impl<const NOM: u32, const DENOM: u32> JsonSerialize for $kind<$int, NOM, DENOM> {
+      fn serialize(&self) -> impl Iterator<Item = char> {
+        // `u64::MAX` has twenty digits
+        IntInterator::<20>::new(self.$to())
+      }
+    }
+  };
+}
+fugit_type!(Duration, u32, from_ticks, ticks);
+fugit_type!(Duration, u64, from_ticks, ticks);
+fugit_type!(Instant, u32, from_ticks, ticks);
+fugit_type!(Instant, u64, from_ticks, ticks);
+fugit_type!(Rate, u32, from_raw, raw);
+fugit_type!(Rate, u64, from_raw, raw);
+
+#[test]
+fn fugit() {
+  use crate::{ConstStack, Deserializer, GetMany};
+
+  let timeout = fugit::MillisDurationU32::from_ticks(1500);
+  let deadline = fugit::TimerInstantU64::<1_000_000>::from_ticks(u64::MAX);
+  let baud = fugit::HertzU32::from_raw(115_200);
+  assert_eq!(timeout.serialize().collect::<String>(), "1500");
+  assert_eq!(deadline.serialize().collect::<String>(), u64::MAX.to_string());
+  assert_eq!(baud.serialize().collect::<String>(), "115200");
+
+  let json = format!(r#"{{"timeout":1500,"deadline":{},"baud":115200}}"#, u64::MAX);
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(json.as_bytes()).unwrap();
+  let config = <(
+    Option<fugit::MillisDurationU32>,
+    Option<fugit::TimerInstantU64<1_000_000>>,
+    Option<fugit::HertzU32>,
+  )>::get_many(deserializer.value().unwrap(), ["timeout", "deadline", "baud"])
+  .unwrap();
+  assert_eq!(config, (Some(timeout), Some(deadline), Some(baud)));
+
+  // The ticks must be representable by the type
+  let mut deserializer =
+    Deserializer::<_, ConstStack<32>>::new(br#"{"timeout":4294967296}"#.as_slice()).unwrap();
+  assert!(
+    <(Option<fugit::MillisDurationU32>,)>::get_many(deserializer.value().unwrap(), ["timeout"])
+      .is_err()
+  );
+}
@@ -0,0 +1,130 @@
+//! Hashing the structure of documents.
+//!
+//! The hash is of the value represented by the document, not of its serialization, allowing
+//! documents to be compared for semantic equality (as for caching or deduplication) without
+//! deserializing them into `JsonValue`s or canonicalizing their serializations.
+
+use core::hash::{Hasher, BuildHasher};
+
+use crate::{Read, Stack, JsonError, Type, Value};
+
+// Tags distinguishing each type of value
+const OBJECT: u8 = 0;
+const ARRAY: u8 = 1;
+const STRING: u8 = 2;
+const INTEGER: u8 = 3;
+const FLOAT: u8 = 4;
+const BOOL: u8 = 5;
+const NULL: u8 = 6;
+
+/// Hash the characters of a string, terminated so the hash is prefix-free.
+fn hash_str<'read, R: Read<'read>, S: Stack>(
+  hasher: &mut impl Hasher,
+  string: impl Iterator<Item = Result<char, JsonError<'read, R, S>>>,
+) -> Result<(), JsonError<'read, R, S>> {
+  for char in string {
+    hasher.write_u32(u32::from(char?));
+  }
+  // This is not a valid `char`, making it unambiguous
+  hasher.write_u32(u32::MAX);
+  Ok(())
+}
+
+/// Compute the structural hash of a value.
+///
+/// Two values have the same hash if they're equal, where:
+/// - Objects are equal if they have the same fields, regardless of the order they were serialized
+///   in
+/// - Strings are equal if they represent the same string, regardless of their escape sequences
+/// - Numbers are equal if they represent the same value, regardless of their representation (such
+///   as `1`, `1.0`, and `1e0`), with numbers which aren't integers compared as `f64`s
+///
+/// As with any hash, values with the same hash are not guaranteed to be equal. The quality of the
+/// hash is dependent on the quality of the hasher built, and objects' fields are combined with
+/// addition (allowing them to be combined regardless of their order). If a key is present
+/// multiple times within an object, each instance is hashed.
+///
+/// This recurses for each nested structure (bounded by the depth allowed by the `Stack` used).
+pub fn structural_hash<'read, R: Read<'read>, S: Stack>(
+  value: Value<'read, '_, R, S>,
+  build_hasher: &impl BuildHasher,
+) -> Result<u64, JsonError<'read, R, S>> {
+  let mut hasher = build_hasher.build_hasher();
+  match value.kind() {
+    Type::Object => {
+      hasher.write_u8(OBJECT);
+      let mut fields_hash = 0u64;
+      let mut len = 0u64;
+      let mut fields = value.fields()?;
+      while let Some(field) = fields.next() {
+        let mut field = field?;
+        let mut field_hasher = build_hasher.build_hasher();
+        hash_str(&mut field_hasher, field.key()?)?;
+        field_hasher.write_u64(structural_hash(field.value()?, build_hasher)?);
+        fields_hash = fields_hash.wrapping_add(field_hasher.finish());
+        len += 1;
+      }
+      hasher.write_u64(fields_hash);
+      hasher.write_u64(len);
+    }
+    Type::Array => {
+      hasher.write_u8(ARRAY);
+      let mut len = 0u64;
+      let mut values = value.iterate()?;
+      while let Some(value) = values.next() {
+        hasher.write_u64(structural_hash(value?, build_hasher)?);
+        len += 1;
+      }
+      hasher.write_u64(len);
+    }
+    Type::String => {
+      hasher.write_u8(STRING);
+      hash_str(&mut hasher, value.to_str()?)?;
+    }
+    Type::Number => {
+      let number = value.to_number()?;
+      if let Some(integer) = number.i64() {
+        hasher.write_u8(INTEGER);
+        hasher.write_i64(integer);
+      } else {
+        hasher.write_u8(FLOAT);
+        // Numbers exceeding the range of an `f64` will all have the same hash
+        hasher.write_u64(number.f64().map_or(u64::MAX, f64::to_bits));
+      }
+    }
+    Type::Bool => {
+      hasher.write_u8(BOOL);
+      hasher.write_u8(u8::from(value.to_bool()?));
+    }
+    Type::Null => {
+      value.to_null()?;
+      hasher.write_u8(NULL);
+    }
+  }
+  Ok(hasher.finish())
+}
+
+#[test]
+fn hash() {
+  use std::{hash::BuildHasherDefault, collections::hash_map::DefaultHasher};
+  use crate::{ConstStack, Deserializer};
+
+  let hash = |json: &str| {
+    let mut deserializer = Deserializer::<_, ConstStack<32>>::new(json.as_bytes()).unwrap();
+    structural_hash(deserializer.value().unwrap(), &BuildHasherDefault::<DefaultHasher>::default())
+      .unwrap()
+  };
+
+  let a = hash(r#"{"a":[1,"b",null],"c":{"d":0.5,"e":true}}"#);
+  assert_eq!(a, hash(r#"{ "c": { "e": true, "d": 5e-1 }, "a": [1.0, "\u0062", null] }"#));
+  for distinct in [
+    r#"{"a":[1,"b",null],"c":{"d":0.5}}"#,
+    r#"{"a":[1,null,"b"],"c":{"d":0.5,"e":true}}"#,
+    r#"{"a":[1,"b",null],"c":{"d":0.5,"e":false}}"#,
+    r#"{"a":[1,"b",null],"c":{"d":0.25,"e":true}}"#,
+    r#"{"a":[1,"b",null],"c":{"de":0.5,"":true}}"#,
+    r#"{"a":[1,"b",null],"c":[0.5,true]}"#,
+  ] {
+    assert_ne!(a, hash(distinct));
+  }
+}
@@ -0,0 +1,98 @@
+use crate::{
+  Read, Stack, JsonError, TypeError, Value, JsonDeserialize, JsonSerialize,
+  string::{read_ascii, serialize_ascii},
+};
+
+/// Deserialize a UUID from a string, in any of the formats accepted by `Uuid::try_parse`.
+#[cfg(feature = "uuid")]
+impl JsonDeserialize for uuid::Uuid {
+  fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+    value: Value<'read, 'parent, B, S>,
+  ) -> Result<Self, JsonError<'read, B, S>> {
+    let (buf, len) = read_ascii::<_, _, { uuid::fmt::Urn::LENGTH }>(value)?;
+    uuid::Uuid::try_parse_ascii(&buf[.. len]).map_err(|_| JsonError::TypeError(TypeError))
+  }
+}
+
+/// Serialize a UUID as a lowercase, hyphenated string.
+#[cfg(feature = "uuid")]
+impl JsonSerialize for uuid::Uuid {
+  fn serialize(&self) -> impl Iterator<Item = char> {
+    let mut buf = [0; uuid::fmt::Hyphenated::LENGTH];
+    self.hyphenated().encode_lower(&mut buf);
+    serialize_ascii(buf, buf.len())
+  }
+}
+
+/// Deserialize a ULID from its Crockford base32 string.
+#[cfg(feature = "ulid")]
+impl JsonDeserialize for ulid::Ulid {
+  fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+    value: Value<'read, 'parent, B, S>,
+  ) -> Result<Self, JsonError<'read, B, S>> {
+    let (buf, len) = read_ascii::<_, _, { ulid::ULID_LEN }>(value)?;
+    let str = core::str::from_utf8(&buf[.. len]).map_err(|_| JsonError::InternalError)?;
+    ulid::Ulid::from_string(str).map_err(|_| JsonError::TypeError(TypeError))
+  }
+}
+
+/// Serialize a ULID as its Crockford base32 string.
+#[cfg(feature = "ulid")]
+impl JsonSerialize for ulid::Ulid {
+  fn serialize(&self) -> impl Iterator<Item = char> {
+    let mut buf = [0; ulid::ULID_LEN];
+    self.array_to_str(&mut buf);
+    serialize_ascii(buf, buf.len())
+  }
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn uuid() {
+  use crate::{ConstStack, Deserializer, GetMany};
+
+  let id = uuid::Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+  assert_eq!(id.serialize().collect::<String>(), r#""67e55044-10b1-426f-9247-bb680e5fe0c8""#);
+
+  let deserialize = |json: &'static str| {
+    let mut deserializer = Deserializer::<_, ConstStack<32>>::new(json.as_bytes()).unwrap();
+    <(Option<uuid::Uuid>,)>::get_many(deserializer.value().unwrap(), ["id"]).map(|(id,)| id)
+  };
+  for json in [
+    r#"{"id":"67e55044-10b1-426f-9247-bb680e5fe0c8"}"#,
+    r#"{"id":"67E55044-10B1-426F-9247-BB680E5FE0C8"}"#,
+    r#"{"id":"67e5504410b1426f9247bb680e5fe0c8"}"#,
+    r#"{"id":"urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8"}"#,
+  ] {
+    assert_eq!(deserialize(json).unwrap(), Some(id));
+  }
+  for json in [
+    r#"{"id":"67e55044-10b1-426f-9247-bb680e5fe0c"}"#,
+    r#"{"id":"urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c80"}"#,
+    r#"{"id":"67e55044-10b1-426f-9247-bb680e5fé0c8"}"#,
+    r#"{"id":1}"#,
+  ] {
+    assert!(deserialize(json).is_err());
+  }
+}
+
+#[cfg(feature = "ulid")]
+#[test]
+fn ulid() {
+  use crate::{ConstStack, Deserializer, GetMany};
+
+  let id = ulid::Ulid::from_parts(1_469_922_850_259, 0x1234_5678_9abc_def0);
+  let serialization = id.serialize().collect::<String>();
+  assert_eq!(serialization, format!(r#""{}""#, id.array_to_str(&mut [0; ulid::ULID_LEN])));
+
+  let deserialize = |json: &str| {
+    let mut deserializer = Deserializer::<_, ConstStack<32>>::new(json.as_bytes()).unwrap();
+    <(Option<ulid::Ulid>,)>::get_many(deserializer.value().unwrap(), ["id"])
+      .map(|(id,)| id)
+      .map_err(|_| ())
+  };
+  assert_eq!(deserialize(&format!(r#"{{"id":{serialization}}}"#)).unwrap(), Some(id));
+  assert!(deserialize(r#"{"id":"01ARZ3NDEKTSV4RRFFQ69G5FA"}"#).is_err());
+  assert!(deserialize(r#"{"id":"01ARZ3NDEKTSV4RRFFQ69G5FAVV"}"#).is_err());
+  assert!(deserialize(r#"{"id":"01ARZ3NDEKTSV4RRFFQ69G5FA!"}"#).is_err());
+}
@@ -0,0 +1,231 @@
+//! Inferring the structure of documents.
+//!
+//! A `Shape` is inferred from one or many values in a streaming pass, without deserializing them
+//! into `JsonValue`s. It records the types of the values observed, the fields of objects (and if
+//! they were present within every object), and the shape of the elements of arrays. This is
+//! useful for exploring undocumented payloads, with the shape emitted as a
+//! [JSON Schema](https://json-schema.org) via `Shape::to_json_schema`.
+
+use alloc::{string::String, vec::Vec, boxed::Box};
+
+use crate::{Read, Stack, JsonError, Type, Value, JsonValue};
+
+// Flags for each type of value observed
+const OBJECT: u8 = 1 << 0;
+const ARRAY: u8 = 1 << 1;
+const STRING: u8 = 1 << 2;
+const INTEGER: u8 = 1 << 3;
+const FLOAT: u8 = 1 << 4;
+const BOOL: u8 = 1 << 5;
+const NULL: u8 = 1 << 6;
+
+/// A field observed within objects.
+#[derive(Clone, Default, Debug)]
+struct Field {
+  key: String,
+  /// The amount of objects the field was present within.
+  objects: u64,
+  /// The index of the last object the field was present within.
+  last_object: u64,
+  shape: Shape,
+}
+
+/// The shape inferred for a value.
+#[derive(Clone, Default, Debug)]
+pub struct Shape {
+  /// The amount of values observed.
+  observed: u64,
+  /// The types of the values observed.
+  types: u8,
+  /// The amount of objects observed.
+  objects: u64,
+  /// The fields of the objects observed, in the order they were first observed.
+  fields: Vec<Field>,
+  /// The shape of the elements of the arrays observed.
+  elements: Option<Box<Shape>>,
+}
+
+impl Shape {
+  /// Create a new shape, which has yet to observe any values.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Observe a value, updating the shape inferred.
+  ///
+  /// This recurses for each nested structure (bounded by the depth allowed by the `Stack` used).
+  pub fn observe<'read, R: Read<'read>, S: Stack>(
+    &mut self,
+    value: Value<'read, '_, R, S>,
+  ) -> Result<(), JsonError<'read, R, S>> {
+    self.observed += 1;
+    match value.kind() {
+      Type::Object => {
+        self.types |= OBJECT;
+        self.objects += 1;
+        let mut fields = value.fields()?;
+        while let Some(field) = fields.next() {
+          let mut field = field?;
+          let key = field.key()?.collect::<Result<String, _>>()?;
+          let i = match self.fields.iter().position(|field| field.key == key) {
+            Some(i) => i,
+            None => {
+              self.fields.push(Field { key, ..Field::default() });
+              self.fields.len() - 1
+            }
+          };
+          let observed = &mut self.fields[i];
+          // Don't count a key present multiple times within an object multiple times
+          if observed.last_object != self.objects {
+            observed.last_object = self.objects;
+            observed.objects += 1;
+          }
+          observed.shape.observe(field.value()?)?;
+        }
+      }
+      Type::Array => {
+        self.types |= ARRAY;
+        let mut elements = value.iterate()?;
+        while let Some(element) = elements.next() {
+          self.elements.get_or_insert_with(Box::default).observe(element?)?;
+        }
+      }
+      // The string is advanced past (and validated) when the deserializer next advances
+      Type::String => self.types |= STRING,
+      Type::Number => self.types |= if value.to_number()?.is_integer() { INTEGER } else { FLOAT },
+      Type::Bool => {
+        value.to_bool()?;
+        self.types |= BOOL;
+      }
+      Type::Null => {
+        value.to_null()?;
+        self.types |= NULL;
+      }
+    }
+    Ok(())
+  }
+
+  /// The amount of values observed.
+  pub fn observed(&self) -> u64 {
+    self.observed
+  }
+
+  /// The shape of a field, and if it was present within every object observed.
+  pub fn field(&self, key: &str) -> Option<(&Shape, bool)> {
+    self
+      .fields
+      .iter()
+      .find(|field| field.key == key)
+      .map(|field| (&field.shape, field.objects == self.objects))
+  }
+
+  /// The shape of the elements of the arrays observed.
+  ///
+  /// This is `None` if no arrays with elements were observed.
+  pub fn elements(&self) -> Option<&Shape> {
+    self.elements.as_deref()
+  }
+
+  /// The shape as a JSON Schema.
+  ///
+  /// This has the `type` keyword (omitted if no values were observed), with `integer` solely used
+  /// if every number observed was an integer. For objects, this has the `properties` and
+  /// `required` keywords. For arrays with elements, this has the `items` keyword.
+  pub fn to_json_schema(&self) -> JsonValue {
+    let string = |str: &str| JsonValue::String(String::from(str));
+
+    let mut types = Vec::new();
+    for (flag, name) in [
+      (OBJECT, "object"),
+      (ARRAY, "array"),
+      (STRING, "string"),
+      (INTEGER, "integer"),
+      (FLOAT, "number"),
+      (BOOL, "boolean"),
+      (NULL, "null"),
+    ] {
+      // `number` subsumes `integer`
+      if ((self.types & flag) != 0) && (!((flag == INTEGER) && ((self.types & FLOAT) != 0))) {
+        types.push(string(name));
+      }
+    }
+
+    let mut schema = Vec::new();
+    match types.len() {
+      0 => {}
+      1 => schema.push((String::from("type"), types.swap_remove(0))),
+      _ => schema.push((String::from("type"), JsonValue::Array(types))),
+    }
+    if (self.types & OBJECT) != 0 {
+      schema.push((
+        String::from("properties"),
+        JsonValue::Object(
+          self
+            .fields
+            .iter()
+            .map(|field| (field.key.clone(), field.shape.to_json_schema()))
+            .collect(),
+        ),
+      ));
+      schema.push((
+        String::from("required"),
+        JsonValue::Array(
+          self
+            .fields
+            .iter()
+            .filter(|field| field.objects == self.objects)
+            .map(|field| string(&field.key))
+            .collect(),
+        ),
+      ));
+    }
+    if let Some(elements) = &self.elements {
+      schema.push((String::from("items"), elements.to_json_schema()));
+    }
+    JsonValue::Object(schema)
+  }
+}
+
+#[test]
+fn infer() {
+  use crate::{ConstStack, Deserializer, JsonStructure, JsonSerialize};
+
+  let mut shape = Shape::new();
+  for document in [
+    r#"{"id": 1, "name": "a", "tags": ["b"], "owner": {"id": 2}}"#,
+    r#"{"id": 2.5, "tags": [], "owner": null, "extra": [1, "c"], "id": 3}"#,
+  ] {
+    let mut deserializer = Deserializer::<_, ConstStack<32>>::new(document.as_bytes()).unwrap();
+    shape.observe(deserializer.value().unwrap()).unwrap();
+  }
+
+  assert_eq!(shape.observed(), 2);
+  let (id, required) = shape.field("id").unwrap();
+  assert!(required);
+  assert_eq!(id.observed(), 3);
+  assert!(!shape.field("name").unwrap().1);
+  assert!(shape.field("missing").is_none());
+  assert_eq!(shape.field("tags").unwrap().0.elements().unwrap().observed(), 1);
+
+  let expected = JsonValue::deserialize_structure::<_, ConstStack<32>>(
+    r#"{
+      "type": "object",
+      "properties": {
+        "id": { "type": "number" },
+        "name": { "type": "string" },
+        "tags": { "type": "array", "items": { "type": "string" } },
+        "owner": {
+          "type": ["object", "null"],
+          "properties": { "id": { "type": "integer" } },
+          "required": ["id"]
+        },
+        "extra": { "type": "array", "items": { "type": ["string", "integer"] } }
+      },
+      "required": ["id", "tags", "owner"]
+    }"#
+      .as_bytes(),
+  )
+  .unwrap();
+  assert_eq!(shape.to_json_schema(), expected);
+  assert_eq!(Shape::new().to_json_schema().serialize().collect::<String>(), "{}");
+}
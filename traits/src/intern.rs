@@ -0,0 +1,161 @@
+use alloc::{string::String, rc::Rc, sync::Arc, collections::BTreeSet};
+
+use crate::{Read, Stack, JsonError, Value, JsonDeserialize, JsonDeserializeSeed, seeds::KeySink};
+
+/// An interner for strings.
+///
+/// Deserializing a string with an interner allows deduplicating repeated strings, such as the
+/// keys of the objects within a large array, instead of allocating each instance of the string.
+pub trait Interner {
+  /// The handle for an interned string.
+  type Interned;
+
+  /// Intern a string.
+  ///
+  /// If the string was previously interned, this SHOULD return a handle to the existing instance.
+  fn intern(&mut self, str: &str) -> Self::Interned;
+}
+
+macro_rules! interner {
+  ($set: ident, $handle: ident) => {
+    impl Interner for $set<$handle<str>> {
+      type Interned = $handle<str>;
+      fn intern(&mut self, str: &str) -> Self::Interned {
+        if let Some(interned) = self.get(str) {
+          return interned.clone();
+        }
+        let interned = $handle::<str>::from(str);
+        self.insert(interned.clone());
+        interned
+      }
+    }
+  };
+}
+interner!(BTreeSet, Rc);
+interner!(BTreeSet, Arc);
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+#[cfg(feature = "std")]
+interner!(HashSet, Rc);
+#[cfg(feature = "std")]
+interner!(HashSet, Arc);
+
+/// A context for deserializing strings with an `Interner`.
+///
+/// Each string is collected into a single buffer, reused across strings, before being interned.
+/// This means deserializing a string which was already interned does not perform an allocation.
+pub struct Interning<I: Interner> {
+  interner: I,
+  buffer: String,
+}
+
+impl<I: Interner> Interning<I> {
+  /// Create a new context for deserializing strings with the specified interner.
+  pub fn new(interner: I) -> Self {
+    Self { interner, buffer: String::new() }
+  }
+
+  /// Access the interner.
+  pub fn interner(&mut self) -> &mut I {
+    &mut self.interner
+  }
+
+  /// Consume this context, returning the interner.
+  pub fn into_interner(self) -> I {
+    self.interner
+  }
+
+  /// Deserialize a string, interning it.
+  pub fn string<'read, 'parent, R: Read<'read>, S: Stack>(
+    &mut self,
+    value: Value<'read, 'parent, R, S>,
+  ) -> Result<I::Interned, JsonError<'read, R, S>> {
+    self.sink(value.to_str()?)
+  }
+
+  /// Deserialize an object into a map, interning its keys.
+  ///
+  /// If a field is present multiple times, the map will be collected with each instance of the
+  /// field, in the order they were serialized.
+  pub fn map<
+    'read,
+    'parent,
+    T: JsonDeserialize,
+    M: FromIterator<(I::Interned, T)>,
+    R: Read<'read>,
+    S: Stack,
+  >(
+    &mut self,
+    value: Value<'read, 'parent, R, S>,
+  ) -> Result<M, JsonError<'read, R, S>> {
+    crate::seeds::deserialize_map(value, self, core::marker::PhantomData::<T>)?.collect()
+  }
+}
+
+impl<I: Interner> KeySink for Interning<I> {
+  type Key = I::Interned;
+  fn sink<E>(&mut self, key: impl Iterator<Item = Result<char, E>>) -> Result<Self::Key, E> {
+    self.buffer.clear();
+    for char in key {
+      self.buffer.push(char?);
+    }
+    Ok(self.interner.intern(&self.buffer))
+  }
+}
+
+impl<I: Interner> JsonDeserializeSeed for Interning<I> {
+  type Value = I::Interned;
+  #[inline(always)]
+  fn deserialize<'read, 'parent, R: Read<'read>, S: Stack>(
+    &mut self,
+    value: Value<'read, 'parent, R, S>,
+  ) -> Result<I::Interned, JsonError<'read, R, S>> {
+    self.string(value)
+  }
+}
+
+impl<I: Interner + Default> Default for Interning<I> {
+  fn default() -> Self {
+    Self::new(I::default())
+  }
+}
+
+#[test]
+fn interning() {
+  use crate::{ConstStack, Deserializer};
+
+  let mut interning = Interning::new(BTreeSet::<Rc<str>>::new());
+
+  {
+    let serialization = r#"[{ "id": 1, "kind": 2 }, { "id": 3, "kind": 4 }]"#;
+    let mut deserializer =
+      Deserializer::<_, ConstStack<32>>::new(serialization.as_bytes()).unwrap();
+    let mut records = deserializer.value().unwrap().iterate().unwrap();
+    let mut maps = alloc::vec::Vec::<alloc::collections::BTreeMap<Rc<str>, u64>>::new();
+    while let Some(record) = records.next() {
+      maps.push(interning.map(record.unwrap()).unwrap());
+    }
+    assert_eq!(maps.len(), 2);
+    assert_eq!(maps[1].values().copied().collect::<alloc::vec::Vec<_>>(), [3, 4]);
+
+    let first_id = maps[0].keys().next().unwrap();
+    let second_id = maps[1].keys().next().unwrap();
+    assert_eq!(&**first_id, "id");
+    assert!(Rc::ptr_eq(first_id, second_id));
+  }
+
+  {
+    let serialization = r#"["id", "name", "id"]"#;
+    let mut deserializer =
+      Deserializer::<_, ConstStack<32>>::new(serialization.as_bytes()).unwrap();
+    let mut strings = deserializer.value().unwrap().iterate().unwrap();
+    let mut interned = alloc::vec::Vec::new();
+    while let Some(string) = strings.next() {
+      interned.push(interning.string(string.unwrap()).unwrap());
+    }
+    assert!(Rc::ptr_eq(&interned[0], &interned[2]));
+    assert_eq!(&*interned[1], "name");
+  }
+
+  assert_eq!(interning.interner().len(), 3);
+}
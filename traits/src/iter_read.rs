@@ -0,0 +1,111 @@
+use core::iter::Map;
+
+use crate::{ErrorKind, Read, StringRead};
+
+/// An error when reading from an `IteratorRead`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IteratorReadError {
+  /// The serialization ended before all of the bytes requested were read.
+  Ended,
+}
+
+// The characters of a serialization, as read by a `StringRead`
+type Chars<I> = Map<I, fn(char) -> Result<char, ErrorKind>>;
+
+/// A reader of the bytes of a serialization.
+///
+/// This encodes the characters yielded by an iterator (such as the output of
+/// `JsonSerialize::serialize`) as UTF-8, as they're read. This allows streaming a serialization
+/// into sinks which consume readers (such as the bodies of HTTP requests), or deserializing it,
+/// without collecting it into a `String`. With the `std` feature, this implements
+/// `std::io::Read`.
+#[derive(Clone, Debug)]
+pub struct IteratorRead<I: Iterator<Item = char>> {
+  // The encoding is shared with `StringRead`, with the characters always being `Ok`
+  read: StringRead<Chars<I>>,
+}
+
+impl<I: Iterator<Item = char>> IteratorRead<I> {
+  /// Read the bytes of a serialization.
+  #[inline(always)]
+  pub fn new(iter: I) -> Self {
+    IteratorRead { read: StringRead::new(iter.map(Ok as fn(_) -> _)) }
+  }
+
+  /// Read into a buffer, returning the amount of bytes read.
+  ///
+  /// This solely returns `0` if the serialization has ended (or the buffer is empty).
+  pub(crate) fn read_available(&mut self, buffer: &mut [u8]) -> usize {
+    // `StringRead` solely errors if a character is an error, which none are
+    self.read.read_available(buffer).unwrap_or(0)
+  }
+}
+
+impl<I: Iterator<Item = char>> Read<'_> for IteratorRead<I> {
+  type Error = IteratorReadError;
+
+  #[inline(always)]
+  fn read_exact(&mut self, slice: &mut [u8]) -> Result<(), Self::Error> {
+    if self.read_available(slice) != slice.len() {
+      Err(IteratorReadError::Ended)?;
+    }
+    Ok(())
+  }
+
+  #[inline(always)]
+  fn read_into_buffer(&mut self, buffer: &mut [u8]) -> Result<usize, Self::Error> {
+    match self.read_available(buffer) {
+      0 => Err(IteratorReadError::Ended),
+      read => Ok(read),
+    }
+  }
+}
+
+#[cfg(feature = "std")]
+impl<I: Iterator<Item = char>> std::io::Read for IteratorRead<I> {
+  #[inline(always)]
+  fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+    Ok(self.read_available(buf))
+  }
+}
+
+#[cfg(all(feature = "alloc", feature = "sequences"))]
+#[test]
+fn iterator_read() {
+  use alloc::string::String;
+  use crate::{ConstStack, JsonSerialize, JsonStructure};
+
+  let value = [String::from("a\u{e9}\u{1F600}"), String::from("\"")];
+  let serialization = value.serialize().collect::<String>();
+
+  // Read the serialization a byte at a time, splitting the multi-byte characters
+  let mut reader = IteratorRead::new(value.serialize());
+  let mut bytes = alloc::vec::Vec::new();
+  let mut byte = [0; 1];
+  while reader.read_available(&mut byte) == 1 {
+    bytes.push(byte[0]);
+  }
+  assert_eq!(bytes, serialization.as_bytes());
+  assert_eq!(reader.read_into_buffer(&mut [0; 1]), Err(IteratorReadError::Ended));
+
+  let mut reader = IteratorRead::new(value.serialize());
+  let mut bytes = [0; 5];
+  reader.read_exact(&mut bytes).unwrap();
+  assert_eq!(&bytes, &serialization.as_bytes()[.. 5]);
+  assert_eq!(reader.read_exact(&mut [0; 64]), Err(IteratorReadError::Ended));
+
+  // The serialization may be deserialized without being collected
+  let deserialized =
+    <[String; 2]>::deserialize_structure::<_, ConstStack<32>>(IteratorRead::new(value.serialize()))
+      .map_err(|_| ())
+      .unwrap();
+  assert_eq!(deserialized, value);
+
+  #[cfg(feature = "std")]
+  {
+    use std::io::Read as _;
+    let mut read = String::new();
+    IteratorRead::new(value.serialize()).read_to_string(&mut read).unwrap();
+    assert_eq!(read, serialization);
+  }
+}
@@ -0,0 +1,546 @@
+use alloc::{string::String, vec::Vec, boxed::Box};
+
+use crate::{Read, Stack, JsonError, Value, JsonDeserialize, JsonNumber, JsonValue, JsonPointer};
+
+/// The schema was invalid, or used an unsupported reference.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InvalidSchema;
+
+/// The types a schema allows, as a bitmask.
+mod types {
+  pub(super) const NULL: u8 = 1 << 0;
+  pub(super) const BOOLEAN: u8 = 1 << 1;
+  pub(super) const OBJECT: u8 = 1 << 2;
+  pub(super) const ARRAY: u8 = 1 << 3;
+  pub(super) const NUMBER: u8 = 1 << 4;
+  pub(super) const STRING: u8 = 1 << 5;
+  pub(super) const INTEGER: u8 = 1 << 6;
+}
+
+/// A compiled (sub)schema.
+#[derive(Clone, Debug)]
+enum Node {
+  Bool(bool),
+  Keywords(Box<Keywords>),
+}
+
+/// The keywords of a schema.
+#[derive(Clone, Default, Debug)]
+struct Keywords {
+  reference: Option<usize>,
+
+  types: Option<u8>,
+  enumeration: Option<Vec<JsonValue>>,
+  constant: Option<JsonValue>,
+
+  multiple_of: Option<f64>,
+  maximum: Option<f64>,
+  exclusive_maximum: Option<f64>,
+  minimum: Option<f64>,
+  exclusive_minimum: Option<f64>,
+
+  max_length: Option<usize>,
+  min_length: Option<usize>,
+
+  max_items: Option<usize>,
+  min_items: Option<usize>,
+  unique_items: bool,
+  max_contains: Option<usize>,
+  min_contains: Option<usize>,
+
+  max_properties: Option<usize>,
+  min_properties: Option<usize>,
+  required: Vec<String>,
+  dependent_required: Vec<(String, Vec<String>)>,
+
+  all_of: Vec<Node>,
+  any_of: Option<Vec<Node>>,
+  one_of: Option<Vec<Node>>,
+  not: Option<Node>,
+  condition: Option<Node>,
+  then: Option<Node>,
+  otherwise: Option<Node>,
+
+  properties: Vec<(String, Node)>,
+  additional_properties: Option<Node>,
+  property_names: Option<Node>,
+  dependent_schemas: Vec<(String, Node)>,
+
+  prefix_items: Vec<Node>,
+  items: Option<Node>,
+  contains: Option<Node>,
+}
+
+/// The fields of an object, solely yielding the last instance of each key.
+fn fields(object: &[(String, JsonValue)]) -> impl Iterator<Item = &(String, JsonValue)> {
+  object
+    .iter()
+    .enumerate()
+    .filter(|(i, (key, _))| !object[(i + 1) ..].iter().any(|(other, _)| other == key))
+    .map(|(_, field)| field)
+}
+
+/// If two values are equal, as defined by JSON Schema.
+///
+/// Numbers are compared by their mathematical value and objects are compared regardless of the
+/// order of their fields.
+#[allow(clippy::float_cmp)]
+fn equal(a: &JsonValue, b: &JsonValue) -> bool {
+  match (a, b) {
+    (JsonValue::Number(JsonNumber::Integer(a)), JsonValue::Number(JsonNumber::Integer(b))) => {
+      a == b
+    }
+    (JsonValue::Number(a), JsonValue::Number(b)) => a.f64() == b.f64(),
+    (JsonValue::Array(a), JsonValue::Array(b)) => {
+      (a.len() == b.len()) && a.iter().zip(b).all(|(a, b)| equal(a, b))
+    }
+    (JsonValue::Object(a), JsonValue::Object(b)) => {
+      (fields(a).count() == fields(b).count()) &&
+        fields(a).all(|(key, a)| get(b, key).is_some_and(|b| equal(a, b)))
+    }
+    (a, b) => a == b,
+  }
+}
+
+/// Get the value of a field within an object.
+fn get<'object>(object: &'object [(String, JsonValue)], key: &str) -> Option<&'object JsonValue> {
+  object.iter().rev().find(|(field, _)| field == key).map(|(_, value)| value)
+}
+
+/// Resolve a reference to a location within the root schema.
+///
+/// The reference is a URI fragment, whose JSON Pointer is percent-encoded.
+fn resolve<'root>(root: &'root JsonValue, reference: &str) -> Option<&'root JsonValue> {
+  let fragment = reference.strip_prefix('#')?;
+  let mut pointer = Vec::with_capacity(fragment.len());
+  let mut bytes = fragment.bytes();
+  while let Some(byte) = bytes.next() {
+    if byte == b'%' {
+      let hex = [bytes.next()?, bytes.next()?];
+      pointer.push(u8::from_str_radix(core::str::from_utf8(&hex).ok()?, 16).ok()?);
+    } else {
+      pointer.push(byte);
+    }
+  }
+  core::str::from_utf8(&pointer).ok()?.parse::<JsonPointer>().ok()?.resolve(root)
+}
+
+struct Compiler<'root> {
+  root: &'root JsonValue,
+  references: Vec<Node>,
+  resolved: Vec<(&'root str, usize)>,
+}
+
+impl<'root> Compiler<'root> {
+  fn number(value: &JsonValue) -> Result<f64, InvalidSchema> {
+    match value {
+      JsonValue::Number(number) => Ok(number.f64()),
+      _ => Err(InvalidSchema),
+    }
+  }
+
+  /// A non-negative integer, as used to bound lengths and counts.
+  ///
+  /// Integers exceeding `usize::MAX` saturate to it. As no length or count can reach
+  /// `usize::MAX`, this doesn't change which instances are valid.
+  fn non_negative_integer(value: &JsonValue) -> Result<usize, InvalidSchema> {
+    match value {
+      JsonValue::Number(JsonNumber::Integer(integer)) => {
+        let integer = u64::try_from(*integer).map_err(|_| InvalidSchema)?;
+        Ok(usize::try_from(integer).unwrap_or(usize::MAX))
+      }
+      // Integers may be represented with a fractional part of zero, or exceed an `i64`
+      JsonValue::Number(JsonNumber::Float(float)) => {
+        let float = f64::from(*float);
+        if (float < 0.0) || ((float % 1.0) != 0.0) {
+          Err(InvalidSchema)?;
+        }
+        // This cast saturates
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(float as usize)
+      }
+      _ => Err(InvalidSchema),
+    }
+  }
+
+  fn strings(value: &JsonValue) -> Result<Vec<String>, InvalidSchema> {
+    let JsonValue::Array(array) = value else { Err(InvalidSchema)? };
+    array
+      .iter()
+      .map(|value| match value {
+        JsonValue::String(string) => Ok(string.clone()),
+        _ => Err(InvalidSchema),
+      })
+      .collect()
+  }
+
+  fn schemas(&mut self, value: &'root JsonValue) -> Result<Vec<Node>, InvalidSchema> {
+    let JsonValue::Array(array) = value else { Err(InvalidSchema)? };
+    array.iter().map(|schema| self.compile(schema)).collect()
+  }
+
+  fn schema_map(&mut self, value: &'root JsonValue) -> Result<Vec<(String, Node)>, InvalidSchema> {
+    let JsonValue::Object(object) = value else { Err(InvalidSchema)? };
+    fields(object).map(|(key, schema)| Ok((key.clone(), self.compile(schema)?))).collect()
+  }
+
+  fn reference(&mut self, reference: &'root str) -> Result<usize, InvalidSchema> {
+    if let Some((_, i)) = self.resolved.iter().find(|(resolved, _)| *resolved == reference) {
+      return Ok(*i);
+    }
+    let schema = resolve(self.root, reference).ok_or(InvalidSchema)?;
+    // Reserve the index before compiling the referenced schema, as it may reference itself
+    let i = self.references.len();
+    self.references.push(Node::Bool(true));
+    self.resolved.push((reference, i));
+    self.references[i] = self.compile(schema)?;
+    Ok(i)
+  }
+
+  fn compile(&mut self, schema: &'root JsonValue) -> Result<Node, InvalidSchema> {
+    let object = match schema {
+      JsonValue::Bool(bool) => return Ok(Node::Bool(*bool)),
+      JsonValue::Object(object) => object,
+      _ => Err(InvalidSchema)?,
+    };
+
+    let mut keywords = Keywords::default();
+    for (keyword, value) in fields(object) {
+      match keyword.as_str() {
+        "$ref" => {
+          let JsonValue::String(reference) = value else { Err(InvalidSchema)? };
+          keywords.reference = Some(self.reference(reference)?);
+        }
+
+        "type" => {
+          let type_bit = |value: &JsonValue| {
+            let JsonValue::String(kind) = value else { Err(InvalidSchema)? };
+            Ok(match kind.as_str() {
+              "null" => types::NULL,
+              "boolean" => types::BOOLEAN,
+              "object" => types::OBJECT,
+              "array" => types::ARRAY,
+              "number" => types::NUMBER,
+              "string" => types::STRING,
+              "integer" => types::INTEGER,
+              _ => Err(InvalidSchema)?,
+            })
+          };
+          keywords.types = Some(match value {
+            JsonValue::Array(kinds) => {
+              kinds.iter().try_fold(0, |types, kind| Ok(types | type_bit(kind)?))?
+            }
+            kind => type_bit(kind)?,
+          });
+        }
+        "enum" => {
+          let JsonValue::Array(values) = value else { Err(InvalidSchema)? };
+          keywords.enumeration = Some(values.clone());
+        }
+        "const" => keywords.constant = Some(value.clone()),
+
+        "multipleOf" => {
+          let multiple_of = Self::number(value)?;
+          if multiple_of <= 0.0 {
+            Err(InvalidSchema)?;
+          }
+          keywords.multiple_of = Some(multiple_of);
+        }
+        "maximum" => keywords.maximum = Some(Self::number(value)?),
+        "exclusiveMaximum" => keywords.exclusive_maximum = Some(Self::number(value)?),
+        "minimum" => keywords.minimum = Some(Self::number(value)?),
+        "exclusiveMinimum" => keywords.exclusive_minimum = Some(Self::number(value)?),
+
+        "maxLength" => keywords.max_length = Some(Self::non_negative_integer(value)?),
+        "minLength" => keywords.min_length = Some(Self::non_negative_integer(value)?),
+
+        "maxItems" => keywords.max_items = Some(Self::non_negative_integer(value)?),
+        "minItems" => keywords.min_items = Some(Self::non_negative_integer(value)?),
+        "uniqueItems" => {
+          let JsonValue::Bool(unique_items) = value else { Err(InvalidSchema)? };
+          keywords.unique_items = *unique_items;
+        }
+        "maxContains" => keywords.max_contains = Some(Self::non_negative_integer(value)?),
+        "minContains" => keywords.min_contains = Some(Self::non_negative_integer(value)?),
+
+        "maxProperties" => keywords.max_properties = Some(Self::non_negative_integer(value)?),
+        "minProperties" => keywords.min_properties = Some(Self::non_negative_integer(value)?),
+        "required" => keywords.required = Self::strings(value)?,
+        "dependentRequired" => {
+          let JsonValue::Object(object) = value else { Err(InvalidSchema)? };
+          keywords.dependent_required = fields(object)
+            .map(|(key, required)| Ok((key.clone(), Self::strings(required)?)))
+            .collect::<Result<_, _>>()?;
+        }
+
+        "allOf" => keywords.all_of = self.schemas(value)?,
+        "anyOf" => keywords.any_of = Some(self.schemas(value)?),
+        "oneOf" => keywords.one_of = Some(self.schemas(value)?),
+        "not" => keywords.not = Some(self.compile(value)?),
+        "if" => keywords.condition = Some(self.compile(value)?),
+        "then" => keywords.then = Some(self.compile(value)?),
+        "else" => keywords.otherwise = Some(self.compile(value)?),
+
+        "properties" => keywords.properties = self.schema_map(value)?,
+        "additionalProperties" => keywords.additional_properties = Some(self.compile(value)?),
+        "propertyNames" => keywords.property_names = Some(self.compile(value)?),
+        "dependentSchemas" => keywords.dependent_schemas = self.schema_map(value)?,
+
+        "prefixItems" => keywords.prefix_items = self.schemas(value)?,
+        "items" => keywords.items = Some(self.compile(value)?),
+        "contains" => keywords.contains = Some(self.compile(value)?),
+
+        // Unknown keywords, and annotations, are ignored
+        _ => {}
+      }
+    }
+    Ok(Node::Keywords(Box::new(keywords)))
+  }
+}
+
+/// A compiled JSON Schema, as specified by
+/// [draft 2020-12](https://json-schema.org/draft/2020-12/json-schema-core).
+///
+/// This implements the assertions and applicators of the core and validation vocabularies, with
+/// the following exceptions:
+/// - `pattern` and `patternProperties` are ignored, as no regular expression engine is available.
+/// - `unevaluatedItems` and `unevaluatedProperties` are ignored.
+/// - `$ref` may solely reference a location within the schema itself (such as `#/$defs/name`),
+///   with `$id`, `$anchor`, and `$dynamicRef` ignored.
+/// - A `$ref` may not apply itself to the same instance (such as `{"$ref": "#"}`), as validation
+///   would never terminate. References may still recurse into the instance's items or properties.
+/// - `format` and `content*` are treated as annotations and not asserted.
+///
+/// Instances are validated once deserialized into a `JsonValue`, as the applicators may evaluate
+/// the same value against multiple schemas.
+#[derive(Clone, Debug)]
+pub struct JsonSchema {
+  root: Node,
+  references: Vec<Node>,
+}
+
+impl JsonSchema {
+  /// Compile a schema.
+  pub fn compile(schema: &JsonValue) -> Result<Self, InvalidSchema> {
+    let mut compiler = Compiler { root: schema, references: Vec::new(), resolved: Vec::new() };
+    let root = compiler.compile(schema)?;
+    let schema = JsonSchema { root, references: compiler.references };
+    for (i, reference) in schema.references.iter().enumerate() {
+      if schema.applies(reference, i, &mut Vec::new()) {
+        Err(InvalidSchema)?;
+      }
+    }
+    Ok(schema)
+  }
+
+  /// If validating an instance against this node may apply the reference `target` to the same
+  /// instance.
+  ///
+  /// References already `visited` aren't walked again, ensuring this terminates.
+  fn applies(&self, node: &Node, target: usize, visited: &mut Vec<usize>) -> bool {
+    let Node::Keywords(keywords) = node else { return false };
+    if let Some(reference) = keywords.reference {
+      if reference == target {
+        return true;
+      }
+      if !visited.contains(&reference) {
+        visited.push(reference);
+        if self.applies(&self.references[reference], target, visited) {
+          return true;
+        }
+      }
+    }
+    keywords
+      .all_of
+      .iter()
+      .chain(keywords.any_of.iter().flatten())
+      .chain(keywords.one_of.iter().flatten())
+      .chain(&keywords.not)
+      .chain(&keywords.condition)
+      .chain(&keywords.then)
+      .chain(&keywords.otherwise)
+      .chain(keywords.dependent_schemas.iter().map(|(_, schema)| schema))
+      .any(|node| self.applies(node, target, visited))
+  }
+
+  fn valid(&self, node: &Node, instance: &JsonValue) -> bool {
+    let keywords = match node {
+      Node::Bool(bool) => return *bool,
+      Node::Keywords(keywords) => keywords,
+    };
+
+    if let Some(reference) = keywords.reference &&
+      !self.valid(&self.references[reference], instance)
+    {
+      return false;
+    }
+
+    if let Some(types) = keywords.types {
+      let kind = match instance {
+        JsonValue::Null => types::NULL,
+        JsonValue::Bool(_) => types::BOOLEAN,
+        JsonValue::Object(_) => types::OBJECT,
+        JsonValue::Array(_) => types::ARRAY,
+        JsonValue::String(_) => types::STRING,
+        JsonValue::Number(number) => {
+          let integer = match number {
+            JsonNumber::Integer(_) => true,
+            JsonNumber::Float(float) => (f64::from(*float) % 1.0) == 0.0,
+          };
+          types::NUMBER | if integer { types::INTEGER } else { 0 }
+        }
+      };
+      if (types & kind) == 0 {
+        return false;
+      }
+    }
+    if let Some(enumeration) = &keywords.enumeration &&
+      !enumeration.iter().any(|value| equal(value, instance))
+    {
+      return false;
+    }
+    if let Some(constant) = &keywords.constant &&
+      !equal(constant, instance)
+    {
+      return false;
+    }
+
+    let all_of = keywords.all_of.iter().all(|schema| self.valid(schema, instance));
+    let any_of = keywords
+      .any_of
+      .as_ref()
+      .is_none_or(|any_of| any_of.iter().any(|schema| self.valid(schema, instance)));
+    let one_of = keywords.one_of.as_ref().is_none_or(|one_of| {
+      one_of.iter().filter(|schema| self.valid(schema, instance)).count() == 1
+    });
+    let not = keywords.not.as_ref().is_none_or(|not| !self.valid(not, instance));
+    let condition = keywords.condition.as_ref().is_none_or(|condition| {
+      let branch =
+        if self.valid(condition, instance) { &keywords.then } else { &keywords.otherwise };
+      branch.as_ref().is_none_or(|branch| self.valid(branch, instance))
+    });
+    if !(all_of && any_of && one_of && not && condition) {
+      return false;
+    }
+
+    match instance {
+      JsonValue::Number(number) => Self::valid_number(keywords, *number),
+      JsonValue::String(string) => {
+        let len = string.chars().count();
+        keywords.max_length.is_none_or(|max| len <= max) &&
+          keywords.min_length.is_none_or(|min| len >= min)
+      }
+      JsonValue::Array(array) => self.valid_array(keywords, array),
+      JsonValue::Object(object) => self.valid_object(keywords, instance, object),
+      JsonValue::Null | JsonValue::Bool(_) => true,
+    }
+  }
+
+  fn valid_number(keywords: &Keywords, number: JsonNumber) -> bool {
+    let number = number.f64();
+    keywords.multiple_of.is_none_or(|multiple_of| {
+      let quotient = number / multiple_of;
+      quotient.is_finite() && ((quotient % 1.0) == 0.0)
+    }) && keywords.maximum.is_none_or(|maximum| number <= maximum) &&
+      keywords.exclusive_maximum.is_none_or(|maximum| number < maximum) &&
+      keywords.minimum.is_none_or(|minimum| number >= minimum) &&
+      keywords.exclusive_minimum.is_none_or(|minimum| number > minimum)
+  }
+
+  fn valid_array(&self, keywords: &Keywords, array: &[JsonValue]) -> bool {
+    if keywords.max_items.is_some_and(|max| array.len() > max) ||
+      keywords.min_items.is_some_and(|min| array.len() < min)
+    {
+      return false;
+    }
+    if keywords.unique_items &&
+      array.iter().enumerate().any(|(i, a)| array[(i + 1) ..].iter().any(|b| equal(a, b)))
+    {
+      return false;
+    }
+
+    for (schema, element) in keywords.prefix_items.iter().zip(array) {
+      if !self.valid(schema, element) {
+        return false;
+      }
+    }
+    if let Some(items) = &keywords.items &&
+      !array.iter().skip(keywords.prefix_items.len()).all(|element| self.valid(items, element))
+    {
+      return false;
+    }
+
+    if let Some(contains) = &keywords.contains {
+      let matches = array.iter().filter(|element| self.valid(contains, element)).count();
+      if (matches < keywords.min_contains.unwrap_or(1)) ||
+        keywords.max_contains.is_some_and(|max| matches > max)
+      {
+        return false;
+      }
+    }
+    true
+  }
+
+  fn valid_object(
+    &self,
+    keywords: &Keywords,
+    instance: &JsonValue,
+    object: &[(String, JsonValue)],
+  ) -> bool {
+    let len = fields(object).count();
+    if keywords.max_properties.is_some_and(|max| len > max) ||
+      keywords.min_properties.is_some_and(|min| len < min)
+    {
+      return false;
+    }
+    let present = |key: &str| get(object, key).is_some();
+    if !keywords.required.iter().all(|key| present(key)) {
+      return false;
+    }
+    for (key, required) in &keywords.dependent_required {
+      if present(key) && (!required.iter().all(|key| present(key))) {
+        return false;
+      }
+    }
+    for (key, schema) in &keywords.dependent_schemas {
+      if present(key) && (!self.valid(schema, instance)) {
+        return false;
+      }
+    }
+
+    for (key, value) in fields(object) {
+      if let Some(names) = &keywords.property_names &&
+        !self.valid(names, &JsonValue::String(key.clone()))
+      {
+        return false;
+      }
+      let schema = keywords
+        .properties
+        .iter()
+        .find(|(property, _)| property == key)
+        .map(|(_, schema)| schema)
+        .or(keywords.additional_properties.as_ref());
+      if let Some(schema) = schema &&
+        !self.valid(schema, value)
+      {
+        return false;
+      }
+    }
+    true
+  }
+
+  /// If an instance is valid per this schema.
+  pub fn is_valid(&self, instance: &JsonValue) -> bool {
+    self.valid(&self.root, instance)
+  }
+
+  /// Validate a value per this schema.
+  ///
+  /// This deserializes the value into a `JsonValue`, returning if it's valid.
+  pub fn validate<'read, R: Read<'read>, S: Stack>(
+    &self,
+    value: Value<'read, '_, R, S>,
+  ) -> Result<bool, JsonError<'read, R, S>> {
+    Ok(self.is_valid(&JsonValue::deserialize(value)?))
+  }
+}
@@ -9,20 +9,106 @@ extern crate alloc;
 pub use core_json::*;
 
 mod tri;
+mod count;
+mod iter_read;
+mod chunks;
+mod depth;
+mod validate;
 
 mod primitives;
 mod float;
 mod option;
+mod seeds;
+#[cfg(feature = "sequences")]
 mod sequences;
+#[cfg(all(feature = "sets", feature = "alloc"))]
+mod sets;
+#[cfg(all(feature = "maps", feature = "alloc"))]
+mod maps;
 mod string;
+mod core_types;
+#[cfg(feature = "fugit")]
+mod fugit;
+#[cfg(any(feature = "uuid", feature = "ulid"))]
+mod ids;
+#[cfg(any(feature = "time", feature = "chrono"))]
+mod datetime;
+#[cfg(feature = "rust_decimal")]
+mod decimal;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "bumpalo")]
+mod arena;
+#[cfg(any(feature = "smol_str", feature = "compact_str"))]
+mod small_strings;
+#[cfg(feature = "unicode-normalization")]
+mod normalize;
+mod many;
+mod sink;
+mod display;
+mod wire_format;
+
+pub mod schema;
+pub mod hash;
 
 #[cfg(feature = "alloc")]
 mod boxed;
 #[cfg(feature = "alloc")]
-mod maps;
+mod intern;
+#[cfg(feature = "alloc")]
+mod value;
+#[cfg(feature = "alloc")]
+mod pointer;
+#[cfg(feature = "alloc")]
+mod json_schema;
+#[cfg(feature = "alloc")]
+pub mod patch;
+#[cfg(feature = "alloc")]
+pub mod csv;
+#[cfg(feature = "alloc")]
+pub mod eq;
+#[cfg(feature = "alloc")]
+pub mod infer;
+#[cfg(feature = "alloc")]
+pub mod transform;
 
 pub use tri::Tri;
+pub use core_types::Ignored;
+pub use count::ByteCount;
+pub use iter_read::{IteratorReadError, IteratorRead};
+pub use chunks::Chunks;
+pub use depth::DepthLimited;
+#[doc(hidden)]
+pub use validate::validate_serialization;
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub use string::deserialize_constrained_string;
 pub use float::JsonF64;
+#[cfg(feature = "zmij")]
+pub use float::ExactF64;
+pub use seeds::SequenceSeed;
+#[cfg(feature = "bumpalo")]
+pub use arena::{ArenaStr, ArenaVec};
+#[cfg(feature = "unicode-normalization")]
+pub use normalize::{NfcString, key_matches_nfc};
+pub use many::GetMany;
+pub use sink::{AsyncWrite, serialize_async};
+pub use display::DisplayJson;
+pub use wire_format::{JsonWireFormat, WireFormat};
+#[cfg(feature = "alloc")]
+pub use seeds::MapSeed;
+#[cfg(all(feature = "maps", feature = "alloc"))]
+pub use maps::Sorted;
+#[cfg(feature = "alloc")]
+pub use intern::{Interner, Interning};
+#[cfg(feature = "alloc")]
+pub use validate::is_valid_json_output;
+#[cfg(feature = "alloc")]
+pub use value::{JsonNumber, JsonValue};
+#[cfg(feature = "alloc")]
+pub use pointer::{InvalidPointer, JsonPointer};
+#[cfg(feature = "alloc")]
+pub use json_schema::{InvalidSchema, JsonSchema};
 
 /// An item which can be deserialized from a `Value`.
 ///
@@ -35,6 +121,74 @@ pub trait JsonDeserialize: Sized {
   ) -> Result<Self, JsonError<'read, B, S>>;
 }
 
+/// A seed which can deserialize an item from a `Value`.
+///
+/// This is akin to `JsonDeserialize`, except the seed is present while deserializing, allowing it
+/// to provide context (such as a registry or an allocator) necessary to build the item. Any
+/// `JsonDeserialize` type may be used as a seed via `PhantomData`, and seeds may be used for the
+/// elements of collections via `SequenceSeed` and `MapSeed`.
+pub trait JsonDeserializeSeed {
+  /// The item deserialized.
+  type Value;
+
+  /// Decode an item from a `Value`.
+  fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+    &mut self,
+    value: Value<'read, 'parent, B, S>,
+  ) -> Result<Self::Value, JsonError<'read, B, S>>;
+
+  /// Deserialize an item from an JSON-serialized blob.
+  ///
+  /// This method SHOULD NOT be overriden.
+  fn deserialize_structure<'read, B: Read<'read>, S: Stack>(
+    &mut self,
+    json: B,
+  ) -> Result<Self::Value, JsonError<'read, B, S>> {
+    let mut json = Deserializer::new(json)?;
+    let value = json.value()?;
+    self.deserialize(value)
+  }
+}
+
+impl<T: JsonDeserialize> JsonDeserializeSeed for core::marker::PhantomData<T> {
+  type Value = T;
+  #[inline(always)]
+  fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+    &mut self,
+    value: Value<'read, 'parent, B, S>,
+  ) -> Result<T, JsonError<'read, B, S>> {
+    T::deserialize(value)
+  }
+}
+
+impl<D: ?Sized + JsonDeserializeSeed> JsonDeserializeSeed for &mut D {
+  type Value = D::Value;
+  #[inline(always)]
+  fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+    &mut self,
+    value: Value<'read, 'parent, B, S>,
+  ) -> Result<D::Value, JsonError<'read, B, S>> {
+    D::deserialize(self, value)
+  }
+}
+
+/// An object with a known set of fields.
+///
+/// This is implemented by `core-json-derive` when deriving `JsonDeserialize`, allowing the fields
+/// expected to be enumerated (such as for error messages or validation).
+pub trait JsonFields {
+  /// The keys of the fields, as serialized.
+  const FIELDS: &'static [&'static str];
+
+  /// The keys of the fields, as serialized.
+  ///
+  /// This method SHOULD NOT be overriden.
+  #[inline(always)]
+  fn field_names() -> &'static [&'static str] {
+    Self::FIELDS
+  }
+}
+
 /// A structure which can deserialized from a JSON serialization.
 pub trait JsonStructure: JsonDeserialize {
   /// Deserialize this structure from an JSON-serialized blob.
@@ -50,6 +204,23 @@ pub trait JsonStructure: JsonDeserialize {
     let value = json.value()?;
     Self::deserialize(value)
   }
+
+  /// Deserialize this structure from an JSON-serialized blob, writing the path to where an error
+  /// was encountered to `path` if an error is returned.
+  ///
+  /// This method SHOULD NOT be overriden.
+  #[cfg(feature = "path")]
+  fn deserialize_structure_with_path<'read, B: Read<'read>, S: Stack>(
+    json: B,
+    path: &mut Path,
+  ) -> Result<Self, JsonError<'read, B, S>> {
+    let mut json = Deserializer::new(json)?;
+    let res = json.value().and_then(Self::deserialize);
+    if res.is_err() {
+      path.clone_from(json.path());
+    }
+    res
+  }
 }
 
 /// An item which can be serialized as JSON.
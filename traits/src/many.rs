@@ -0,0 +1,111 @@
+use crate::{Read, Stack, JsonError, Value, JsonDeserialize};
+
+/// Find which of the candidates a string is, if any.
+///
+/// This matches the string against the candidates as its characters are read, without buffering
+/// it.
+pub(crate) fn find_str<'read, R: Read<'read>, S: Stack, const N: usize>(
+  string: impl Iterator<Item = Result<char, JsonError<'read, R, S>>>,
+  candidates: &[&str; N],
+) -> Result<Option<usize>, JsonError<'read, R, S>> {
+  let mut matching = [true; N];
+  let mut len = 0;
+  for char in string {
+    let char = match char {
+      Ok(char) => char,
+      // If the string isn't UTF-8, it can't be any of the candidates
+      Err(JsonError::NotUtf8) => return Ok(None),
+      Err(e) => Err(e)?,
+    };
+    let mut encoded = [0; 4];
+    let encoded = char.encode_utf8(&mut encoded).as_bytes();
+    for (candidate, matching) in candidates.iter().zip(&mut matching) {
+      *matching &= candidate.as_bytes().get(len .. (len + encoded.len())) == Some(encoded);
+    }
+    len += encoded.len();
+  }
+  Ok(
+    candidates
+      .iter()
+      .zip(matching)
+      .position(|(candidate, matching)| matching && (candidate.len() == len)),
+  )
+}
+
+/// A tuple of items which may be deserialized from several fields of an object.
+///
+/// This is implemented for tuples of `Option`s of up to eight items. Any `JsonDeserialize` type
+/// may be used for the items, including `JsonValue` (with the `alloc` feature) to capture the
+/// values as-is.
+pub trait GetMany<const N: usize>: Sized {
+  /// Deserialize the values of the fields with the specified keys, in a single pass over the
+  /// object.
+  ///
+  /// The items are in the order of the keys, regardless of the order of the fields within the
+  /// object. An item is `None` if its field wasn't present. If a key is present multiple times
+  /// within the object, the last instance is used. The values of all other fields are skipped.
+  fn get_many<'read, 'parent, B: Read<'read>, S: Stack>(
+    value: Value<'read, 'parent, B, S>,
+    keys: [&str; N],
+  ) -> Result<Self, JsonError<'read, B, S>>;
+}
+
+macro_rules! get_many_tuple {
+  ($n: literal, $($i: tt: $T: ident),+) => {
+    impl<$($T: JsonDeserialize),+> GetMany<$n> for ($(Option<$T>,)+) {
+      fn get_many<'read, 'parent, R: Read<'read>, S: Stack>(
+        value: Value<'read, 'parent, R, S>,
+        keys: [&str; $n],
+      ) -> Result<Self, JsonError<'read, R, S>> {
+        let mut result = ($(None::<$T>,)+);
+        let mut fields = value.fields()?;
+        while let Some(field) = fields.next() {
+          let mut field = field?;
+          match find_str(field.key()?, &keys)? {
+            $(Some($i) => result.$i = Some($T::deserialize(field.value()?)?),)+
+            _ => {}
+          }
+        }
+        Ok(result)
+      }
+    }
+  };
+}
+get_many_tuple!(1, 0: A);
+get_many_tuple!(2, 0: A, 1: B);
+get_many_tuple!(3, 0: A, 1: B, 2: C);
+get_many_tuple!(4, 0: A, 1: B, 2: C, 3: D);
+get_many_tuple!(5, 0: A, 1: B, 2: C, 3: D, 4: E);
+get_many_tuple!(6, 0: A, 1: B, 2: C, 3: D, 4: E, 5: F);
+get_many_tuple!(7, 0: A, 1: B, 2: C, 3: D, 4: E, 5: F, 6: G);
+get_many_tuple!(8, 0: A, 1: B, 2: C, 3: D, 4: E, 5: F, 6: G, 7: H);
+
+#[cfg(feature = "sequences")]
+#[test]
+fn get_many() {
+  use crate::{ConstStack, Deserializer};
+
+  let json = r#"{ "c": [1, 2], "a": 1, "\u0062": true, "ab": 3, "a": 2, "d": { "a": 4 } }"#;
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(json.as_bytes()).unwrap();
+  let (a, b, missing, c) = <(Option<u8>, Option<bool>, Option<u8>, Option<[u8; 2]>)>::get_many(
+    deserializer.value().unwrap(),
+    ["a", "b", "missing", "c"],
+  )
+  .unwrap();
+  assert_eq!(a, Some(2));
+  assert_eq!(b, Some(true));
+  assert_eq!(missing, None);
+  assert_eq!(c, Some([1, 2]));
+
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(json.as_bytes()).unwrap();
+  assert!(matches!(
+    <(Option<u8>,)>::get_many(deserializer.value().unwrap(), ["b"]),
+    Err(JsonError::TypeError(_))
+  ));
+
+  let mut deserializer = Deserializer::<_, ConstStack<32>>::new(&b"[]"[..]).unwrap();
+  assert!(matches!(
+    <(Option<u8>,)>::get_many(deserializer.value().unwrap(), ["a"]),
+    Err(JsonError::TypeError(_))
+  ));
+}
@@ -1,38 +1,15 @@
-use alloc::{string::String, collections::BTreeMap};
+use alloc::{string::String, vec::Vec, collections::BTreeMap};
 #[cfg(feature = "std")]
 use std::collections::HashMap;
+#[cfg(feature = "hashbrown")]
+use core::hash::BuildHasher;
 
-use crate::{Read, Stack, JsonError, Value, JsonDeserialize, JsonStructure, JsonSerialize};
-
-type YieldedField<'read, T, R, S> = Result<(String, T), JsonError<'read, R, S>>;
-fn deserialize_map<'read, 'parent, T: JsonDeserialize, R: Read<'read>, S: Stack>(
-  value: Value<'read, 'parent, R, S>,
-) -> Result<impl Iterator<Item = YieldedField<'read, T, R, S>>, JsonError<'read, R, S>> {
-  let mut iter = value.fields()?;
-  Ok(core::iter::from_fn(move || {
-    let mut field = match iter.next()? {
-      Ok(value) => value,
-      Err(e) => return Some(Err(e)),
-    };
-    let key = match (match field.key() {
-      Ok(key) => key,
-      Err(e) => return Some(Err(e)),
-    })
-    .collect::<Result<String, _>>()
-    {
-      Ok(key) => key,
-      Err(e) => return Some(Err(e)),
-    };
-    let value = match field.value() {
-      Ok(value) => value,
-      Err(e) => return Some(Err(e)),
-    };
-    match T::deserialize(value) {
-      Ok(value) => Some(Ok((key, value))),
-      Err(e) => Some(Err(e)),
-    }
-  }))
-}
+use core::marker::PhantomData;
+
+use crate::{
+  Read, Stack, JsonError, Value, JsonDeserialize, JsonStructure, JsonSerialize,
+  seeds::{StringKeys, deserialize_map},
+};
 
 fn serialize_field<'serializing>(
   (key, value): (&'serializing str, &'serializing (impl 'serializing + JsonSerialize)),
@@ -57,9 +34,11 @@ impl<T: JsonDeserialize> JsonDeserialize for BTreeMap<String, T> {
   fn deserialize<'read, 'parent, R: Read<'read>, S: Stack>(
     value: Value<'read, 'parent, R, S>,
   ) -> Result<Self, JsonError<'read, R, S>> {
-    deserialize_map::<T, _, _>(value)?.collect()
+    deserialize_map(value, &mut StringKeys, PhantomData::<T>)?.collect()
   }
 }
+/// The fields are serialized in the map's order, sorted by their keys. For `String` keys, this is
+/// the order of their UTF-8 encodings, making the serialization deterministic.
 impl<K: AsRef<str>, T: JsonSerialize> JsonSerialize for BTreeMap<K, T> {
   fn serialize(&self) -> impl Iterator<Item = char> {
     serialize_map(self.iter().map(|(key, value)| (key.as_ref(), value)))
@@ -72,9 +51,11 @@ impl<T: JsonDeserialize> JsonDeserialize for HashMap<String, T> {
   fn deserialize<'read, 'parent, R: Read<'read>, S: Stack>(
     value: Value<'read, 'parent, R, S>,
   ) -> Result<Self, JsonError<'read, R, S>> {
-    deserialize_map::<T, _, _>(value)?.collect()
+    deserialize_map(value, &mut StringKeys, PhantomData::<T>)?.collect()
   }
 }
+/// The fields are serialized in the map's iteration order, which is undefined. `Sorted` may be used
+/// to serialize the fields sorted by their keys.
 #[cfg(feature = "std")]
 impl<K: AsRef<str>, T: JsonSerialize> JsonSerialize for HashMap<K, T> {
   fn serialize(&self) -> impl Iterator<Item = char> {
@@ -84,7 +65,53 @@ impl<K: AsRef<str>, T: JsonSerialize> JsonSerialize for HashMap<K, T> {
 #[cfg(feature = "std")]
 impl<T: JsonDeserialize> JsonStructure for HashMap<String, T> {}
 
-#[cfg(feature = "alloc")]
+#[cfg(feature = "hashbrown")]
+impl<T: JsonDeserialize, H: BuildHasher + Default> JsonDeserialize
+  for hashbrown::HashMap<String, T, H>
+{
+  fn deserialize<'read, 'parent, R: Read<'read>, S: Stack>(
+    value: Value<'read, 'parent, R, S>,
+  ) -> Result<Self, JsonError<'read, R, S>> {
+    deserialize_map(value, &mut StringKeys, PhantomData::<T>)?.collect()
+  }
+}
+/// The fields are serialized in the map's iteration order, which is undefined. `Sorted` may be used
+/// to serialize the fields sorted by their keys.
+#[cfg(feature = "hashbrown")]
+impl<K: AsRef<str>, T: JsonSerialize, H> JsonSerialize for hashbrown::HashMap<K, T, H> {
+  fn serialize(&self) -> impl Iterator<Item = char> {
+    serialize_map(self.iter().map(|(key, value)| (key.as_ref(), value)))
+  }
+}
+#[cfg(feature = "hashbrown")]
+impl<T: JsonDeserialize, H: BuildHasher + Default> JsonStructure
+  for hashbrown::HashMap<String, T, H>
+{
+}
+
+/// A map, serialized with its fields sorted by their keys.
+///
+/// The fields of hash maps are serialized in an undefined order, which may differ between
+/// serializations of equal maps. Wrapping a reference to the map with this sorts the fields by the
+/// UTF-8 encodings of their keys, making the serialization deterministic (as necessary to hash,
+/// diff, or compare serializations). References to the map's entries are collected into a buffer
+/// to be sorted, so the buffer is bounded by the amount of entries (not their sizes). The values
+/// are still serialized lazily.
+#[derive(Clone, Copy, Debug)]
+pub struct Sorted<'map, M>(pub &'map M);
+
+impl<'map, M, K: 'map + AsRef<str>, T: 'map + JsonSerialize> JsonSerialize for Sorted<'map, M>
+where
+  &'map M: IntoIterator<Item = (&'map K, &'map T)>,
+{
+  fn serialize(&self) -> impl Iterator<Item = char> {
+    let mut fields =
+      self.0.into_iter().map(|(key, value)| (key.as_ref(), value)).collect::<Vec<_>>();
+    fields.sort_unstable_by_key(|(key, _)| *key);
+    serialize_map(fields.into_iter())
+  }
+}
+
 #[test]
 fn btree_map() {
   assert_eq!(BTreeMap::<String, u16>::new().serialize().collect::<String>().as_str(), "{}");
@@ -117,3 +144,53 @@ fn hash_map() {
   test_map(HashMap::from([("key1".to_string(), 1)]));
   test_map(HashMap::from([("key1".to_string(), 1), ("key2".to_string(), 2)]));
 }
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn hashbrown_map() {
+  type Map = hashbrown::HashMap<String, u16, std::hash::RandomState>;
+  assert_eq!(Map::default().serialize().collect::<String>().as_str(), "{}");
+  let test_map = |map: Map| {
+    assert_eq!(
+      Map::deserialize_structure::<_, crate::ConstStack<32>>(
+        map.serialize().collect::<String>().as_bytes()
+      )
+      .unwrap(),
+      map
+    );
+  };
+  test_map(Map::from_iter([("key1".to_string(), 1)]));
+  test_map(Map::from_iter([("key1".to_string(), 1), ("key2".to_string(), 2)]));
+}
+
+#[test]
+fn sorted() {
+  use alloc::string::ToString;
+
+  let map = BTreeMap::from([("b", 2), ("a", 1), ("\u{e9}", 4), ("B", 3)]);
+  let serialization = "{\"B\":3,\"a\":1,\"b\":2,\"\u{e9}\":4}";
+  assert_eq!(map.serialize().collect::<String>(), serialization);
+  assert_eq!(Sorted(&map).serialize().collect::<String>(), serialization);
+  assert_eq!(Sorted(&BTreeMap::<String, u8>::new()).serialize().collect::<String>(), "{}");
+
+  // Keys whose order differs from the order of their strings are sorted by their strings
+  #[derive(PartialEq, Eq, PartialOrd, Ord)]
+  struct Reversed(core::cmp::Reverse<String>);
+  impl AsRef<str> for Reversed {
+    fn as_ref(&self) -> &str {
+      &self.0.0
+    }
+  }
+  let map = BTreeMap::from(
+    ["a", "c", "b"].map(|key| (Reversed(core::cmp::Reverse(key.to_string())), key.len())),
+  );
+  assert_eq!(map.serialize().collect::<String>(), r#"{"c":1,"b":1,"a":1}"#);
+  assert_eq!(Sorted(&map).serialize().collect::<String>(), r#"{"a":1,"b":1,"c":1}"#);
+
+  #[cfg(feature = "std")]
+  {
+    let map = (0u8 .. 32).map(|i| (i.to_string(), i)).collect::<HashMap<_, _>>();
+    let expected = map.clone().into_iter().collect::<BTreeMap<_, _>>();
+    assert!(Sorted(&map).serialize().eq(expected.serialize()));
+  }
+}
@@ -0,0 +1,179 @@
+use core::ops::Deref;
+use alloc::string::String;
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{Read, Stack, JsonError, Value, Field, JsonDeserialize, JsonSerialize};
+
+/// Normalize characters, as they're read, to Unicode Normalization Form C (NFC).
+///
+/// The first error from the underlying iterator is written to `error`, ending the iterator.
+fn nfc<'error, E>(
+  chars: impl 'error + Iterator<Item = Result<char, E>>,
+  error: &'error mut Option<E>,
+) -> impl 'error + Iterator<Item = char> {
+  chars.map_while(|char| char.map_err(|e| *error = Some(e)).ok()).nfc()
+}
+
+/// A `String` normalized to Unicode Normalization Form C (NFC).
+///
+/// Strings deserialized into this are normalized as they're read, so canonically-equivalent
+/// strings (such as "é" written as a single precomposed character or as "e" followed by a
+/// combining accent) are deserialized identically. This allows comparing them, or using them as
+/// the keys of a map (so canonically-equivalent keys are considered duplicates), without each
+/// comparison having to normalize them.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct NfcString(String);
+
+impl NfcString {
+  /// Normalize a string.
+  pub fn new(str: &str) -> Self {
+    Self(str.nfc().collect())
+  }
+
+  /// Consume this, returning the normalized `String`.
+  pub fn into_string(self) -> String {
+    self.0
+  }
+}
+
+impl From<&str> for NfcString {
+  fn from(str: &str) -> Self {
+    Self::new(str)
+  }
+}
+
+impl Deref for NfcString {
+  type Target = str;
+  fn deref(&self) -> &str {
+    &self.0
+  }
+}
+
+impl AsRef<str> for NfcString {
+  fn as_ref(&self) -> &str {
+    &self.0
+  }
+}
+
+impl JsonDeserialize for NfcString {
+  fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+    value: Value<'read, 'parent, B, S>,
+  ) -> Result<Self, JsonError<'read, B, S>> {
+    let mut error = None;
+    let string = nfc(value.to_str()?, &mut error).collect();
+    match error {
+      Some(e) => Err(e),
+      None => Ok(Self(string)),
+    }
+  }
+}
+
+impl JsonSerialize for NfcString {
+  fn serialize(&self) -> impl Iterator<Item = char> {
+    self.0.as_str().serialize()
+  }
+}
+
+/// Check if a field's key matches the expected key, once both are normalized to Unicode
+/// Normalization Form C (NFC).
+///
+/// This is `Field::key_matches`, except canonically-equivalent keys match. As the key must be
+/// decoded to be normalized, this doesn't have `Field::key_matches`'s fast path for ASCII keys.
+pub fn key_matches_nfc<'read, R: Read<'read>, S: Stack>(
+  field: &mut Field<'read, '_, R, S>,
+  expected: &str,
+) -> Result<bool, JsonError<'read, R, S>> {
+  let mut error = None;
+  let matches = nfc(field.key()?, &mut error).eq(expected.nfc());
+  match error {
+    Some(e) => Err(e),
+    None => Ok(matches),
+  }
+}
+
+#[cfg(feature = "maps")]
+mod maps {
+  use core::marker::PhantomData;
+  use alloc::collections::BTreeMap;
+  #[cfg(feature = "std")]
+  use std::collections::HashMap;
+
+  use crate::{
+    Read, Stack, JsonError, Value, JsonDeserialize, JsonStructure,
+    seeds::{KeySink, deserialize_map},
+  };
+  use super::{NfcString, nfc};
+
+  /// A `KeySink` which normalizes each key into an `NfcString`.
+  struct NfcKeys;
+  impl KeySink for NfcKeys {
+    type Key = NfcString;
+    fn sink<E>(&mut self, key: impl Iterator<Item = Result<char, E>>) -> Result<NfcString, E> {
+      let mut error = None;
+      let key = nfc(key, &mut error).collect();
+      match error {
+        Some(e) => Err(e),
+        None => Ok(NfcString(key)),
+      }
+    }
+  }
+
+  /// Keys which are canonically equivalent are considered duplicates, with the last instance of
+  /// the key retained.
+  impl<T: JsonDeserialize> JsonDeserialize for BTreeMap<NfcString, T> {
+    fn deserialize<'read, 'parent, R: Read<'read>, S: Stack>(
+      value: Value<'read, 'parent, R, S>,
+    ) -> Result<Self, JsonError<'read, R, S>> {
+      deserialize_map(value, &mut NfcKeys, PhantomData::<T>)?.collect()
+    }
+  }
+  impl<T: JsonDeserialize> JsonStructure for BTreeMap<NfcString, T> {}
+
+  /// Keys which are canonically equivalent are considered duplicates, with the last instance of
+  /// the key retained.
+  #[cfg(feature = "std")]
+  impl<T: JsonDeserialize> JsonDeserialize for HashMap<NfcString, T> {
+    fn deserialize<'read, 'parent, R: Read<'read>, S: Stack>(
+      value: Value<'read, 'parent, R, S>,
+    ) -> Result<Self, JsonError<'read, R, S>> {
+      deserialize_map(value, &mut NfcKeys, PhantomData::<T>)?.collect()
+    }
+  }
+  #[cfg(feature = "std")]
+  impl<T: JsonDeserialize> JsonStructure for HashMap<NfcString, T> {}
+}
+
+#[cfg(feature = "maps")]
+#[test]
+fn normalize() {
+  use alloc::collections::BTreeMap;
+  use crate::{ConstStack, Deserializer, JsonStructure};
+
+  // "é" as a precomposed character, and as "e" followed by a combining acute accent
+  let serialization = r#"{"café": 1, "café": 2, "café": 3}"#;
+
+  let map =
+    BTreeMap::<NfcString, u8>::deserialize_structure::<_, ConstStack<1>>(serialization.as_bytes())
+      .unwrap();
+  assert_eq!(map.len(), 1);
+  assert_eq!(map[&NfcString::new("cafe\u{301}")], 3);
+  assert_eq!(&**map.keys().next().unwrap(), "caf\u{e9}");
+  assert_eq!(map.serialize().collect::<String>(), "{\"caf\u{e9}\":3}");
+
+  // The key is consumed when checked, so each key is checked within its own pass
+  for (expected, matches) in [("café", true), ("cafe\u{301}", true), ("cafe", false)] {
+    let mut deserializer = Deserializer::<_, ConstStack<1>>::new(serialization.as_bytes()).unwrap();
+    let mut fields = deserializer.value().unwrap().fields().unwrap();
+    while let Some(field) = fields.next() {
+      let mut field = field.unwrap();
+      assert_eq!(key_matches_nfc(&mut field, expected).unwrap(), matches);
+      assert!(field.value().unwrap().to_number().unwrap().i64().is_some());
+    }
+  }
+
+  // Errors within the strings are returned
+  let mut deserializer = Deserializer::<_, ConstStack<1>>::new(&br#"["a\x"]"#[..]).unwrap();
+  let mut elements = deserializer.value().unwrap().iterate().unwrap();
+  assert!(NfcString::deserialize(elements.next().unwrap().unwrap()).is_err());
+}
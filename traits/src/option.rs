@@ -3,9 +3,9 @@ use crate::{Read, Stack, JsonError, Type, Value, JsonDeserialize, JsonSerialize}
 impl<T: JsonDeserialize> JsonDeserialize for Option<T> {
   /// This will accept `null` as a representation of `None`.
   fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
-    mut value: Value<'read, 'parent, B, S>,
+    value: Value<'read, 'parent, B, S>,
   ) -> Result<Self, JsonError<'read, B, S>> {
-    if matches!(value.kind()?, Type::Null) {
+    if matches!(value.kind(), Type::Null) {
       let () = value.to_null()?;
       return Ok(None);
     }
@@ -0,0 +1,303 @@
+//! Producing JSON Patches, as specified by
+//! [RFC 6902](https://datatracker.ietf.org/doc/html/rfc6902), from the differences between
+//! documents.
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+  Read, Stack, JsonError, Type, Value, JsonDeserialize, JsonSerialize, JsonValue, JsonPointer,
+};
+
+/// An operation within a JSON Patch, as specified by
+/// [RFC 6902](https://datatracker.ietf.org/doc/html/rfc6902).
+///
+/// The paths are `JsonPointer`s.
+#[derive(Clone, PartialEq, Debug)]
+pub enum PatchOperation {
+  /// Add a value at the path.
+  Add {
+    /// The path to add the value at.
+    path: JsonPointer,
+    /// The value to add.
+    value: JsonValue,
+  },
+  /// Remove the value at the path.
+  Remove {
+    /// The path of the value to remove.
+    path: JsonPointer,
+  },
+  /// Replace the value at the path.
+  Replace {
+    /// The path of the value to replace.
+    path: JsonPointer,
+    /// The value to replace the existing value with.
+    value: JsonValue,
+  },
+}
+
+impl JsonSerialize for PatchOperation {
+  fn serialize(&self) -> impl Iterator<Item = char> {
+    let (op, path, value) = match self {
+      PatchOperation::Add { path, value } => ("add", path, Some(value)),
+      PatchOperation::Remove { path } => ("remove", path, None),
+      PatchOperation::Replace { path, value } => ("replace", path, Some(value)),
+    };
+    r#"{"op":"#
+      .chars()
+      .chain(op.serialize())
+      .chain(r#","path":"#.chars())
+      .chain(path.serialize())
+      .chain(value.into_iter().flat_map(|value| r#","value":"#.chars().chain(value.serialize())))
+      .chain(core::iter::once('}'))
+  }
+}
+
+fn diff_object(
+  path: &mut JsonPointer,
+  from: &[(String, JsonValue)],
+  to: &[(String, JsonValue)],
+  patch: &mut Vec<PatchOperation>,
+) {
+  // If a key is present multiple times, only its last instance is considered
+  fn find<'fields>(
+    fields: &'fields [(String, JsonValue)],
+    key: &str,
+  ) -> Option<&'fields JsonValue> {
+    fields.iter().rev().find(|(field, _)| field == key).map(|(_, value)| value)
+  }
+  let last = |fields: &[(String, JsonValue)], i: usize| {
+    !fields[(i + 1) ..].iter().any(|(field, _)| *field == fields[i].0)
+  };
+
+  for (i, (key, from_value)) in from.iter().enumerate() {
+    if !last(from, i) {
+      continue;
+    }
+    path.push_key(key);
+    match find(to, key) {
+      Some(to_value) => diff_values(path, from_value, to_value, patch),
+      None => patch.push(PatchOperation::Remove { path: path.clone() }),
+    }
+    path.pop();
+  }
+  for (i, (key, to_value)) in to.iter().enumerate() {
+    if (!last(to, i)) || find(from, key).is_some() {
+      continue;
+    }
+    path.push_key(key);
+    patch.push(PatchOperation::Add { path: path.clone(), value: to_value.clone() });
+    path.pop();
+  }
+}
+
+fn diff_values(
+  path: &mut JsonPointer,
+  from: &JsonValue,
+  to: &JsonValue,
+  patch: &mut Vec<PatchOperation>,
+) {
+  match (from, to) {
+    (JsonValue::Object(from), JsonValue::Object(to)) => diff_object(path, from, to, patch),
+    (JsonValue::Array(from), JsonValue::Array(to)) => {
+      for (i, (from, to)) in from.iter().zip(to).enumerate() {
+        path.push_index(i);
+        diff_values(path, from, to, patch);
+        path.pop();
+      }
+      // Remove the elements which are no longer present, from the last to the first
+      for i in (to.len() .. from.len()).rev() {
+        path.push_index(i);
+        patch.push(PatchOperation::Remove { path: path.clone() });
+        path.pop();
+      }
+      // Add the elements which are newly present
+      for (i, to) in to.iter().enumerate().skip(from.len()) {
+        path.push_index(i);
+        patch.push(PatchOperation::Add { path: path.clone(), value: to.clone() });
+        path.pop();
+      }
+    }
+    (from, to) => {
+      if from != to {
+        patch.push(PatchOperation::Replace { path: path.clone(), value: to.clone() });
+      }
+    }
+  }
+}
+
+/// Produce a JSON Patch which transforms one value into another.
+///
+/// The patch will solely use the `add`, `remove`, and `replace` operations. If a key is present
+/// multiple times within an object, only its last instance is considered.
+pub fn diff(from: &JsonValue, to: &JsonValue) -> Vec<PatchOperation> {
+  let mut patch = Vec::new();
+  diff_values(&mut JsonPointer::new(), from, to, &mut patch);
+  patch
+}
+
+fn diff_streams_internal<'read, R: Read<'read>, S: Stack>(
+  path: &mut JsonPointer,
+  from: Value<'read, '_, R, S>,
+  to: Value<'read, '_, R, S>,
+  patch: &mut Vec<PatchOperation>,
+) -> Result<(), JsonError<'read, R, S>> {
+  match (from.kind(), to.kind()) {
+    (Type::Object, Type::Object) => {
+      let mut from = from.fields()?;
+      let mut to = to.fields()?;
+      let diverged = loop {
+        match (from.next(), to.next()) {
+          (Some(from_field), Some(to_field)) => {
+            let mut from_field = from_field?;
+            let mut to_field = to_field?;
+            let from_key = from_field.key()?.collect::<Result<String, _>>()?;
+            let to_key = to_field.key()?.collect::<Result<String, _>>()?;
+            if from_key == to_key {
+              path.push_key(&from_key);
+              diff_streams_internal(path, from_field.value()?, to_field.value()?, patch)?;
+              path.pop();
+              continue;
+            }
+
+            // As the objects diverged, deserialize the rest of them and diff them as objects
+            break Some((
+              Vec::from([(from_key, JsonValue::deserialize(from_field.value()?)?)]),
+              Vec::from([(to_key, JsonValue::deserialize(to_field.value()?)?)]),
+            ));
+          }
+          (Some(from_field), None) => {
+            let mut from_field = from_field?;
+            path.push_key(&from_field.key()?.collect::<Result<String, _>>()?);
+            patch.push(PatchOperation::Remove { path: path.clone() });
+            path.pop();
+          }
+          (None, Some(to_field)) => {
+            let mut to_field = to_field?;
+            path.push_key(&to_field.key()?.collect::<Result<String, _>>()?);
+            let value = JsonValue::deserialize(to_field.value()?)?;
+            patch.push(PatchOperation::Add { path: path.clone(), value });
+            path.pop();
+          }
+          (None, None) => break None,
+        }
+      };
+      if let Some((mut from_rest, mut to_rest)) = diverged {
+        while let Some(field) = from.next() {
+          let mut field = field?;
+          let key = field.key()?.collect::<Result<String, _>>()?;
+          from_rest.push((key, JsonValue::deserialize(field.value()?)?));
+        }
+        while let Some(field) = to.next() {
+          let mut field = field?;
+          let key = field.key()?.collect::<Result<String, _>>()?;
+          to_rest.push((key, JsonValue::deserialize(field.value()?)?));
+        }
+        diff_object(path, &from_rest, &to_rest, patch);
+      }
+    }
+    (Type::Array, Type::Array) => {
+      let mut from = from.iterate()?;
+      let mut to = to.iterate()?;
+      let mut i = 0;
+      let mut removed = 0;
+      loop {
+        match (from.next(), to.next()) {
+          (Some(from), Some(to)) => {
+            path.push_index(i);
+            diff_streams_internal(path, from?, to?, patch)?;
+            path.pop();
+          }
+          (Some(from), None) => {
+            drop(from?);
+            removed += 1;
+          }
+          (None, Some(to)) => {
+            path.push_index(i);
+            let value = JsonValue::deserialize(to?)?;
+            patch.push(PatchOperation::Add { path: path.clone(), value });
+            path.pop();
+          }
+          (None, None) => break,
+        }
+        i += 1;
+      }
+      // Remove the elements which are no longer present, from the last to the first
+      for i in ((i - removed) .. i).rev() {
+        path.push_index(i);
+        patch.push(PatchOperation::Remove { path: path.clone() });
+        path.pop();
+      }
+    }
+    _ => {
+      let from = JsonValue::deserialize(from)?;
+      let to = JsonValue::deserialize(to)?;
+      if from != to {
+        patch.push(PatchOperation::Replace { path: path.clone(), value: to });
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Produce a JSON Patch which transforms one value into another, while deserializing them.
+///
+/// This walks both values in lockstep, solely deserializing into `JsonValue`s the portions which
+/// differ. This is most efficient for documents with the same shape (objects with the same keys
+/// in the same order), falling back to deserializing the remainder of an object once the objects
+/// diverge.
+///
+/// For objects without duplicate keys, the patch produced is equivalent to the one produced by
+/// `diff`. While the objects have the same keys in the same order, their fields are diffed pairwise
+/// as they're read, so when a key is present multiple times, the patch will describe the
+/// differences between every instance of the key (not solely the last instance, as `diff` would).
+pub fn diff_streams<'read, R: Read<'read>, S: Stack>(
+  from: Value<'read, '_, R, S>,
+  to: Value<'read, '_, R, S>,
+) -> Result<Vec<PatchOperation>, JsonError<'read, R, S>> {
+  let mut patch = Vec::new();
+  diff_streams_internal(&mut JsonPointer::new(), from, to, &mut patch)?;
+  Ok(patch)
+}
+
+#[cfg(feature = "sequences")]
+#[test]
+fn patch() {
+  use alloc::string::ToString;
+  use crate::{ConstStack, Deserializer, JsonStructure};
+
+  let from = r#"{"a":1,"b":[1,2,3],"c":{"d/e":"f","g":null},"h~":true}"#;
+  let to = r#"{"a":1,"b":[1,4],"c":{"g":null,"i":[]},"j":false}"#;
+
+  let check = |patch: &[PatchOperation]| {
+    assert_eq!(
+      patch.serialize().collect::<String>(),
+      r#"[{"op":"replace","path":"/b/1","value":4},{"op":"remove","path":"/b/2"},"#.to_string() +
+        r#"{"op":"remove","path":"/c/d~1e"},{"op":"add","path":"/c/i","value":[]},"# +
+        r#"{"op":"remove","path":"/h~0"},{"op":"add","path":"/j","value":false}]"#
+    );
+  };
+
+  let value =
+    |json: &str| JsonValue::deserialize_structure::<_, ConstStack<32>>(json.as_bytes()).unwrap();
+  check(&diff(&value(from), &value(to)));
+  assert!(diff(&value(from), &value(from)).is_empty());
+
+  let mut from = Deserializer::<_, ConstStack<32>>::new(from.as_bytes()).unwrap();
+  let mut to = Deserializer::<_, ConstStack<32>>::new(to.as_bytes()).unwrap();
+  check(&diff_streams(from.value().unwrap(), to.value().unwrap()).unwrap());
+
+  // `diff` solely considers the last instance of a duplicated key, while `diff_streams` diffs the
+  // instances pairwise
+  let from = r#"{"a":1,"a":2}"#;
+  let to = r#"{"a":3,"a":2}"#;
+  assert!(diff(&value(from), &value(to)).is_empty());
+  let mut from = Deserializer::<_, ConstStack<32>>::new(from.as_bytes()).unwrap();
+  let mut to = Deserializer::<_, ConstStack<32>>::new(to.as_bytes()).unwrap();
+  assert_eq!(
+    diff_streams(from.value().unwrap(), to.value().unwrap())
+      .unwrap()
+      .serialize()
+      .collect::<String>(),
+    r#"[{"op":"replace","path":"/a","value":3}]"#
+  );
+}
@@ -0,0 +1,249 @@
+use alloc::{borrow::Cow, string::String};
+use core::{fmt, str::FromStr};
+
+use crate::{JsonSerialize, JsonValue};
+
+/// An error when parsing a JSON Pointer.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InvalidPointer;
+
+/// A JSON Pointer, as specified by [RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901).
+///
+/// This is a sequence of reference tokens, each either the key of a field within an object or the
+/// index of an element within an array. The tokens are escaped as they're pushed (`~` as `~0`, `/`
+/// as `~1`), and unescaped as they're iterated.
+///
+/// This is the owned, unbounded counterpart to `schema::Pointer` (and the `Path` of
+/// deserializers, with the `path` feature), which may be converted into it.
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct JsonPointer {
+  /// The pointer, as escaped.
+  pointer: String,
+}
+
+/// Unescape a reference token.
+fn unescape(token: &str) -> Cow<'_, str> {
+  if !token.contains('~') {
+    return Cow::Borrowed(token);
+  }
+  // `~1` is unescaped before `~0`, so `~01` is unescaped to `~1` and not `/`
+  Cow::Owned(token.replace("~1", "/").replace("~0", "~"))
+}
+
+impl JsonPointer {
+  /// Create a new pointer, pointing to the root value.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// If this pointer points to the root value.
+  pub fn is_root(&self) -> bool {
+    self.pointer.is_empty()
+  }
+
+  /// Push the key of a field onto this pointer.
+  pub fn push_key(&mut self, key: &str) {
+    self.pointer.push('/');
+    for char in key.chars() {
+      match char {
+        '~' => self.pointer.push_str("~0"),
+        '/' => self.pointer.push_str("~1"),
+        _ => self.pointer.push(char),
+      }
+    }
+  }
+
+  /// Push the index of an element onto this pointer.
+  pub fn push_index(&mut self, index: usize) {
+    use core::fmt::Write;
+    write!(self.pointer, "/{index}").expect("couldn't write to `String`");
+  }
+
+  /// Pop the innermost reference token from this pointer.
+  ///
+  /// Returns `false` if this pointer pointed to the root value.
+  pub fn pop(&mut self) -> bool {
+    match self.pointer.rfind('/') {
+      Some(i) => {
+        self.pointer.truncate(i);
+        true
+      }
+      None => false,
+    }
+  }
+
+  /// The reference tokens of this pointer, unescaped, from the outermost to the innermost.
+  pub fn tokens(&self) -> impl Iterator<Item = Cow<'_, str>> {
+    self.pointer.split('/').skip(1).map(unescape)
+  }
+
+  /// If this pointer starts with the specified pointer, pointing to it or a value within it.
+  pub fn starts_with(&self, prefix: &JsonPointer) -> bool {
+    self
+      .pointer
+      .strip_prefix(prefix.as_str())
+      .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+  }
+
+  /// This pointer, as escaped.
+  pub fn as_str(&self) -> &str {
+    &self.pointer
+  }
+
+  /// Resolve this pointer against a value.
+  ///
+  /// If a key is present multiple times within an object, the last instance is used. The indexes
+  /// of elements must be in their canonical form (without leading zeroes), and the `-` token (the
+  /// element after the last element) never resolves.
+  pub fn resolve<'value>(&self, value: &'value JsonValue) -> Option<&'value JsonValue> {
+    let mut value = value;
+    for token in self.tokens() {
+      value = match value {
+        JsonValue::Object(_) => value.get(&token)?,
+        JsonValue::Array(array) => {
+          if (!token.bytes().all(|byte| byte.is_ascii_digit())) ||
+            (token.starts_with('0') && (token.len() != 1))
+          {
+            None?;
+          }
+          array.get(token.parse::<usize>().ok()?)?
+        }
+        _ => None?,
+      };
+    }
+    Some(value)
+  }
+}
+
+impl FromStr for JsonPointer {
+  type Err = InvalidPointer;
+  /// Parse a JSON Pointer, as escaped.
+  ///
+  /// This does not percent-decode the pointer, as necessary when it's the fragment of a URI.
+  fn from_str(pointer: &str) -> Result<Self, InvalidPointer> {
+    if !(pointer.is_empty() || pointer.starts_with('/')) {
+      Err(InvalidPointer)?;
+    }
+    let mut chars = pointer.chars();
+    while let Some(char) = chars.next() {
+      if (char == '~') && (!matches!(chars.next(), Some('0' | '1'))) {
+        Err(InvalidPointer)?;
+      }
+    }
+    Ok(JsonPointer { pointer: String::from(pointer) })
+  }
+}
+
+impl fmt::Display for JsonPointer {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&self.pointer)
+  }
+}
+
+impl JsonSerialize for JsonPointer {
+  fn serialize(&self) -> impl Iterator<Item = char> {
+    self.pointer.serialize()
+  }
+}
+
+impl From<&crate::schema::Pointer> for JsonPointer {
+  /// Convert from a `schema::Pointer`.
+  ///
+  /// If the `schema::Pointer` was truncated, this will solely have the segments recorded.
+  fn from(pointer: &crate::schema::Pointer) -> Self {
+    let mut result = JsonPointer::new();
+    for segment in pointer.segments() {
+      match segment {
+        crate::schema::PointerSegment::Key(key) => result.push_key(key),
+        crate::schema::PointerSegment::Index(index) => result.push_index(index),
+      }
+    }
+    result
+  }
+}
+
+#[cfg(feature = "path")]
+impl From<&crate::Path> for JsonPointer {
+  /// Convert from the `Path` of a deserializer.
+  ///
+  /// If the `Path` was truncated, this will solely have the segments (and the portions of keys)
+  /// recorded.
+  fn from(path: &crate::Path) -> Self {
+    let mut result = JsonPointer::new();
+    for segment in path.segments() {
+      match segment {
+        crate::PathSegment::Key(key) => result.push_key(key),
+        crate::PathSegment::Index(index) => result.push_index(index),
+      }
+    }
+    result
+  }
+}
+
+#[test]
+fn pointer() {
+  use alloc::{vec::Vec, string::ToString};
+  use crate::{ConstStack, JsonStructure};
+
+  let mut pointer = JsonPointer::new();
+  assert!(pointer.is_root());
+  pointer.push_key("a/b");
+  pointer.push_index(1);
+  pointer.push_key("~1");
+  pointer.push_key("");
+  assert_eq!(pointer.to_string(), "/a~1b/1/~01/");
+  assert_eq!(pointer.tokens().collect::<Vec<_>>(), ["a/b", "1", "~1", ""]);
+  assert_eq!(pointer.serialize().collect::<String>(), r#""/a~1b/1/~01/""#);
+  assert_eq!("/a~1b/1/~01/".parse::<JsonPointer>(), Ok(pointer.clone()));
+
+  assert!(pointer.starts_with(&JsonPointer::new()));
+  assert!(pointer.starts_with(&"/a~1b/1".parse().unwrap()));
+  assert!(pointer.starts_with(&pointer));
+  assert!(!pointer.starts_with(&"/a~1b/10".parse().unwrap()));
+  assert!(!pointer.starts_with(&"/a~1".parse().unwrap()));
+
+  assert!(pointer.pop());
+  assert!(pointer.pop());
+  assert_eq!(pointer.as_str(), "/a~1b/1");
+  assert!(pointer.pop());
+  assert!(pointer.pop());
+  assert!(pointer.is_root());
+  assert!(!pointer.pop());
+
+  for invalid in ["a", "/~", "/~2", "/a~"] {
+    assert_eq!(invalid.parse::<JsonPointer>(), Err(InvalidPointer));
+  }
+
+  // The examples from RFC 6901
+  let value = JsonValue::deserialize_structure::<_, ConstStack<32>>(
+    br#"{"foo":["bar","baz"],"":0,"a/b":1,"c%d":2,"e^f":3,"g|h":4,"i\\j":5,"k\"l":6," ":7,"m~n":8}"#
+      .as_slice(),
+  )
+  .unwrap();
+  let resolve = |pointer: &str| pointer.parse::<JsonPointer>().unwrap().resolve(&value).cloned();
+  assert_eq!(resolve(""), Some(value.clone()));
+  assert_eq!(resolve("/foo/0"), Some(JsonValue::String("bar".to_string())));
+  for (pointer, i) in
+    ["/", "/a~1b", "/c%d", "/e^f", "/g|h", "/i\\j", "/k\"l", "/ ", "/m~0n"].into_iter().zip(0 ..)
+  {
+    assert_eq!(resolve(pointer), Some(JsonValue::Number(crate::JsonNumber::Integer(i))));
+  }
+  for unresolvable in ["/foo/2", "/foo/-", "/foo/01", "/foo/+1", "/bar", "/foo/0/0"] {
+    assert_eq!(resolve(unresolvable), None);
+  }
+
+  // Convert the pointer to a violation of a schema
+  use crate::schema::{Schema, array, object, null};
+  let mut schema_pointer = crate::schema::Pointer::new();
+  assert!(
+    array(object().field("a/b", null()))
+      .validate_structure::<_, ConstStack<32>>(
+        br#"[{"a/b":null},{"a/b":1}]"#.as_slice(),
+        &mut schema_pointer
+      )
+      .is_err()
+  );
+  let pointer = JsonPointer::from(&schema_pointer);
+  assert_eq!(pointer.to_string(), schema_pointer.to_string());
+  assert_eq!(pointer.tokens().collect::<Vec<_>>(), ["1", "a/b"]);
+}
@@ -1,12 +1,13 @@
-use crate::{Read, Stack, JsonError, Value, JsonDeserialize, JsonSerialize};
+use crate::{Read, Stack, JsonError, NotRepresentable, Value, JsonDeserialize, JsonSerialize};
+use crate::Number;
 
-struct IntInterator<const CAPACITY: usize> {
+pub(crate) struct IntInterator<const CAPACITY: usize> {
   buf: [u8; CAPACITY],
   i: usize,
   len: usize,
 }
 impl<const CAPACITY: usize> IntInterator<CAPACITY> {
-  fn new(value: impl core::fmt::Display) -> Self {
+  pub(crate) fn new(value: impl core::fmt::Display) -> Self {
     use core::fmt::Write;
 
     /// A `core::fmt::Write` which writes to a slice.
@@ -55,12 +56,11 @@ macro_rules! int_primitive {
       fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
         value: Value<'read, 'parent, B, S>,
       ) -> Result<Self, JsonError<'read, B, S>> {
-        value
-          .to_number()?
-          .i64()
-          .ok_or(JsonError::TypeError)?
-          .try_into()
-          .map_err(|_| JsonError::TypeError)
+        // Every integer within the range of an `i64` or a `u64` has a magnitude within a `u64`
+        let (negative, magnitude) = value.to_number()?.try_magnitude()?;
+        let magnitude = i128::from(magnitude);
+        let value = if negative { -magnitude } else { magnitude };
+        Ok(value.try_into().map_err(|_| NotRepresentable::OutOfRange)?)
       }
     }
 
@@ -125,6 +125,118 @@ impl JsonSerialize for () {
   }
 }
 
+impl JsonDeserialize for Number {
+  fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+    value: Value<'read, 'parent, B, S>,
+  ) -> Result<Self, JsonError<'read, B, S>> {
+    value.to_number()
+  }
+}
+
+/// Serialize a number, exactly where possible.
+///
+/// Numbers whose significant digits were all retained (see `Number::precision_lost`) are
+/// serialized exactly (though not necessarily as originally written), with negative zero
+/// serialized as `-0`. Numbers whose significant digits were truncated are serialized with their
+/// first `f64::DIGITS` significant digits. As JSON can't represent `NaN` or the infinities, those
+/// are serialized as `null`, as are numbers whose exponent exceeded this library's bounds.
+impl JsonSerialize for Number {
+  fn serialize(&self) -> impl Iterator<Item = char> {
+    // A sign, the significant digits retained, a decimal point or exponent marker, and the
+    // exponent (with its sign)
+    const CAPACITY: usize = 1 + 29 + 1 + 1 + 20;
+    // The largest scale written with leading zeroes, as opposed to with a negative exponent
+    const MAX_SCALE: usize = 19;
+
+    if let Some((negative, magnitude)) = self.magnitude() {
+      // `magnitude` considers zero non-negative, so negative zero is identified via its `f64`
+      let negative =
+        negative || ((magnitude == 0) && self.f64().is_some_and(f64::is_sign_negative));
+      let sign = if negative { "-" } else { "" };
+      return IntInterator::<CAPACITY>::new(format_args!("{sign}{magnitude}"));
+    }
+
+    if let Some((negative, digits, exponent)) = self.digits().filter(|_| !self.precision_lost()) {
+      let sign = if negative { "-" } else { "" };
+      let digits = core::str::from_utf8(digits).expect("`Number::digits` wasn't ASCII");
+      // Position the decimal point within the digits, unless that requires too many leading zeroes
+      let scale = usize::try_from(exponent.unsigned_abs()).unwrap_or(usize::MAX);
+      if (exponent < 0) && ((scale <= MAX_SCALE) || (scale <= digits.len())) {
+        return match digits.len().checked_sub(scale).filter(|integer| *integer != 0) {
+          Some(integer) => IntInterator::<CAPACITY>::new(format_args!(
+            "{sign}{}.{}",
+            &digits[.. integer],
+            &digits[integer ..]
+          )),
+          None => IntInterator::<CAPACITY>::new(format_args!("{sign}0.{digits:0>scale$}")),
+        };
+      }
+      if exponent == 0 {
+        return IntInterator::<CAPACITY>::new(format_args!("{sign}{digits}"));
+      }
+      return IntInterator::<CAPACITY>::new(format_args!("{sign}{digits}e{exponent}"));
+    }
+
+    match self.imprecise_str() {
+      Some((str, len)) => IntInterator::<CAPACITY>::new(
+        core::str::from_utf8(&str[.. len]).expect("`Number::imprecise_str` wasn't ASCII"),
+      ),
+      None => IntInterator::<CAPACITY>::new("null"),
+    }
+  }
+}
+
+#[cfg(all(feature = "alloc", feature = "sequences"))]
+#[test]
+fn number() {
+  use crate::{ConstStack, JsonStructure};
+
+  let reserialize = |json: &str| {
+    let numbers =
+      Vec::<Number>::deserialize_structure::<_, ConstStack<2>>(std::format!("[{json}]").as_bytes())
+        .map_err(|_| ())
+        .unwrap();
+    numbers[0].serialize().collect::<String>()
+  };
+
+  for (json, expected) in [
+    ("0", "0"),
+    ("-0", "-0"),
+    ("-0.0", "-0"),
+    ("-0e5", "-0"),
+    ("-1", "-1"),
+    ("18446744073709551615", "18446744073709551615"),
+    ("-18446744073709551615", "-18446744073709551615"),
+    ("1.5", "1.5"),
+    ("-0.05", "-0.05"),
+    ("1.50", "1.5"),
+    ("10.0", "10"),
+    ("1E3", "1000"),
+    ("1e-19", "0.0000000000000000001"),
+    ("1e-20", "1e-20"),
+    ("1.8446744073709551615", "1.8446744073709551615"),
+    ("1e30", "1e30"),
+    ("184467440737095516150", "18446744073709551615e1"),
+    ("184467440737095516151", "184467440737095516151"),
+    ("-98765432109876543210.5", "-98765432109876543210.5"),
+    ("0.1000000000000000000001", "0.1000000000000000000001"),
+    ("1.2345678901234567890123456789", "1.2345678901234567890123456789"),
+    ("-12345678901234567890123456789", "-12345678901234567890123456789"),
+    ("1234567890123456789012345678.9e5", "12345678901234567890123456789e4"),
+    ("1.0000000000000000000000000001e-10", "10000000000000000000000000001e-38"),
+    ("0.00000000000000000012345678901234567891", "12345678901234567891e-38"),
+    // Numbers with more significant digits than retained are serialized imprecisely
+    ("1234567890123456789012345678901", "123456789012345e16"),
+    ("123456789012345678901234567890", "12345678901234567890123456789e1"),
+  ] {
+    assert_eq!(reserialize(json), expected, "{json}");
+    // The serialization is itself a number, which reserializes identically
+    assert_eq!(reserialize(&reserialize(json)), expected, "{json}");
+  }
+  // Negative zero retains its sign through the round trip
+  assert!(reserialize("-0").parse::<f64>().unwrap().is_sign_negative());
+}
+
 #[cfg(feature = "alloc")]
 #[test]
 fn int_iterator() {
@@ -134,3 +246,48 @@ fn int_iterator() {
   assert_eq!(JsonSerialize::serialize(&i64::MAX).collect::<String>(), format!("{}", i64::MAX));
   assert_eq!(JsonSerialize::serialize(&i64::MIN).collect::<String>(), format!("{}", i64::MIN));
 }
+
+#[cfg(feature = "sequences")]
+#[test]
+fn int_not_representable() {
+  use crate::{ConstStack, ErrorKind, JsonStructure};
+
+  let deserialize = |serialization: &str| {
+    <[u8; 1]>::deserialize_structure::<_, ConstStack<2>>(serialization.as_bytes())
+      .map_err(ErrorKind::from)
+  };
+  assert_eq!(deserialize("[255]"), Ok([255]));
+  assert_eq!(
+    deserialize("[1.5]"),
+    Err(ErrorKind::NumberNotRepresentable(NotRepresentable::LossyFraction))
+  );
+  assert_eq!(
+    deserialize("[256]"),
+    Err(ErrorKind::NumberNotRepresentable(NotRepresentable::OutOfRange))
+  );
+  assert_eq!(
+    deserialize("[1e99]"),
+    Err(ErrorKind::NumberNotRepresentable(NotRepresentable::OutOfRange))
+  );
+  assert_eq!(deserialize("[true]"), Err(ErrorKind::TypeError));
+}
+
+#[cfg(feature = "sequences")]
+#[test]
+fn u64_ids() {
+  use crate::{ConstStack, JsonStructure};
+
+  let ids = <[u64; 2]>::deserialize_structure::<_, ConstStack<2>>(
+    b"[18446744073709551615, 1.8446744073709551e19]".as_slice(),
+  )
+  .unwrap();
+  assert_eq!(ids, [u64::MAX, 18446744073709551000]);
+  assert_eq!(
+    <[i128; 2]>::deserialize_structure::<_, ConstStack<2>>(
+      b"[-18446744073709551615, -9223372036854775808]".as_slice()
+    )
+    .unwrap(),
+    [-i128::from(u64::MAX), i128::from(i64::MIN)]
+  );
+  assert!(<[u64; 1]>::deserialize_structure::<_, ConstStack<2>>(b"[-1]".as_slice()).is_err());
+}
@@ -0,0 +1,658 @@
+//! Validating JSON against simple schemas, without allocating.
+//!
+//! Schemas are built from combinators, such as
+//! `schema::object().field("id", schema::integer().min(0))`, and validated over the streaming
+//! deserializer. Upon the first violation, validation stops, with the `Pointer` to the value
+//! which violated the schema.
+//!
+//! This is not an implementation of JSON Schema, solely offering basic validation of payloads.
+
+use core::fmt;
+
+use crate::{Read, Stack, JsonError, TypeError, Type, Value, Deserializer};
+
+/// A segment within a `Pointer`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PointerSegment {
+  /// The key of a field within an object.
+  Key(&'static str),
+  /// The index of an element within an array.
+  Index(usize),
+}
+
+/// A pointer to a value within a JSON structure.
+///
+/// The pointer is bounded, solely recording the outermost `Pointer::CAPACITY` segments, allowing
+/// it to be maintained without allocating. Its `Display` implementation is the JSON Pointer, as
+/// specified by [RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901), ending with `...` if
+/// the pointer was truncated.
+#[derive(Clone, Debug)]
+pub struct Pointer {
+  segments: [PointerSegment; Pointer::CAPACITY],
+  /// The depth of the pointer, which may exceed `Pointer::CAPACITY`.
+  depth: usize,
+}
+
+impl Pointer {
+  /// The maximum depth of segments recorded.
+  pub const CAPACITY: usize = 8;
+
+  /// Create a new pointer, pointing to the root value.
+  pub const fn new() -> Self {
+    Pointer { segments: [PointerSegment::Index(0); Pointer::CAPACITY], depth: 0 }
+  }
+
+  fn push(&mut self, segment: PointerSegment) {
+    if let Some(slot) = self.segments.get_mut(self.depth) {
+      *slot = segment;
+    }
+    self.depth += 1;
+  }
+
+  fn pop(&mut self) {
+    self.depth = self.depth.saturating_sub(1);
+  }
+
+  /// If this pointer was truncated, due to exceeding `Pointer::CAPACITY` segments.
+  pub fn truncated(&self) -> bool {
+    self.depth > Pointer::CAPACITY
+  }
+
+  /// The segments of this pointer, from the outermost to the innermost.
+  pub fn segments(&self) -> impl Iterator<Item = PointerSegment> + '_ {
+    self.segments[.. self.depth.min(Pointer::CAPACITY)].iter().copied()
+  }
+}
+
+impl Default for Pointer {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl fmt::Display for Pointer {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for segment in self.segments() {
+      f.write_str("/")?;
+      match segment {
+        PointerSegment::Key(key) => {
+          for char in key.chars() {
+            match char {
+              '~' => f.write_str("~0")?,
+              '/' => f.write_str("~1")?,
+              _ => fmt::Write::write_char(f, char)?,
+            }
+          }
+        }
+        PointerSegment::Index(index) => write!(f, "{index}")?,
+      }
+    }
+    if self.truncated() {
+      f.write_str("...")?;
+    }
+    Ok(())
+  }
+}
+
+/// A violation of a schema.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Violation {
+  /// The value was of an unexpected type.
+  UnexpectedType {
+    /// The type expected.
+    expected: Type,
+    /// The type found.
+    found: Type,
+  },
+  /// The number was expected to be an integer.
+  NotInteger,
+  /// The integer exceeded the range of an `i64`.
+  IntegerOutOfRange,
+  /// The number was less than the minimum allowed.
+  BelowMinimum,
+  /// The number was greater than the maximum allowed.
+  AboveMaximum,
+  /// The string, or array, was shorter than the minimum length allowed.
+  TooShort,
+  /// The string, or array, was longer than the maximum length allowed.
+  TooLong,
+  /// The object was missing a required field.
+  MissingField(&'static str),
+}
+
+/// An error when validating a value against a schema.
+#[derive(Debug)]
+pub enum SchemaError<'read, R: Read<'read>, S: Stack> {
+  /// The JSON could not be deserialized.
+  JsonError(JsonError<'read, R, S>),
+  /// The JSON violated the schema.
+  Violation(Violation),
+}
+
+impl<'read, R: Read<'read>, S: Stack> From<JsonError<'read, R, S>> for SchemaError<'read, R, S> {
+  fn from(error: JsonError<'read, R, S>) -> Self {
+    SchemaError::JsonError(error)
+  }
+}
+
+impl<'read, R: Read<'read>, S: Stack> From<Violation> for SchemaError<'read, R, S> {
+  fn from(violation: Violation) -> Self {
+    SchemaError::Violation(violation)
+  }
+}
+
+/// A schema which values may be validated against.
+pub trait Schema {
+  /// Validate a value against this schema.
+  ///
+  /// `pointer` is expected to point to `value`. If an error is returned, `pointer` will point to
+  /// the value where the error was encountered. Otherwise, it will be left as it was.
+  fn validate<'read, R: Read<'read>, S: Stack>(
+    &self,
+    value: Value<'read, '_, R, S>,
+    pointer: &mut Pointer,
+  ) -> Result<(), SchemaError<'read, R, S>>;
+
+  /// Validate a JSON-serialized blob against this schema.
+  ///
+  /// If an error is returned, `pointer` will be written to with the pointer to the value where
+  /// the error was encountered.
+  ///
+  /// This method SHOULD NOT be overriden.
+  fn validate_structure<'read, R: Read<'read>, S: Stack>(
+    &self,
+    json: R,
+    pointer: &mut Pointer,
+  ) -> Result<(), SchemaError<'read, R, S>> {
+    *pointer = Pointer::new();
+    let mut json = Deserializer::new(json)?;
+    self.validate(json.value()?, pointer)
+  }
+}
+
+/// Check a value is of the expected type.
+fn expect<'read, R: Read<'read>, S: Stack>(
+  value: &Value<'read, '_, R, S>,
+  expected: Type,
+) -> Result<(), SchemaError<'read, R, S>> {
+  let found = value.kind();
+  if found != expected {
+    Err(Violation::UnexpectedType { expected, found })?;
+  }
+  Ok(())
+}
+
+/// A schema accepting any value.
+#[derive(Clone, Copy, Debug)]
+pub struct Any;
+
+/// A schema accepting any value.
+pub const fn any() -> Any {
+  Any
+}
+
+impl Schema for Any {
+  fn validate<'read, R: Read<'read>, S: Stack>(
+    &self,
+    value: Value<'read, '_, R, S>,
+    _pointer: &mut Pointer,
+  ) -> Result<(), SchemaError<'read, R, S>> {
+    drop(value);
+    Ok(())
+  }
+}
+
+/// A schema accepting `null`.
+#[derive(Clone, Copy, Debug)]
+pub struct Null;
+
+/// A schema accepting `null`.
+pub const fn null() -> Null {
+  Null
+}
+
+impl Schema for Null {
+  fn validate<'read, R: Read<'read>, S: Stack>(
+    &self,
+    value: Value<'read, '_, R, S>,
+    _pointer: &mut Pointer,
+  ) -> Result<(), SchemaError<'read, R, S>> {
+    expect(&value, Type::Null)?;
+    Ok(value.to_null()?)
+  }
+}
+
+/// A schema accepting booleans.
+#[derive(Clone, Copy, Debug)]
+pub struct Boolean;
+
+/// A schema accepting booleans.
+pub const fn boolean() -> Boolean {
+  Boolean
+}
+
+impl Schema for Boolean {
+  fn validate<'read, R: Read<'read>, S: Stack>(
+    &self,
+    value: Value<'read, '_, R, S>,
+    _pointer: &mut Pointer,
+  ) -> Result<(), SchemaError<'read, R, S>> {
+    expect(&value, Type::Bool)?;
+    value.to_bool()?;
+    Ok(())
+  }
+}
+
+/// A schema accepting integers representable as an `i64`, within an inclusive range.
+#[derive(Clone, Copy, Debug)]
+pub struct Integer {
+  min: i64,
+  max: i64,
+}
+
+/// A schema accepting integers representable as an `i64`.
+pub const fn integer() -> Integer {
+  Integer { min: i64::MIN, max: i64::MAX }
+}
+
+impl Integer {
+  /// Require the integer be greater than or equal to `min`.
+  pub const fn min(mut self, min: i64) -> Self {
+    self.min = min;
+    self
+  }
+
+  /// Require the integer be less than or equal to `max`.
+  pub const fn max(mut self, max: i64) -> Self {
+    self.max = max;
+    self
+  }
+}
+
+impl Schema for Integer {
+  fn validate<'read, R: Read<'read>, S: Stack>(
+    &self,
+    value: Value<'read, '_, R, S>,
+    _pointer: &mut Pointer,
+  ) -> Result<(), SchemaError<'read, R, S>> {
+    expect(&value, Type::Number)?;
+    let number = value.to_number()?;
+    let integer = number.i64().ok_or(if number.is_integer() {
+      Violation::IntegerOutOfRange
+    } else {
+      Violation::NotInteger
+    })?;
+    if integer < self.min {
+      Err(Violation::BelowMinimum)?;
+    }
+    if integer > self.max {
+      Err(Violation::AboveMaximum)?;
+    }
+    Ok(())
+  }
+}
+
+/// A schema accepting numbers, within an inclusive range.
+#[derive(Clone, Copy, Debug)]
+pub struct Number {
+  min: f64,
+  max: f64,
+}
+
+/// A schema accepting numbers.
+pub const fn number() -> Number {
+  Number { min: f64::NEG_INFINITY, max: f64::INFINITY }
+}
+
+impl Number {
+  /// Require the number be greater than or equal to `min`.
+  pub const fn min(mut self, min: f64) -> Self {
+    self.min = min;
+    self
+  }
+
+  /// Require the number be less than or equal to `max`.
+  pub const fn max(mut self, max: f64) -> Self {
+    self.max = max;
+    self
+  }
+}
+
+impl Schema for Number {
+  fn validate<'read, R: Read<'read>, S: Stack>(
+    &self,
+    value: Value<'read, '_, R, S>,
+    _pointer: &mut Pointer,
+  ) -> Result<(), SchemaError<'read, R, S>> {
+    expect(&value, Type::Number)?;
+    let number = value.to_number()?.f64().ok_or(JsonError::TypeError(TypeError))?;
+    if number < self.min {
+      Err(Violation::BelowMinimum)?;
+    }
+    if number > self.max {
+      Err(Violation::AboveMaximum)?;
+    }
+    Ok(())
+  }
+}
+
+/// A schema accepting strings, with a length (in characters) within an inclusive range.
+#[derive(Clone, Copy, Debug)]
+pub struct Str {
+  min_len: usize,
+  max_len: usize,
+}
+
+/// A schema accepting strings.
+pub const fn string() -> Str {
+  Str { min_len: 0, max_len: usize::MAX }
+}
+
+impl Str {
+  /// Require the string have at least `min_len` characters.
+  pub const fn min_len(mut self, min_len: usize) -> Self {
+    self.min_len = min_len;
+    self
+  }
+
+  /// Require the string have at most `max_len` characters.
+  pub const fn max_len(mut self, max_len: usize) -> Self {
+    self.max_len = max_len;
+    self
+  }
+}
+
+impl Schema for Str {
+  fn validate<'read, R: Read<'read>, S: Stack>(
+    &self,
+    value: Value<'read, '_, R, S>,
+    _pointer: &mut Pointer,
+  ) -> Result<(), SchemaError<'read, R, S>> {
+    expect(&value, Type::String)?;
+    let mut len = 0usize;
+    for char in value.to_str()? {
+      char?;
+      len += 1;
+      if len > self.max_len {
+        Err(Violation::TooLong)?;
+      }
+    }
+    if len < self.min_len {
+      Err(Violation::TooShort)?;
+    }
+    Ok(())
+  }
+}
+
+/// A schema accepting arrays, whose elements are all accepted by a schema.
+#[derive(Clone, Copy, Debug)]
+pub struct Array<E: Schema> {
+  element: E,
+  min_len: usize,
+  max_len: usize,
+}
+
+/// A schema accepting arrays, whose elements are all accepted by `element`.
+pub const fn array<E: Schema>(element: E) -> Array<E> {
+  Array { element, min_len: 0, max_len: usize::MAX }
+}
+
+impl<E: Schema> Array<E> {
+  /// Require the array have at least `min_len` elements.
+  pub const fn min_len(mut self, min_len: usize) -> Self {
+    self.min_len = min_len;
+    self
+  }
+
+  /// Require the array have at most `max_len` elements.
+  pub const fn max_len(mut self, max_len: usize) -> Self {
+    self.max_len = max_len;
+    self
+  }
+}
+
+impl<E: Schema> Schema for Array<E> {
+  fn validate<'read, R: Read<'read>, S: Stack>(
+    &self,
+    value: Value<'read, '_, R, S>,
+    pointer: &mut Pointer,
+  ) -> Result<(), SchemaError<'read, R, S>> {
+    expect(&value, Type::Array)?;
+    let mut elements = value.iterate()?;
+    let mut len = 0;
+    while let Some(element) = elements.next() {
+      let element = element?;
+      if len == self.max_len {
+        Err(Violation::TooLong)?;
+      }
+      pointer.push(PointerSegment::Index(len));
+      self.element.validate(element, pointer)?;
+      pointer.pop();
+      len += 1;
+    }
+    if len < self.min_len {
+      Err(Violation::TooShort)?;
+    }
+    Ok(())
+  }
+}
+
+/// A field within an `Object` schema.
+#[derive(Clone, Copy, Debug)]
+pub struct FieldSchema<S: Schema> {
+  key: &'static str,
+  schema: S,
+  required: bool,
+}
+
+/// The fields of an `Object` schema.
+///
+/// This is implemented for `()` and for `(F, FieldSchema<S>)`, allowing the fields to be built up
+/// without allocating.
+pub trait Fields {
+  /// The amount of fields.
+  const LEN: usize;
+
+  #[doc(hidden)]
+  fn key(&self, i: usize) -> &'static str;
+
+  #[doc(hidden)]
+  fn required(&self, i: usize) -> bool;
+
+  #[doc(hidden)]
+  fn validate<'read, R: Read<'read>, S: Stack>(
+    &self,
+    i: usize,
+    value: Value<'read, '_, R, S>,
+    pointer: &mut Pointer,
+  ) -> Result<(), SchemaError<'read, R, S>>;
+}
+
+impl Fields for () {
+  const LEN: usize = 0;
+  fn key(&self, _i: usize) -> &'static str {
+    unreachable!("indexed into an empty list of fields")
+  }
+  fn required(&self, _i: usize) -> bool {
+    unreachable!("indexed into an empty list of fields")
+  }
+  fn validate<'read, R: Read<'read>, S: Stack>(
+    &self,
+    _i: usize,
+    _value: Value<'read, '_, R, S>,
+    _pointer: &mut Pointer,
+  ) -> Result<(), SchemaError<'read, R, S>> {
+    unreachable!("indexed into an empty list of fields")
+  }
+}
+
+impl<F: Fields, T: Schema> Fields for (F, FieldSchema<T>) {
+  const LEN: usize = F::LEN + 1;
+  fn key(&self, i: usize) -> &'static str {
+    if i == F::LEN { self.1.key } else { self.0.key(i) }
+  }
+  fn required(&self, i: usize) -> bool {
+    if i == F::LEN { self.1.required } else { self.0.required(i) }
+  }
+  fn validate<'read, R: Read<'read>, S: Stack>(
+    &self,
+    i: usize,
+    value: Value<'read, '_, R, S>,
+    pointer: &mut Pointer,
+  ) -> Result<(), SchemaError<'read, R, S>> {
+    if i == F::LEN {
+      self.1.schema.validate(value, pointer)
+    } else {
+      self.0.validate(i, value, pointer)
+    }
+  }
+}
+
+/// A schema accepting objects, whose fields are accepted by their schemas.
+///
+/// Fields not present within the schema are allowed, and not validated. If a field is present
+/// multiple times, each instance is validated.
+#[derive(Clone, Copy, Debug)]
+pub struct Object<F: Fields> {
+  fields: F,
+}
+
+/// A schema accepting objects.
+pub const fn object() -> Object<()> {
+  Object { fields: () }
+}
+
+impl<F: Fields> Object<F> {
+  /// The maximum amount of fields an object schema may have.
+  pub const MAX_FIELDS: usize = 64;
+
+  /// Require a field be present, with a value accepted by `schema`.
+  ///
+  /// This will panic if the object schema already has `Object::MAX_FIELDS` fields.
+  pub fn field<T: Schema>(self, key: &'static str, schema: T) -> Object<(F, FieldSchema<T>)> {
+    assert!(F::LEN < Self::MAX_FIELDS, "object schema had too many fields");
+    Object { fields: (self.fields, FieldSchema { key, schema, required: true }) }
+  }
+
+  /// Allow a field to be present, requiring its value be accepted by `schema` if it is.
+  ///
+  /// This will panic if the object schema already has `Object::MAX_FIELDS` fields.
+  pub fn optional<T: Schema>(self, key: &'static str, schema: T) -> Object<(F, FieldSchema<T>)> {
+    assert!(F::LEN < Self::MAX_FIELDS, "object schema had too many fields");
+    Object { fields: (self.fields, FieldSchema { key, schema, required: false }) }
+  }
+}
+
+impl<F: Fields> Schema for Object<F> {
+  fn validate<'read, R: Read<'read>, S: Stack>(
+    &self,
+    value: Value<'read, '_, R, S>,
+    pointer: &mut Pointer,
+  ) -> Result<(), SchemaError<'read, R, S>> {
+    expect(&value, Type::Object)?;
+
+    let all = if F::LEN == 64 { u64::MAX } else { (1u64 << F::LEN) - 1 };
+    let mut seen = 0u64;
+    let mut fields = value.fields()?;
+    while let Some(field) = fields.next() {
+      let mut field = field?;
+
+      // Match the key against the keys of the fields as it's read, without buffering it
+      let mut candidates = all;
+      let mut len = 0;
+      for char in field.key()? {
+        let mut encoded = [0; 4];
+        let encoded = char?.encode_utf8(&mut encoded).as_bytes();
+        for i in 0 .. F::LEN {
+          if self.fields.key(i).as_bytes().get(len .. (len + encoded.len())) != Some(encoded) {
+            candidates &= !(1 << i);
+          }
+        }
+        len += encoded.len();
+      }
+      let i = (0 .. F::LEN)
+        .find(|i| ((candidates & (1 << i)) != 0) && (self.fields.key(*i).len() == len));
+
+      let value = field.value()?;
+      let Some(i) = i else {
+        drop(value);
+        continue;
+      };
+      seen |= 1 << i;
+      pointer.push(PointerSegment::Key(self.fields.key(i)));
+      self.fields.validate(i, value, pointer)?;
+      pointer.pop();
+    }
+
+    if let Some(i) = (0 .. F::LEN).find(|i| self.fields.required(*i) && ((seen & (1 << i)) == 0)) {
+      Err(Violation::MissingField(self.fields.key(i)))?;
+    }
+    Ok(())
+  }
+}
+
+#[test]
+fn schema() {
+  use crate::ConstStack;
+
+  let schema = object()
+    .field("id", integer().min(0))
+    .field("name", string().max_len(8))
+    .optional("score", number().max(1.0))
+    .optional("friends", array(object().field("id", integer()).optional("a/b~", null())));
+
+  let validate = |json: &str| {
+    let mut pointer = Pointer::new();
+    let res = schema.validate_structure::<_, ConstStack<32>>(json.as_bytes(), &mut pointer);
+    (
+      match res {
+        Ok(()) => None,
+        Err(SchemaError::Violation(violation)) => Some(violation),
+        Err(SchemaError::JsonError(e)) => panic!("JSON error: {e:?}"),
+      },
+      pointer.to_string(),
+    )
+  };
+
+  assert_eq!(validate(r#"{"id":1,"name":"a"}"#).0, None);
+  assert_eq!(
+    validate(r#"{"name":"a","extra":[{}],"id":1,"score":0.5,"friends":[{"id":-1,"a/b~":null}]}"#).0,
+    None
+  );
+  assert_eq!(
+    validate(r#"{"id":-1,"name":"a"}"#),
+    (Some(Violation::BelowMinimum), "/id".to_string())
+  );
+  assert_eq!(
+    validate(r#"{"id":1.5,"name":"a"}"#),
+    (Some(Violation::NotInteger), "/id".to_string())
+  );
+  assert_eq!(
+    validate(r#"{"id":9223372036854775808,"name":"a"}"#),
+    (Some(Violation::IntegerOutOfRange), "/id".to_string())
+  );
+  assert_eq!(
+    validate(r#"{"id":1e400,"name":"a"}"#),
+    (Some(Violation::IntegerOutOfRange), "/id".to_string())
+  );
+  assert_eq!(
+    validate(r#"{"id":9223372036854775807.5,"name":"a"}"#),
+    (Some(Violation::NotInteger), "/id".to_string())
+  );
+  assert_eq!(
+    validate(r#"{"id":1,"name":"abcdefghi"}"#),
+    (Some(Violation::TooLong), "/name".to_string())
+  );
+  assert_eq!(validate(r#"{"id":1}"#), (Some(Violation::MissingField("name")), String::new()));
+  assert_eq!(
+    validate(r#"{"id":1,"name":"a","friends":[{"id":1},{"id":2,"a/b~":true}]}"#),
+    (
+      Some(Violation::UnexpectedType { expected: Type::Null, found: Type::Bool }),
+      "/friends/1/a~1b~0".to_string()
+    )
+  );
+  assert_eq!(
+    validate(r#"[]"#),
+    (Some(Violation::UnexpectedType { expected: Type::Object, found: Type::Array }), String::new())
+  );
+}
@@ -0,0 +1,229 @@
+use core::marker::PhantomData;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::{Read, Stack, JsonError, Value, ArrayIterator, JsonDeserializeSeed};
+#[cfg(any(feature = "sequences", all(feature = "sets", feature = "alloc")))]
+use crate::JsonSerialize;
+
+pub(crate) struct Sequence<'read, 'parent, R: Read<'read>, S: Stack, D: JsonDeserializeSeed> {
+  pub(crate) iterator: ArrayIterator<'read, 'parent, R, S>,
+  pub(crate) seed: D,
+}
+impl<'read, 'parent, R: Read<'read>, S: Stack, D: JsonDeserializeSeed> Iterator
+  for Sequence<'read, 'parent, R, S, D>
+{
+  type Item = Result<D::Value, JsonError<'read, R, S>>;
+  fn next(&mut self) -> Option<Self::Item> {
+    match self.iterator.next()? {
+      Ok(value) => Some(self.seed.deserialize(value)),
+      Err(e) => Some(Err(e)),
+    }
+  }
+}
+
+/// A seed for deserializing a JSON array into a collection, deserializing each element with a
+/// seed.
+pub struct SequenceSeed<D: JsonDeserializeSeed, C: FromIterator<D::Value>> {
+  seed: D,
+  _collection: PhantomData<C>,
+}
+
+impl<D: JsonDeserializeSeed, C: FromIterator<D::Value>> SequenceSeed<D, C> {
+  /// Create a seed for a collection from the seed for its elements.
+  pub fn new(seed: D) -> Self {
+    Self { seed, _collection: PhantomData }
+  }
+
+  /// Consume this seed, returning the seed for the elements.
+  pub fn into_inner(self) -> D {
+    self.seed
+  }
+}
+
+impl<D: JsonDeserializeSeed, C: FromIterator<D::Value>> JsonDeserializeSeed for SequenceSeed<D, C> {
+  type Value = C;
+  fn deserialize<'read, 'parent, R: Read<'read>, S: Stack>(
+    &mut self,
+    value: Value<'read, 'parent, R, S>,
+  ) -> Result<C, JsonError<'read, R, S>> {
+    (Sequence { iterator: value.iterate()?, seed: &mut self.seed }).collect()
+  }
+}
+
+#[cfg(any(feature = "sequences", all(feature = "sets", feature = "alloc")))]
+pub(crate) fn serialize_sequence<'element, T: 'element + JsonSerialize>(
+  iterator: impl Iterator<Item = &'element T>,
+) -> impl Iterator<Item = char> {
+  struct ConnectWithCommas<I: Iterator<Item = char>, II: Iterator<Item = I>> {
+    iterator: II,
+    current: I,
+  }
+  impl<I: Iterator<Item = char>, II: Iterator<Item = I>> Iterator for ConnectWithCommas<I, II> {
+    type Item = char;
+    fn next(&mut self) -> Option<char> {
+      match self.current.next() {
+        Some(char) => Some(char),
+        None => {
+          let next = self.iterator.next()?;
+          self.current = next;
+          Some(',')
+        }
+      }
+    }
+  }
+
+  let mut iterator = iterator.map(JsonSerialize::serialize);
+  core::iter::once('[')
+    .chain(
+      iterator.next().map(|current| ConnectWithCommas { iterator, current }).into_iter().flatten(),
+    )
+    .chain(core::iter::once(']'))
+}
+
+#[cfg(feature = "alloc")]
+/// A sink for the keys of fields within an object.
+pub(crate) trait KeySink {
+  /// The type of the keys yielded.
+  type Key;
+  /// Sink a key, as represented by an iterator of its characters.
+  fn sink<E>(&mut self, key: impl Iterator<Item = Result<char, E>>) -> Result<Self::Key, E>;
+}
+
+#[cfg(feature = "alloc")]
+/// A `KeySink` which collects each key into a `String`.
+pub(crate) struct StringKeys;
+#[cfg(feature = "alloc")]
+impl KeySink for StringKeys {
+  type Key = String;
+  #[inline(always)]
+  fn sink<E>(&mut self, key: impl Iterator<Item = Result<char, E>>) -> Result<String, E> {
+    key.collect()
+  }
+}
+
+#[cfg(feature = "alloc")]
+type YieldedField<'read, K, D, R, S> =
+  Result<(<K as KeySink>::Key, <D as JsonDeserializeSeed>::Value), JsonError<'read, R, S>>;
+#[cfg(feature = "alloc")]
+pub(crate) fn deserialize_map<
+  'read,
+  'parent,
+  'sink,
+  K: KeySink,
+  D: JsonDeserializeSeed,
+  R: Read<'read>,
+  S: Stack,
+>(
+  value: Value<'read, 'parent, R, S>,
+  keys: &'sink mut K,
+  mut seed: D,
+) -> Result<impl Iterator<Item = YieldedField<'read, K, D, R, S>>, JsonError<'read, R, S>> {
+  let mut iter = value.fields()?;
+  Ok(core::iter::from_fn(move || {
+    let (key, value) = match iter.next_key_value(|key| keys.sink(key))? {
+      Ok(field) => field,
+      Err(e) => return Some(Err(e)),
+    };
+    Some(key.and_then(|key| Ok((key, seed.deserialize(value)?))))
+  }))
+}
+
+#[cfg(feature = "alloc")]
+/// A seed for deserializing a JSON object into a map, deserializing each value with a seed.
+///
+/// If a field is present multiple times, the map will be collected with each instance of the
+/// field, in the order they were serialized.
+pub struct MapSeed<D: JsonDeserializeSeed, M: FromIterator<(String, D::Value)>> {
+  seed: D,
+  _map: PhantomData<M>,
+}
+
+#[cfg(feature = "alloc")]
+impl<D: JsonDeserializeSeed, M: FromIterator<(String, D::Value)>> MapSeed<D, M> {
+  /// Create a seed for a map from the seed for its values.
+  pub fn new(seed: D) -> Self {
+    Self { seed, _map: PhantomData }
+  }
+
+  /// Consume this seed, returning the seed for the values.
+  pub fn into_inner(self) -> D {
+    self.seed
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<D: JsonDeserializeSeed, M: FromIterator<(String, D::Value)>> JsonDeserializeSeed
+  for MapSeed<D, M>
+{
+  type Value = M;
+  fn deserialize<'read, 'parent, R: Read<'read>, S: Stack>(
+    &mut self,
+    value: Value<'read, 'parent, R, S>,
+  ) -> Result<M, JsonError<'read, R, S>> {
+    deserialize_map(value, &mut StringKeys, &mut self.seed)?.collect()
+  }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn seeded_seq() {
+  use alloc::vec::Vec;
+  use crate::JsonDeserialize;
+
+  // A seed which offsets each number by a runtime value
+  struct Offset(u64);
+  impl JsonDeserializeSeed for Offset {
+    type Value = u64;
+    fn deserialize<'read, 'parent, R: Read<'read>, S: Stack>(
+      &mut self,
+      value: Value<'read, 'parent, R, S>,
+    ) -> Result<u64, JsonError<'read, R, S>> {
+      Ok(u64::deserialize(value)? + self.0)
+    }
+  }
+
+  let mut seed = SequenceSeed::<_, Vec<Vec<u64>>>::new(SequenceSeed::new(Offset(10)));
+  assert_eq!(
+    seed.deserialize_structure::<_, crate::ConstStack<32>>("[[1, 2], [], [3]]".as_bytes()).unwrap(),
+    [[11, 12].as_slice(), &[], &[13]],
+  );
+  assert_eq!(seed.into_inner().into_inner().0, 10);
+
+  assert_eq!(
+    SequenceSeed::<_, Vec<u8>>::new(PhantomData)
+      .deserialize_structure::<_, crate::ConstStack<32>>("[1, 2]".as_bytes())
+      .unwrap(),
+    [1, 2],
+  );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn seeded_map() {
+  use alloc::{string::ToString, collections::BTreeMap};
+  use crate::JsonDeserialize;
+
+  // A seed which counts the values deserialized
+  struct Counter(usize);
+  impl JsonDeserializeSeed for Counter {
+    type Value = (usize, bool);
+    fn deserialize<'read, 'parent, R: Read<'read>, S: Stack>(
+      &mut self,
+      value: Value<'read, 'parent, R, S>,
+    ) -> Result<Self::Value, JsonError<'read, R, S>> {
+      self.0 += 1;
+      Ok((self.0, bool::deserialize(value)?))
+    }
+  }
+
+  let mut seed = MapSeed::<_, BTreeMap<String, _>>::new(Counter(0));
+  assert_eq!(
+    seed
+      .deserialize_structure::<_, crate::ConstStack<32>>(r#"{ "a": true, "b": false }"#.as_bytes())
+      .unwrap(),
+    BTreeMap::from([("a".to_string(), (1, true)), ("b".to_string(), (2, false))]),
+  );
+  assert_eq!(seed.into_inner().0, 2);
+}
@@ -0,0 +1,60 @@
+use core::marker::PhantomData;
+
+use crate::{
+  Read, Stack, JsonError, Value, JsonDeserialize, JsonStructure, JsonSerialize,
+  seeds::{Sequence, serialize_sequence},
+};
+
+macro_rules! set {
+  ($($deser_bounds: path)|+, $($ser_bounds: path)|+, $kind: ty $(, $hasher: ident)?) => {
+    impl<T: $($deser_bounds +)+ $(, $hasher: core::hash::BuildHasher + Default)?> JsonDeserialize
+      for $kind
+    {
+      /// This will deserialize a JSON array into a set, accepting duplicates and any ordering.
+      #[inline(always)]
+      fn deserialize<'read, 'parent, R: Read<'read>, S: Stack>(
+        value: Value<'read, 'parent, R, S>,
+      ) -> Result<Self, JsonError<'read, R, S>> {
+        (Sequence { iterator: value.iterate()?, seed: PhantomData::<T> }).collect()
+      }
+    }
+    impl<T: $($deser_bounds +)+ $(, $hasher: core::hash::BuildHasher + Default)?> JsonStructure
+      for $kind
+    {
+    }
+    impl<T: $($ser_bounds +)+ $(, $hasher)?> JsonSerialize for $kind {
+      /// This will serialize a set as its elements into a JSON array, with undefined ordering.
+      #[inline(always)]
+      fn serialize(&self) -> impl Iterator<Item = char> {
+        serialize_sequence(self.iter())
+      }
+    }
+  };
+}
+set!(Ord | JsonDeserialize, Ord | JsonSerialize, alloc::collections::BTreeSet<T>);
+#[cfg(feature = "std")]
+set!(
+  Eq | core::hash::Hash | JsonDeserialize,
+  Eq | core::hash::Hash | JsonSerialize,
+  std::collections::HashSet<T>
+);
+#[cfg(feature = "hashbrown")]
+set!(
+  Eq | core::hash::Hash | JsonDeserialize,
+  Eq | core::hash::Hash | JsonSerialize,
+  hashbrown::HashSet<T, H>,
+  H
+);
+
+#[test]
+fn btree_set() {
+  use alloc::{string::String, collections::BTreeSet};
+
+  let set = BTreeSet::from([3u8, 1, 2]);
+  assert_eq!(set.serialize().collect::<String>(), "[1,2,3]");
+  assert_eq!(
+    BTreeSet::<u8>::deserialize_structure::<_, crate::ConstStack<32>>(b"[2, 3, 1, 3]".as_slice())
+      .unwrap(),
+    set
+  );
+}
@@ -0,0 +1,104 @@
+use core::future::Future;
+
+use crate::JsonSerialize;
+
+/// A sink which bytes may be asynchronously written to.
+pub trait AsyncWrite {
+  /// The error when writing to this sink.
+  type Error;
+
+  /// Write the entirety of the bytes to this sink.
+  ///
+  /// The returned future should only resolve once the sink has accepted all of the bytes,
+  /// providing backpressure to the serialization.
+  fn write_all(&mut self, bytes: &[u8]) -> impl Future<Output = Result<(), Self::Error>>;
+}
+
+/// Serialize a value into an asynchronous sink.
+///
+/// The serialization is encoded as UTF-8 into `buffer`, which is written to the sink (and awaited)
+/// whenever it's full, and once more after the serialization ends. This allows streaming large
+/// serializations over slow sinks without holding more than `buffer` in memory. If `buffer` is
+/// too small to hold a character, the character will be written to the sink by itself.
+pub async fn serialize_async<W: AsyncWrite>(
+  value: &(impl ?Sized + JsonSerialize),
+  sink: &mut W,
+  buffer: &mut [u8],
+) -> Result<(), W::Error> {
+  let mut len = 0;
+  for char in value.serialize() {
+    let mut encoded = [0; 4];
+    let encoded = char.encode_utf8(&mut encoded).as_bytes();
+    if (buffer.len() - len) < encoded.len() {
+      if len != 0 {
+        sink.write_all(&buffer[.. len]).await?;
+        len = 0;
+      }
+      if buffer.len() < encoded.len() {
+        sink.write_all(encoded).await?;
+        continue;
+      }
+    }
+    buffer[len .. (len + encoded.len())].copy_from_slice(encoded);
+    len += encoded.len();
+  }
+  if len != 0 {
+    sink.write_all(&buffer[.. len]).await?;
+  }
+  Ok(())
+}
+
+#[test]
+fn async_sink() {
+  use core::{
+    pin::{Pin, pin},
+    task::{Poll, Context, Waker},
+  };
+
+  // A sink which is only ready every other poll, recording the chunks written to it
+  struct Sink {
+    chunks: Vec<Vec<u8>>,
+  }
+  impl AsyncWrite for Sink {
+    type Error = ();
+    fn write_all(&mut self, bytes: &[u8]) -> impl Future<Output = Result<(), ()>> {
+      struct Pending(bool);
+      impl Future for Pending {
+        type Output = ();
+        fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+          if self.0 {
+            return Poll::Ready(());
+          }
+          self.0 = true;
+          Poll::Pending
+        }
+      }
+      async move {
+        Pending(false).await;
+        self.chunks.push(bytes.to_vec());
+        Ok(())
+      }
+    }
+  }
+
+  let value = "a\"é😀";
+
+  // The serialization is `Send` when the sink and its futures are
+  fn send<T: Send>(_: &T) {}
+  send(&serialize_async(value, &mut Sink { chunks: vec![] }, &mut []));
+
+  for buffer_len in [1, 2, 4, 5, 64] {
+    let mut sink = Sink { chunks: vec![] };
+    let mut buffer = vec![0; buffer_len];
+    let mut polls = 0;
+    {
+      let mut future = pin!(serialize_async(value, &mut sink, &mut buffer));
+      while future.as_mut().poll(&mut Context::from_waker(Waker::noop())).is_pending() {
+        polls += 1;
+      }
+    }
+    assert_eq!(polls, sink.chunks.len());
+    assert!(sink.chunks.iter().all(|chunk| chunk.len() <= buffer_len.max(4)));
+    assert_eq!(sink.chunks.concat(), value.serialize().collect::<String>().as_bytes());
+  }
+}
@@ -0,0 +1,70 @@
+use crate::{Read, Stack, JsonError, Value, JsonDeserialize, JsonSerialize};
+
+/// Deserialize a string, only allocating if it's too long to be stored inline.
+#[cfg(feature = "smol_str")]
+impl JsonDeserialize for smol_str::SmolStr {
+  fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+    value: Value<'read, 'parent, B, S>,
+  ) -> Result<Self, JsonError<'read, B, S>> {
+    let mut builder = smol_str::SmolStrBuilder::new();
+    for char in value.to_str()? {
+      builder.push(char?);
+    }
+    Ok(builder.finish())
+  }
+}
+
+#[cfg(feature = "smol_str")]
+impl JsonSerialize for smol_str::SmolStr {
+  fn serialize(&self) -> impl Iterator<Item = char> {
+    self.as_str().serialize()
+  }
+}
+
+/// Deserialize a string, only allocating if it's too long to be stored inline.
+#[cfg(feature = "compact_str")]
+impl JsonDeserialize for compact_str::CompactString {
+  fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
+    value: Value<'read, 'parent, B, S>,
+  ) -> Result<Self, JsonError<'read, B, S>> {
+    let mut string = compact_str::CompactString::default();
+    for char in value.to_str()? {
+      string.push(char?);
+    }
+    Ok(string)
+  }
+}
+
+#[cfg(feature = "compact_str")]
+impl JsonSerialize for compact_str::CompactString {
+  fn serialize(&self) -> impl Iterator<Item = char> {
+    self.as_str().serialize()
+  }
+}
+
+#[cfg(all(feature = "smol_str", feature = "compact_str", feature = "sequences"))]
+#[test]
+fn small_strings() {
+  use crate::{ConstStack, JsonStructure};
+
+  let long = "a string which is too long to be stored inline, requiring an allocation";
+  let serialization = format!(r#"["key", "é\n", "", "{long}"]"#);
+
+  let smol =
+    <[smol_str::SmolStr; 4]>::deserialize_structure::<_, ConstStack<1>>(serialization.as_bytes())
+      .unwrap();
+  assert_eq!(smol, ["key", "é\n", "", long]);
+  assert!(!smol[0].is_heap_allocated());
+  assert!(smol[3].is_heap_allocated());
+
+  let compact = <[compact_str::CompactString; 4]>::deserialize_structure::<_, ConstStack<1>>(
+    serialization.as_bytes(),
+  )
+  .unwrap();
+  assert_eq!(compact, ["key", "é\n", "", long]);
+  assert!(!compact[0].is_heap_allocated());
+  assert!(compact[3].is_heap_allocated());
+
+  assert_eq!(smol.serialize().collect::<String>(), compact.serialize().collect::<String>());
+  assert_eq!(smol.serialize().collect::<String>(), format!(r#"["key","é\u000a","","{long}"]"#));
+}
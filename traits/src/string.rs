@@ -1,7 +1,15 @@
 use crate::JsonSerialize;
 
+#[cfg(any(
+  feature = "alloc",
+  feature = "uuid",
+  feature = "ulid",
+  feature = "time",
+  feature = "chrono"
+))]
+use crate::{Read, Stack, JsonError, TypeError, Value};
 #[cfg(feature = "alloc")]
-use crate::{Read, Stack, JsonError, Value, JsonDeserialize};
+use crate::JsonDeserialize;
 #[cfg(feature = "alloc")]
 impl JsonDeserialize for alloc::string::String {
   fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
@@ -11,6 +19,65 @@ impl JsonDeserialize for alloc::string::String {
   }
 }
 
+/// Deserialize a `String`, as done by code derived by `core-json-derive` for fields with the
+/// `max_len` or `ascii_only` attributes.
+///
+/// The constraints are checked as each character is read, erroring (with
+/// `JsonError::LengthLimitExceeded` or `JsonError::TypeError` respectively) before the rest of the
+/// string is read.
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub fn deserialize_constrained_string<'read, 'parent, B: Read<'read>, S: Stack>(
+  value: Value<'read, 'parent, B, S>,
+  max_len: Option<usize>,
+  ascii_only: bool,
+) -> Result<alloc::string::String, JsonError<'read, B, S>> {
+  let mut len = 0;
+  value
+    .to_str()?
+    .map(|char| {
+      let char = char?;
+      len += 1;
+      if max_len.is_some_and(|max_len| len > max_len) {
+        Err(JsonError::LengthLimitExceeded)?;
+      }
+      if ascii_only && (!char.is_ascii()) {
+        Err(JsonError::TypeError(TypeError))?;
+      }
+      Ok(char)
+    })
+    .collect()
+}
+
+#[cfg(any(feature = "uuid", feature = "ulid", feature = "time", feature = "chrono"))]
+/// Read a string of at most `N` ASCII characters, without allocating.
+///
+/// Returns the buffer and the length of the string read into it.
+pub(crate) fn read_ascii<'read, 'parent, B: Read<'read>, S: Stack, const N: usize>(
+  value: Value<'read, 'parent, B, S>,
+) -> Result<([u8; N], usize), JsonError<'read, B, S>> {
+  let mut buf = [0; N];
+  let mut len = 0;
+  for char in value.to_str()? {
+    let byte =
+      u8::try_from(char?).ok().filter(u8::is_ascii).ok_or(JsonError::TypeError(TypeError))?;
+    *buf.get_mut(len).ok_or(JsonError::TypeError(TypeError))? = byte;
+    len += 1;
+  }
+  Ok((buf, len))
+}
+
+#[cfg(any(feature = "uuid", feature = "ulid", feature = "time", feature = "chrono"))]
+/// Serialize the first `len` bytes of a buffer, as an ASCII string which doesn't require escaping.
+pub(crate) fn serialize_ascii<const N: usize>(
+  buf: [u8; N],
+  len: usize,
+) -> impl Iterator<Item = char> {
+  core::iter::once('"')
+    .chain(buf.into_iter().take(len).map(char::from))
+    .chain(core::iter::once('"'))
+}
+
 struct CharIterator<I: Iterator<Item = char>> {
   iter: I,
   buf: [char; 12],
@@ -0,0 +1,371 @@
+//! Streaming transforms, copying a document while modifying it.
+//!
+//! The transforms read a document from a `Value` and write the transformed document to a
+//! `core::fmt::Write`, without deserializing the entire document into a `JsonValue`. Values are
+//! addressed by `JsonPointer`s into the original document, so dropping an element of an array
+//! doesn't change the pointers of the elements following it.
+//!
+//! The document written has no insignificant whitespace, with its strings reserialized (as by
+//! `JsonValue`) and its numbers reserialized exactly where possible (as by `Number`).
+
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::{
+  Read, Stack, JsonError, Type, Value, JsonDeserialize, JsonSerialize, JsonValue, JsonPointer,
+};
+
+/// An error when transforming a document.
+#[derive(Clone, Copy, Debug)]
+pub enum TransformError<E> {
+  /// An error from the deserializer of the document.
+  SourceError(E),
+  /// An error from the writer.
+  WriteError,
+}
+
+impl<E> From<core::fmt::Error> for TransformError<E> {
+  #[inline(always)]
+  fn from(_: core::fmt::Error) -> Self {
+    TransformError::WriteError
+  }
+}
+
+/// The action to take for a value.
+#[derive(Clone, Copy)]
+enum Action<'action> {
+  /// Copy the value, deciding the actions for the values within it.
+  Copy,
+  /// Copy the value if it's an object or array, deciding the actions for the values within it,
+  /// and otherwise drop it.
+  Descend,
+  /// Replace the value.
+  Replace(&'action JsonValue),
+  /// Drop the value, and its key if it's the value of a field.
+  Drop,
+}
+
+fn write_serialization<E>(
+  out: &mut impl Write,
+  value: &(impl ?Sized + JsonSerialize),
+) -> Result<(), TransformError<E>> {
+  Ok(value.serialize().try_for_each(|char| out.write_char(char))?)
+}
+
+/// Decide the action to take for a value.
+fn decide_for<'read, 'action, R: Read<'read>, S: Stack>(
+  value: &Value<'read, '_, R, S>,
+  pointer: &JsonPointer,
+  decide: &impl Fn(&JsonPointer) -> Action<'action>,
+) -> Action<'action> {
+  match decide(pointer) {
+    Action::Descend if !matches!(value.kind(), Type::Object | Type::Array) => Action::Drop,
+    action => action,
+  }
+}
+
+/// Apply an action to a value.
+fn apply<'read, 'action, R: Read<'read>, S: Stack>(
+  value: Value<'read, '_, R, S>,
+  action: Action<'action>,
+  pointer: &mut JsonPointer,
+  out: &mut impl Write,
+  decide: &impl Fn(&JsonPointer) -> Action<'action>,
+  rename: &impl Fn(&JsonPointer, &str) -> Option<&'action str>,
+) -> Result<(), TransformError<JsonError<'read, R, S>>> {
+  match action {
+    Action::Copy | Action::Descend => {}
+    // The value is skipped when the deserializer next advances
+    Action::Replace(replacement) => return write_serialization(out, replacement),
+    Action::Drop => return Ok(()),
+  }
+
+  match value.kind() {
+    Type::Object => {
+      out.write_char('{')?;
+      let mut first = true;
+      let mut fields = value.fields().map_err(TransformError::SourceError)?;
+      while let Some(field) = fields.next() {
+        let mut field = field.map_err(TransformError::SourceError)?;
+        let key = field
+          .key()
+          .map_err(TransformError::SourceError)?
+          .collect::<Result<String, _>>()
+          .map_err(TransformError::SourceError)?;
+        let value = field.value().map_err(TransformError::SourceError)?;
+
+        let renamed = rename(pointer, &key);
+        pointer.push_key(&key);
+        let action = decide_for(&value, pointer, decide);
+        if !matches!(action, Action::Drop) {
+          if !first {
+            out.write_char(',')?;
+          }
+          first = false;
+          write_serialization(out, renamed.unwrap_or(key.as_str()))?;
+          out.write_char(':')?;
+        }
+        apply(value, action, pointer, out, decide, rename)?;
+        pointer.pop();
+      }
+      out.write_char('}')?;
+    }
+    Type::Array => {
+      out.write_char('[')?;
+      let mut first = true;
+      let mut i = 0;
+      let mut elements = value.iterate().map_err(TransformError::SourceError)?;
+      while let Some(element) = elements.next() {
+        let element = element.map_err(TransformError::SourceError)?;
+
+        pointer.push_index(i);
+        i += 1;
+        let action = decide_for(&element, pointer, decide);
+        if !matches!(action, Action::Drop) {
+          if !first {
+            out.write_char(',')?;
+          }
+          first = false;
+        }
+        apply(element, action, pointer, out, decide, rename)?;
+        pointer.pop();
+      }
+      out.write_char(']')?;
+    }
+    Type::Number => {
+      write_serialization(out, &value.to_number().map_err(TransformError::SourceError)?)?
+    }
+    _ => write_serialization(
+      out,
+      &JsonValue::deserialize(value).map_err(TransformError::SourceError)?,
+    )?,
+  }
+  Ok(())
+}
+
+/// Transform a value, deciding the action to take for it and each value within it.
+///
+/// `rename` is called with the pointer to each object and the key of each of its fields, returning
+/// the key to write if it should be renamed. If the value itself is dropped, `null` is written.
+fn transform<'read, 'action, R: Read<'read>, S: Stack>(
+  value: Value<'read, '_, R, S>,
+  out: &mut impl Write,
+  decide: &impl Fn(&JsonPointer) -> Action<'action>,
+  rename: &impl Fn(&JsonPointer, &str) -> Option<&'action str>,
+) -> Result<(), TransformError<JsonError<'read, R, S>>> {
+  let mut pointer = JsonPointer::new();
+  let action = decide_for(&value, &pointer, decide);
+  if matches!(action, Action::Drop) {
+    return Ok(out.write_str("null")?);
+  }
+  apply(value, action, &mut pointer, out, decide, rename)
+}
+
+/// How to redact a value.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Redaction {
+  /// Replace the value (such as with `"***"`).
+  Replace(JsonValue),
+  /// Drop the value, and its key if it's the value of a field.
+  Drop,
+}
+
+/// Copy a value, redacting the values at the specified pointers.
+///
+/// If a pointer is specified multiple times, the first redaction is used. If the value itself is
+/// dropped, `null` is written. If a key is present multiple times within an object, each instance
+/// is redacted.
+///
+/// This recurses for each nested structure (bounded by the depth allowed by the `Stack` used).
+pub fn redact<'read, R: Read<'read>, S: Stack>(
+  value: Value<'read, '_, R, S>,
+  redactions: &[(JsonPointer, Redaction)],
+  out: &mut impl Write,
+) -> Result<(), TransformError<JsonError<'read, R, S>>> {
+  transform(
+    value,
+    out,
+    &|pointer| match redactions.iter().find(|(redacted, _)| redacted == pointer) {
+      Some((_, Redaction::Replace(replacement))) => Action::Replace(replacement),
+      Some((_, Redaction::Drop)) => Action::Drop,
+      None => Action::Copy,
+    },
+    &|_, _| None,
+  )
+}
+
+/// Copy a value, solely keeping the values at the specified pointers.
+///
+/// The values within the values kept are kept in their entirety. The objects and arrays which
+/// may contain the values kept (per the pointers) are kept, without their other fields and
+/// elements, and are written empty if none of the values were present (so an object root is
+/// written as `{}`). If no pointers are specified, `null` is written.
+///
+/// This recurses for each nested structure (bounded by the depth allowed by the `Stack` used).
+pub fn project<'read, R: Read<'read>, S: Stack>(
+  value: Value<'read, '_, R, S>,
+  kept: &[JsonPointer],
+  out: &mut impl Write,
+) -> Result<(), TransformError<JsonError<'read, R, S>>> {
+  transform(
+    value,
+    out,
+    &|pointer| {
+      if kept.iter().any(|kept| pointer.starts_with(kept)) {
+        Action::Copy
+      } else if kept.iter().any(|kept| kept.starts_with(pointer)) {
+        Action::Descend
+      } else {
+        Action::Drop
+      }
+    },
+    &|_, _| None,
+  )
+}
+
+/// A renaming of a key.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Rename {
+  /// The pointer to the value within which the key is renamed.
+  ///
+  /// The key is renamed for the fields of the object pointed to, and of every object within it,
+  /// so the root pointer renames the key throughout the document.
+  pub scope: JsonPointer,
+  /// The key to rename.
+  pub from: String,
+  /// The key to rename it to.
+  pub to: String,
+}
+
+/// Copy a value, renaming keys.
+///
+/// If multiple renamings apply to a key, the first is used. Keys are solely renamed once, so a key
+/// renamed to the key of another renaming won't be renamed again. The scopes are pointers into the
+/// original document, using the original keys.
+///
+/// This recurses for each nested structure (bounded by the depth allowed by the `Stack` used).
+pub fn rename_keys<'read, R: Read<'read>, S: Stack>(
+  value: Value<'read, '_, R, S>,
+  renames: &[Rename],
+  out: &mut impl Write,
+) -> Result<(), TransformError<JsonError<'read, R, S>>> {
+  transform(value, out, &|_| Action::Copy, &|object, key| {
+    renames
+      .iter()
+      .find(|rename| (rename.from == key) && object.starts_with(&rename.scope))
+      .map(|rename| rename.to.as_str())
+  })
+}
+
+#[test]
+fn redaction() {
+  use alloc::vec::Vec;
+  use crate::{ConstStack, Deserializer};
+
+  let check = |json: &str, redactions: &[(&str, Redaction)]| {
+    let redactions = redactions
+      .iter()
+      .map(|(pointer, redaction)| (pointer.parse().unwrap(), redaction.clone()))
+      .collect::<Vec<_>>();
+    let mut deserializer = Deserializer::<_, ConstStack<32>>::new(json.as_bytes()).unwrap();
+    let mut out = String::new();
+    redact(deserializer.value().unwrap(), &redactions, &mut out).unwrap();
+    out
+  };
+  let stars = || Redaction::Replace(JsonValue::String(String::from("***")));
+
+  let json =
+    r#"{ "user": "a", "password": "b", "tokens": [1, 2, 3], "nested": { "password": [{}] } }"#;
+  assert_eq!(
+    check(json, &[]),
+    r#"{"user":"a","password":"b","tokens":[1,2,3],"nested":{"password":[{}]}}"#
+  );
+  assert_eq!(
+    check(
+      json,
+      &[("/password", stars()), ("/nested/password", stars()), ("/tokens/1", Redaction::Drop)]
+    ),
+    r#"{"user":"a","password":"***","tokens":[1,3],"nested":{"password":"***"}}"#
+  );
+  assert_eq!(
+    check(
+      json,
+      &[("/user", Redaction::Drop), ("/tokens/0", Redaction::Drop), ("/nested", Redaction::Drop)]
+    ),
+    r#"{"password":"b","tokens":[2,3]}"#
+  );
+  assert_eq!(check(json, &[("", stars())]), r#""***""#);
+  assert_eq!(check(json, &[("", Redaction::Drop)]), "null");
+  assert_eq!(check(r#"{"a~/":1,"a~/":2}"#, &[("/a~0~1", Redaction::Drop)]), "{}");
+  // Numbers are copied exactly, even if they aren't representable as an `i64` or `f64`
+  assert_eq!(
+    check("[18446744073709551615, 0.1000000000000000001, 1.50]", &[]),
+    "[18446744073709551615,0.1000000000000000001,1.5]"
+  );
+}
+
+#[test]
+fn projection() {
+  use alloc::vec::Vec;
+  use crate::{ConstStack, Deserializer};
+
+  let check = |json: &str, kept: &[&str]| {
+    let kept = kept.iter().map(|pointer| pointer.parse().unwrap()).collect::<Vec<_>>();
+    let mut deserializer = Deserializer::<_, ConstStack<32>>::new(json.as_bytes()).unwrap();
+    let mut out = String::new();
+    project(deserializer.value().unwrap(), &kept, &mut out).unwrap();
+    out
+  };
+
+  let json = r#"{ "id": 1, "user": { "name": "a", "roles": ["b", "c"] }, "items": [1, [2], 3] }"#;
+  assert_eq!(
+    check(json, &[""]),
+    r#"{"id":1,"user":{"name":"a","roles":["b","c"]},"items":[1,[2],3]}"#
+  );
+  assert_eq!(check(json, &["/id", "/user/roles"]), r#"{"id":1,"user":{"roles":["b","c"]}}"#);
+  assert_eq!(
+    check(json, &["/user/roles/1", "/items/1/0", "/items/2"]),
+    r#"{"user":{"roles":["c"]},"items":[[2],3]}"#
+  );
+  // Scalars containing nothing kept are dropped
+  assert_eq!(check(json, &["/id/0", "/missing"]), "{}");
+  assert_eq!(check(json, &[]), "null");
+}
+
+#[test]
+fn renaming() {
+  use crate::{ConstStack, Deserializer};
+
+  let check = |json: &str, renames: &[(&str, &str, &str)]| {
+    let renames = renames
+      .iter()
+      .map(|(scope, from, to)| Rename {
+        scope: scope.parse().unwrap(),
+        from: String::from(*from),
+        to: String::from(*to),
+      })
+      .collect::<alloc::vec::Vec<_>>();
+    let mut deserializer = Deserializer::<_, ConstStack<32>>::new(json.as_bytes()).unwrap();
+    let mut out = String::new();
+    rename_keys(deserializer.value().unwrap(), &renames, &mut out).unwrap();
+    out
+  };
+
+  let json = r#"{ "id": 1, "user": { "id": 2, "name": "a" }, "items": [{ "id": 3 }] }"#;
+  assert_eq!(check(json, &[]), r#"{"id":1,"user":{"id":2,"name":"a"},"items":[{"id":3}]}"#);
+  assert_eq!(
+    check(json, &[("", "id", "uuid")]),
+    r#"{"uuid":1,"user":{"uuid":2,"name":"a"},"items":[{"uuid":3}]}"#
+  );
+  assert_eq!(
+    check(json, &[("/user", "id", "user_id"), ("/items", "id", "item_id"), ("", "user", "id")]),
+    r#"{"id":1,"id":{"user_id":2,"name":"a"},"items":[{"item_id":3}]}"#
+  );
+  // Renamings aren't chained
+  assert_eq!(check(r#"{"a":1,"b":2}"#, &[("", "a", "b"), ("", "b", "a")]), r#"{"b":1,"a":2}"#);
+  // Numbers are copied with all of their retained significant digits
+  assert_eq!(
+    check(r#"{"a":[184467440737095516151,-98765432109876543210.5]}"#, &[("", "a", "b")]),
+    r#"{"b":[184467440737095516151,-98765432109876543210.5]}"#
+  );
+}
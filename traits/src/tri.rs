@@ -28,9 +28,9 @@ pub enum Tri<T> {
 
 impl<T: JsonDeserialize> JsonDeserialize for Tri<T> {
   fn deserialize<'read, 'parent, B: Read<'read>, S: Stack>(
-    mut value: Value<'read, 'parent, B, S>,
+    value: Value<'read, 'parent, B, S>,
   ) -> Result<Self, JsonError<'read, B, S>> {
-    if matches!(value.kind()?, Type::Null) {
+    if matches!(value.kind(), Type::Null) {
       let () = value.to_null()?;
       return Ok(Tri::Null);
     }
@@ -0,0 +1,157 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::{State, Deserializer, JsonVisitor, walk, IteratorRead};
+
+// A visitor which ignores every event, as `walk` solely needs to validate the value
+#[cfg(feature = "alloc")]
+struct Validate;
+#[cfg(feature = "alloc")]
+impl JsonVisitor for Validate {}
+
+#[cfg(feature = "alloc")]
+const fn is_whitespace(char: char) -> bool {
+  matches!(char, ' ' | '\t' | '\n' | '\r')
+}
+
+/// Check if a serialization is valid JSON.
+///
+/// This deserializes the serialization (such as the output of `JsonSerialize::serialize`) without
+/// collecting it, checking it's a single value (optionally surrounded by whitespace) with valid
+/// syntax. Its depth is unbounded, as it's intended for checking serializers (such as within tests
+/// or debug builds), not for untrusted input.
+#[cfg(feature = "alloc")]
+pub fn is_valid_json_output(serialization: impl Iterator<Item = char>) -> bool {
+  let mut serialization = serialization.skip_while(|char| is_whitespace(*char)).peekable();
+  let Some(first) = serialization.peek().copied() else { return false };
+  let Ok(mut deserializer) =
+    Deserializer::<_, Vec<State>>::new(IteratorRead::new(&mut serialization))
+  else {
+    return false;
+  };
+
+  match first {
+    '{' | '[' => {}
+    '"' => {
+      return deserializer.string().is_ok_and(|mut string| string.all(|char| char.is_ok()));
+    }
+    't' | 'f' => return deserializer.bool().is_ok(),
+    'n' => return deserializer.null().is_ok(),
+    _ => return deserializer.number().is_ok(),
+  }
+
+  let valid = deserializer.value().is_ok_and(|value| walk(value, &mut Validate).is_ok()) &&
+    deserializer
+      .buffered()
+      .is_ok_and(|buffered| buffered.iter().all(|byte| is_whitespace(char::from(*byte))));
+  // Check the rest of the serialization, which wasn't buffered by the deserializer
+  drop(deserializer);
+  valid && serialization.all(is_whitespace)
+}
+
+#[cfg(all(feature = "validate-output", debug_assertions, feature = "std"))]
+std::thread_local! {
+  /// If a serialization is being validated, or yielded after being validated, on this thread.
+  static VALIDATING: core::cell::Cell<bool> = const { core::cell::Cell::new(false) };
+}
+/// If a serialization is being validated, or yielded after being validated.
+///
+/// Without the `std` feature, there's no notion of threads, so this is shared by all threads.
+/// Serializations on other threads may then go unvalidated while one is validated.
+#[cfg(all(feature = "validate-output", debug_assertions, not(feature = "std")))]
+static VALIDATING: core::sync::atomic::AtomicBool = core::sync::atomic::AtomicBool::new(false);
+
+/// A guard marking serializations as being validated, until dropped.
+#[cfg(all(feature = "validate-output", debug_assertions))]
+struct Validating {
+  // If serializations were already being validated when this guard was created
+  already: bool,
+}
+
+#[cfg(all(feature = "validate-output", debug_assertions))]
+impl Validating {
+  fn set(validating: bool) -> bool {
+    #[cfg(feature = "std")]
+    return VALIDATING.replace(validating);
+    #[cfg(not(feature = "std"))]
+    return VALIDATING.swap(validating, core::sync::atomic::Ordering::Relaxed);
+  }
+
+  fn enter() -> Self {
+    Validating { already: Self::set(true) }
+  }
+}
+
+#[cfg(all(feature = "validate-output", debug_assertions))]
+impl Drop for Validating {
+  fn drop(&mut self) {
+    Self::set(self.already);
+  }
+}
+
+/// A serialization returned by `validate_serialization`.
+#[doc(hidden)]
+pub struct Validated<I: Iterator<Item = char>> {
+  serialization: I,
+  // If this serialization was validated, and serializations nested within it aren't to be
+  #[cfg(all(feature = "validate-output", debug_assertions))]
+  validated: bool,
+}
+
+impl<I: Iterator<Item = char>> Iterator for Validated<I> {
+  type Item = char;
+
+  #[inline(always)]
+  fn next(&mut self) -> Option<char> {
+    // Nested serializations may be created as this is iterated, and were already validated
+    #[cfg(all(feature = "validate-output", debug_assertions))]
+    let _validating = self.validated.then(Validating::enter);
+    self.serialization.next()
+  }
+}
+
+/// Serialize a value, as done by code derived by `core-json-derive`.
+///
+/// With the `validate-output` feature and debug assertions enabled, this first checks the
+/// serialization is valid JSON, panicking otherwise. As this serializes the value an additional
+/// time, it's solely intended to catch bugs in serializers during development. Serializations
+/// nested within a serialization being validated (or being yielded after being validated) aren't
+/// validated themselves, as the outermost serialization's validation covers them, so each value
+/// is serialized at most twice regardless of how deeply it's nested.
+#[doc(hidden)]
+#[inline(always)]
+pub fn validate_serialization<I: Iterator<Item = char>>(serialize: impl Fn() -> I) -> Validated<I> {
+  #[cfg(all(feature = "validate-output", debug_assertions))]
+  {
+    let validating = Validating::enter();
+    let validated = !validating.already;
+    if validated {
+      assert!(is_valid_json_output(serialize()), "serialization wasn't valid JSON");
+    }
+    Validated { serialization: serialize(), validated }
+  }
+  #[cfg(not(all(feature = "validate-output", debug_assertions)))]
+  Validated { serialization: serialize() }
+}
+
+#[cfg(all(feature = "alloc", feature = "sequences"))]
+#[test]
+fn valid_json_output() {
+  use alloc::{string::String, vec};
+  use crate::JsonSerialize;
+
+  for valid in ["{}", " [1, \"a\", {\"b\": null}] \n", "\"a\\\"\"", "true", "null", "-1.5e3"] {
+    assert!(is_valid_json_output(valid.chars()), "{valid}");
+  }
+  // The depth is unbounded
+  assert!(is_valid_json_output("[".repeat(1000).chars().chain("]".repeat(1000).chars())));
+  for invalid in
+    ["", " ", "{", "[1,]", "{\"a\"}", "[] []", "[]]", "\"a", "tru", "1 2", "01", "[\"\\x\"]"]
+  {
+    assert!(!is_valid_json_output(invalid.chars()), "{invalid}");
+  }
+
+  assert!(is_valid_json_output(vec![String::from("a"), String::new()].serialize()));
+  assert!(is_valid_json_output(Some(1u8).serialize()));
+}
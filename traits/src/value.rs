@@ -0,0 +1,277 @@
+use alloc::{string::String, vec::Vec, boxed::Box};
+
+#[cfg(feature = "serde_json")]
+use crate::NotRepresentable;
+use crate::{
+  Read, Stack, JsonError, TypeError, Type, Value, JsonF64, JsonDeserialize, JsonStructure,
+  JsonSerialize,
+};
+
+/// A number within a `JsonValue`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum JsonNumber {
+  /// A number representable as an `i64`.
+  Integer(i64),
+  /// A number not representable as an `i64`.
+  Float(JsonF64),
+}
+
+impl JsonNumber {
+  /// This number as an `f64`.
+  ///
+  /// This may be lossy for integers which exceed the precision of an `f64`.
+  #[allow(clippy::cast_precision_loss)]
+  pub fn f64(self) -> f64 {
+    match self {
+      JsonNumber::Integer(integer) => integer as f64,
+      JsonNumber::Float(float) => float.into(),
+    }
+  }
+}
+
+/// A dynamically-typed JSON value.
+///
+/// Objects are represented as a list of their fields, in the order they were serialized, as JSON
+/// allows a key to be present multiple times within an object.
+///
+/// Deserializing a `JsonValue` allocates for the entire value, and will recurse for each nested
+/// structure (bounded by the depth allowed by the `Stack` used).
+#[derive(Clone, PartialEq, Debug)]
+pub enum JsonValue {
+  /// The `null` unit value.
+  Null,
+  /// A boolean.
+  Bool(bool),
+  /// A number.
+  Number(JsonNumber),
+  /// A string.
+  String(String),
+  /// An array.
+  Array(Vec<JsonValue>),
+  /// An object.
+  Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+  /// Get the value of a field within this object.
+  ///
+  /// If the field is present multiple times, the last instance is returned. If this isn't an
+  /// object, `None` is returned.
+  pub fn get(&self, key: &str) -> Option<&JsonValue> {
+    let JsonValue::Object(fields) = self else { None? };
+    fields.iter().rev().find(|(field, _)| field == key).map(|(_, value)| value)
+  }
+}
+
+impl JsonDeserialize for JsonValue {
+  fn deserialize<'read, 'parent, R: Read<'read>, S: Stack>(
+    value: Value<'read, 'parent, R, S>,
+  ) -> Result<Self, JsonError<'read, R, S>> {
+    Ok(match value.kind() {
+      Type::Null => {
+        value.to_null()?;
+        JsonValue::Null
+      }
+      Type::Bool => JsonValue::Bool(value.to_bool()?),
+      Type::Number => {
+        let number = value.to_number()?;
+        JsonValue::Number(match number.i64() {
+          Some(integer) => JsonNumber::Integer(integer),
+          None => JsonNumber::Float(
+            number
+              .f64()
+              .and_then(|float| JsonF64::try_from(float).ok())
+              .ok_or(JsonError::TypeError(TypeError))?,
+          ),
+        })
+      }
+      Type::String => JsonValue::String(value.to_str()?.collect::<Result<_, _>>()?),
+      Type::Array => {
+        let mut array = Vec::new();
+        let mut elements = value.iterate()?;
+        while let Some(element) = elements.next() {
+          array.push(JsonValue::deserialize(element?)?);
+        }
+        JsonValue::Array(array)
+      }
+      Type::Object => {
+        let mut object = Vec::new();
+        let mut fields = value.fields()?;
+        while let Some(field) = fields.next() {
+          let mut field = field?;
+          let key = field.key()?.collect::<Result<_, _>>()?;
+          object.push((key, JsonValue::deserialize(field.value()?)?));
+        }
+        JsonValue::Object(object)
+      }
+    })
+  }
+}
+impl JsonStructure for JsonValue {}
+
+/// Serialize a `JsonValue`.
+///
+/// As `JsonValue` is recursive, the iterators for nested values are boxed.
+fn serialize_value(value: &JsonValue) -> Box<dyn '_ + Iterator<Item = char>> {
+  match value {
+    JsonValue::Null => Box::new("null".chars()),
+    JsonValue::Bool(bool) => Box::new(bool.serialize()),
+    JsonValue::Number(JsonNumber::Integer(integer)) => Box::new(integer.serialize()),
+    JsonValue::Number(JsonNumber::Float(float)) => Box::new(float.serialize()),
+    JsonValue::String(string) => Box::new(string.serialize()),
+    JsonValue::Array(array) => Box::new(
+      core::iter::once('[')
+        .chain(array.iter().enumerate().flat_map(|(i, element)| {
+          (i != 0).then_some(',').into_iter().chain(serialize_value(element))
+        }))
+        .chain(core::iter::once(']')),
+    ),
+    JsonValue::Object(object) => Box::new(
+      core::iter::once('{')
+        .chain(object.iter().enumerate().flat_map(|(i, (key, value))| {
+          (i != 0)
+            .then_some(',')
+            .into_iter()
+            .chain(key.serialize())
+            .chain(core::iter::once(':'))
+            .chain(serialize_value(value))
+        }))
+        .chain(core::iter::once('}')),
+    ),
+  }
+}
+
+impl JsonSerialize for JsonValue {
+  fn serialize(&self) -> impl Iterator<Item = char> {
+    serialize_value(self)
+  }
+}
+
+#[cfg(feature = "serde_json")]
+impl TryFrom<serde_json::Value> for JsonValue {
+  type Error = NotRepresentable;
+
+  /// Convert from a `serde_json::Value`.
+  ///
+  /// Numbers which aren't representable as an `i64` are converted to `JsonNumber::Float`, which
+  /// may be lossy. Objects' fields are converted in the order `serde_json` iterates them in.
+  ///
+  /// Numbers which exceed the range of an `f64` (as `serde_json` may represent with its
+  /// `arbitrary_precision` feature) cause `NotRepresentable::OutOfRange` to be returned.
+  fn try_from(value: serde_json::Value) -> Result<JsonValue, NotRepresentable> {
+    Ok(match value {
+      serde_json::Value::Null => JsonValue::Null,
+      serde_json::Value::Bool(bool) => JsonValue::Bool(bool),
+      serde_json::Value::Number(number) => JsonValue::Number(match number.as_i64() {
+        Some(integer) => JsonNumber::Integer(integer),
+        None => JsonNumber::Float(
+          number
+            .as_f64()
+            .and_then(|float| JsonF64::try_from(float).ok())
+            .ok_or(NotRepresentable::OutOfRange)?,
+        ),
+      }),
+      serde_json::Value::String(string) => JsonValue::String(string),
+      serde_json::Value::Array(array) => {
+        JsonValue::Array(array.into_iter().map(JsonValue::try_from).collect::<Result<_, _>>()?)
+      }
+      serde_json::Value::Object(object) => JsonValue::Object(
+        object
+          .into_iter()
+          .map(|(key, value)| Ok((key, JsonValue::try_from(value)?)))
+          .collect::<Result<_, _>>()?,
+      ),
+    })
+  }
+}
+
+#[cfg(feature = "serde_json")]
+impl From<JsonValue> for serde_json::Value {
+  /// Convert into a `serde_json::Value`.
+  ///
+  /// As `serde_json::Value` can't represent a key present multiple times within an object, the
+  /// last instance of the key is retained (matching `JsonValue::get`).
+  fn from(value: JsonValue) -> serde_json::Value {
+    match value {
+      JsonValue::Null => serde_json::Value::Null,
+      JsonValue::Bool(bool) => serde_json::Value::Bool(bool),
+      JsonValue::Number(JsonNumber::Integer(integer)) => serde_json::Value::from(integer),
+      JsonValue::Number(JsonNumber::Float(float)) => serde_json::Value::from(f64::from(float)),
+      JsonValue::String(string) => serde_json::Value::String(string),
+      JsonValue::Array(array) => {
+        serde_json::Value::Array(array.into_iter().map(serde_json::Value::from).collect())
+      }
+      JsonValue::Object(object) => serde_json::Value::Object(
+        object.into_iter().map(|(key, value)| (key, serde_json::Value::from(value))).collect(),
+      ),
+    }
+  }
+}
+
+#[test]
+fn json_value() {
+  use alloc::{vec, string::ToString};
+
+  let serialization =
+    r#"{"null":null,"bool":true,"int":-1,"float":0.5,"string":"a\"b","array":[1,[]],"obj":{}}"#;
+  let value =
+    JsonValue::deserialize_structure::<_, crate::ConstStack<32>>(serialization.as_bytes()).unwrap();
+  assert_eq!(
+    value,
+    JsonValue::Object(vec![
+      ("null".to_string(), JsonValue::Null),
+      ("bool".to_string(), JsonValue::Bool(true)),
+      ("int".to_string(), JsonValue::Number(JsonNumber::Integer(-1))),
+      ("float".to_string(), JsonValue::Number(JsonNumber::Float(0.5.try_into().unwrap()))),
+      ("string".to_string(), JsonValue::String("a\"b".to_string())),
+      (
+        "array".to_string(),
+        JsonValue::Array(
+          vec![JsonValue::Number(JsonNumber::Integer(1)), JsonValue::Array(vec![]),]
+        ),
+      ),
+      ("obj".to_string(), JsonValue::Object(vec![])),
+    ])
+  );
+  assert_eq!(value.get("bool"), Some(&JsonValue::Bool(true)));
+  let reserialized = value.serialize().collect::<String>();
+  assert_eq!(
+    JsonValue::deserialize_structure::<_, crate::ConstStack<32>>(reserialized.as_bytes()).unwrap(),
+    value
+  );
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn serde_json_value() {
+  use alloc::string::ToString;
+
+  let serialization = r#"{"null":null,"bool":true,"int":-1,"float":0.5,"str":"a\"b","arr":[1,[]],"#
+    .to_string() +
+    r#""obj":{"a":1,"a":2}}"#;
+  let value =
+    JsonValue::deserialize_structure::<_, crate::ConstStack<32>>(serialization.as_bytes()).unwrap();
+  let serde_value = serde_json::from_str::<serde_json::Value>(&serialization).unwrap();
+
+  // `serde_json` retains the last instance of a duplicated key, as does our conversion
+  let converted = serde_json::Value::from(value);
+  assert_eq!(converted, serde_value);
+  assert_eq!(converted["obj"]["a"], 2);
+  assert_eq!(
+    serde_json::Value::from(JsonValue::try_from(serde_value.clone()).unwrap()),
+    serde_value
+  );
+
+  let value = JsonValue::try_from(serde_value).unwrap();
+  assert_eq!(value.get("int"), Some(&JsonValue::Number(JsonNumber::Integer(-1))));
+  assert_eq!(
+    value.get("obj").and_then(|obj| obj.get("a")),
+    Some(&JsonValue::Number(JsonNumber::Integer(2)))
+  );
+
+  // Integers exceeding an `i64` are converted to floats
+  assert_eq!(
+    JsonValue::try_from(serde_json::Value::from(u64::MAX)).unwrap(),
+    JsonValue::Number(JsonNumber::Float(18446744073709551615.0.try_into().unwrap()))
+  );
+}
@@ -0,0 +1,195 @@
+use core::{fmt, marker::PhantomData};
+
+use crate::{Number, Tri, JsonF64};
+
+/// A type whose serialization has a known shape.
+///
+/// This is implemented by `core-json-derive` when deriving `JsonSerialize` for a `struct` with the
+/// `wire_format` attribute, allowing binaries to describe the JSON they expect (such as within
+/// their documentation or from debug endpoints).
+pub trait JsonWireFormat {
+  /// Write a description of the shape of this type's serialization.
+  ///
+  /// Objects are described as `{ "key": shape }`, with keys which may be omitted marked
+  /// `"key"?: shape`, and the values of maps described as `{ [string]: shape }`. Arrays are
+  /// described as `[shape]`. Values which may be one of several shapes are described as
+  /// `shape | shape`. Scalars are described as `null`, `boolean`, `integer`, `number`, or
+  /// `string`.
+  fn wire_format(f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+/// The description of the shape of a type's serialization, per `JsonWireFormat`.
+///
+/// This implements `Display`, allowing the description to be written or collected into a
+/// `String`.
+pub struct WireFormat<T: ?Sized + JsonWireFormat>(PhantomData<T>);
+
+impl<T: ?Sized + JsonWireFormat> WireFormat<T> {
+  /// The description of the shape of `T`'s serialization.
+  pub const fn new() -> Self {
+    WireFormat(PhantomData)
+  }
+}
+
+impl<T: ?Sized + JsonWireFormat> Default for WireFormat<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: ?Sized + JsonWireFormat> fmt::Display for WireFormat<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    T::wire_format(f)
+  }
+}
+
+macro_rules! scalar {
+  ($shape: literal, $($kind: ty),+) => {
+    $(
+      impl JsonWireFormat for $kind {
+        fn wire_format(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+          f.write_str($shape)
+        }
+      }
+    )+
+  };
+}
+scalar!("null", ());
+scalar!("boolean", bool);
+scalar!("integer", i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+scalar!("number", Number, JsonF64);
+#[cfg(feature = "zmij")]
+scalar!("number", crate::ExactF64);
+scalar!("string", str);
+#[cfg(feature = "alloc")]
+scalar!("string", alloc::string::String);
+#[cfg(feature = "smol_str")]
+scalar!("string", smol_str::SmolStr);
+#[cfg(feature = "compact_str")]
+scalar!("string", compact_str::CompactString);
+#[cfg(feature = "unicode-normalization")]
+scalar!("string", crate::NfcString);
+
+impl<T: ?Sized> JsonWireFormat for PhantomData<T> {
+  fn wire_format(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("null")
+  }
+}
+
+impl<T: ?Sized + JsonWireFormat> JsonWireFormat for &T {
+  fn wire_format(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    T::wire_format(f)
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized + JsonWireFormat> JsonWireFormat for alloc::boxed::Box<T> {
+  fn wire_format(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    T::wire_format(f)
+  }
+}
+
+impl<T: JsonWireFormat> JsonWireFormat for Option<T> {
+  fn wire_format(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    T::wire_format(f)?;
+    f.write_str(" | null")
+  }
+}
+
+impl<T: JsonWireFormat> JsonWireFormat for Tri<T> {
+  fn wire_format(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    T::wire_format(f)?;
+    f.write_str(" | null")
+  }
+}
+
+#[cfg(any(feature = "sequences", all(feature = "sets", any(feature = "alloc", feature = "std"))))]
+fn sequence<T: ?Sized + JsonWireFormat>(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+  f.write_str("[")?;
+  T::wire_format(f)?;
+  f.write_str("]")
+}
+
+#[cfg(feature = "sequences")]
+impl<T: JsonWireFormat, const N: usize> JsonWireFormat for [T; N] {
+  fn wire_format(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    sequence::<T>(f)
+  }
+}
+#[cfg(feature = "sequences")]
+impl<T: JsonWireFormat> JsonWireFormat for [T] {
+  fn wire_format(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    sequence::<T>(f)
+  }
+}
+#[cfg(all(feature = "sequences", feature = "alloc"))]
+impl<T: JsonWireFormat> JsonWireFormat for alloc::vec::Vec<T> {
+  fn wire_format(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    sequence::<T>(f)
+  }
+}
+
+#[cfg(all(feature = "sets", feature = "alloc"))]
+impl<T: JsonWireFormat> JsonWireFormat for alloc::collections::BTreeSet<T> {
+  fn wire_format(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    sequence::<T>(f)
+  }
+}
+#[cfg(all(feature = "sets", feature = "std"))]
+impl<T: JsonWireFormat, H> JsonWireFormat for std::collections::HashSet<T, H> {
+  fn wire_format(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    sequence::<T>(f)
+  }
+}
+#[cfg(all(feature = "sets", feature = "hashbrown"))]
+impl<T: JsonWireFormat, H> JsonWireFormat for hashbrown::HashSet<T, H> {
+  fn wire_format(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    sequence::<T>(f)
+  }
+}
+
+#[cfg(all(feature = "maps", feature = "alloc"))]
+fn map<T: JsonWireFormat>(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+  f.write_str("{ [string]: ")?;
+  T::wire_format(f)?;
+  f.write_str(" }")
+}
+
+#[cfg(all(feature = "maps", feature = "alloc"))]
+impl<K, T: JsonWireFormat> JsonWireFormat for alloc::collections::BTreeMap<K, T> {
+  fn wire_format(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    map::<T>(f)
+  }
+}
+#[cfg(all(feature = "maps", feature = "std"))]
+impl<K, T: JsonWireFormat, H> JsonWireFormat for std::collections::HashMap<K, T, H> {
+  fn wire_format(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    map::<T>(f)
+  }
+}
+#[cfg(all(feature = "maps", feature = "hashbrown"))]
+impl<K, T: JsonWireFormat, H> JsonWireFormat for hashbrown::HashMap<K, T, H> {
+  fn wire_format(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    map::<T>(f)
+  }
+}
+
+#[cfg(all(feature = "alloc", feature = "sequences", feature = "maps"))]
+#[test]
+fn wire_format() {
+  use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+    collections::BTreeMap,
+  };
+
+  fn describe<T: ?Sized + JsonWireFormat>() -> String {
+    WireFormat::<T>::new().to_string()
+  }
+  assert_eq!(describe::<()>(), "null");
+  assert_eq!(describe::<u8>(), "integer");
+  assert_eq!(describe::<&str>(), "string");
+  assert_eq!(describe::<Option<bool>>(), "boolean | null");
+  assert_eq!(describe::<[Vec<Option<JsonF64>>]>(), "[[number | null]]");
+  assert_eq!(describe::<BTreeMap<String, [u8; 2]>>(), "{ [string]: [integer] }");
+}